@@ -0,0 +1,47 @@
+//! How this extension was compiled, for provenance sidecars.
+//!
+//! Capacities can differ in the last bits between machines: FMA contraction
+//! and vector width depend on the target features the crate was built with
+//! (`-C target-cpu=native` vs. a generic build), not on the CPU it runs on.
+//! Only the compiled artifact knows those, so the binding reports them and
+//! `viterbo.provenance.environment()` records them next to the runtime CPU
+//! flags.
+
+use pyo3::prelude::*;
+use pyo3::types::PyDict;
+
+pub fn register(m: &PyModule) -> PyResult<()> {
+    m.add_function(wrap_pyfunction!(build_info, m)?)?;
+    Ok(())
+}
+
+/// Numerically relevant target features enabled at compile time.
+fn target_features() -> Vec<&'static str> {
+    [
+        ("sse2", cfg!(target_feature = "sse2")),
+        ("sse4.1", cfg!(target_feature = "sse4.1")),
+        ("sse4.2", cfg!(target_feature = "sse4.2")),
+        ("avx", cfg!(target_feature = "avx")),
+        ("avx2", cfg!(target_feature = "avx2")),
+        ("fma", cfg!(target_feature = "fma")),
+        ("avx512f", cfg!(target_feature = "avx512f")),
+        ("neon", cfg!(target_feature = "neon")),
+    ]
+    .into_iter()
+    .filter_map(|(name, enabled)| enabled.then_some(name))
+    .collect()
+}
+
+/// `{"target_arch", "target_os", "debug_assertions", "target_features", "rustflags"}`.
+///
+/// `rustflags` is the `RUSTFLAGS` environment seen by the compiler, if any.
+#[pyfunction]
+fn build_info(py: Python<'_>) -> PyResult<PyObject> {
+    let dict = PyDict::new_bound(py);
+    dict.set_item("target_arch", std::env::consts::ARCH)?;
+    dict.set_item("target_os", std::env::consts::OS)?;
+    dict.set_item("debug_assertions", cfg!(debug_assertions))?;
+    dict.set_item("target_features", target_features())?;
+    dict.set_item("rustflags", option_env!("RUSTFLAGS"))?;
+    Ok(dict.into_any().unbind())
+}
@@ -1,21 +1,240 @@
 //! Oriented-edge capacity bindings.
 
-use crate::common::poly4_from_py_halfspaces;
+use crate::common::{
+    map_budget_err, map_generator_error, map_volume_err, poly4_from_py_halfspaces, py_to_json,
+    solve_capacity, PyHalfspaces,
+};
+use crate::errors::{SolverBudgetExceeded, ViterboError};
+use numpy::ndarray::Array2;
+use numpy::IntoPyArray;
 use pyo3::prelude::*;
+use pyo3::types::PyDict;
+use rayon::prelude::*;
+use rayon::ThreadPoolBuilder;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::thread;
+use std::time::Duration;
+use viterbo::geom4::{volume4, Poly4};
 use viterbo::oriented_edge::solve_with_defaults;
+use viterbo::rand4::from_config;
 
+/// How often a running batch checks for Ctrl-C.
+const SIGNAL_POLL: Duration = Duration::from_millis(50);
+
+/// EHZ capacity, `None` when the solver finds no cycle.
+///
+/// With `max_nodes`, raises `SolverBudgetExceeded` once the search has
+/// expanded that many path nodes without finishing.
 #[pyfunction]
+#[pyo3(signature = (hs, max_nodes = None))]
 pub fn poly4_capacity_ehz_from_halfspaces(
-    hs: Vec<((f64, f64, f64, f64), f64)>,
+    hs: PyHalfspaces<'_>,
+    max_nodes: Option<usize>,
 ) -> PyResult<Option<f64>> {
     let mut poly = poly4_from_py_halfspaces(hs)?;
-    Ok(solve_with_defaults(&mut poly).map(|(c, _cycle)| c))
+    solve_capacity(&mut poly, max_nodes).map_err(map_budget_err)
+}
+
+// No `capacity_orbit` binding yet: `solve_with_defaults` returns the
+// capacity and an opaque ridge cycle, but neither the fixed point `z*` nor
+// per-segment times, and lifting the cycle out of the ridge charts into an
+// `(N, 4)` polyline needs both. Bind it once the solver reports them.
+
+/// Systolic ratio `c_EHZ² / (2·vol)` with its components, in one call.
+///
+/// Returns `{"systolic_ratio", "capacity", "volume"}`; the ratio and capacity
+/// are `None` when the solver finds no cycle.
+#[pyfunction]
+pub fn systolic_ratio(py: Python<'_>, hs: PyHalfspaces<'_>) -> PyResult<PyObject> {
+    let mut poly = poly4_from_py_halfspaces(hs)?;
+    let volume = volume4(&mut poly).map_err(map_volume_err)?;
+    let capacity = solve_with_defaults(&mut poly).map(|(c, _cycle)| c);
+    let dict = PyDict::new_bound(py);
+    dict.set_item("systolic_ratio", capacity.map(|c| c * c / (2.0 * volume)))?;
+    dict.set_item("capacity", capacity)?;
+    dict.set_item("volume", volume)?;
+    Ok(dict.into_any().unbind())
+}
+
+/// Solve a batch of polytopes on a rayon pool with the GIL released.
+///
+/// Results come back in input order (`None` where the solver finds no cycle).
+/// `n_threads = None` (or 0) uses one thread per core. Inputs are validated
+/// up front, so a malformed entry fails the call before any solve starts.
+/// `max_nodes` budgets each solve; the first exhausted one raises
+/// `SolverBudgetExceeded` naming its batch index.
+///
+/// `progress(done, total)` is called (GIL re-acquired briefly) after each
+/// solve, e.g. `tqdm.update`-style heartbeats. Ctrl-C, or an exception the
+/// callback raises, stops the batch: solves not yet started are skipped and
+/// the exception is re-raised once the in-flight ones finish.
+#[pyfunction]
+#[pyo3(signature = (batch, n_threads = None, progress = None, max_nodes = None))]
+pub fn capacity_ehz_batch(
+    py: Python<'_>,
+    batch: Vec<PyHalfspaces<'_>>,
+    n_threads: Option<usize>,
+    progress: Option<PyObject>,
+    max_nodes: Option<usize>,
+) -> PyResult<Vec<Option<f64>>> {
+    let polys = batch
+        .into_iter()
+        .enumerate()
+        .map(|(i, hs)| {
+            poly4_from_py_halfspaces(hs).map_err(|err| {
+                let msg = format!("batch[{i}]: {}", err.value_bound(py));
+                PyErr::from_type_bound(err.get_type_bound(py), msg)
+            })
+        })
+        .collect::<PyResult<Vec<_>>>()?;
+    run_interruptible(py, n_threads, polys, progress.as_ref(), |mut poly| {
+        solve_capacity(&mut poly, max_nodes)
+    })?
+    .into_iter()
+    .enumerate()
+    .map(|(i, solved)| {
+        solved.map_err(|err| SolverBudgetExceeded::new_err(format!("batch[{i}]: {err}")))
+    })
+    .collect()
+}
+
+/// Sample `rows` polytopes from a registry config and solve them in parallel,
+/// returning NumPy columns ready for a zero-copy Arrow `RecordBatch`.
+///
+/// Columns: `halfspace_values` `(Σm, 5)` with row offsets `halfspace_offsets`
+/// `(rows + 1,)` (Arrow list layout), `capacity`, `volume`, `systolic_ratio`
+/// (NaN where undefined) and `replay` (JSON strings). Fewer rows come back
+/// when a finite family is exhausted; Ctrl-C cancels like `capacity_ehz_batch`.
+/// `viterbo.rust.arrow` wraps this.
+#[pyfunction]
+#[pyo3(signature = (config, rows, n_threads = None))]
+pub fn sample_capacity_columns(
+    py: Python<'_>,
+    config: &Bound<'_, PyDict>,
+    rows: usize,
+    n_threads: Option<usize>,
+) -> PyResult<PyObject> {
+    let mut gen = from_config(&py_to_json(config.as_any())?).map_err(map_generator_error)?;
+    let mut samples = Vec::with_capacity(rows);
+    while samples.len() < rows {
+        match gen.next_sample().map_err(map_generator_error)? {
+            Some(sample) => samples.push(sample),
+            None => break,
+        }
+    }
+    let solved: Vec<(Poly4, f64, f64)> =
+        run_interruptible(py, n_threads, samples.iter().collect(), None, |sample| {
+            let mut poly = sample.polytope.clone();
+            poly.ensure_halfspaces_from_v();
+            let volume = volume4(&mut poly).unwrap_or(f64::NAN);
+            let capacity = solve_with_defaults(&mut poly).map_or(f64::NAN, |(c, _)| c);
+            (poly, volume, capacity)
+        })?;
+    let mut offsets = vec![0_i64];
+    let mut values = Vec::new();
+    for (poly, _, _) in &solved {
+        values.extend(
+            poly.h
+                .iter()
+                .flat_map(|h| [h.n[0], h.n[1], h.n[2], h.n[3], h.c]),
+        );
+        offsets.push(offsets.last().unwrap() + poly.h.len() as i64);
+    }
+    let column = |f: fn(&(Poly4, f64, f64)) -> f64| -> Vec<f64> { solved.iter().map(f).collect() };
+    let dict = PyDict::new_bound(py);
+    dict.set_item(
+        "halfspace_values",
+        Array2::from_shape_vec((values.len() / 5, 5), values)
+            .expect("five entries per halfspace")
+            .into_pyarray_bound(py),
+    )?;
+    dict.set_item("halfspace_offsets", offsets.into_pyarray_bound(py))?;
+    dict.set_item("capacity", column(|r| r.2).into_pyarray_bound(py))?;
+    dict.set_item("volume", column(|r| r.1).into_pyarray_bound(py))?;
+    dict.set_item(
+        "systolic_ratio",
+        column(|r| r.2 * r.2 / (2.0 * r.1)).into_pyarray_bound(py),
+    )?;
+    let replay: Vec<String> = samples.iter().map(|s| s.replay.to_string()).collect();
+    dict.set_item("replay", replay)?;
+    Ok(dict.into_any().unbind())
+}
+
+/// Map `solve` over `items` on a rayon pool with the GIL released.
+///
+/// Python only runs signal handlers on the main thread, so the calling
+/// thread polls `check_signals` while the pool works. Once a handler raises
+/// (Ctrl-C → `KeyboardInterrupt`) or `progress(done, total)` does, items not
+/// yet started are skipped and the error is returned after the in-flight
+/// solves finish; the solver itself has no cancellation point. No native
+/// thread outlives the call.
+fn run_interruptible<I, T, F>(
+    py: Python<'_>,
+    n_threads: Option<usize>,
+    items: Vec<I>,
+    progress: Option<&PyObject>,
+    solve: F,
+) -> PyResult<Vec<T>>
+where
+    I: Send,
+    T: Send,
+    F: Fn(I) -> T + Send + Sync,
+{
+    let pool = ThreadPoolBuilder::new()
+        .num_threads(n_threads.unwrap_or(0))
+        .build()
+        .map_err(|err| ViterboError::new_err(err.to_string()))?;
+    let total = items.len();
+    let done = AtomicUsize::new(0);
+    let cancelled = AtomicBool::new(false);
+    let first_err: Mutex<Option<PyErr>> = Mutex::new(None);
+    let fail = |err: PyErr| {
+        cancelled.store(true, Ordering::Relaxed);
+        first_err.lock().unwrap().get_or_insert(err);
+    };
+    let results: Vec<Option<T>> = thread::scope(|scope| {
+        let worker = scope.spawn(|| {
+            pool.install(|| {
+                items
+                    .into_par_iter()
+                    .map(|item| {
+                        if cancelled.load(Ordering::Relaxed) {
+                            return None;
+                        }
+                        let out = solve(item);
+                        let n = done.fetch_add(1, Ordering::Relaxed) + 1;
+                        if let Some(cb) = progress {
+                            if let Err(err) = Python::with_gil(|py| cb.call1(py, (n, total))) {
+                                fail(err);
+                            }
+                        }
+                        Some(out)
+                    })
+                    .collect()
+            })
+        });
+        while !worker.is_finished() {
+            py.allow_threads(|| thread::sleep(SIGNAL_POLL));
+            if let Err(err) = py.check_signals() {
+                fail(err);
+            }
+        }
+        worker.join().expect("capacity worker panicked")
+    });
+    match first_err.into_inner().unwrap() {
+        Some(err) => Err(err),
+        None => Ok(results
+            .into_iter()
+            .map(|r| r.expect("no item skipped"))
+            .collect()),
+    }
 }
 
 pub fn register(m: &PyModule) -> PyResult<()> {
-    m.add_function(wrap_pyfunction!(
-        poly4_capacity_ehz_from_halfspaces,
-        m
-    )?)?;
+    m.add_function(wrap_pyfunction!(poly4_capacity_ehz_from_halfspaces, m)?)?;
+    m.add_function(wrap_pyfunction!(capacity_ehz_batch, m)?)?;
+    m.add_function(wrap_pyfunction!(systolic_ratio, m)?)?;
+    m.add_function(wrap_pyfunction!(sample_capacity_columns, m)?)?;
     Ok(())
 }
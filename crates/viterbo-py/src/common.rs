@@ -1,29 +1,157 @@
+use crate::errors::{
+    DegenerateSampleError, InvalidParams, SolverBudgetExceeded, UnboundedPolytopeError,
+};
 use nalgebra::Vector4;
-use pyo3::exceptions::PyValueError;
+use numpy::ndarray::Array2;
+use numpy::{IntoPyArray, PyArray2, PyReadonlyArray2};
 use pyo3::prelude::*;
+use pyo3::types::PyDict;
+use serde_json::Value;
 use viterbo::geom4::{Hs4, Poly4, VolumeError};
+use viterbo::oriented_edge::{solve, solve_with_budget, BudgetExceeded, GeomCfg};
+use viterbo::rand4::{is_bounded, GeneratorError};
 
-pub fn poly4_from_py_halfspaces(
-    hs: Vec<((f64, f64, f64, f64), f64)>,
-) -> PyResult<Poly4> {
-    if hs.len() < 5 {
-        return Err(PyValueError::new_err(
+/// Halfspaces as Python passes them: a float64 `(m, 5)` array of rows
+/// `[n0, n1, n2, n3, c]`, read in place (no list conversion).
+pub type PyHalfspaces<'py> = PyReadonlyArray2<'py, f64>;
+
+pub fn poly4_from_py_halfspaces(hs: PyHalfspaces<'_>) -> PyResult<Poly4> {
+    let view = hs.as_array();
+    if view.ncols() != 5 {
+        return Err(InvalidParams::new_err(format!(
+            "halfspaces must have shape (m, 5), got (m, {})",
+            view.ncols()
+        )));
+    }
+    if view.nrows() < 5 {
+        return Err(InvalidParams::new_err(
             "need at least 5 half-spaces for a bounded 4D polytope",
         ));
     }
     let mut poly = Poly4::from_h(
-        hs.into_iter()
-            .map(|(normal, c)| {
-                let n = Vector4::new(normal.0, normal.1, normal.2, normal.3);
-                Hs4::new(n, c)
-            })
+        view.rows()
+            .into_iter()
+            .map(|row| Hs4::new(Vector4::new(row[0], row[1], row[2], row[3]), row[4]))
             .collect(),
     );
     poly.check_canonical()
-        .map_err(|err| PyValueError::new_err(err))?;
+        .map_err(|msg| canonical_error(&poly, msg))?;
+    Ok(poly)
+}
+
+/// A `check_canonical` failure as Python sees it: `DegenerateSampleError` if
+/// no offset is positive (the origin cannot be interior, e.g. an empty
+/// H-rep), `UnboundedPolytopeError` if the halfspaces leave a recession
+/// direction open, `InvalidParams` otherwise.
+pub fn canonical_error(poly: &Poly4, msg: String) -> PyErr {
+    if poly.h.iter().all(|hs| hs.c <= 0.0) {
+        DegenerateSampleError::new_err(format!("no halfspace has a positive offset: {msg}"))
+    } else if is_bounded(&poly.h) {
+        InvalidParams::new_err(msg)
+    } else {
+        UnboundedPolytopeError::new_err(format!("halfspaces do not bound a polytope: {msg}"))
+    }
+}
+
+/// Convex hull of a float64 `(n, 4)` vertex array, with facets computed.
+pub fn poly4_from_py_vertices(vs: PyReadonlyArray2<'_, f64>) -> PyResult<Poly4> {
+    let view = vs.as_array();
+    if view.ncols() != 4 || view.nrows() < 5 {
+        return Err(InvalidParams::new_err(format!(
+            "vertices must have shape (n >= 5, 4), got ({}, {})",
+            view.nrows(),
+            view.ncols()
+        )));
+    }
+    let mut poly = Poly4::from_v(
+        view.rows()
+            .into_iter()
+            .map(|row| Vector4::new(row[0], row[1], row[2], row[3]))
+            .collect(),
+    );
+    poly.ensure_halfspaces_from_v();
+    poly.check_canonical().map_err(InvalidParams::new_err)?;
     Ok(poly)
 }
 
+/// `(m, 5)` array of halfspace rows; the buffer is handed to NumPy without a copy.
+pub fn halfspaces_to_numpy<'py>(py: Python<'py>, poly: &Poly4) -> Bound<'py, PyArray2<f64>> {
+    let data = poly
+        .h
+        .iter()
+        .flat_map(|h| [h.n[0], h.n[1], h.n[2], h.n[3], h.c])
+        .collect();
+    Array2::from_shape_vec((poly.h.len(), 5), data)
+        .expect("five entries per halfspace")
+        .into_pyarray_bound(py)
+}
+
+/// `(n, 4)` array of vertices; the buffer is handed to NumPy without a copy.
+pub fn vertices_to_numpy<'py>(py: Python<'py>, poly: &Poly4) -> Bound<'py, PyArray2<f64>> {
+    let data = poly
+        .v
+        .iter()
+        .flat_map(|v| [v[0], v[1], v[2], v[3]])
+        .collect();
+    Array2::from_shape_vec((poly.v.len(), 4), data)
+        .expect("four entries per vertex")
+        .into_pyarray_bound(py)
+}
+
+/// `{"vertices": (n, 4) array, "halfspaces": (m, 5) array}` with both
+/// representations filled in.
+pub fn poly4_to_py(py: Python<'_>, mut poly: Poly4) -> PyResult<PyObject> {
+    poly.ensure_vertices_from_h();
+    poly.ensure_halfspaces_from_v();
+    let dict = PyDict::new_bound(py);
+    dict.set_item("vertices", vertices_to_numpy(py, &poly))?;
+    dict.set_item("halfspaces", halfspaces_to_numpy(py, &poly))?;
+    Ok(dict.into_any().unbind())
+}
+
+/// Python object → JSON via the `json` module (configs stay plain dicts).
+pub fn py_to_json(obj: &Bound<'_, PyAny>) -> PyResult<Value> {
+    let text: String = obj
+        .py()
+        .import_bound("json")?
+        .call_method1("dumps", (obj,))?
+        .extract()?;
+    serde_json::from_str(&text).map_err(|err| InvalidParams::new_err(err.to_string()))
+}
+
+/// JSON → Python object via the `json` module.
+pub fn json_to_py(py: Python<'_>, value: &Value) -> PyResult<PyObject> {
+    Ok(py
+        .import_bound("json")?
+        .call_method1("loads", (value.to_string(),))?
+        .unbind())
+}
+
 pub fn map_volume_err(err: VolumeError) -> PyErr {
-    PyValueError::new_err(err.to_string())
+    DegenerateSampleError::new_err(err.to_string())
+}
+
+pub fn map_budget_err(err: BudgetExceeded) -> PyErr {
+    SolverBudgetExceeded::new_err(err.to_string())
+}
+
+/// Capacity of `poly` (`None` if no cycle closes); `max_nodes = None` runs
+/// the search to completion.
+pub fn solve_capacity(
+    poly: &mut Poly4,
+    max_nodes: Option<usize>,
+) -> Result<Option<f64>, BudgetExceeded> {
+    let cfg = GeomCfg::default();
+    let solved = match max_nodes {
+        Some(max_nodes) => solve_with_budget(poly, cfg, max_nodes)?,
+        None => solve(poly, cfg),
+    };
+    Ok(solved.map(|(c, _cycle)| c))
+}
+
+pub fn map_generator_error(err: GeneratorError) -> PyErr {
+    match err {
+        GeneratorError::InvalidParams(_) => InvalidParams::new_err(err.to_string()),
+        GeneratorError::Degenerate(_) => DegenerateSampleError::new_err(err.to_string()),
+    }
 }
@@ -0,0 +1,68 @@
+//! Python exception hierarchy for the native bindings.
+//!
+//! Callers need to tell "retry with another seed" from "skip this row" from
+//! "fix the config", which a flat `ValueError(str)` cannot express. Every
+//! binding raises a subclass of `ViterboError`; the base derives from
+//! `ValueError` so existing `except ValueError` handlers keep working.
+//!
+//! - `InvalidParams`: malformed input (shapes, config keys, non-canonical H-rep).
+//! - `DegenerateSampleError`: the geometry collapsed (lower-dimensional body,
+//!   singular map, origin not interior); retry with another sample.
+//! - `UnboundedPolytopeError`: the halfspaces do not bound a polytope (4D
+//!   H-reps failing `check_canonical` with an open recession direction, and
+//!   unbounded 2D polygons).
+//! - `SolverBudgetExceeded`: the capacity search hit its `max_nodes` budget
+//!   before finishing; retry with a larger budget or skip the row.
+
+use pyo3::create_exception;
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+
+create_exception!(
+    viterbo_native,
+    ViterboError,
+    PyValueError,
+    "Base class of all errors raised by viterbo_native."
+);
+create_exception!(
+    viterbo_native,
+    InvalidParams,
+    ViterboError,
+    "Malformed input: array shapes, config keys or a non-canonical H-rep."
+);
+create_exception!(
+    viterbo_native,
+    DegenerateSampleError,
+    ViterboError,
+    "The polytope or map is degenerate; another sample may succeed."
+);
+create_exception!(
+    viterbo_native,
+    UnboundedPolytopeError,
+    ViterboError,
+    "The halfspaces do not bound a polytope."
+);
+create_exception!(
+    viterbo_native,
+    SolverBudgetExceeded,
+    ViterboError,
+    "The capacity search ran out of its node budget before finishing."
+);
+
+pub fn register(py: Python<'_>, m: &PyModule) -> PyResult<()> {
+    m.add("ViterboError", py.get_type_bound::<ViterboError>())?;
+    m.add("InvalidParams", py.get_type_bound::<InvalidParams>())?;
+    m.add(
+        "DegenerateSampleError",
+        py.get_type_bound::<DegenerateSampleError>(),
+    )?;
+    m.add(
+        "UnboundedPolytopeError",
+        py.get_type_bound::<UnboundedPolytopeError>(),
+    )?;
+    m.add(
+        "SolverBudgetExceeded",
+        py.get_type_bound::<SolverBudgetExceeded>(),
+    )?;
+    Ok(())
+}
@@ -1,10 +1,13 @@
 //! Geometric helper bindings (kept separate so `lib.rs` stays tiny).
 
-use crate::common::{map_volume_err, poly4_from_py_halfspaces};
-use nalgebra::Vector2;
-use pyo3::exceptions::PyNotImplementedError;
+use crate::common::{map_volume_err, poly4_from_py_halfspaces, vertices_to_numpy, PyHalfspaces};
+use crate::errors::{DegenerateSampleError, InvalidParams};
+use nalgebra::{Vector2, Vector4};
+use numpy::ndarray::Array2;
+use numpy::{IntoPyArray, PyReadonlyArray2};
 use pyo3::prelude::*;
-use viterbo::geom4::volume4;
+use pyo3::types::{PyDict, PyList};
+use viterbo::geom4::{face_lattice4, volume4, volume4_from_vertices};
 
 #[pyfunction]
 pub fn parallelogram_area(a: (f64, f64), b: (f64, f64)) -> f64 {
@@ -14,31 +17,67 @@ pub fn parallelogram_area(a: (f64, f64), b: (f64, f64)) -> f64 {
 }
 
 #[pyfunction]
-pub fn polygon_sampler_todo() -> PyResult<()> {
-    Err(PyNotImplementedError::new_err(
-        "TODO: Bindings for viterbo.geom2.rand.draw_polygon_radial are deferred.",
-    ))
+pub fn poly4_volume_from_halfspaces(hs: PyHalfspaces<'_>) -> PyResult<f64> {
+    let mut poly = poly4_from_py_halfspaces(hs)?;
+    volume4(&mut poly).map_err(map_volume_err)
 }
 
+/// Volume of the hull of an `(n, 4)` vertex array, without building the H-rep.
 #[pyfunction]
-pub fn polygon_polar_todo() -> PyResult<()> {
-    Err(PyNotImplementedError::new_err(
-        "TODO: Bindings for viterbo.geom2.rand.polar are deferred.",
-    ))
+pub fn poly4_volume_from_vertices(vertices: PyReadonlyArray2<'_, f64>) -> PyResult<f64> {
+    let view = vertices.as_array();
+    if view.ncols() != 4 {
+        return Err(InvalidParams::new_err(format!(
+            "vertices must have shape (n, 4), got (n, {})",
+            view.ncols()
+        )));
+    }
+    let points: Vec<Vector4<f64>> = view
+        .rows()
+        .into_iter()
+        .map(|row| Vector4::new(row[0], row[1], row[2], row[3]))
+        .collect();
+    volume4_from_vertices(&points)
+        .ok_or_else(|| DegenerateSampleError::new_err("vertices do not span a 4-dimensional body"))
 }
 
+/// Vertices plus facet/ridge/edge incidences of an `(m, 5)` H-rep.
+///
+/// Returns `{"vertices": (n, 4), "facets": [idx, ...] (one per input row,
+/// empty if redundant), "ridges": (r, 2) facet pairs, "ridge_vertices":
+/// [idx, ...], "edges": (e, 2) vertex pairs}`; all indices are int arrays.
 #[pyfunction]
-pub fn poly4_volume_from_halfspaces(
-    hs: Vec<((f64, f64, f64, f64), f64)>,
-) -> PyResult<f64> {
-    let mut poly = poly4_from_py_halfspaces(hs)?;
-    volume4(&mut poly).map_err(map_volume_err)
+pub fn enumerate_faces(py: Python<'_>, halfspaces: PyHalfspaces<'_>) -> PyResult<PyObject> {
+    let mut poly = poly4_from_py_halfspaces(halfspaces)?;
+    let lattice = face_lattice4(&mut poly);
+    let index_list = |sets: Vec<Vec<usize>>| {
+        PyList::new_bound(py, sets.into_iter().map(|s| s.into_pyarray_bound(py)))
+    };
+    let pairs = |pairs: Vec<[usize; 2]>| {
+        let n = pairs.len();
+        let flat = pairs.into_iter().flatten().collect();
+        Array2::from_shape_vec((n, 2), flat)
+            .expect("two entries per pair")
+            .into_pyarray_bound(py)
+    };
+    let (ridge_facets, ridge_vertices): (Vec<_>, Vec<_>) = lattice
+        .ridges
+        .into_iter()
+        .map(|r| (r.facets, r.vertices))
+        .unzip();
+    let dict = PyDict::new_bound(py);
+    dict.set_item("vertices", vertices_to_numpy(py, &poly))?;
+    dict.set_item("facets", index_list(lattice.facets))?;
+    dict.set_item("ridges", pairs(ridge_facets))?;
+    dict.set_item("ridge_vertices", index_list(ridge_vertices))?;
+    dict.set_item("edges", pairs(lattice.edges))?;
+    Ok(dict.into_any().unbind())
 }
 
 pub fn register(m: &PyModule) -> PyResult<()> {
     m.add_function(wrap_pyfunction!(parallelogram_area, m)?)?;
-    m.add_function(wrap_pyfunction!(polygon_sampler_todo, m)?)?;
-    m.add_function(wrap_pyfunction!(polygon_polar_todo, m)?)?;
     m.add_function(wrap_pyfunction!(poly4_volume_from_halfspaces, m)?)?;
+    m.add_function(wrap_pyfunction!(poly4_volume_from_vertices, m)?)?;
+    m.add_function(wrap_pyfunction!(enumerate_faces, m)?)?;
     Ok(())
 }
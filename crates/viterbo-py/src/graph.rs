@@ -0,0 +1,222 @@
+//! Oriented-edge graph inspection bindings.
+//!
+//! When a polytope yields no cycle, the first questions are which ridges and
+//! edges the graph has and which edge domains are empty. The handle below
+//! snapshots exactly that (per-edge lower bounds, rotation increments, ridge
+//! chart polygons) so it can be inspected from Python without re-solving.
+
+use crate::common::{poly4_from_py_halfspaces, PyHalfspaces};
+use crate::errors::{DegenerateSampleError, InvalidParams};
+use nalgebra::Vector2;
+use numpy::ndarray::Array2;
+use numpy::{IntoPyArray, PyArray2};
+use pyo3::exceptions::PyIndexError;
+use pyo3::prelude::*;
+use pyo3::types::PyDict;
+use viterbo::api::{build_graph, GeomCfg};
+use viterbo::prelude::HalfspaceIntersection;
+
+/// One graph edge as exposed to Python.
+#[derive(Clone, Copy)]
+struct EdgeRow {
+    from: usize,
+    to: usize,
+    facet: usize,
+    /// Minimum of the action increment over `dom ψ_ij`; `None` if the
+    /// domain is empty or unbounded (the edge can never be used).
+    lb_action: Option<f64>,
+    rotation_inc: f64,
+}
+
+/// Read-only snapshot of the oriented-edge graph of a polytope.
+#[pyclass(name = "OrientedEdgeGraph", module = "viterbo_native")]
+pub struct PyOrientedEdgeGraph {
+    num_facets: usize,
+    ridge_facets: Vec<(usize, usize)>,
+    ridge_polygons: Vec<Option<Vec<Vector2<f64>>>>,
+    edges: Vec<EdgeRow>,
+}
+
+#[pymethods]
+impl PyOrientedEdgeGraph {
+    #[getter]
+    fn num_facets(&self) -> usize {
+        self.num_facets
+    }
+
+    #[getter]
+    fn num_ridges(&self) -> usize {
+        self.ridge_facets.len()
+    }
+
+    #[getter]
+    fn num_edges(&self) -> usize {
+        self.edges.len()
+    }
+
+    /// `[(from, to, facet, lb_action, rotation_inc), ...]` in graph order.
+    fn edges(&self) -> Vec<(usize, usize, usize, Option<f64>, f64)> {
+        self.edges
+            .iter()
+            .map(|e| (e.from, e.to, e.facet, e.lb_action, e.rotation_inc))
+            .collect()
+    }
+
+    /// The two facets meeting in ridge `i`.
+    fn ridge_facets(&self, i: usize) -> PyResult<(usize, usize)> {
+        self.ridge_facets
+            .get(i)
+            .copied()
+            .ok_or_else(|| ridge_out_of_range(i, self.ridge_facets.len()))
+    }
+
+    /// `(k, 2)` vertex array of ridge `i` in its chart; `None` if the chart
+    /// polygon is empty or unbounded.
+    fn ridge_polygon<'py>(
+        &self,
+        py: Python<'py>,
+        i: usize,
+    ) -> PyResult<Option<Bound<'py, PyArray2<f64>>>> {
+        let polygon = self
+            .ridge_polygons
+            .get(i)
+            .ok_or_else(|| ridge_out_of_range(i, self.ridge_polygons.len()))?;
+        Ok(polygon.as_ref().map(|vs| {
+            let data = vs.iter().flat_map(|v| [v.x, v.y]).collect();
+            Array2::from_shape_vec((vs.len(), 2), data)
+                .expect("two entries per vertex")
+                .into_pyarray_bound(py)
+        }))
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "OrientedEdgeGraph(facets={}, ridges={}, edges={})",
+            self.num_facets,
+            self.ridge_facets.len(),
+            self.edges.len()
+        )
+    }
+}
+
+/// Chart and polygon of the 2-face `F_i ∩ F_j`, for plotting τ-domains.
+///
+/// Returns `{"ridge": index, "chart_ut": (4, 2), "vertices": (k, 2)}`:
+/// `chart_ut` is `Ridge.chart_ut` (left inverse of the chart on the plane)
+/// and `vertices` the ridge polygon in chart coordinates, in boundary order.
+#[pyfunction]
+#[pyo3(signature = (halfspaces, i, j, geom_cfg=None))]
+pub fn face2_polygon(
+    py: Python<'_>,
+    halfspaces: PyHalfspaces<'_>,
+    i: usize,
+    j: usize,
+    geom_cfg: Option<&Bound<'_, PyDict>>,
+) -> PyResult<PyObject> {
+    let cfg = geom_cfg_from_dict(geom_cfg)?;
+    let mut poly = poly4_from_py_halfspaces(halfspaces)?;
+    let graph = build_graph(&mut poly, cfg);
+    let (index, ridge) = graph
+        .ridges
+        .iter()
+        .enumerate()
+        .find(|(_, r)| {
+            let (a, b) = (r.facets.0 .0, r.facets.1 .0);
+            (a, b) == (i, j) || (b, a) == (i, j)
+        })
+        .ok_or_else(|| {
+            InvalidParams::new_err(format!("facets {i} and {j} do not share a 2-face"))
+        })?;
+    let vs = bounded_vertices(ridge.poly.halfspace_intersection()).ok_or_else(|| {
+        DegenerateSampleError::new_err(format!("ridge ({i}, {j}) has no bounded chart polygon"))
+    })?;
+    let chart = Array2::from_shape_fn((4, 2), |(r, c)| ridge.chart_ut[(r, c)]);
+    let data = vs.iter().flat_map(|v| [v.x, v.y]).collect();
+    let vertices = Array2::from_shape_vec((vs.len(), 2), data).expect("two entries per vertex");
+    let dict = PyDict::new_bound(py);
+    dict.set_item("ridge", index)?;
+    dict.set_item("chart_ut", chart.into_pyarray_bound(py))?;
+    dict.set_item("vertices", vertices.into_pyarray_bound(py))?;
+    Ok(dict.into_any().unbind())
+}
+
+fn geom_cfg_from_dict(dict: Option<&Bound<'_, PyDict>>) -> PyResult<GeomCfg> {
+    let mut cfg = GeomCfg::default();
+    for (key, value) in dict.into_iter().flat_map(|d| d.iter()) {
+        let key: String = key.extract()?;
+        let slot = match key.as_str() {
+            "eps_det" => &mut cfg.eps_det,
+            "eps_feas" => &mut cfg.eps_feas,
+            "eps_tau" => &mut cfg.eps_tau,
+            other => {
+                return Err(InvalidParams::new_err(format!(
+                    "unknown geom_cfg key '{other}' (expected eps_det, eps_feas, eps_tau)"
+                )))
+            }
+        };
+        *slot = value.extract()?;
+    }
+    Ok(cfg)
+}
+
+fn ridge_out_of_range(i: usize, len: usize) -> PyErr {
+    PyIndexError::new_err(format!("ridge {i} out of range ({len} ridges)"))
+}
+
+fn bounded_vertices(intersection: HalfspaceIntersection) -> Option<Vec<Vector2<f64>>> {
+    match intersection {
+        HalfspaceIntersection::Bounded(vs) if !vs.is_empty() => Some(vs),
+        _ => None,
+    }
+}
+
+/// Build the oriented-edge graph of an `(m, 5)` H-rep.
+///
+/// `geom_cfg` overrides any of `eps_det`, `eps_feas`, `eps_tau`; omitted keys
+/// keep the `GeomCfg::default()` values.
+#[pyfunction]
+#[pyo3(signature = (halfspaces, geom_cfg=None))]
+pub fn build_oriented_edge_graph(
+    halfspaces: PyHalfspaces<'_>,
+    geom_cfg: Option<&Bound<'_, PyDict>>,
+) -> PyResult<PyOrientedEdgeGraph> {
+    let cfg = geom_cfg_from_dict(geom_cfg)?;
+    let mut poly = poly4_from_py_halfspaces(halfspaces)?;
+    let graph = build_graph(&mut poly, cfg);
+    let edges = graph
+        .edges
+        .iter()
+        .map(|e| EdgeRow {
+            from: e.from.0,
+            to: e.to.0,
+            facet: e.facet.0,
+            lb_action: bounded_vertices(e.dom_in.halfspace_intersection()).map(|vs| {
+                vs.into_iter()
+                    .map(|z| e.action_inc.eval(z))
+                    .fold(f64::INFINITY, f64::min)
+            }),
+            rotation_inc: e.rotation_inc,
+        })
+        .collect();
+    Ok(PyOrientedEdgeGraph {
+        num_facets: graph.num_facets,
+        ridge_facets: graph
+            .ridges
+            .iter()
+            .map(|r| (r.facets.0 .0, r.facets.1 .0))
+            .collect(),
+        ridge_polygons: graph
+            .ridges
+            .iter()
+            .map(|r| bounded_vertices(r.poly.halfspace_intersection()))
+            .collect(),
+        edges,
+    })
+}
+
+pub fn register(m: &PyModule) -> PyResult<()> {
+    m.add_class::<PyOrientedEdgeGraph>()?;
+    m.add_function(wrap_pyfunction!(build_oriented_edge_graph, m)?)?;
+    m.add_function(wrap_pyfunction!(face2_polygon, m)?)?;
+    Ok(())
+}
@@ -4,17 +4,33 @@
 //! (`geom`, `rand4`, ...). That keeps future extensions reviewable and avoids
 //! churn during rebases.
 
+mod build_info;
 mod capacity;
 mod common;
+mod errors;
 mod geom;
+mod graph;
+mod optimize;
+mod plot;
+mod poly2;
+mod poly4;
 mod rand4;
+mod stream;
 
 use pyo3::prelude::*;
 
 #[pymodule]
 fn viterbo_native(py: Python<'_>, m: &PyModule) -> PyResult<()> {
+    errors::register(py, m)?;
+    build_info::register(m)?;
     geom::register(m)?;
     capacity::register(m)?;
+    graph::register(m)?;
+    optimize::register(m)?;
+    plot::register(m)?;
+    poly2::register(m)?;
+    poly4::register(m)?;
     rand4::register(py, m)?;
+    stream::register(m)?;
     Ok(())
 }
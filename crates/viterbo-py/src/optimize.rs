@@ -0,0 +1,76 @@
+//! Bindings for the `viterbo::optimize` systolic-ratio searches.
+//!
+//! One call runs one budgeted optimizer pass from a start body; looping,
+//! checkpointing and dumping the best body live in `viterbo.search`, so a
+//! Python process can stop between passes without losing work.
+
+use crate::common::{
+    halfspaces_to_numpy, map_generator_error, poly4_from_py_halfspaces, PyHalfspaces,
+};
+use crate::errors::InvalidParams;
+use pyo3::prelude::*;
+use pyo3::types::PyDict;
+use viterbo::geom4::{Hs4, Poly4};
+use viterbo::optimize::{
+    optimize_offsets, systolic_objective, CmaEs, DerivativeFreeOptimizer, NelderMead,
+};
+
+pub fn register(m: &PyModule) -> PyResult<()> {
+    m.add_function(wrap_pyfunction!(optimize_systolic, m)?)?;
+    Ok(())
+}
+
+/// Maximize the systolic ratio over the facet offsets of `halfspaces`.
+///
+/// `optimizer` is `"cma_es"` (step `sigma0`, population `lambda_`) or
+/// `"nelder_mead"` (initial simplex edge `step`). Runs at most `budget`
+/// objective evaluations with the GIL released and returns
+/// `{"optimizer", "evaluations", "best_value", "best_halfspaces", "history"}`;
+/// `best_value` is `-inf` if no evaluated body had a defined ratio.
+#[pyfunction]
+#[pyo3(signature = (
+    halfspaces,
+    optimizer = "cma_es",
+    budget = 200,
+    seed = 0,
+    sigma0 = 0.1,
+    lambda_ = None,
+    step = 0.1,
+))]
+#[allow(clippy::too_many_arguments)]
+fn optimize_systolic(
+    py: Python<'_>,
+    halfspaces: PyHalfspaces<'_>,
+    optimizer: &str,
+    budget: usize,
+    seed: u64,
+    sigma0: f64,
+    lambda_: Option<usize>,
+    step: f64,
+) -> PyResult<PyObject> {
+    let start = poly4_from_py_halfspaces(halfspaces)?;
+    let mut opt: Box<dyn DerivativeFreeOptimizer + Send> = match optimizer {
+        "cma_es" => Box::new(CmaEs {
+            sigma0,
+            lambda: lambda_,
+            seed,
+        }),
+        "nelder_mead" => Box::new(NelderMead { step }),
+        other => {
+            return Err(InvalidParams::new_err(format!(
+                "unknown optimizer {other:?}; expected \"cma_es\" or \"nelder_mead\""
+            )))
+        }
+    };
+    let report = py
+        .allow_threads(|| optimize_offsets(&start, &systolic_objective, opt.as_mut(), budget))
+        .map_err(map_generator_error)?;
+    let best = Poly4::from_h(report.best.into_iter().map(Hs4::from).collect());
+    let dict = PyDict::new_bound(py);
+    dict.set_item("optimizer", report.optimizer)?;
+    dict.set_item("evaluations", report.evaluations)?;
+    dict.set_item("best_value", report.best_value)?;
+    dict.set_item("best_halfspaces", halfspaces_to_numpy(py, &best))?;
+    dict.set_item("history", report.history)?;
+    Ok(dict.into_any().unbind())
+}
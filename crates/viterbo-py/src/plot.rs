@@ -0,0 +1,167 @@
+//! SVG charts for `viterbo.atlas.figures`, drawn with `plotters`.
+//!
+//! Python aggregates the atlas (histogram bins, plotted points, counts) and
+//! passes the series in; this module only draws them. Only the SVG backend
+//! is compiled in, so the extension gains no system font or bitmap
+//! dependencies. Colours follow the matplotlib "tab10" order.
+
+use crate::errors::{InvalidParams, ViterboError};
+use plotters::coord::types::RangedCoordf64;
+use plotters::prelude::*;
+use pyo3::prelude::*;
+
+const SIZE: (u32, u32) = (640, 400);
+const PALETTE: [RGBColor; 7] = [
+    RGBColor(0x1f, 0x77, 0xb4),
+    RGBColor(0xff, 0x7f, 0x0e),
+    RGBColor(0x2c, 0xa0, 0x2c),
+    RGBColor(0xd6, 0x27, 0x28),
+    RGBColor(0x94, 0x67, 0xbd),
+    RGBColor(0x8c, 0x56, 0x4b),
+    RGBColor(0xe3, 0x77, 0xc2),
+];
+/// Scatter dot radius in pixels (plotters rounds radii to whole pixels).
+const DOT_RADIUS: u32 = 3;
+
+type Chart<'a> = ChartContext<'a, SVGBackend<'a>, Cartesian2d<RangedCoordf64, RangedCoordf64>>;
+type DrawResult<T> = Result<T, DrawingAreaErrorKind<std::io::Error>>;
+
+pub fn register(m: &PyModule) -> PyResult<()> {
+    m.add_function(wrap_pyfunction!(bars_svg, m)?)?;
+    m.add_function(wrap_pyfunction!(scatter_svg, m)?)?;
+    Ok(())
+}
+
+/// Bar chart: bar `k` spans `left[k]..right[k]` and has height `height[k]`.
+///
+/// Zero-height bars are not drawn. `colour` indexes the palette. Returns the
+/// SVG document as a string.
+#[pyfunction]
+#[pyo3(signature = (left, right, height, *, title, xlabel, ylabel, colour = 0))]
+pub fn bars_svg(
+    left: Vec<f64>,
+    right: Vec<f64>,
+    height: Vec<f64>,
+    title: &str,
+    xlabel: &str,
+    ylabel: &str,
+    colour: usize,
+) -> PyResult<String> {
+    if left.len() != right.len() || left.len() != height.len() {
+        return Err(InvalidParams::new_err(format!(
+            "left, right and height must have equal lengths, got {}, {}, {}",
+            left.len(),
+            right.len(),
+            height.len()
+        )));
+    }
+    let xlim = match (left.first(), right.last()) {
+        (Some(&lo), Some(&hi)) if hi > lo => (lo, hi),
+        _ => (0.0, 1.0),
+    };
+    let top = height.iter().copied().fold(0.0, f64::max);
+    let ylim = (0.0, if top > 0.0 { top } else { 1.0 });
+    let fill = palette(colour).filled();
+    render(title, xlabel, ylabel, xlim, ylim, |chart| {
+        let bars = (0..height.len())
+            .filter(|&k| height[k] > 0.0)
+            .map(|k| Rectangle::new([(left[k], 0.0), (right[k], height[k])], fill));
+        chart.draw_series(bars)?;
+        Ok(())
+    })
+}
+
+/// Scatter plot of `(x[k], y[k])`, coloured by `group[k]`.
+///
+/// Groups get palette colours in sorted order and one legend entry each.
+/// Axes span the data with 5% padding. Returns the SVG document as a string.
+#[pyfunction]
+#[pyo3(signature = (x, y, group, *, title, xlabel, ylabel))]
+pub fn scatter_svg(
+    x: Vec<f64>,
+    y: Vec<f64>,
+    group: Vec<String>,
+    title: &str,
+    xlabel: &str,
+    ylabel: &str,
+) -> PyResult<String> {
+    if x.len() != y.len() || x.len() != group.len() {
+        return Err(InvalidParams::new_err(format!(
+            "x, y and group must have equal lengths, got {}, {}, {}",
+            x.len(),
+            y.len(),
+            group.len()
+        )));
+    }
+    let mut groups: Vec<&str> = group.iter().map(String::as_str).collect();
+    groups.sort_unstable();
+    groups.dedup();
+    render(title, xlabel, ylabel, span(&x), span(&y), |chart| {
+        for (slot, &name) in groups.iter().enumerate() {
+            let fill = palette(slot).filled();
+            let dots = (0..x.len())
+                .filter(|&k| group[k] == name)
+                .map(|k| Circle::new((x[k], y[k]), DOT_RADIUS, fill));
+            chart
+                .draw_series(dots)?
+                .label(name)
+                .legend(move |(px, py)| Circle::new((px, py), 4, fill));
+        }
+        if !groups.is_empty() {
+            chart
+                .configure_series_labels()
+                .position(SeriesLabelPosition::UpperLeft)
+                .background_style(WHITE)
+                .border_style(BLACK)
+                .draw()?;
+        }
+        Ok(())
+    })
+}
+
+/// Shared frame: white background, caption, axes with labels, no grid.
+fn render(
+    title: &str,
+    xlabel: &str,
+    ylabel: &str,
+    xlim: (f64, f64),
+    ylim: (f64, f64),
+    draw: impl FnOnce(&mut Chart<'_>) -> DrawResult<()>,
+) -> PyResult<String> {
+    let mut out = String::new();
+    let drawn: DrawResult<()> = (|| {
+        let root = SVGBackend::with_string(&mut out, SIZE).into_drawing_area();
+        root.fill(&WHITE)?;
+        let mut chart: Chart<'_> = ChartBuilder::on(&root)
+            .caption(title, ("sans-serif", 14))
+            .margin(8)
+            .x_label_area_size(40)
+            .y_label_area_size(56)
+            .build_cartesian_2d(xlim.0..xlim.1, ylim.0..ylim.1)?;
+        chart
+            .configure_mesh()
+            .disable_mesh()
+            .x_desc(xlabel)
+            .y_desc(ylabel)
+            .draw()?;
+        draw(&mut chart)?;
+        root.present()
+    })();
+    drawn.map_err(|err| ViterboError::new_err(format!("drawing SVG failed: {err}")))?;
+    Ok(out)
+}
+
+fn palette(slot: usize) -> RGBColor {
+    PALETTE[slot % PALETTE.len()]
+}
+
+/// Data range padded by 5% on each side (±0.5 for a single value).
+fn span(values: &[f64]) -> (f64, f64) {
+    let lo = values.iter().copied().fold(f64::INFINITY, f64::min);
+    let hi = values.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+    if !lo.is_finite() || !hi.is_finite() {
+        return (0.0, 1.0);
+    }
+    let pad = if hi > lo { 0.05 * (hi - lo) } else { 0.5 };
+    (lo - pad, hi + pad)
+}
@@ -0,0 +1,235 @@
+//! `Poly2` as a Python class, wired to the radial polygon sampler.
+//!
+//! Notebook exploration of the 2D factors behind Mahler products wants a
+//! native object: draw a polygon from a replay token, recentre it, take the
+//! polar, and look at areas without round-tripping through 4D products.
+
+use crate::errors::{DegenerateSampleError, InvalidParams, UnboundedPolytopeError};
+use crate::rand4::{bounds_from_dict, radial_cfg_from_dict};
+use nalgebra::Vector2;
+use numpy::ndarray::Array2;
+use numpy::{IntoPyArray, PyArray2, PyReadonlyArray2};
+use pyo3::prelude::*;
+use pyo3::types::{PyDict, PyType};
+use viterbo::geom2::rand::{draw_polygon_radial, polar, recenter_rescale, RadialCfg, ReplayToken};
+use viterbo::geom2::{HalfspaceIntersection, Hs2, Poly2};
+
+/// Convex polygon in H-representation (`n·x ≤ c`).
+#[pyclass(name = "Poly2", module = "viterbo_native")]
+#[derive(Clone)]
+pub struct PyPoly2 {
+    pub inner: Poly2,
+}
+
+#[pymethods]
+impl PyPoly2 {
+    /// Build from an `(m, 3)` array of rows `[n0, n1, c]`.
+    #[classmethod]
+    fn from_halfspaces(
+        _cls: &Bound<'_, PyType>,
+        halfspaces: PyReadonlyArray2<'_, f64>,
+    ) -> PyResult<Self> {
+        Ok(Self {
+            inner: poly2_from_numpy(halfspaces)?,
+        })
+    }
+
+    /// Radial sampler polygon for replay token `(seed, index)`.
+    ///
+    /// `radial_cfg` takes the same keys as the Mahler-product params
+    /// (`vertex_count`, `angle_jitter_frac`, ...); omitted keys keep defaults.
+    #[classmethod]
+    #[pyo3(signature = (seed, index, radial_cfg = None))]
+    fn from_radial(
+        _cls: &Bound<'_, PyType>,
+        seed: u64,
+        index: u64,
+        radial_cfg: Option<&Bound<'_, PyDict>>,
+    ) -> PyResult<Self> {
+        Ok(Self {
+            inner: sample_radial(radial_cfg, seed, index)?,
+        })
+    }
+
+    /// Shoelace area of the vertex cycle.
+    fn area(&self) -> PyResult<f64> {
+        let vs = self.vertex_cycle()?;
+        let twice: f64 = vs
+            .iter()
+            .zip(vs.iter().cycle().skip(1))
+            .map(|(a, b)| a.x * b.y - a.y * b.x)
+            .sum();
+        Ok(0.5 * twice.abs())
+    }
+
+    /// `(n, 2)` vertex array in boundary order.
+    fn vertices<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyArray2<f64>>> {
+        vertices_to_numpy2(py, &self.inner)
+    }
+
+    /// Polar polygon `K°` (requires the origin in the interior).
+    fn polar(&self) -> PyResult<Self> {
+        let inner = polar(&self.inner).ok_or_else(|| {
+            DegenerateSampleError::new_err("polar undefined: origin not interior")
+        })?;
+        Ok(Self { inner })
+    }
+
+    /// Recentre at the area centroid and rescale into `bounds`
+    /// (`r_in_min`, `r_out_max`); returns `(polygon, r_in, r_out)`.
+    fn recenter_rescale(&self, bounds: &Bound<'_, PyDict>) -> PyResult<(Self, f64, f64)> {
+        let bounds = bounds_from_dict(bounds.as_gil_ref())?;
+        let (inner, r_in, r_out) = recenter_rescale(&self.inner, bounds).ok_or_else(|| {
+            DegenerateSampleError::new_err("bounds cannot be met by this polygon")
+        })?;
+        Ok((Self { inner }, r_in, r_out))
+    }
+
+    /// Pickle via the `(m, 3)` halfspace array (`from_halfspaces` restores it).
+    fn __reduce__(&self, py: Python<'_>) -> PyResult<(PyObject, PyObject)> {
+        let restore = py.get_type_bound::<Self>().getattr("from_halfspaces")?;
+        let state = (halfspaces_to_numpy2(py, &self.inner),);
+        Ok((restore.unbind(), state.into_py(py)))
+    }
+
+    fn __repr__(&self) -> String {
+        format!("Poly2(halfspaces={})", self.inner.hs.len())
+    }
+}
+
+impl PyPoly2 {
+    fn vertex_cycle(&self) -> PyResult<Vec<Vector2<f64>>> {
+        vertex_cycle(&self.inner)
+    }
+}
+
+/// Radial sampler polygon as `{"vertices": (n, 2), "halfspaces": (m, 3)}` arrays.
+///
+/// Same polygon as `Poly2.from_radial(seed, index, cfg)`; `cfg=None` keeps
+/// the `RadialCfg` defaults.
+#[pyfunction]
+#[pyo3(name = "draw_polygon_radial", signature = (cfg, seed, index))]
+fn draw_polygon_radial_py(
+    py: Python<'_>,
+    cfg: Option<&Bound<'_, PyDict>>,
+    seed: u64,
+    index: u64,
+) -> PyResult<PyObject> {
+    let poly = sample_radial(cfg, seed, index)?;
+    let dict = PyDict::new_bound(py);
+    dict.set_item("vertices", vertices_to_numpy2(py, &poly)?)?;
+    dict.set_item("halfspaces", halfspaces_to_numpy2(py, &poly))?;
+    Ok(dict.into_any().unbind())
+}
+
+/// Polar `K°` of an `(m, 3)` H-rep, returned as `{"vertices", "halfspaces"}` arrays.
+///
+/// The polar depends on the origin: `recenter=True` first translates `K` to
+/// its area centroid; `bounds` (`r_in_min`, `r_out_max`) applies the same
+/// `recenter_rescale` as the Mahler-product sampler, so results match the
+/// Rust pipeline factor for factor.
+#[pyfunction]
+#[pyo3(signature = (halfspaces, recenter=false, bounds=None))]
+fn polygon_polar(
+    py: Python<'_>,
+    halfspaces: PyReadonlyArray2<'_, f64>,
+    recenter: bool,
+    bounds: Option<&Bound<'_, PyDict>>,
+) -> PyResult<PyObject> {
+    let mut poly = poly2_from_numpy(halfspaces)?;
+    if let Some(bounds) = bounds {
+        let bounds = bounds_from_dict(bounds.as_gil_ref())?;
+        poly = recenter_rescale(&poly, bounds)
+            .ok_or_else(|| DegenerateSampleError::new_err("bounds cannot be met by this polygon"))?
+            .0;
+    } else if recenter {
+        let s = area_centroid(&poly)?;
+        let mut shifted = Poly2::default();
+        for h in &poly.hs {
+            shifted.insert_halfspace(Hs2::new(h.n, h.c - h.n.dot(&s)));
+        }
+        poly = shifted;
+    }
+    let dual = polar(&poly)
+        .ok_or_else(|| DegenerateSampleError::new_err("polar undefined: origin not interior"))?;
+    let dict = PyDict::new_bound(py);
+    dict.set_item("vertices", vertices_to_numpy2(py, &dual)?)?;
+    dict.set_item("halfspaces", halfspaces_to_numpy2(py, &dual))?;
+    Ok(dict.into_any().unbind())
+}
+
+fn poly2_from_numpy(halfspaces: PyReadonlyArray2<'_, f64>) -> PyResult<Poly2> {
+    let view = halfspaces.as_array();
+    if view.ncols() != 3 {
+        return Err(InvalidParams::new_err(format!(
+            "halfspaces must have shape (m, 3), got (m, {})",
+            view.ncols()
+        )));
+    }
+    let mut poly = Poly2::default();
+    for row in view.rows() {
+        poly.insert_halfspace(Hs2::new(Vector2::new(row[0], row[1]), row[2]));
+    }
+    Ok(poly)
+}
+
+/// Centroid of the polygon as a region (shoelace weights).
+fn area_centroid(poly: &Poly2) -> PyResult<Vector2<f64>> {
+    let vs = vertex_cycle(poly)?;
+    let (mut twice_area, mut moment) = (0.0, Vector2::zeros());
+    for (a, b) in vs.iter().zip(vs.iter().cycle().skip(1)) {
+        let cross = a.x * b.y - a.y * b.x;
+        twice_area += cross;
+        moment += (a + b) * cross;
+    }
+    if twice_area.abs() < f64::EPSILON {
+        return Err(DegenerateSampleError::new_err("polygon has zero area"));
+    }
+    Ok(moment / (3.0 * twice_area))
+}
+
+fn sample_radial(cfg: Option<&Bound<'_, PyDict>>, seed: u64, index: u64) -> PyResult<Poly2> {
+    let cfg = match cfg {
+        Some(dict) => radial_cfg_from_dict(dict.as_gil_ref())?,
+        None => RadialCfg::default(),
+    };
+    draw_polygon_radial(cfg, ReplayToken { seed, index }).ok_or_else(|| {
+        InvalidParams::new_err(format!(
+            "radial sampler produced no polygon (seed={seed}, index={index})"
+        ))
+    })
+}
+
+fn vertex_cycle(poly: &Poly2) -> PyResult<Vec<Vector2<f64>>> {
+    match poly.halfspace_intersection() {
+        HalfspaceIntersection::Bounded(vs) => Ok(vs),
+        HalfspaceIntersection::Empty => Err(DegenerateSampleError::new_err("polygon is empty")),
+        HalfspaceIntersection::Unbounded => {
+            Err(UnboundedPolytopeError::new_err("polygon is unbounded"))
+        }
+    }
+}
+
+/// `(n, 2)` vertex array in boundary order.
+fn vertices_to_numpy2<'py>(py: Python<'py>, poly: &Poly2) -> PyResult<Bound<'py, PyArray2<f64>>> {
+    let vs = vertex_cycle(poly)?;
+    let data = vs.iter().flat_map(|v| [v.x, v.y]).collect();
+    Ok(Array2::from_shape_vec((vs.len(), 2), data)
+        .expect("two entries per vertex")
+        .into_pyarray_bound(py))
+}
+
+/// `(m, 3)` array of rows `[n0, n1, c]`.
+fn halfspaces_to_numpy2<'py>(py: Python<'py>, poly: &Poly2) -> Bound<'py, PyArray2<f64>> {
+    let data = poly.hs.iter().flat_map(|h| [h.n.x, h.n.y, h.c]).collect();
+    Array2::from_shape_vec((poly.hs.len(), 3), data)
+        .expect("three entries per halfspace")
+        .into_pyarray_bound(py)
+}
+
+pub fn register(m: &PyModule) -> PyResult<()> {
+    m.add_class::<PyPoly2>()?;
+    m.add_function(wrap_pyfunction!(draw_polygon_radial_py, m)?)?;
+    m.add_function(wrap_pyfunction!(polygon_polar, m)?)?;
+    Ok(())
+}
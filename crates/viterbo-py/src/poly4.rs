@@ -0,0 +1,289 @@
+//! `Poly4` as a Python class.
+//!
+//! The function-per-operation bindings re-parse the H-rep on every call.
+//! `PyPoly4` keeps the Rust polytope alive between calls, so vertices are
+//! enumerated once and reused by `volume()`, `vertices` and friends.
+
+use crate::common::{
+    canonical_error, halfspaces_to_numpy, map_generator_error, map_volume_err,
+    poly4_from_py_halfspaces, poly4_from_py_vertices, poly4_to_py, vertices_to_numpy, PyHalfspaces,
+};
+use crate::errors::{DegenerateSampleError, InvalidParams};
+use crate::poly2::PyPoly2;
+use nalgebra::{Matrix4, Vector4};
+use numpy::{PyArray2, PyReadonlyArray1, PyReadonlyArray2};
+use pyo3::prelude::*;
+use pyo3::types::PyType;
+use viterbo::geom4::{
+    centroid4, contains4, hausdorff4, lagrangian_product as product4, mahler_volume4,
+    minkowski_sum4, polar4, santalo_point4, volume4, volume4_mc, Hs4, Poly4,
+};
+use viterbo::rand4::{is_symplectic, linear_image4};
+
+/// Convex 4-polytope in (x1, x2, y1, y2) coordinates.
+#[pyclass(name = "Poly4", module = "viterbo_native")]
+#[derive(Clone)]
+pub struct PyPoly4 {
+    pub inner: Poly4,
+}
+
+#[pymethods]
+impl PyPoly4 {
+    /// Build from an `(m, 5)` array of rows `[n0, n1, n2, n3, c]` (`n·x ≤ c`).
+    #[classmethod]
+    fn from_halfspaces(_cls: &Bound<'_, PyType>, halfspaces: PyHalfspaces<'_>) -> PyResult<Self> {
+        Ok(Self {
+            inner: poly4_from_py_halfspaces(halfspaces)?,
+        })
+    }
+
+    /// Convex hull of an `(n, 4)` vertex array.
+    #[classmethod]
+    fn from_vertices(
+        _cls: &Bound<'_, PyType>,
+        vertices: PyReadonlyArray2<'_, f64>,
+    ) -> PyResult<Self> {
+        Ok(Self {
+            inner: poly4_from_py_vertices(vertices)?,
+        })
+    }
+
+    fn volume(&mut self) -> PyResult<f64> {
+        volume4(&mut self.inner).map_err(map_volume_err)
+    }
+
+    /// `(n, 4)` vertex array (enumerated on first access).
+    #[getter]
+    fn vertices<'py>(&mut self, py: Python<'py>) -> Bound<'py, PyArray2<f64>> {
+        self.inner.ensure_vertices_from_h();
+        vertices_to_numpy(py, &self.inner)
+    }
+
+    /// `(m, 5)` halfspace array.
+    #[getter]
+    fn halfspaces<'py>(&mut self, py: Python<'py>) -> Bound<'py, PyArray2<f64>> {
+        self.inner.ensure_halfspaces_from_v();
+        halfspaces_to_numpy(py, &self.inner)
+    }
+
+    /// Whether the origin lies in the interior (every offset positive).
+    fn contains_origin(&self) -> bool {
+        !self.inner.h.is_empty() && self.inner.h.iter().all(|h| h.c > 0.0)
+    }
+
+    /// Image `{M x + t : x ∈ K}` under an invertible affine map.
+    fn push_forward(
+        &self,
+        m: PyReadonlyArray2<'_, f64>,
+        t: PyReadonlyArray1<'_, f64>,
+    ) -> PyResult<Self> {
+        let (m, t) = affine_from_py(m, t)?;
+        Ok(Self {
+            inner: affine_image(&self.inner, &m, &t)?,
+        })
+    }
+
+    /// Raise unless the H-rep is canonical: `UnboundedPolytopeError` for an
+    /// unbounded H-rep, `InvalidParams` for any other violation.
+    fn check_canonical(&mut self) -> PyResult<()> {
+        self.inner
+            .check_canonical()
+            .map_err(|msg| canonical_error(&self.inner, msg))
+    }
+
+    /// Pickle as both cached representations, so unpickling skips the
+    /// canonical check and vertex enumeration (workers get the exact object).
+    fn __reduce__(&self, py: Python<'_>) -> PyResult<(PyObject, PyObject)> {
+        let restore = py.get_type_bound::<Self>().getattr("_from_state")?;
+        let state = (
+            halfspaces_to_numpy(py, &self.inner),
+            vertices_to_numpy(py, &self.inner),
+        );
+        Ok((restore.unbind(), state.into_py(py)))
+    }
+
+    /// Inverse of `__reduce__`; trusts its input.
+    #[classmethod]
+    fn _from_state(
+        _cls: &Bound<'_, PyType>,
+        halfspaces: PyReadonlyArray2<'_, f64>,
+        vertices: PyReadonlyArray2<'_, f64>,
+    ) -> PyResult<Self> {
+        let (hs, vs) = (halfspaces.as_array(), vertices.as_array());
+        if hs.ncols() != 5 || vs.ncols() != 4 {
+            return Err(InvalidParams::new_err(
+                "Poly4 state needs (m, 5) and (n, 4) arrays",
+            ));
+        }
+        let mut inner = Poly4::from_h(
+            hs.rows()
+                .into_iter()
+                .map(|r| Hs4::new(Vector4::new(r[0], r[1], r[2], r[3]), r[4]))
+                .collect(),
+        );
+        inner.v = vs
+            .rows()
+            .into_iter()
+            .map(|r| Vector4::new(r[0], r[1], r[2], r[3]))
+            .collect();
+        Ok(Self { inner })
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "Poly4(halfspaces={}, vertices={})",
+            self.inner.h.len(),
+            self.inner.v.len()
+        )
+    }
+}
+
+/// Push an H-rep forward under `x ↦ M x + t`; returns `{vertices, halfspaces}`.
+///
+/// `M` must be symplectic unless `check_symplectic=False`, so augmented rows
+/// keep their capacity label.
+#[pyfunction]
+#[pyo3(signature = (halfspaces, m, t, check_symplectic=true))]
+fn poly4_push_forward(
+    py: Python<'_>,
+    halfspaces: PyHalfspaces<'_>,
+    m: PyReadonlyArray2<'_, f64>,
+    t: PyReadonlyArray1<'_, f64>,
+    check_symplectic: bool,
+) -> PyResult<PyObject> {
+    let poly = poly4_from_py_halfspaces(halfspaces)?;
+    let (m, t) = affine_from_py(m, t)?;
+    if check_symplectic && !is_symplectic(&m) {
+        return Err(InvalidParams::new_err(
+            "poly4_push_forward: M is not symplectic (pass check_symplectic=False to allow)",
+        ));
+    }
+    poly4_to_py(py, affine_image(&poly, &m, &t)?)
+}
+
+/// Polar dual about the origin of an `(m, 5)` H-rep, as `{vertices, halfspaces}`.
+#[pyfunction]
+fn poly4_polar(py: Python<'_>, halfspaces: PyHalfspaces<'_>) -> PyResult<PyObject> {
+    let mut poly = poly4_from_py_halfspaces(halfspaces)?;
+    let dual = polar4(&mut poly)
+        .ok_or_else(|| DegenerateSampleError::new_err("polar undefined: origin not interior"))?;
+    poly4_to_py(py, dual)
+}
+
+/// Mahler volume `vol(K) · vol((K − s)°)` of an `(m, 5)` H-rep.
+///
+/// `center` picks `s`: `"origin"` (default), `"centroid"` or `"santalo"`
+/// (the minimizing centre, as in Mahler-product experiments).
+#[pyfunction]
+#[pyo3(signature = (halfspaces, center="origin"))]
+fn poly4_mahler_volume(halfspaces: PyHalfspaces<'_>, center: &str) -> PyResult<f64> {
+    let mut poly = poly4_from_py_halfspaces(halfspaces)?;
+    let s = match center {
+        "origin" => Some(Vector4::zeros()),
+        "centroid" => centroid4(&mut poly),
+        "santalo" => santalo_point4(&mut poly),
+        other => {
+            return Err(InvalidParams::new_err(format!(
+                "unknown center '{other}' (expected origin, centroid, santalo)"
+            )))
+        }
+    }
+    .ok_or_else(|| DegenerateSampleError::new_err("polytope is lower-dimensional"))?;
+    mahler_volume4(&mut poly, &s).ok_or_else(|| {
+        DegenerateSampleError::new_err(format!("Mahler volume undefined: {center} not interior"))
+    })
+}
+
+/// Monte Carlo `(estimate, standard_error)` of the volume of an `(m, 5)` H-rep.
+///
+/// Rejection sampling from the vertex bounding box, seeded by `seed`; a quick
+/// check for large-facet bodies where the exact `volume()` is overkill.
+#[pyfunction]
+fn poly4_volume_mc(
+    halfspaces: PyHalfspaces<'_>,
+    n_samples: usize,
+    seed: u64,
+) -> PyResult<(f64, f64)> {
+    let mut poly = poly4_from_py_halfspaces(halfspaces)?;
+    if n_samples == 0 {
+        return Err(InvalidParams::new_err("n_samples must be positive"));
+    }
+    volume4_mc(&mut poly, n_samples, seed)
+        .ok_or_else(|| DegenerateSampleError::new_err("polytope is empty or lower-dimensional"))
+}
+
+/// Lagrangian product `K × T` (`K` in the `x`-plane, `T` in the `y`-plane).
+#[pyfunction]
+fn lagrangian_product(poly_a: &PyPoly2, poly_b: &PyPoly2) -> PyResult<PyPoly4> {
+    let inner = product4(&poly_a.inner, &poly_b.inner).ok_or_else(|| {
+        DegenerateSampleError::new_err("lagrangian_product: factors must be bounded polygons")
+    })?;
+    Ok(PyPoly4 { inner })
+}
+
+/// Minkowski sum `K + L`.
+#[pyfunction]
+fn minkowski_sum(a: &PyPoly4, b: &PyPoly4) -> PyPoly4 {
+    PyPoly4 {
+        inner: minkowski_sum4(&mut a.inner.clone(), &mut b.inner.clone()),
+    }
+}
+
+/// `inner ⊆ outer` for two `(m, 5)` H-reps, up to a relative tolerance.
+#[pyfunction]
+fn poly4_contains(hs_outer: PyHalfspaces<'_>, hs_inner: PyHalfspaces<'_>) -> PyResult<bool> {
+    let mut outer = poly4_from_py_halfspaces(hs_outer)?;
+    let mut inner = poly4_from_py_halfspaces(hs_inner)?;
+    Ok(contains4(&mut outer, &mut inner))
+}
+
+/// Hausdorff distance between two `(m, 5)` H-reps (exact, via face projections).
+#[pyfunction]
+fn poly4_hausdorff(hs_a: PyHalfspaces<'_>, hs_b: PyHalfspaces<'_>) -> PyResult<f64> {
+    let mut a = poly4_from_py_halfspaces(hs_a)?;
+    let mut b = poly4_from_py_halfspaces(hs_b)?;
+    Ok(hausdorff4(&mut a, &mut b))
+}
+
+fn affine_from_py(
+    m: PyReadonlyArray2<'_, f64>,
+    t: PyReadonlyArray1<'_, f64>,
+) -> PyResult<(Matrix4<f64>, Vector4<f64>)> {
+    let (m, t) = (m.as_array(), t.as_array());
+    if m.shape() != [4, 4] || t.len() != 4 {
+        return Err(InvalidParams::new_err(
+            "push_forward expects M of shape (4, 4) and t of shape (4,)",
+        ));
+    }
+    Ok((
+        Matrix4::from_fn(|i, j| m[[i, j]]),
+        Vector4::new(t[0], t[1], t[2], t[3]),
+    ))
+}
+
+/// `M K + t`: linear image, then offsets `c + n·t` and shifted vertices.
+fn affine_image(poly: &Poly4, m: &Matrix4<f64>, t: &Vector4<f64>) -> PyResult<Poly4> {
+    let image = linear_image4(poly, m).map_err(map_generator_error)?;
+    let mut shifted = Poly4::from_h(
+        image
+            .h
+            .iter()
+            .map(|h| Hs4::new(h.n, h.c + h.n.dot(t)))
+            .collect(),
+    );
+    shifted.v = image.v.iter().map(|v| v + t).collect();
+    Ok(shifted)
+}
+
+pub fn register(m: &PyModule) -> PyResult<()> {
+    m.add_class::<PyPoly4>()?;
+    m.add_function(wrap_pyfunction!(poly4_push_forward, m)?)?;
+    m.add_function(wrap_pyfunction!(poly4_polar, m)?)?;
+    m.add_function(wrap_pyfunction!(poly4_mahler_volume, m)?)?;
+    m.add_function(wrap_pyfunction!(poly4_volume_mc, m)?)?;
+    m.add_function(wrap_pyfunction!(lagrangian_product, m)?)?;
+    m.add_function(wrap_pyfunction!(minkowski_sum, m)?)?;
+    m.add_function(wrap_pyfunction!(poly4_contains, m)?)?;
+    m.add_function(wrap_pyfunction!(poly4_hausdorff, m)?)?;
+    Ok(())
+}
@@ -2,19 +2,23 @@
 //!
 //! The functions exposed here intentionally mirror the Python expectations:
 //! - inputs are plain `dict`s / lists so configs round-trip through JSON;
-//! - outputs are small dictionaries (`vertices`, `halfspaces`) that higher
-//!   layers can convert to richer types without touching this module.
+//! - outputs are small dictionaries of NumPy arrays (`vertices` `(n, 4)`,
+//!   `halfspaces` `(m, 5)`) that higher layers can convert to richer types
+//!   without touching this module.
 
+use crate::common::{map_generator_error, poly4_to_py, py_to_json};
+use crate::errors::InvalidParams;
 use nalgebra::Matrix4;
-use pyo3::exceptions::PyValueError;
+use numpy::ndarray::Array2;
+use numpy::{IntoPyArray, PyArray2};
 use pyo3::prelude::*;
-use pyo3::types::{PyAny, PyDict, PyList};
+use pyo3::types::{PyAny, PyDict};
 use viterbo::geom2::rand::{
     Bounds2, RadialCfg, ReplayToken as Poly2ReplayToken, VertexCount,
 };
-use viterbo::geom4::Poly4;
+use serde_json::json;
 use viterbo::rand4::{
-    GeneratorError, MahlerProductGenerator, MahlerProductParams, RegularProductEnumParams,
+    from_config, random_symplectic4, DirectionMode, DirectionSampler, MahlerProductGenerator, MahlerProductParams, RegularProductEnumParams,
     RegularProductEnumerator, RegularProductReplay, RegularPolygonSpec,
     SymmetricHalfspaceGenerator, SymmetricHalfspaceParams,
 };
@@ -23,6 +27,8 @@ pub fn register(py: Python<'_>, m: &PyModule) -> PyResult<()> {
     m.add_function(wrap_pyfunction!(rand4_symmetric_halfspace_sample, m)?)?;
     m.add_function(wrap_pyfunction!(rand4_mahler_product_sample, m)?)?;
     m.add_function(wrap_pyfunction!(rand4_regular_product_sample, m)?)?;
+    m.add_function(wrap_pyfunction!(rand4_regenerate, m)?)?;
+    m.add_function(wrap_pyfunction!(random_symplectic_4, m)?)?;
     // Keep the interpreter handle alive for potential future stateful sources.
     let _ = py;
     Ok(())
@@ -82,8 +88,42 @@ fn rand4_regular_product_sample(
     Ok(Some(obj))
 }
 
-fn map_generator_error(err: GeneratorError) -> PyErr {
-    PyValueError::new_err(err.to_string())
+/// Rebuild the row behind a stored replay value, for any registry family.
+///
+/// `family`/`params` are the atlas source entry; `random_anisotropy` must be
+/// passed again when the row came from an anisotropic stream.
+#[pyfunction]
+#[pyo3(signature = (family, params, replay, random_anisotropy=None))]
+fn rand4_regenerate(
+    py: Python<'_>,
+    family: &str,
+    params: &Bound<'_, PyDict>,
+    replay: &Bound<'_, PyAny>,
+    random_anisotropy: Option<&Bound<'_, PyDict>>,
+) -> PyResult<PyObject> {
+    let mut config = json!({ "family": family, "params": py_to_json(params.as_any())? });
+    if let Some(dist) = random_anisotropy {
+        config["random_anisotropy"] = py_to_json(dist.as_any())?;
+    }
+    let gen = from_config(&config).map_err(map_generator_error)?;
+    let poly = gen
+        .regenerate_json(&py_to_json(replay)?)
+        .map_err(map_generator_error)?;
+    poly4_to_py(py, poly)
+}
+
+/// Random `(4, 4)` symplectic matrix `K₁ · diag(e^s, e^−s) · K₂` (`K` Haar in U(2),
+/// `|s_k| ≤ max_squeeze`), reproducible from `seed`.
+#[pyfunction]
+#[pyo3(signature = (seed, max_squeeze=1.0))]
+fn random_symplectic_4(
+    py: Python<'_>,
+    seed: u64,
+    max_squeeze: f64,
+) -> PyResult<Bound<'_, PyArray2<f64>>> {
+    let a = random_symplectic4(seed, max_squeeze).map_err(map_generator_error)?;
+    let rows = Array2::from_shape_fn((4, 4), |(i, j)| a[(i, j)]);
+    Ok(rows.into_pyarray_bound(py))
 }
 
 fn symmetric_params_from_dict(dict: &PyDict) -> PyResult<SymmetricHalfspaceParams> {
@@ -94,11 +134,24 @@ fn symmetric_params_from_dict(dict: &PyDict) -> PyResult<SymmetricHalfspaceParam
         Some(value) if !value.is_none() => Some(matrix4_from_any(value)?),
         _ => None,
     };
+    let sampler = match dict.get_item("sampler")? {
+        Some(value) if !value.is_none() => match DirectionMode::try_from(value.extract::<String>()?)
+        {
+            Ok(DirectionMode::Iid(sampler)) => sampler,
+            _ => {
+                return Err(InvalidParams::new_err(
+                    "sampler must be 'gaussian' or 'legacy_cube'",
+                ))
+            }
+        },
+        _ => DirectionSampler::default(),
+    };
     Ok(SymmetricHalfspaceParams {
         directions,
         radius_min,
         radius_max,
         anisotropy,
+        sampler,
     })
 }
 
@@ -121,10 +174,10 @@ fn mahler_params_from_dict(dict: &PyDict) -> PyResult<MahlerProductParams> {
 fn regular_product_params_from_dict(dict: &PyDict) -> PyResult<RegularProductEnumParams> {
     let factors_a_any = dict
         .get_item("factors_a")?
-        .ok_or_else(|| PyValueError::new_err("missing 'factors_a' list"))?;
+        .ok_or_else(|| InvalidParams::new_err("missing 'factors_a' list"))?;
     let factors_b_any = dict
         .get_item("factors_b")?
-        .ok_or_else(|| PyValueError::new_err("missing 'factors_b' list"))?;
+        .ok_or_else(|| InvalidParams::new_err("missing 'factors_b' list"))?;
     let factors_a = polygon_specs_from_seq(factors_a_any, "factors_a")?;
     let factors_b = polygon_specs_from_seq(factors_b_any, "factors_b")?;
     let max_pairs = match dict.get_item("max_pairs")? {
@@ -144,7 +197,7 @@ fn polygon_specs_from_seq(obj: &PyAny, label: &str) -> PyResult<Vec<RegularPolyg
     for (idx, item_res) in iter.enumerate() {
         let item = item_res?;
         let dict = item.downcast::<PyDict>().map_err(|_| {
-            PyValueError::new_err(format!(
+            InvalidParams::new_err(format!(
                 "{label}[{idx}] must be a dict with 'sides', 'rotation', 'scale'"
             ))
         })?;
@@ -155,14 +208,14 @@ fn polygon_specs_from_seq(obj: &PyAny, label: &str) -> PyResult<Vec<RegularPolyg
         specs.push(spec);
     }
     if specs.is_empty() {
-        return Err(PyValueError::new_err(format!(
+        return Err(InvalidParams::new_err(format!(
             "{label} must contain at least one polygon"
         )));
     }
     Ok(specs)
 }
 
-fn radial_cfg_from_dict(dict: &PyDict) -> PyResult<RadialCfg> {
+pub(crate) fn radial_cfg_from_dict(dict: &PyDict) -> PyResult<RadialCfg> {
     let mut cfg = RadialCfg::default();
     if let Some(vc_any) = dict.get_item("vertex_count")? {
         cfg.vertex_count = parse_vertex_count(vc_any)?;
@@ -182,7 +235,7 @@ fn radial_cfg_from_dict(dict: &PyDict) -> PyResult<RadialCfg> {
     Ok(cfg)
 }
 
-fn bounds_from_dict(dict: &PyDict) -> PyResult<Bounds2> {
+pub(crate) fn bounds_from_dict(dict: &PyDict) -> PyResult<Bounds2> {
     Ok(Bounds2 {
         r_in_min: get_with_default(dict, "r_in_min", 0.1)?,
         r_out_max: get_with_default(dict, "r_out_max", 2.0)?,
@@ -194,7 +247,7 @@ fn parse_vertex_count(obj: &PyAny) -> PyResult<VertexCount> {
         return Ok(VertexCount::Fixed(fixed));
     }
     let dict = obj.downcast::<PyDict>().map_err(|_| {
-        PyValueError::new_err("vertex_count must be an int or {\"kind\": ...}")
+        InvalidParams::new_err("vertex_count must be an int or {\"kind\": ...}")
     })?;
     let kind = get_required::<String>(dict, "kind")?;
     match kind.as_str() {
@@ -207,7 +260,7 @@ fn parse_vertex_count(obj: &PyAny) -> PyResult<VertexCount> {
             let max = get_required::<usize>(dict, "max")?;
             Ok(VertexCount::Uniform { min, max })
         }
-        other => Err(PyValueError::new_err(format!(
+        other => Err(InvalidParams::new_err(format!(
             "vertex_count.kind must be 'fixed' or 'uniform', got {other}"
         ))),
     }
@@ -216,14 +269,14 @@ fn parse_vertex_count(obj: &PyAny) -> PyResult<VertexCount> {
 fn matrix4_from_any(obj: &PyAny) -> PyResult<Matrix4<f64>> {
     let rows: Vec<Vec<f64>> = obj.extract()?;
     if rows.len() != 4 {
-        return Err(PyValueError::new_err(
+        return Err(InvalidParams::new_err(
             "anisotropy matrices must have four rows",
         ));
     }
     let mut data = [0.0f64; 16];
     for (i, row) in rows.into_iter().enumerate() {
         if row.len() != 4 {
-            return Err(PyValueError::new_err(
+            return Err(InvalidParams::new_err(
                 "anisotropy matrices must have four columns",
             ));
         }
@@ -234,28 +287,10 @@ fn matrix4_from_any(obj: &PyAny) -> PyResult<Matrix4<f64>> {
     Ok(Matrix4::from_row_slice(&data))
 }
 
-fn poly4_to_py(py: Python<'_>, mut poly: Poly4) -> PyResult<PyObject> {
-    poly.ensure_vertices_from_h();
-    poly.ensure_halfspaces_from_v();
-
-    let verts = PyList::empty(py);
-    for v in &poly.v {
-        verts.append(PyList::new(py, [v[0], v[1], v[2], v[3]]))?;
-    }
-    let halfspaces = PyList::empty(py);
-    for h in &poly.h {
-        halfspaces.append(PyList::new(py, [h.n[0], h.n[1], h.n[2], h.n[3], h.c]))?;
-    }
-    let dict = PyDict::new(py);
-    dict.set_item("vertices", verts)?;
-    dict.set_item("halfspaces", halfspaces)?;
-    Ok(dict.into())
-}
-
 fn get_required<'py, T: FromPyObject<'py>>(dict: &'py PyDict, key: &str) -> PyResult<T> {
     match dict.get_item(key)? {
         Some(value) => value.extract(),
-        None => Err(PyValueError::new_err(format!(
+        None => Err(InvalidParams::new_err(format!(
             "missing required key '{key}'"
         ))),
     }
@@ -0,0 +1,60 @@
+//! Python iterators over the Rust streaming generators.
+//!
+//! One-shot `rand4_*_sample` calls rebuild a generator per row, so Python
+//! has to manage indices and loses the streaming semantics (finite
+//! enumerations, per-family replay values). `PolytopeStream` wraps the
+//! registry stream (`viterbo::rand4::from_config`) and implements the
+//! iterator protocol; configs use the atlas schema
+//! `{"family": ..., "params": {...}, "seed": ...}`.
+
+use crate::common::{json_to_py, map_generator_error, poly4_to_py, py_to_json};
+use pyo3::prelude::*;
+use pyo3::types::PyDict;
+use serde_json::Value;
+use viterbo::rand4::{from_config, ErasedGenerator};
+
+/// Iterator yielding `{"vertices", "halfspaces", "params", "replay"}` dicts.
+#[pyclass(name = "PolytopeStream", module = "viterbo_native", unsendable)]
+pub struct PyPolytopeStream {
+    inner: Box<dyn ErasedGenerator>,
+    params: Value,
+}
+
+#[pymethods]
+impl PyPolytopeStream {
+    #[new]
+    fn new(config: &Bound<'_, PyDict>) -> PyResult<Self> {
+        let config = py_to_json(config.as_any())?;
+        let inner = from_config(&config).map_err(map_generator_error)?;
+        Ok(Self {
+            inner,
+            params: config.get("params").cloned().unwrap_or(Value::Null),
+        })
+    }
+
+    /// Registry name of the family.
+    #[getter]
+    fn family(&self) -> &'static str {
+        self.inner.kind().name()
+    }
+
+    fn __iter__(slf: PyRef<'_, Self>) -> PyRef<'_, Self> {
+        slf
+    }
+
+    fn __next__(&mut self, py: Python<'_>) -> PyResult<Option<PyObject>> {
+        let Some(sample) = self.inner.next_sample().map_err(map_generator_error)? else {
+            return Ok(None);
+        };
+        let row = poly4_to_py(py, sample.polytope)?;
+        let dict = row.downcast_bound::<PyDict>(py)?;
+        dict.set_item("params", json_to_py(py, &self.params)?)?;
+        dict.set_item("replay", json_to_py(py, &sample.replay)?)?;
+        Ok(Some(row))
+    }
+}
+
+pub fn register(m: &PyModule) -> PyResult<()> {
+    m.add_class::<PyPolytopeStream>()?;
+    Ok(())
+}
@@ -20,10 +20,10 @@ use viterbo::geom2::rand::{
     VertexCount,
 };
 use viterbo::rand4::{
-    MahlerProductGenerator, MahlerProductParams, PolytopeGenerator4, RandomFacesGenerator,
-    RandomFacesParams, RandomVerticesGenerator, RandomVerticesParams, RegularPolygonSpec,
-    RegularProductEnumParams, RegularProductEnumerator, SymmetricHalfspaceGenerator,
-    SymmetricHalfspaceParams,
+    DirectionSampler, MahlerProductGenerator, MahlerProductParams, PolytopeGenerator4,
+    RandomFacesGenerator, RandomFacesParams, RandomVerticesGenerator, RandomVerticesParams,
+    RegularPolygonSpec, RegularProductEnumParams, RegularProductEnumerator,
+    SymmetricHalfspaceGenerator, SymmetricHalfspaceParams,
 };
 
 fn bench_gen_2d(c: &mut Criterion) {
@@ -87,6 +87,7 @@ fn bench_gen_4d(c: &mut Criterion) {
         radius_min: 0.4,
         radius_max: 1.2,
         anisotropy: None,
+        sampler: DirectionSampler::default(),
         max_attempts: 10,
     };
     group.bench_function(BenchmarkId::new("random_vertices_next", "5-25"), |b| {
@@ -106,6 +107,7 @@ fn bench_gen_4d(c: &mut Criterion) {
         radius_min: 0.4,
         radius_max: 1.2,
         anisotropy: None,
+        sampler: DirectionSampler::default(),
         max_attempts: 20,
     };
     group.bench_function(BenchmarkId::new("random_faces_next", "5-10"), |b| {
@@ -126,6 +128,7 @@ fn bench_gen_4d(c: &mut Criterion) {
         anisotropy: Some(Matrix4::new(
             1.1, 0.0, 0.0, 0.0, 0.0, 0.9, 0.0, 0.0, 0.0, 0.0, 1.05, 0.0, 0.0, 0.0, 0.0, 0.95,
         )),
+        sampler: DirectionSampler::default(),
     };
     group.bench_function(
         BenchmarkId::new("sym_halfspaces_generate_single", "d5"),
@@ -11,8 +11,8 @@
 //! Ticket: 8ed3-2d-4d-generators
 
 use viterbo::rand4::{
-    PolytopeGenerator4, RandomFacesGenerator, RandomFacesParams, RandomVerticesGenerator,
-    RandomVerticesParams,
+    DirectionSampler, PolytopeGenerator4, RandomFacesGenerator, RandomFacesParams,
+    RandomVerticesGenerator, RandomVerticesParams,
 };
 
 fn main() {
@@ -35,6 +35,7 @@ fn show_vertices_mode() {
         radius_min: 0.5,
         radius_max: 1.5,
         anisotropy: None,
+        sampler: DirectionSampler::default(),
         max_attempts: 10,
     };
     let mut gen = RandomVerticesGenerator::new(params, 2025).unwrap();
@@ -55,6 +56,7 @@ fn show_faces_mode() {
         radius_min: 0.4,
         radius_max: 1.2,
         anisotropy: None,
+        sampler: DirectionSampler::default(),
         max_attempts: 20,
     };
     let mut gen = RandomFacesGenerator::new(params, 777).unwrap();
@@ -0,0 +1,12 @@
+//! Curated entry points for internal callers.
+//!
+//! Why this file exists
+//! - Benches, examples and the Python bindings mostly need “a polytope, its
+//!   graph, its capacity”; one glob import covers that without tracking which
+//!   submodule owns what. Not a stable surface (AGENTS.md, “API Policy”).
+
+pub use crate::geom4::{reeb_on_facets, volume4, Hs4, Poly4};
+pub use crate::oriented_edge::{
+    build_graph, solve, solve_with_budget, solve_with_defaults, BudgetExceeded, EdgeData, FacetId,
+    GeomCfg, Graph, RidgeId,
+};
@@ -0,0 +1,370 @@
+//! 2D convex polygons in H-representation, for ridge charts and Mahler factors.
+//!
+//! Why this file exists
+//! - The oriented-edge search pushes small polygons (ridge domains, candidate
+//!   sets) through affine chart maps millions of times. An H-rep makes a
+//!   push-forward pure algebra (`n' = M⁻ᵀ n`, `c' = c + n'·t`) and an
+//!   intersection a concatenation; vertices are only built on demand.
+//! - `Poly2` is the strict form from the design notes: unit normals sorted by
+//!   angle, parallels coalesced to the most restrictive offset. Every
+//!   constructor goes through `insert_halfspace`, so the invariant holds after
+//!   intersections and push-forwards too. An infeasible row with zero normal
+//!   (`0·x ≤ c < 0`) is kept as a marker of the empty set.
+//! - `halfspace_intersection` walks each boundary line and clips it against
+//!   all other rows (O(m²), fine at ridge sizes). Angle order makes the
+//!   surviving edges a counter-clockwise cycle, so the vertices come out ccw.
+//!
+//! References
+//! - TH: docs/src/thesis/geom2d_polytopes.md
+//! - Code: crates/viterbo/src/oriented_edge/dfs.rs (main consumer)
+
+pub mod rand;
+
+use nalgebra::{Matrix2, Vector2};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Relative tolerance for feasibility and for coalescing parallel rows.
+const EPS_REL: f64 = 1e-9;
+
+/// Half-plane `n·x ≤ c`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Hs2 {
+    pub n: Vector2<f64>,
+    pub c: f64,
+}
+
+impl Hs2 {
+    pub fn new(n: Vector2<f64>, c: f64) -> Self {
+        Self { n, c }
+    }
+}
+
+/// Affine map `x ↦ m x + t`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Aff2 {
+    pub m: Matrix2<f64>,
+    pub t: Vector2<f64>,
+}
+
+impl Aff2 {
+    pub fn identity() -> Self {
+        Self {
+            m: Matrix2::identity(),
+            t: Vector2::zeros(),
+        }
+    }
+
+    pub fn apply(&self, x: &Vector2<f64>) -> Vector2<f64> {
+        self.m * x + self.t
+    }
+
+    /// `self ∘ inner`: first `inner`, then `self`.
+    pub fn compose(&self, inner: &Aff2) -> Aff2 {
+        Aff2 {
+            m: self.m * inner.m,
+            t: self.m * inner.t + self.t,
+        }
+    }
+
+    /// Inverse map; `None` if the linear part is singular.
+    pub fn inverse(&self) -> Option<Aff2> {
+        let inv = self.m.try_inverse()?;
+        Some(Aff2 {
+            m: inv,
+            t: -(inv * self.t),
+        })
+    }
+}
+
+/// Outcome of intersecting the half-planes of a `Poly2`.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum HalfspaceIntersection {
+    /// No point, or only a point or segment (zero area).
+    Empty,
+    /// Nonempty with a recession direction.
+    Unbounded,
+    /// Vertices in counter-clockwise order.
+    Bounded(Vec<Vector2<f64>>),
+}
+
+/// Strict H-rep: unit normals sorted by angle, parallels coalesced.
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Poly2 {
+    pub hs: Vec<Hs2>,
+}
+
+impl Poly2 {
+    /// Polygon of the given rows (any order, any normal length).
+    pub fn from_halfspaces(hs: impl IntoIterator<Item = Hs2>) -> Self {
+        let mut poly = Poly2::default();
+        for h in hs {
+            poly.insert_halfspace(h);
+        }
+        poly
+    }
+
+    /// Add `n·x ≤ c`, keeping the normals unit, sorted and free of parallels.
+    pub fn insert_halfspace(&mut self, h: Hs2) {
+        let norm = h.n.norm();
+        if norm.is_nan() || norm <= 0.0 {
+            // `0·x ≤ c`: trivially true, or the empty-set marker.
+            if h.c < 0.0 && !self.is_marked_empty() {
+                self.hs.insert(0, Hs2::new(Vector2::zeros(), -1.0));
+            }
+            return;
+        }
+        let (n, c) = (h.n / norm, h.c / norm);
+        let angle = n.y.atan2(n.x);
+        let pos = self
+            .hs
+            .partition_point(|g| g.n == Vector2::zeros() || g.n.y.atan2(g.n.x) < angle);
+        for k in [pos.wrapping_sub(1), pos] {
+            if let Some(g) = self.hs.get_mut(k) {
+                if (g.n - n).norm() <= EPS_REL {
+                    g.c = g.c.min(c);
+                    return;
+                }
+            }
+        }
+        self.hs.insert(pos, Hs2::new(n, c));
+    }
+
+    /// `self ∩ other`.
+    pub fn intersect(&self, other: &Poly2) -> Poly2 {
+        let mut out = self.clone();
+        for h in &other.hs {
+            out.insert_halfspace(*h);
+        }
+        out
+    }
+
+    /// Whether `x` satisfies every row up to `eps`.
+    pub fn contains(&self, x: &Vector2<f64>, eps: f64) -> bool {
+        self.hs.iter().all(|h| h.n.dot(x) <= h.c + eps)
+    }
+
+    /// Image under `f`; `None` if `f` is not invertible.
+    pub fn push_forward(&self, f: &Aff2) -> Option<Poly2> {
+        if f.m.determinant().abs() <= f64::EPSILON * f.m.norm_squared() {
+            return None;
+        }
+        let inv_t = f.m.try_inverse()?.transpose();
+        let mut out = Poly2::default();
+        for h in &self.hs {
+            if h.n == Vector2::zeros() {
+                out.insert_halfspace(*h);
+                continue;
+            }
+            let n = inv_t * h.n;
+            out.insert_halfspace(Hs2::new(n, h.c + n.dot(&f.t)));
+        }
+        Some(out)
+    }
+
+    /// Convex hull of `points` (Andrew's monotone chain); empty if the hull
+    /// has no interior.
+    pub fn from_points_convex_hull(points: &[Vector2<f64>]) -> Poly2 {
+        let hull = convex_hull(points);
+        let mut poly = Poly2::default();
+        if hull.len() < 3 {
+            poly.insert_halfspace(Hs2::new(Vector2::zeros(), -1.0));
+            return poly;
+        }
+        for (a, b) in hull.iter().zip(hull.iter().cycle().skip(1)) {
+            let d = b - a;
+            poly.insert_halfspace(Hs2::new(Vector2::new(d.y, -d.x), d.y * a.x - d.x * a.y));
+        }
+        poly
+    }
+
+    /// Vertices of `{x : n·x ≤ c for all rows}`.
+    pub fn halfspace_intersection(&self) -> HalfspaceIntersection {
+        if self.is_marked_empty() {
+            return HalfspaceIntersection::Empty;
+        }
+        if self.hs.is_empty() {
+            return HalfspaceIntersection::Unbounded;
+        }
+        let scale = self.hs.iter().map(|h| h.c.abs()).fold(1.0, f64::max);
+        let eps = EPS_REL * scale;
+        let mut verts: Vec<Vector2<f64>> = Vec::with_capacity(self.hs.len());
+        for (i, h) in self.hs.iter().enumerate() {
+            // Boundary line `p + s·d`, traversed counter-clockwise.
+            let p = h.n * h.c;
+            let d = Vector2::new(-h.n.y, h.n.x);
+            let (mut lo, mut hi) = (f64::NEG_INFINITY, f64::INFINITY);
+            let mut feasible = true;
+            for (j, g) in self.hs.iter().enumerate() {
+                if j == i {
+                    continue;
+                }
+                let slope = g.n.dot(&d);
+                let slack = g.c - g.n.dot(&p);
+                if slope.abs() <= EPS_REL {
+                    if slack < -eps {
+                        feasible = false;
+                        break;
+                    }
+                } else if slope > 0.0 {
+                    hi = hi.min(slack / slope);
+                } else {
+                    lo = lo.max(slack / slope);
+                }
+            }
+            if !feasible || lo > hi + eps {
+                continue;
+            }
+            if !lo.is_finite() || !hi.is_finite() {
+                return HalfspaceIntersection::Unbounded;
+            }
+            let start = p + d * lo;
+            if verts.last().is_none_or(|v| (v - start).norm() > eps) {
+                verts.push(start);
+            }
+        }
+        while verts.len() > 1 && (verts[0] - verts[verts.len() - 1]).norm() <= eps {
+            verts.pop();
+        }
+        if verts.len() < 3 || signed_area(&verts) <= eps * eps {
+            return HalfspaceIntersection::Empty;
+        }
+        HalfspaceIntersection::Bounded(verts)
+    }
+
+    fn is_marked_empty(&self) -> bool {
+        self.hs.first().is_some_and(|h| h.n == Vector2::zeros())
+    }
+}
+
+/// Signed area of a closed polygon (positive for ccw order).
+pub fn signed_area(verts: &[Vector2<f64>]) -> f64 {
+    0.5 * verts
+        .iter()
+        .zip(verts.iter().cycle().skip(1))
+        .map(|(a, b)| a.x * b.y - a.y * b.x)
+        .sum::<f64>()
+}
+
+/// Strictly convex hull in ccw order (collinear points dropped).
+pub(crate) fn convex_hull(points: &[Vector2<f64>]) -> Vec<Vector2<f64>> {
+    let mut pts: Vec<Vector2<f64>> = points.to_vec();
+    pts.sort_by(|a, b| a.x.total_cmp(&b.x).then(a.y.total_cmp(&b.y)));
+    pts.dedup();
+    if pts.len() < 3 {
+        return pts;
+    }
+    let cross = |o: &Vector2<f64>, a: &Vector2<f64>, b: &Vector2<f64>| {
+        (a.x - o.x) * (b.y - o.y) - (a.y - o.y) * (b.x - o.x)
+    };
+    let mut hull: Vec<Vector2<f64>> = Vec::with_capacity(2 * pts.len());
+    for pass in 0..2 {
+        let start = hull.len();
+        let iter: Box<dyn Iterator<Item = &Vector2<f64>>> = if pass == 0 {
+            Box::new(pts.iter())
+        } else {
+            Box::new(pts.iter().rev())
+        };
+        for p in iter {
+            while hull.len() >= start + 2
+                && cross(&hull[hull.len() - 2], &hull[hull.len() - 1], p) <= 0.0
+            {
+                hull.pop();
+            }
+            hull.push(*p);
+        }
+        hull.pop();
+    }
+    hull
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn square(r: f64) -> Poly2 {
+        Poly2::from_halfspaces([
+            Hs2::new(Vector2::new(1.0, 0.0), r),
+            Hs2::new(Vector2::new(0.0, 1.0), r),
+            Hs2::new(Vector2::new(-1.0, 0.0), r),
+            Hs2::new(Vector2::new(0.0, -1.0), r),
+        ])
+    }
+
+    #[test]
+    fn square_vertices_are_ccw() {
+        let HalfspaceIntersection::Bounded(vs) = square(1.0).halfspace_intersection() else {
+            panic!("square must be bounded");
+        };
+        assert_eq!(vs.len(), 4);
+        assert!((signed_area(&vs) - 4.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn parallels_coalesce_and_contradictions_are_empty() {
+        let mut p = square(1.0);
+        p.insert_halfspace(Hs2::new(Vector2::new(2.0, 0.0), 1.0));
+        assert_eq!(p.hs.len(), 4);
+        assert!(p.hs.iter().any(|h| h.n.x == 1.0 && h.c == 0.5));
+        p.insert_halfspace(Hs2::new(Vector2::new(-1.0, 0.0), -0.75));
+        assert_eq!(p.halfspace_intersection(), HalfspaceIntersection::Empty);
+        let open = Poly2::from_halfspaces([Hs2::new(Vector2::new(1.0, 0.0), 1.0)]);
+        assert_eq!(
+            open.halfspace_intersection(),
+            HalfspaceIntersection::Unbounded
+        );
+        assert_eq!(
+            Poly2::default().halfspace_intersection(),
+            HalfspaceIntersection::Unbounded
+        );
+    }
+
+    #[test]
+    fn push_forward_matches_vertex_images() {
+        let f = Aff2 {
+            m: Matrix2::new(1.2, 0.1, -0.05, 0.9),
+            t: Vector2::new(0.3, -0.2),
+        };
+        let p = square(1.0);
+        let HalfspaceIntersection::Bounded(vs) =
+            p.push_forward(&f).unwrap().halfspace_intersection()
+        else {
+            panic!("image must be bounded");
+        };
+        assert_eq!(vs.len(), 4);
+        for v in [(1.0, 1.0), (-1.0, 1.0), (-1.0, -1.0), (1.0, -1.0)] {
+            let img = f.apply(&Vector2::new(v.0, v.1));
+            assert!(vs.iter().any(|w| (w - img).norm() < 1e-9));
+        }
+        let back = f.inverse().unwrap().compose(&f);
+        assert!((back.m - Matrix2::identity()).norm() < 1e-12 && back.t.norm() < 1e-12);
+    }
+
+    #[test]
+    fn hull_drops_interior_and_collinear_points() {
+        let pts = [
+            Vector2::new(0.0, 0.0),
+            Vector2::new(2.0, 0.0),
+            Vector2::new(1.0, 0.0),
+            Vector2::new(2.0, 2.0),
+            Vector2::new(0.0, 2.0),
+            Vector2::new(1.0, 1.0),
+        ];
+        let HalfspaceIntersection::Bounded(vs) =
+            Poly2::from_points_convex_hull(&pts).halfspace_intersection()
+        else {
+            panic!("hull must be bounded");
+        };
+        assert_eq!(vs.len(), 4);
+        assert!((signed_area(&vs) - 4.0).abs() < 1e-12);
+        let flat = [Vector2::new(0.0, 0.0), Vector2::new(1.0, 1.0)];
+        assert_eq!(
+            Poly2::from_points_convex_hull(&flat).halfspace_intersection(),
+            HalfspaceIntersection::Empty
+        );
+    }
+}
@@ -0,0 +1,292 @@
+//! Random convex polygons and the 2D steps of the Mahler-product sampler.
+//!
+//! Why this file exists
+//! - `MahlerProductGenerator` builds `K × K°` from a random polygon `K`. The
+//!   three steps (draw `K`, move it into a reference position, take its
+//!   polar) are reused by the Python bindings, so they live here as plain
+//!   functions rather than inside the generator.
+//! - The radial model puts `n` points at angles `φ + (k + δ_k)·2π/n` and radii
+//!   `base·(1 + ε_k)`. Bounded jitter keeps `K` round enough that its polar is
+//!   well conditioned; points that fall out of convex position are redrawn
+//!   (same RNG), so the vertex count is exactly `n` whenever a draw succeeds.
+//! - Rows are addressed by `ReplayToken { seed, index }` and seeded through
+//!   `rand4::seeding`, the same per-row mixing as every 4D family.
+//!
+//! References
+//! - TH: docs/src/thesis/geom2d_polytopes.md
+//! - TH: docs/src/thesis/random-polytopes.md (“Mahler Product Sampler”)
+
+use std::f64::consts::PI;
+
+use nalgebra::Vector2;
+use rand::Rng;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use super::{convex_hull, signed_area, HalfspaceIntersection, Hs2, Poly2};
+use crate::rand4::seeding::token_rng;
+
+/// Redraws of the radial model before settling for the hull of the last draw.
+const MAX_CONVEX_DRAWS: usize = 100;
+/// Offsets at or below this (relative) are treated as the origin on the boundary.
+const EPS_REL: f64 = 1e-9;
+
+/// Row address `(seed, index)`; each row has its own RNG.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ReplayToken {
+    pub seed: u64,
+    pub index: u64,
+}
+
+/// Number of polygon vertices.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum VertexCount {
+    Fixed(usize),
+    /// Uniform on `min..=max`.
+    Uniform {
+        min: usize,
+        max: usize,
+    },
+}
+
+/// Parameters of [`draw_polygon_radial`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct RadialCfg {
+    pub vertex_count: VertexCount,
+    /// Angular jitter `δ_k ∈ [−f, f]` as a fraction of the spacing `2π/n` (`0 ≤ f < 0.5`).
+    pub angle_jitter_frac: f64,
+    /// Relative radial jitter `ε_k ∈ [−r, r]` (`0 ≤ r < 1`).
+    pub radial_jitter: f64,
+    pub base_radius: f64,
+    /// Rotate the whole polygon by a uniform phase `φ`.
+    pub random_phase: bool,
+}
+
+impl Default for RadialCfg {
+    fn default() -> Self {
+        Self {
+            vertex_count: VertexCount::Uniform { min: 5, max: 10 },
+            angle_jitter_frac: 0.25,
+            radial_jitter: 0.15,
+            base_radius: 1.0,
+            random_phase: true,
+        }
+    }
+}
+
+impl RadialCfg {
+    pub(crate) fn is_valid(&self) -> bool {
+        let counts_ok = match self.vertex_count {
+            VertexCount::Fixed(n) => n >= 3,
+            VertexCount::Uniform { min, max } => min >= 3 && min <= max,
+        };
+        counts_ok
+            && (0.0..0.5).contains(&self.angle_jitter_frac)
+            && (0.0..1.0).contains(&self.radial_jitter)
+            && self.base_radius > 0.0
+            && self.base_radius.is_finite()
+    }
+}
+
+/// In-/out-radius window for [`recenter_rescale`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Bounds2 {
+    /// Smallest admissible distance from the origin to an edge line.
+    pub r_in_min: f64,
+    /// Largest admissible distance from the origin to a vertex.
+    pub r_out_max: f64,
+}
+
+impl Default for Bounds2 {
+    fn default() -> Self {
+        Self {
+            r_in_min: 0.1,
+            r_out_max: 2.0,
+        }
+    }
+}
+
+/// Random convex polygon of the row `token`; `None` for an invalid `cfg`.
+pub fn draw_polygon_radial(cfg: RadialCfg, token: ReplayToken) -> Option<Poly2> {
+    if !cfg.is_valid() {
+        return None;
+    }
+    let mut rng = token_rng(token);
+    let n = match cfg.vertex_count {
+        VertexCount::Fixed(n) => n,
+        VertexCount::Uniform { min, max } => rng.gen_range(min..=max),
+    };
+    let step = 2.0 * PI / n as f64;
+    let mut hull = Vec::new();
+    for _ in 0..MAX_CONVEX_DRAWS {
+        let phase = if cfg.random_phase {
+            rng.gen_range(0.0..2.0 * PI)
+        } else {
+            0.0
+        };
+        let points: Vec<Vector2<f64>> = (0..n)
+            .map(|k| {
+                let delta = jitter(&mut rng, cfg.angle_jitter_frac);
+                let radius = cfg.base_radius * (1.0 + jitter(&mut rng, cfg.radial_jitter));
+                let theta = phase + (k as f64 + delta) * step;
+                Vector2::new(radius * theta.cos(), radius * theta.sin())
+            })
+            .collect();
+        hull = convex_hull(&points);
+        if hull.len() == n {
+            break;
+        }
+    }
+    (hull.len() >= 3).then(|| Poly2::from_points_convex_hull(&hull))
+}
+
+/// Translate `poly` to its area centroid, then scale about the origin into
+/// `bounds` (unscaled if it already fits). Returns the polygon with its
+/// in-radius and out-radius; `None` if `poly` is not a bounded polygon or no
+/// scale satisfies both bounds.
+pub fn recenter_rescale(poly: &Poly2, bounds: Bounds2) -> Option<(Poly2, f64, f64)> {
+    let verts = bounded_vertices(poly)?;
+    let centroid = area_centroid(&verts);
+    let shifted = Poly2::from_halfspaces(
+        poly.hs
+            .iter()
+            .map(|h| Hs2::new(h.n, h.c - h.n.dot(&centroid))),
+    );
+    let r_in = shifted.hs.iter().map(|h| h.c).fold(f64::INFINITY, f64::min);
+    let r_out = verts
+        .iter()
+        .map(|v| (v - centroid).norm())
+        .fold(0.0, f64::max);
+    if r_in.is_nan() || r_in <= 0.0 {
+        return None;
+    }
+    let (lo, hi) = (bounds.r_in_min / r_in, bounds.r_out_max / r_out);
+    if lo > hi {
+        return None;
+    }
+    let lambda = 1.0_f64.clamp(lo, hi);
+    let scaled = Poly2 {
+        hs: shifted
+            .hs
+            .iter()
+            .map(|h| Hs2::new(h.n, lambda * h.c))
+            .collect(),
+    };
+    Some((scaled, lambda * r_in, lambda * r_out))
+}
+
+/// Polar `K° = { y : ⟨x, y⟩ ≤ 1 ∀x ∈ K }`; `None` unless `K` is bounded with
+/// the origin strictly inside.
+pub fn polar(poly: &Poly2) -> Option<Poly2> {
+    let verts = bounded_vertices(poly)?;
+    let scale = poly.hs.iter().map(|h| h.c.abs()).fold(1.0, f64::max);
+    if poly.hs.iter().any(|h| h.c <= EPS_REL * scale) {
+        return None;
+    }
+    Some(Poly2::from_halfspaces(verts.iter().map(|v| {
+        let norm = v.norm();
+        Hs2::new(v / norm, 1.0 / norm)
+    })))
+}
+
+fn jitter<R: Rng + ?Sized>(rng: &mut R, amplitude: f64) -> f64 {
+    if amplitude > 0.0 {
+        rng.gen_range(-amplitude..=amplitude)
+    } else {
+        0.0
+    }
+}
+
+fn bounded_vertices(poly: &Poly2) -> Option<Vec<Vector2<f64>>> {
+    match poly.halfspace_intersection() {
+        HalfspaceIntersection::Bounded(vs) => Some(vs),
+        _ => None,
+    }
+}
+
+/// Area centroid of a ccw vertex cycle.
+fn area_centroid(verts: &[Vector2<f64>]) -> Vector2<f64> {
+    let area = signed_area(verts);
+    let moment: Vector2<f64> = verts
+        .iter()
+        .zip(verts.iter().cycle().skip(1))
+        .map(|(a, b)| (a + b) * (a.x * b.y - a.y * b.x))
+        .sum();
+    moment / (6.0 * area)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn vertex_count(poly: &Poly2) -> usize {
+        bounded_vertices(poly).map_or(0, |vs| vs.len())
+    }
+
+    #[test]
+    fn radial_draws_replay_and_keep_the_vertex_count() {
+        let cfg = RadialCfg {
+            vertex_count: VertexCount::Fixed(7),
+            ..RadialCfg::default()
+        };
+        for index in 0..20 {
+            let token = ReplayToken { seed: 3, index };
+            let p = draw_polygon_radial(cfg, token).unwrap();
+            assert_eq!(vertex_count(&p), 7);
+            assert_eq!(draw_polygon_radial(cfg, token).unwrap(), p);
+        }
+        let bad = RadialCfg {
+            vertex_count: VertexCount::Fixed(2),
+            ..cfg
+        };
+        assert!(draw_polygon_radial(bad, ReplayToken { seed: 0, index: 0 }).is_none());
+    }
+
+    #[test]
+    fn recenter_rescale_respects_bounds() {
+        let square = Poly2::from_points_convex_hull(&[
+            Vector2::new(2.0, 2.0),
+            Vector2::new(6.0, 2.0),
+            Vector2::new(6.0, 6.0),
+            Vector2::new(2.0, 6.0),
+        ]);
+        let bounds = Bounds2 {
+            r_in_min: 0.1,
+            r_out_max: 1.0,
+        };
+        let (p, r_in, r_out) = recenter_rescale(&square, bounds).unwrap();
+        assert!((r_out - 1.0).abs() < 1e-12);
+        assert!((r_in - 1.0 / 2f64.sqrt()).abs() < 1e-12);
+        assert!(p.hs.iter().all(|h| (h.c - r_in).abs() < 1e-12));
+        let tight = Bounds2 {
+            r_in_min: 0.9,
+            r_out_max: 1.0,
+        };
+        assert!(recenter_rescale(&square, tight).is_none());
+    }
+
+    #[test]
+    fn polar_of_square_is_diamond_and_needs_interior_origin() {
+        let square = Poly2::from_points_convex_hull(&[
+            Vector2::new(-1.0, -1.0),
+            Vector2::new(1.0, -1.0),
+            Vector2::new(1.0, 1.0),
+            Vector2::new(-1.0, 1.0),
+        ]);
+        let dual = polar(&square).unwrap();
+        let verts = bounded_vertices(&dual).unwrap();
+        assert_eq!(verts.len(), 4);
+        assert!(verts.iter().all(|v| (v.norm() - 1.0).abs() < 1e-12));
+        assert!((signed_area(&verts) - 2.0).abs() < 1e-12);
+        let shifted = Poly2::from_points_convex_hull(&[
+            Vector2::new(0.0, 0.0),
+            Vector2::new(1.0, 0.0),
+            Vector2::new(0.0, 1.0),
+        ]);
+        assert!(polar(&shifted).is_none());
+    }
+}
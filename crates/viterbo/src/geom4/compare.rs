@@ -0,0 +1,121 @@
+//! Comparing polytopes: containment and Hausdorff distance.
+//!
+//! Why this file exists
+//! - Capacity is monotone under inclusion, and perturbation studies need the
+//!   size of a perturbation. Python tests used to reimplement both with
+//!   ad-hoc sampling.
+//! - Containment of convex bodies reduces to vertices: `L ⊆ K` iff every
+//!   vertex of `L` satisfies every halfspace of `K`.
+//! - Hausdorff distance: `d(·, B)` is convex, so `sup_{a ∈ A} d(a, B)` is
+//!   attained at a vertex of `A`. The distance from a point to `B` is the
+//!   minimum over the faces of `B` whose affine-hull projection of the point
+//!   stays in `B`. The face holding the nearest point always qualifies, and
+//!   every other candidate is a point of `B`, so the minimum is exact. Faces
+//!   come from `face_lattice4`, so this is quadratic-ish and meant for
+//!   atlas-sized bodies.
+//!
+//! References
+//! - TH: docs/src/thesis/geom4d_polytopes.md
+//! - Schneider, "Convex Bodies", §1.8 (Hausdorff metric).
+
+use nalgebra::Vector4;
+
+use super::faces::face_lattice4;
+use super::polar::span_basis;
+use super::Poly4;
+
+/// Relative tolerance for membership and rank decisions.
+const EPS_REL: f64 = 1e-9;
+
+/// `inner ⊆ outer`, up to a relative tolerance.
+pub fn contains4(outer: &mut Poly4, inner: &mut Poly4) -> bool {
+    outer.ensure_halfspaces_from_v();
+    inner.ensure_vertices_from_h();
+    let eps = EPS_REL * scale(inner).max(scale(outer));
+    inner.v.iter().all(|v| is_member(outer, v, eps))
+}
+
+/// Hausdorff distance `max(sup_A d(·, B), sup_B d(·, A))`.
+pub fn hausdorff4(a: &mut Poly4, b: &mut Poly4) -> f64 {
+    let (faces_a, faces_b) = (faces(a), faces(b));
+    let one_sided = |from: &Poly4, to: &Poly4, to_faces: &[Vec<usize>]| {
+        from.v
+            .iter()
+            .map(|x| distance4(to, to_faces, x))
+            .fold(0.0, f64::max)
+    };
+    one_sided(a, b, &faces_b).max(one_sided(b, a, &faces_a))
+}
+
+/// Every nonempty face of `poly` as a vertex index set (vertices, edges,
+/// ridges, facets).
+fn faces(poly: &mut Poly4) -> Vec<Vec<usize>> {
+    poly.ensure_halfspaces_from_v();
+    let lattice = face_lattice4(poly);
+    (0..poly.v.len())
+        .map(|i| vec![i])
+        .chain(lattice.edges.iter().map(|e| e.to_vec()))
+        .chain(lattice.ridges.into_iter().map(|r| r.vertices))
+        .chain(lattice.facets.into_iter().filter(|f| !f.is_empty()))
+        .collect()
+}
+
+fn distance4(poly: &Poly4, faces: &[Vec<usize>], x: &Vector4<f64>) -> f64 {
+    let eps = EPS_REL * scale(poly).max(x.norm());
+    if is_member(poly, x, eps) {
+        return 0.0;
+    }
+    faces
+        .iter()
+        .filter_map(|face| {
+            let origin = poly.v[face[0]];
+            let d = x - origin;
+            let p = span_basis(&poly.v, face, eps)
+                .iter()
+                .fold(origin, |p, b| p + b * b.dot(&d));
+            is_member(poly, &p, eps).then(|| (x - p).norm())
+        })
+        .fold(f64::INFINITY, f64::min)
+}
+
+fn is_member(poly: &Poly4, x: &Vector4<f64>, eps: f64) -> bool {
+    poly.h.iter().all(|h| h.n.dot(x) <= h.c + eps * h.n.norm())
+}
+
+fn scale(poly: &Poly4) -> f64 {
+    poly.v.iter().map(|v| v.norm()).fold(1.0, f64::max)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::geom4::Hs4;
+
+    fn cube(r: f64) -> Poly4 {
+        let mut h = Vec::new();
+        for k in 0..4 {
+            let mut e = Vector4::zeros();
+            e[k] = 1.0;
+            h.push(Hs4::new(e, r));
+            h.push(Hs4::new(-e, r));
+        }
+        let mut poly = Poly4::from_h(h);
+        poly.ensure_vertices_from_h();
+        poly
+    }
+
+    #[test]
+    fn nested_cubes_contain_one_way() {
+        assert!(contains4(&mut cube(1.0), &mut cube(0.5)));
+        assert!(!contains4(&mut cube(0.5), &mut cube(1.0)));
+        assert!(contains4(&mut cube(1.0), &mut cube(1.0)));
+    }
+
+    #[test]
+    fn hausdorff_of_nested_cubes_is_the_corner_gap() {
+        // Farthest point of the big cube is a corner: |(0.5, 0.5, 0.5, 0.5)| = 1.
+        let d = hausdorff4(&mut cube(1.0), &mut cube(0.5));
+        assert!((d - 1.0).abs() < 1e-9, "{d}");
+        assert!(hausdorff4(&mut cube(1.0), &mut cube(1.0)).abs() < 1e-12);
+    }
+}
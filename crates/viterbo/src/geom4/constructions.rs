@@ -0,0 +1,127 @@
+//! Composite bodies: Lagrangian products of polygons and Minkowski sums.
+//!
+//! Why this file exists
+//! - Notebook experiments assemble bodies like `K × T` (Lagrangian products,
+//!   the Minkowski-billiard setting) or `K + L` by hand, and halfspace
+//!   bookkeeping is where they go wrong. These constructors return canonical
+//!   `Poly4`s with both representations filled in.
+//! - `K × T` with `K` in the `q = (x1, x2)` plane and `T` in the
+//!   `p = (y1, y2)` plane: facets are `(n_K, 0)` and `(0, n_T)` with the
+//!   polygon offsets, vertices are all pairs. No hull needed.
+//! - `K + L` is the hull of all pairwise vertex sums; the quadratic blow-up
+//!   is harmless at atlas sizes (≲ 100 vertices per body).
+//!
+//! References
+//! - TH: docs/src/thesis/geom4d_polytopes.md
+//! - TH: docs/src/thesis/capacity-algorithm-minkowski-billiard.md
+
+use nalgebra::{Vector2, Vector4};
+
+use super::{Hs4, Poly4};
+use crate::geom2::{HalfspaceIntersection, Poly2};
+
+/// `K × T ⊂ R⁴` with `K` in the `q`-plane and `T` in the `p`-plane;
+/// `None` unless both polygons are bounded with nonempty interior.
+pub fn lagrangian_product(k: &Poly2, t: &Poly2) -> Option<Poly4> {
+    let (vk, vt) = (ccw_vertices(k)?, ccw_vertices(t)?);
+    let h = edge_halfspaces(&vk)
+        .map(|(n, c)| Hs4::new(Vector4::new(n.x, n.y, 0.0, 0.0), c))
+        .chain(edge_halfspaces(&vt).map(|(n, c)| Hs4::new(Vector4::new(0.0, 0.0, n.x, n.y), c)))
+        .collect();
+    let mut poly = Poly4::from_h(h);
+    poly.v = vk
+        .iter()
+        .flat_map(|q| vt.iter().map(move |p| Vector4::new(q.x, q.y, p.x, p.y)))
+        .collect();
+    Some(poly)
+}
+
+/// `K + L = { x + y : x ∈ K, y ∈ L }`.
+pub fn minkowski_sum4(a: &mut Poly4, b: &mut Poly4) -> Poly4 {
+    a.ensure_vertices_from_h();
+    b.ensure_vertices_from_h();
+    let sums =
+        a.v.iter()
+            .flat_map(|x| b.v.iter().map(move |y| x + y))
+            .collect();
+    let mut hull = Poly4::from_v(sums);
+    hull.ensure_halfspaces_from_v();
+    // Rebuild the V-rep from the facets so interior sums are dropped.
+    let mut sum = Poly4::from_h(hull.h);
+    sum.ensure_vertices_from_h();
+    sum
+}
+
+/// Boundary cycle in counter-clockwise order; `None` if empty, unbounded or flat.
+fn ccw_vertices(poly: &Poly2) -> Option<Vec<Vector2<f64>>> {
+    let HalfspaceIntersection::Bounded(mut vs) = poly.halfspace_intersection() else {
+        return None;
+    };
+    if vs.len() < 3 {
+        return None;
+    }
+    let twice_area: f64 = vs
+        .iter()
+        .zip(vs.iter().cycle().skip(1))
+        .map(|(a, b)| a.x * b.y - a.y * b.x)
+        .sum();
+    if twice_area.abs() <= f64::EPSILON {
+        return None;
+    }
+    if twice_area < 0.0 {
+        vs.reverse();
+    }
+    Some(vs)
+}
+
+/// Outward unit normal and offset of each edge of a ccw cycle.
+fn edge_halfspaces(vs: &[Vector2<f64>]) -> impl Iterator<Item = (Vector2<f64>, f64)> + '_ {
+    vs.iter().zip(vs.iter().cycle().skip(1)).map(|(a, b)| {
+        let d = b - a;
+        let n = Vector2::new(d.y, -d.x).normalize();
+        (n, n.dot(a))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::geom2::Hs2;
+
+    fn square(r: f64) -> Poly2 {
+        let mut p = Poly2::default();
+        for n in [(1.0, 0.0), (-1.0, 0.0), (0.0, 1.0), (0.0, -1.0)] {
+            p.insert_halfspace(Hs2::new(Vector2::new(n.0, n.1), r));
+        }
+        p
+    }
+
+    fn cube(r: f64) -> Poly4 {
+        let mut h = Vec::new();
+        for k in 0..4 {
+            let mut e = Vector4::zeros();
+            e[k] = 1.0;
+            h.push(Hs4::new(e, r));
+            h.push(Hs4::new(-e, r));
+        }
+        Poly4::from_h(h)
+    }
+
+    #[test]
+    fn product_of_squares_is_the_cube() {
+        let poly = lagrangian_product(&square(1.0), &square(2.0)).unwrap();
+        assert_eq!((poly.h.len(), poly.v.len()), (8, 16));
+        for v in &poly.v {
+            let tight = poly.h.iter().filter(|h| (h.n.dot(v) - h.c).abs() < 1e-9);
+            assert_eq!(tight.count(), 4);
+            assert!((v.x.abs() - 1.0).abs() < 1e-12 && (v.z.abs() - 2.0).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn minkowski_sum_of_cubes_adds_offsets() {
+        let sum = minkowski_sum4(&mut cube(1.0), &mut cube(0.5));
+        assert_eq!((sum.h.len(), sum.v.len()), (8, 16));
+        assert!(sum.h.iter().all(|h| (h.c / h.n.norm() - 1.5).abs() < 1e-9));
+    }
+}
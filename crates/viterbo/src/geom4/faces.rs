@@ -0,0 +1,154 @@
+//! Face lattice of a 4-polytope: facets, ridges and edges by vertex index.
+//!
+//! Why this file exists
+//! - Visualization and combinatorial-type analysis of atlas rows need the
+//!   full skeleton, not just vertices and facet normals. Python code used to
+//!   rebuild it from scipy hulls, which triangulates facets and loses the
+//!   ridges between coplanar triangles.
+//! - Everything is derived from vertex–facet saturation (relative tolerance
+//!   `EPS_REL`, as in `polar`): a facet is its saturated vertex set; facets
+//!   `i < j` meet in a ridge iff their common vertices span a 2-flat; two
+//!   vertices span an edge iff the vertices on every facet through both are
+//!   just those two (the smallest face containing them is a segment).
+//! - Quadratic in facets and vertices; fine at atlas sizes (≲ 100 facets).
+//!
+//! References
+//! - TH: docs/src/thesis/geom4d_polytopes.md
+//! - Code: crates/viterbo/src/spec.md (skeleton terminology)
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use super::polar::span_basis;
+use super::Poly4;
+
+/// Relative tolerance for vertex–facet saturation and rank decisions.
+const EPS_REL: f64 = 1e-9;
+
+/// A 2-face, given by its two facets and its vertices.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Ridge4 {
+    pub facets: [usize; 2],
+    pub vertices: Vec<usize>,
+}
+
+/// Facets, ridges and edges of a polytope, indexing `poly.h` and `poly.v`.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct FaceLattice4 {
+    /// Vertices of each halfspace's facet (empty for redundant halfspaces).
+    pub facets: Vec<Vec<usize>>,
+    pub ridges: Vec<Ridge4>,
+    /// Vertex pairs `[a, b]` with `a < b`.
+    pub edges: Vec<[usize; 2]>,
+}
+
+/// Face lattice of `poly`; fills in the vertices if needed.
+pub fn face_lattice4(poly: &mut Poly4) -> FaceLattice4 {
+    poly.ensure_vertices_from_h();
+    let scale = poly.v.iter().map(|v| v.norm()).fold(1.0, f64::max);
+    let eps = EPS_REL * scale;
+    let facets: Vec<Vec<usize>> = poly
+        .h
+        .iter()
+        .map(|h| {
+            let sat: Vec<usize> = (0..poly.v.len())
+                .filter(|&i| (h.n.dot(&poly.v[i]) - h.c).abs() <= eps * h.n.norm())
+                .collect();
+            let full = !sat.is_empty() && span_basis(&poly.v, &sat, eps).len() == 3;
+            if full {
+                sat
+            } else {
+                Vec::new()
+            }
+        })
+        .collect();
+
+    let mut ridges = Vec::new();
+    for i in 0..facets.len() {
+        for j in i + 1..facets.len() {
+            let common = intersect(&facets[i], &facets[j]);
+            if common.len() >= 3 && span_basis(&poly.v, &common, eps).len() == 2 {
+                ridges.push(Ridge4 {
+                    facets: [i, j],
+                    vertices: common,
+                });
+            }
+        }
+    }
+
+    let through: Vec<Vec<usize>> = (0..poly.v.len())
+        .map(|a| {
+            (0..facets.len())
+                .filter(|&f| facets[f].contains(&a))
+                .collect()
+        })
+        .collect();
+    let mut edges = Vec::new();
+    for a in 0..poly.v.len() {
+        for b in a + 1..poly.v.len() {
+            let shared = intersect(&through[a], &through[b]);
+            let Some((&first, rest)) = shared.split_first() else {
+                continue;
+            };
+            let face = rest
+                .iter()
+                .fold(facets[first].clone(), |acc, &f| intersect(&acc, &facets[f]));
+            if face == [a, b] {
+                edges.push([a, b]);
+            }
+        }
+    }
+
+    FaceLattice4 {
+        facets,
+        ridges,
+        edges,
+    }
+}
+
+/// Intersection of two ascending index lists.
+fn intersect(a: &[usize], b: &[usize]) -> Vec<usize> {
+    a.iter()
+        .copied()
+        .filter(|i| b.binary_search(i).is_ok())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::geom4::Hs4;
+    use nalgebra::Vector4;
+
+    fn cube() -> Poly4 {
+        let mut hs = Vec::new();
+        for k in 0..4 {
+            let mut e = Vector4::zeros();
+            e[k] = 1.0;
+            hs.push(Hs4::new(e, 1.0));
+            hs.push(Hs4::new(-e, 1.0));
+        }
+        Poly4::from_h(hs)
+    }
+
+    #[test]
+    fn cube_skeleton_counts() {
+        let lattice = face_lattice4(&mut cube());
+        assert!(lattice.facets.iter().all(|f| f.len() == 8));
+        assert_eq!(lattice.ridges.len(), 24);
+        assert!(lattice.ridges.iter().all(|r| r.vertices.len() == 4));
+        assert_eq!(lattice.edges.len(), 32);
+    }
+
+    #[test]
+    fn redundant_halfspaces_have_no_facet() {
+        let mut poly = cube();
+        poly.h.push(Hs4::new(Vector4::repeat(1.0), 4.0));
+        let lattice = face_lattice4(&mut poly);
+        assert!(lattice.facets[8].is_empty());
+        assert_eq!(lattice.ridges.len(), 24);
+        assert_eq!(lattice.edges.len(), 32);
+    }
+}
@@ -0,0 +1,225 @@
+//! Volume of a V-rep via a beneath–beyond boundary triangulation.
+//!
+//! Why this file exists
+//! - Many datasets store vertices only. `volume4` needs the H-rep, and the
+//!   V→H conversion enumerates all 4-subsets of vertices (O(V⁴)). For
+//!   volume alone a triangulated boundary suffices.
+//! - Beneath–beyond: start from a full-dimensional simplex, then add points
+//!   one at a time; a point strictly beyond some facets removes them and
+//!   cones the horizon ridges to itself. Points on or beneath every facet
+//!   are skipped, so coplanar inputs (cube vertices) just triangulate the
+//!   flat facets. Cost is O(V · F) for F boundary simplices.
+//! - Volume = Σ over boundary simplices of the cone volume from an interior
+//!   point of the starting simplex, `|det(b − p, c − p, d − p, e − p)| / 24`.
+//!
+//! References
+//! - TH: docs/src/thesis/geom4d_volume.md
+//! - Edelsbrunner, "Algorithms in Combinatorial Geometry", §8.4.
+
+use std::collections::BTreeMap;
+
+use nalgebra::{Matrix3, Matrix4, Vector4};
+
+/// Relative tolerance for visibility and rank decisions.
+const EPS_REL: f64 = 1e-9;
+
+/// Oriented boundary simplex `n·x ≤ c` with the interior on the `≤` side.
+struct Facet {
+    idx: [usize; 4],
+    n: Vector4<f64>,
+    c: f64,
+    alive: bool,
+}
+
+/// Boundary triangulation of `conv(points)` as index 4-tuples; `None` if
+/// the points are lower-dimensional.
+pub fn hull_simplices4(points: &[Vector4<f64>]) -> Option<Vec<[usize; 4]>> {
+    triangulate(points).map(|(simplices, _)| simplices)
+}
+
+/// 4-volume of `conv(points)`; `None` if the points are lower-dimensional.
+pub fn volume4_from_vertices(points: &[Vector4<f64>]) -> Option<f64> {
+    let (simplices, inner) = triangulate(points)?;
+    let volume = simplices
+        .iter()
+        .map(|s| {
+            Matrix4::from_columns(&s.map(|i| points[i] - inner))
+                .determinant()
+                .abs()
+                / 24.0
+        })
+        .sum();
+    Some(volume)
+}
+
+/// Boundary simplices plus the interior point they were oriented against.
+fn triangulate(points: &[Vector4<f64>]) -> Option<(Vec<[usize; 4]>, Vector4<f64>)> {
+    let scale = points.iter().map(|v| v.norm()).fold(1.0, f64::max);
+    let eps = EPS_REL * scale;
+    let start = initial_simplex(points, eps)?;
+    let inner = start.iter().map(|&i| points[i]).sum::<Vector4<f64>>() / 5.0;
+
+    let mut facets: Vec<Facet> = Vec::new();
+    for skip in 0..5 {
+        let mut idx = [0; 4];
+        let mut k = 0;
+        for (j, &i) in start.iter().enumerate() {
+            if j != skip {
+                idx[k] = i;
+                k += 1;
+            }
+        }
+        facets.push(facet(points, idx, &inner)?);
+    }
+
+    for q in 0..points.len() {
+        if start.contains(&q) {
+            continue;
+        }
+        let p = points[q];
+        let visible: Vec<usize> = (0..facets.len())
+            .filter(|&f| facets[f].alive && facets[f].n.dot(&p) > facets[f].c + eps)
+            .collect();
+        if visible.is_empty() {
+            continue;
+        }
+        // Ridges seen once among the visible facets form the horizon.
+        // Ordered, so the facet order (and replayed H-reps) is deterministic.
+        let mut ridges: BTreeMap<[usize; 3], usize> = BTreeMap::new();
+        for &f in &visible {
+            facets[f].alive = false;
+            for ridge in ridges_of(facets[f].idx) {
+                *ridges.entry(ridge).or_default() += 1;
+            }
+        }
+        for (ridge, count) in ridges {
+            if count == 1 {
+                if let Some(new) = facet(points, [ridge[0], ridge[1], ridge[2], q], &inner) {
+                    facets.push(new);
+                }
+            }
+        }
+    }
+    let simplices = facets
+        .into_iter()
+        .filter(|f| f.alive)
+        .map(|f| f.idx)
+        .collect();
+    Some((simplices, inner))
+}
+
+/// Greedy affinely independent 5-subset (first point, then farthest from the
+/// current flat).
+fn initial_simplex(points: &[Vector4<f64>], eps: f64) -> Option<[usize; 5]> {
+    let origin = *points.first()?;
+    let mut chosen = [0usize; 5];
+    let mut basis: Vec<Vector4<f64>> = Vec::with_capacity(4);
+    for slot in chosen.iter_mut().skip(1) {
+        let residual = |v: &Vector4<f64>| {
+            let mut d = v - origin;
+            for b in &basis {
+                d -= b * b.dot(&d);
+            }
+            d
+        };
+        let (best, dist) = points
+            .iter()
+            .enumerate()
+            .map(|(i, v)| (i, residual(v).norm()))
+            .max_by(|a, b| a.1.total_cmp(&b.1))?;
+        if dist <= eps {
+            return None;
+        }
+        basis.push(residual(&points[best]) / dist);
+        *slot = best;
+    }
+    Some(chosen)
+}
+
+/// Hyperplane through four points, oriented away from `inner`.
+fn facet(points: &[Vector4<f64>], idx: [usize; 4], inner: &Vector4<f64>) -> Option<Facet> {
+    let a = points[idx[0]];
+    let n = normal(points[idx[1]] - a, points[idx[2]] - a, points[idx[3]] - a);
+    let norm = n.norm();
+    if norm == 0.0 {
+        return None;
+    }
+    let mut n = n / norm;
+    if n.dot(&(inner - a)) > 0.0 {
+        n = -n;
+    }
+    Some(Facet {
+        idx,
+        n,
+        c: n.dot(&a),
+        alive: true,
+    })
+}
+
+/// Vector orthogonal to `u, v, w` (cofactor expansion; zero if dependent).
+fn normal(u: Vector4<f64>, v: Vector4<f64>, w: Vector4<f64>) -> Vector4<f64> {
+    let minor = |skip: usize| {
+        let cols: Vec<usize> = (0..4).filter(|&k| k != skip).collect();
+        let m = Matrix3::from_fn(|r, c| [u, v, w][r][cols[c]]);
+        m.determinant()
+    };
+    Vector4::new(minor(0), -minor(1), minor(2), -minor(3))
+}
+
+fn ridges_of(idx: [usize; 4]) -> [[usize; 3]; 4] {
+    std::array::from_fn(|skip| {
+        let mut ridge = [0; 3];
+        let mut k = 0;
+        for (j, &i) in idx.iter().enumerate() {
+            if j != skip {
+                ridge[k] = i;
+                k += 1;
+            }
+        }
+        ridge.sort_unstable();
+        ridge
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cube_vertices(side: f64) -> Vec<Vector4<f64>> {
+        (0..16u32)
+            .map(|bits| Vector4::from_fn(|k, _| if bits >> k & 1 == 1 { side } else { -side }))
+            .collect()
+    }
+
+    #[test]
+    fn cube_and_cross_polytope_volumes() {
+        let cube = volume4_from_vertices(&cube_vertices(1.0)).unwrap();
+        assert!((cube - 16.0).abs() < 1e-9, "{cube}");
+        let cross: Vec<Vector4<f64>> = (0..8)
+            .map(|i| {
+                let mut e = Vector4::zeros();
+                e[i / 2] = if i % 2 == 0 { 1.0 } else { -1.0 };
+                e
+            })
+            .collect();
+        let vol = volume4_from_vertices(&cross).unwrap();
+        assert!((vol - 2.0 / 3.0).abs() < 1e-12, "{vol}");
+    }
+
+    #[test]
+    fn interior_points_and_flat_inputs() {
+        let mut points = cube_vertices(0.5);
+        points.push(Vector4::zeros());
+        points.push(Vector4::new(0.1, -0.2, 0.3, 0.0));
+        let vol = volume4_from_vertices(&points).unwrap();
+        assert!((vol - 1.0).abs() < 1e-9, "{vol}");
+        let flat: Vec<Vector4<f64>> = cube_vertices(1.0)
+            .into_iter()
+            .map(|mut v| {
+                v[3] = 0.0;
+                v
+            })
+            .collect();
+        assert!(volume4_from_vertices(&flat).is_none());
+    }
+}
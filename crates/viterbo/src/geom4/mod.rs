@@ -0,0 +1,383 @@
+//! 4D convex polytopes with cached H- and V-representations.
+//!
+//! Why this file exists
+//! - Capacity, volume and every generator work on the same `Poly4`: halfspaces
+//!   `n·x ≤ c` plus vertices, each side filled on demand (`ensure_*`). Keeping
+//!   both in one struct lets callers pay for a conversion once.
+//! - H→V goes through the polar: with the origin strictly inside, the vertices
+//!   of `{n_i·x ≤ c_i}` are the facets of `conv{n_i / c_i}` not containing the
+//!   origin, which `hull::hull_simplices4` finds in O(m · F). Each boundary
+//!   simplex names four halfspaces; solving them gives the exact vertex.
+//!   Without an interior origin (or for flat normal sets) we fall back to the
+//!   O(m⁴) enumeration of 4-subsets; generators only hit that path while
+//!   rejecting bad draws.
+//! - V→H triangulates the hull and merges coplanar boundary simplices into
+//!   facets. Conversions never drop rows from the side that was given, so
+//!   indices into `h` (facet ids in `oriented_edge`) stay stable.
+//! - `check_canonical` is the one validity gate: finite data, origin strictly
+//!   inside, bounded (the unit normals positively span R⁴) and at least five
+//!   vertices. Every generator runs it before emitting a row.
+//!
+//! References
+//! - TH: docs/src/thesis/geom4d_polytopes.md
+//! - Code: crates/viterbo/src/geom4/hull.rs (beneath–beyond triangulation)
+
+pub mod compare;
+pub mod constructions;
+pub mod faces;
+pub mod hull;
+pub mod polar;
+pub mod volume;
+pub mod volume_mc;
+
+pub use compare::*;
+pub use constructions::*;
+pub use faces::*;
+pub use hull::*;
+pub use polar::*;
+pub use volume::*;
+pub use volume_mc::*;
+
+use nalgebra::{Matrix3, Matrix4, Vector4};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::rand4::j4;
+
+/// Relative tolerance for feasibility, interiority and deduplication.
+const EPS_REL: f64 = 1e-9;
+/// Halfspaces closer than this (unit normals, relative offsets) are one facet.
+const EPS_MERGE: f64 = 1e-8;
+
+/// Halfspace `n·x ≤ c`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Hs4 {
+    pub n: Vector4<f64>,
+    pub c: f64,
+}
+
+impl Hs4 {
+    pub fn new(n: Vector4<f64>, c: f64) -> Self {
+        Self { n, c }
+    }
+}
+
+/// Convex polytope; either representation may be empty until requested.
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Poly4 {
+    pub h: Vec<Hs4>,
+    pub v: Vec<Vector4<f64>>,
+}
+
+impl Poly4 {
+    pub fn from_h(h: Vec<Hs4>) -> Self {
+        Self { h, v: Vec::new() }
+    }
+
+    pub fn from_v(v: Vec<Vector4<f64>>) -> Self {
+        Self { h: Vec::new(), v }
+    }
+
+    /// Fill `v` from `h` (no-op if vertices are cached). An empty or
+    /// unbounded H-rep yields only the vertices it has, possibly none.
+    pub fn ensure_vertices_from_h(&mut self) {
+        if !self.v.is_empty() {
+            return;
+        }
+        let origin_inside = self
+            .h
+            .iter()
+            .all(|h| h.c > EPS_REL * h.n.norm() && h.c.is_finite());
+        let via_polar = if origin_inside {
+            vertices_via_polar(&self.h)
+        } else {
+            None
+        };
+        self.v = via_polar.unwrap_or_else(|| vertices_brute_force(&self.h));
+    }
+
+    /// Fill `h` from `v` (no-op if halfspaces are cached); stays empty for
+    /// lower-dimensional point sets.
+    pub fn ensure_halfspaces_from_v(&mut self) {
+        if !self.h.is_empty() {
+            return;
+        }
+        let mut points: Vec<Vector4<f64>> = Vec::with_capacity(self.v.len());
+        for v in &self.v {
+            if !points.contains(v) {
+                points.push(*v);
+            }
+        }
+        let Some(simplices) = hull_simplices4(&points) else {
+            return;
+        };
+        let inner = points.iter().sum::<Vector4<f64>>() / points.len() as f64;
+        let mut h: Vec<Hs4> = Vec::new();
+        for idx in simplices {
+            let Some(hs) = hyperplane_through(idx.map(|i| points[i]), &inner) else {
+                continue;
+            };
+            let tol = EPS_MERGE * hs.c.abs().max(1.0);
+            if !h
+                .iter()
+                .any(|g| (g.n - hs.n).norm() < EPS_MERGE && (g.c - hs.c).abs() < tol)
+            {
+                h.push(hs);
+            }
+        }
+        self.h = h;
+    }
+
+    /// Validate the H-rep (and fill the V-rep): finite, non-zero normals,
+    /// origin strictly inside, bounded, at least five vertices.
+    pub fn check_canonical(&mut self) -> Result<(), String> {
+        if self.h.len() < 5 {
+            return Err(format!(
+                "need at least 5 halfspaces for a bounded 4D polytope, got {}",
+                self.h.len()
+            ));
+        }
+        for (i, h) in self.h.iter().enumerate() {
+            if !(h.n.iter().all(|x| x.is_finite()) && h.c.is_finite()) {
+                return Err(format!("halfspace {i} has non-finite entries"));
+            }
+            let norm = h.n.norm();
+            if norm == 0.0 {
+                return Err(format!("halfspace {i} has a zero normal"));
+            }
+            if h.c <= EPS_REL * norm {
+                return Err(format!(
+                    "origin not strictly inside: halfspace {i} has offset {} (|n| = {norm})",
+                    h.c
+                ));
+            }
+        }
+        if !normals_positively_span(&self.h) {
+            return Err("halfspaces are unbounded: normals do not positively span R^4".into());
+        }
+        self.ensure_vertices_from_h();
+        if self.v.len() < 5 {
+            return Err(format!(
+                "expected at least 5 vertices, found {}",
+                self.v.len()
+            ));
+        }
+        Ok(())
+    }
+
+    /// Image `{M x + t : x ∈ K}`; `None` if `M` is singular.
+    pub fn push_forward(&self, m: &Matrix4<f64>, t: &Vector4<f64>) -> Option<Poly4> {
+        let inv_t = m.try_inverse()?.transpose();
+        if !inv_t.iter().all(|x| x.is_finite()) {
+            return None;
+        }
+        let h = self
+            .h
+            .iter()
+            .map(|h| {
+                let n = inv_t * h.n;
+                let norm = n.norm();
+                Hs4::new(n / norm, (h.c + n.dot(t)) / norm)
+            })
+            .collect();
+        let v = self.v.iter().map(|v| m * v + t).collect();
+        Some(Poly4 { h, v })
+    }
+}
+
+/// Reeb direction `J n` on each facet, in the order of `h`.
+pub fn reeb_on_facets(h: &[Hs4]) -> Vec<Vector4<f64>> {
+    let j = j4();
+    h.iter().map(|hs| j * hs.n).collect()
+}
+
+/// Vertices as the positive-offset facets of `conv{n_i / c_i}`; `None` if
+/// the polar points are flat (then the body is unbounded or degenerate).
+fn vertices_via_polar(h: &[Hs4]) -> Option<Vec<Vector4<f64>>> {
+    let points: Vec<Vector4<f64>> = h.iter().map(|hs| hs.n / hs.c).collect();
+    let simplices = hull_simplices4(&points)?;
+    let inner = points.iter().sum::<Vector4<f64>>() / points.len() as f64;
+    let mut out = Vec::new();
+    for idx in simplices {
+        let Some(facet) = hyperplane_through(idx.map(|i| points[i]), &inner) else {
+            continue;
+        };
+        // Polar facets through (or behind) the origin are recession directions.
+        if facet.c <= EPS_REL {
+            continue;
+        }
+        if let Some(x) = solve_vertex(h, idx) {
+            push_unique(&mut out, x);
+        }
+    }
+    Some(out)
+}
+
+/// All feasible intersections of four halfspaces.
+fn vertices_brute_force(h: &[Hs4]) -> Vec<Vector4<f64>> {
+    let m = h.len();
+    let mut out = Vec::new();
+    for a in 0..m {
+        for b in a + 1..m {
+            for c in b + 1..m {
+                for d in c + 1..m {
+                    if let Some(x) = solve_vertex(h, [a, b, c, d]) {
+                        push_unique(&mut out, x);
+                    }
+                }
+            }
+        }
+    }
+    out
+}
+
+/// The point where the four halfspaces are tight, if unique and feasible.
+fn solve_vertex(h: &[Hs4], idx: [usize; 4]) -> Option<Vector4<f64>> {
+    let a = Matrix4::from_rows(&idx.map(|i| h[i].n.transpose()));
+    let rhs = Vector4::from_fn(|r, _| h[idx[r]].c);
+    let x = a.lu().solve(&rhs)?;
+    if !x.iter().all(|v| v.is_finite()) {
+        return None;
+    }
+    let scale = x.norm().max(1.0);
+    h.iter()
+        .all(|hs| hs.n.dot(&x) <= hs.c + EPS_REL * scale * hs.n.norm())
+        .then_some(x)
+}
+
+fn push_unique(out: &mut Vec<Vector4<f64>>, x: Vector4<f64>) {
+    let tol = EPS_MERGE * x.norm().max(1.0);
+    if !out.iter().any(|y| (y - x).norm() < tol) {
+        out.push(x);
+    }
+}
+
+/// Unit-normal hyperplane through four points with `inner` on the `≤` side.
+fn hyperplane_through(p: [Vector4<f64>; 4], inner: &Vector4<f64>) -> Option<Hs4> {
+    let (u, v, w) = (p[1] - p[0], p[2] - p[0], p[3] - p[0]);
+    let minor = |skip: usize| {
+        let cols: Vec<usize> = (0..4).filter(|&k| k != skip).collect();
+        Matrix3::from_fn(|r, c| [u, v, w][r][cols[c]]).determinant()
+    };
+    let n = Vector4::new(minor(0), -minor(1), minor(2), -minor(3));
+    let norm = n.norm();
+    if norm.is_nan() || norm <= 0.0 {
+        return None;
+    }
+    let mut n = n / norm;
+    if n.dot(&(inner - p[0])) > 0.0 {
+        n = -n;
+    }
+    Some(Hs4::new(n, n.dot(&p[0])))
+}
+
+/// Whether the origin is strictly inside `conv{n_i / |n_i|}`, i.e. the
+/// halfspaces have no common recession direction.
+fn normals_positively_span(h: &[Hs4]) -> bool {
+    let units: Vec<Vector4<f64>> = h.iter().map(|hs| hs.n / hs.n.norm()).collect();
+    let Some(simplices) = hull_simplices4(&units) else {
+        return false;
+    };
+    let inner = units.iter().sum::<Vector4<f64>>() / units.len() as f64;
+    simplices.into_iter().all(|idx| {
+        hyperplane_through(idx.map(|i| units[i]), &inner).is_some_and(|facet| facet.c > EPS_REL)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cube(r: f64) -> Poly4 {
+        let mut h = Vec::new();
+        for k in 0..4 {
+            let mut e = Vector4::zeros();
+            e[k] = 1.0;
+            h.push(Hs4::new(e, r));
+            h.push(Hs4::new(-e, r));
+        }
+        Poly4::from_h(h)
+    }
+
+    #[test]
+    fn cube_round_trips_between_representations() {
+        let mut p = cube(1.0);
+        p.check_canonical().unwrap();
+        assert_eq!(p.v.len(), 16);
+        let mut q = Poly4::from_v(p.v.clone());
+        q.ensure_halfspaces_from_v();
+        assert_eq!(q.h.len(), 8);
+        assert!(q.h.iter().all(|h| (h.c - 1.0).abs() < 1e-12));
+    }
+
+    #[test]
+    fn redundant_rows_are_kept_and_shifted_bodies_use_the_fallback() {
+        let mut p = cube(1.0);
+        p.h.push(Hs4::new(Vector4::repeat(1.0), 10.0));
+        p.ensure_vertices_from_h();
+        assert_eq!((p.h.len(), p.v.len()), (9, 16));
+        let mut shifted = Poly4::from_h(
+            cube(1.0)
+                .h
+                .iter()
+                .map(|h| Hs4::new(h.n, h.c + h.n.x))
+                .collect(),
+        );
+        shifted.ensure_vertices_from_h();
+        assert_eq!(shifted.v.len(), 16);
+        assert!(shifted.check_canonical().is_err());
+    }
+
+    #[test]
+    fn canonical_rejects_unbounded_and_accepts_simplex() {
+        let mut half_cube = Poly4::from_h(cube(1.0).h.into_iter().step_by(2).collect());
+        half_cube
+            .h
+            .push(Hs4::new(Vector4::new(1.0, 1.0, 1.0, 0.0), 1.0));
+        let err = half_cube.check_canonical().unwrap_err();
+        assert!(err.contains("unbounded"), "{err}");
+        let mut simplex = Poly4::from_h(
+            (0..4)
+                .map(|k| {
+                    let mut e = Vector4::zeros();
+                    e[k] = -1.0;
+                    Hs4::new(e, 1.0)
+                })
+                .chain([Hs4::new(Vector4::repeat(1.0), 1.0)])
+                .collect(),
+        );
+        simplex.check_canonical().unwrap();
+        assert_eq!(simplex.v.len(), 5);
+    }
+
+    #[test]
+    fn push_forward_maps_both_representations() {
+        let mut p = cube(1.0);
+        p.ensure_vertices_from_h();
+        let m = Matrix4::new(
+            1.0, 0.5, 0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 0.0, 2.0, 0.0, 0.0, 0.0, 0.0, 0.5,
+        );
+        let t = Vector4::new(0.1, -0.2, 0.0, 0.3);
+        let img = p.push_forward(&m, &t).unwrap();
+        for v in &img.v {
+            assert!(img.h.iter().all(|h| h.n.dot(v) <= h.c + 1e-9));
+            assert_eq!(
+                img.h
+                    .iter()
+                    .filter(|h| (h.n.dot(v) - h.c).abs() < 1e-9)
+                    .count(),
+                4
+            );
+        }
+        assert!(p.push_forward(&Matrix4::zeros(), &t).is_none());
+    }
+
+    #[test]
+    fn reeb_field_is_j_times_normal() {
+        let v = reeb_on_facets(&cube(1.0).h);
+        assert_eq!(v[0], Vector4::new(0.0, 0.0, 1.0, 0.0));
+        assert_eq!(v[4], Vector4::new(-1.0, 0.0, 0.0, 0.0));
+    }
+}
@@ -0,0 +1,269 @@
+//! Polar duals and the interior centres they depend on.
+//!
+//! Why this file exists
+//! - `K° = { y : ⟨x, y⟩ ≤ 1 ∀x ∈ K }` is only meaningful for a chosen origin,
+//!   and the Mahler/Santaló experiments compare duals taken about different
+//!   centres. We keep the dual and the centres (volume centroid, Santaló
+//!   point) next to each other so callers do not re-derive the conventions.
+//! - With both representations at hand the dual is purely combinatorial:
+//!   vertices `v` of K become half-spaces `v·y ≤ 1` of K°, and half-spaces
+//!   `n·x ≤ c` (with `c > 0`) become vertices `n / c`.
+//! - The centroid uses the same facet-fan idea as `volume4`, but keeps the
+//!   first moment: every k-face is coned from its vertex mean over its
+//!   (k−1)-faces, recursively down to vertices. Naive (faces are found by
+//!   vertex saturation) but exact up to rounding and fine at our sizes.
+//! - The Santaló point `s` minimizes `vol((K − s)°)`; the gradient of that
+//!   convex function is `5 · vol(P) · centroid(P)` with `P = (K − s)°`, so we
+//!   run a backtracking descent until the dual's centroid vanishes.
+//! - The Mahler volume `vol(K) · vol((K − s)°)` reuses the same pieces; it is
+//!   minimal at the Santaló point, which is why callers pick the centre.
+//!
+//! References
+//! - TH: docs/src/thesis/geom4d_polytopes.md, docs/src/thesis/geom4d_volume.md
+//! - Santaló (1949); Schneider, "Convex Bodies", §1.6 (polarity).
+
+use nalgebra::Vector4;
+
+use super::{Hs4, Poly4};
+
+/// Relative tolerance for saturation / rank decisions.
+const EPS_REL: f64 = 1e-9;
+/// Iteration cap for the Santaló descent.
+const SANTALO_MAX_ITERS: usize = 200;
+
+/// Polar dual about the origin; `None` unless the origin is strictly interior.
+pub fn polar4(poly: &mut Poly4) -> Option<Poly4> {
+    poly.ensure_vertices_from_h();
+    poly.ensure_halfspaces_from_v();
+    if poly.h.is_empty() || poly.h.iter().any(|h| h.c <= EPS_REL * h.n.norm()) {
+        return None;
+    }
+    let h = poly
+        .v
+        .iter()
+        .map(|v| {
+            let norm = v.norm();
+            Hs4::new(v / norm, 1.0 / norm)
+        })
+        .collect();
+    let mut dual = Poly4::from_h(h);
+    dual.v = poly.h.iter().map(|h| h.n / h.c).collect();
+    Some(dual)
+}
+
+/// Translate `K ↦ K − s` on both representations.
+pub fn translate4(poly: &Poly4, s: &Vector4<f64>) -> Poly4 {
+    let h = poly
+        .h
+        .iter()
+        .map(|h| Hs4::new(h.n, h.c - h.n.dot(s)))
+        .collect();
+    let mut out = Poly4::from_h(h);
+    out.v = poly.v.iter().map(|v| v - s).collect();
+    out
+}
+
+/// Volume and volume centroid; `None` for empty or lower-dimensional input.
+pub fn volume_centroid4(poly: &mut Poly4) -> Option<(f64, Vector4<f64>)> {
+    poly.ensure_vertices_from_h();
+    poly.ensure_halfspaces_from_v();
+    if poly.v.len() < 5 {
+        return None;
+    }
+    let scale = poly.v.iter().map(|v| v.norm()).fold(1.0, f64::max);
+    let eps = EPS_REL * scale;
+    let sat: Vec<Vec<usize>> = poly
+        .h
+        .iter()
+        .map(|h| {
+            let norm = h.n.norm();
+            (0..poly.v.len())
+                .filter(|&i| (h.n.dot(&poly.v[i]) - h.c).abs() <= eps * norm)
+                .collect()
+        })
+        .collect();
+    let all: Vec<usize> = (0..poly.v.len()).collect();
+    if span_basis(&poly.v, &all, eps).len() != 4 {
+        return None;
+    }
+    let (vol, moment) = fan_measure(&poly.v, &sat, &all, 4, eps);
+    (vol > 0.0).then(|| (vol, moment / vol))
+}
+
+/// Volume centroid of `K`.
+pub fn centroid4(poly: &mut Poly4) -> Option<Vector4<f64>> {
+    volume_centroid4(poly).map(|(_, c)| c)
+}
+
+/// Santaló point of `K`: the interior point minimizing `vol((K − s)°)`.
+pub fn santalo_point4(poly: &mut Poly4) -> Option<Vector4<f64>> {
+    let mut s = centroid4(poly)?;
+    let mut f = dual_volume_centroid(poly, &s)?;
+    // Descent steps are measured in primal length units.
+    let mut step = poly
+        .h
+        .iter()
+        .map(|h| (h.c - h.n.dot(&s)) / h.n.norm())
+        .fold(f64::INFINITY, f64::min);
+    for _ in 0..SANTALO_MAX_ITERS {
+        let (vol, g) = f;
+        let g_norm = g.norm();
+        if g_norm * step <= EPS_REL || step <= EPS_REL {
+            break;
+        }
+        let dir = -g / g_norm;
+        loop {
+            let trial = s + dir * step;
+            match dual_volume_centroid(poly, &trial) {
+                // Armijo condition on ∇ vol(P) = 5 vol(P) g.
+                Some(next) if next.0 <= vol - 1e-4 * step * 5.0 * vol * g_norm => {
+                    s = trial;
+                    f = next;
+                    step *= 2.0;
+                    break;
+                }
+                _ => {
+                    step *= 0.5;
+                    if step <= EPS_REL {
+                        break;
+                    }
+                }
+            }
+        }
+    }
+    Some(s)
+}
+
+/// Mahler volume `vol(K) · vol((K − s)°)`; `None` unless `s` is strictly interior.
+pub fn mahler_volume4(poly: &mut Poly4, s: &Vector4<f64>) -> Option<f64> {
+    let (vol, _) = volume_centroid4(poly)?;
+    let (dual_vol, _) = dual_volume_centroid(poly, s)?;
+    Some(vol * dual_vol)
+}
+
+fn dual_volume_centroid(poly: &Poly4, s: &Vector4<f64>) -> Option<(f64, Vector4<f64>)> {
+    let mut shifted = translate4(poly, s);
+    let mut dual = polar4(&mut shifted)?;
+    volume_centroid4(&mut dual)
+}
+
+/// Measure and first moment of the face spanned by `face` (intrinsic dimension `dim`).
+fn fan_measure(
+    verts: &[Vector4<f64>],
+    sat: &[Vec<usize>],
+    face: &[usize],
+    dim: usize,
+    eps: f64,
+) -> (f64, Vector4<f64>) {
+    if dim == 0 {
+        return (1.0, verts[face[0]]);
+    }
+    let apex = face.iter().map(|&i| verts[i]).sum::<Vector4<f64>>() / face.len() as f64;
+    let mut seen: Vec<Vec<usize>> = Vec::new();
+    let mut measure = 0.0;
+    let mut moment = Vector4::zeros();
+    for s in sat {
+        let sub: Vec<usize> = face.iter().copied().filter(|i| s.contains(i)).collect();
+        if sub.len() < dim || sub.len() == face.len() || seen.contains(&sub) {
+            continue;
+        }
+        let basis = span_basis(verts, &sub, eps);
+        if basis.len() != dim - 1 {
+            continue;
+        }
+        let (m, mom) = fan_measure(verts, sat, &sub, dim - 1, eps);
+        seen.push(sub.clone());
+        if m <= 0.0 {
+            continue;
+        }
+        // Height of the apex over the sub-face's affine hull.
+        let mut d = apex - verts[sub[0]];
+        for b in &basis {
+            d -= b * b.dot(&d);
+        }
+        let pyramid = m * d.norm() / dim as f64;
+        let centre = (apex + (mom / m) * dim as f64) / (dim + 1) as f64;
+        measure += pyramid;
+        moment += centre * pyramid;
+    }
+    (measure, moment)
+}
+
+/// Orthonormal basis of the direction space of `aff{verts[i] : i ∈ idx}`.
+pub(crate) fn span_basis(verts: &[Vector4<f64>], idx: &[usize], eps: f64) -> Vec<Vector4<f64>> {
+    let mut basis: Vec<Vector4<f64>> = Vec::with_capacity(4);
+    let origin = verts[idx[0]];
+    for &i in &idx[1..] {
+        let mut d = verts[i] - origin;
+        for b in &basis {
+            d -= b * b.dot(&d);
+        }
+        let norm = d.norm();
+        if norm > eps {
+            basis.push(d / norm);
+            if basis.len() == 4 {
+                break;
+            }
+        }
+    }
+    basis
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn box4(lo: [f64; 4], hi: [f64; 4]) -> Poly4 {
+        let mut hs = Vec::new();
+        for k in 0..4 {
+            let mut e = Vector4::zeros();
+            e[k] = 1.0;
+            hs.push(Hs4::new(e, hi[k]));
+            hs.push(Hs4::new(-e, -lo[k]));
+        }
+        let mut p = Poly4::from_h(hs);
+        p.ensure_vertices_from_h();
+        p
+    }
+
+    #[test]
+    fn centroid_of_shifted_box() {
+        let mut p = box4([-1.0, -0.5, -2.0, -1.0], [3.0, 0.5, 1.0, 2.0]);
+        let (vol, c) = volume_centroid4(&mut p).unwrap();
+        assert!((vol - 4.0 * 1.0 * 3.0 * 3.0).abs() < 1e-9);
+        assert!((c - Vector4::new(1.0, 0.0, -0.5, 0.5)).norm() < 1e-9);
+    }
+
+    #[test]
+    fn polar_of_cube_is_cross_polytope() {
+        let mut cube = box4([-1.0; 4], [1.0; 4]);
+        let mut dual = polar4(&mut cube).unwrap();
+        assert_eq!(dual.v.len(), 8);
+        assert_eq!(dual.h.len(), 16);
+        // vol(cross-polytope of radius 1) = 2^4 / 4! = 2/3.
+        let (vol, c) = volume_centroid4(&mut dual).unwrap();
+        assert!((vol - 2.0 / 3.0).abs() < 1e-9);
+        assert!(c.norm() < 1e-9);
+    }
+
+    #[test]
+    fn santalo_point_of_box_is_its_centre() {
+        let mut p = box4([-1.0, -1.0, -1.0, -1.0], [2.0, 1.0, 1.0, 3.0]);
+        let s = santalo_point4(&mut p).unwrap();
+        assert!((s - Vector4::new(0.5, 0.0, 0.0, 1.0)).norm() < 1e-6, "{s}");
+    }
+
+    #[test]
+    fn mahler_volume_of_cube_is_the_hanner_value() {
+        // Cube × cross-polytope: 16 · 2/3, the conjectured minimum in R⁴.
+        let mut cube = box4([-1.0; 4], [1.0; 4]);
+        let m = mahler_volume4(&mut cube, &Vector4::zeros()).unwrap();
+        assert!((m - 32.0 / 3.0).abs() < 1e-9, "{m}");
+    }
+
+    #[test]
+    fn polar_requires_interior_origin() {
+        let mut p = box4([0.0; 4], [1.0; 4]);
+        assert!(polar4(&mut p).is_none());
+    }
+}
@@ -0,0 +1,210 @@
+//! Exact 4-volume of an H-rep by a facet fan.
+//!
+//! Why this file exists
+//! - Systolic ratios divide by the volume, so it must be exact up to rounding
+//!   and invariant under volume-preserving maps; Monte Carlo (`volume_mc`) is
+//!   only a cross-check.
+//! - Every facet `F` (a halfspace tight on a 3-dimensional vertex set) is
+//!   coned from its vertex mean `p_F` over its ridges; every ridge polygon is
+//!   ordered by angle in a Gram–Schmidt basis of its plane and fan-triangulated.
+//!   The simplices `(anchor, p_F, triangle)` with the anchor at the vertex
+//!   centroid tile the body, so the volume is `Σ |det| / 24`.
+//! - Redundant halfspaces carry no facet and are skipped; duplicated rows are
+//!   counted once. A facet without ridges means the face data is inconsistent
+//!   and is reported instead of silently under-counting.
+//!
+//! References
+//! - TH: docs/src/thesis/geom4d_volume.md
+//! - Ziegler, "Lectures on Polytopes", Ch. 5.
+
+use std::fmt;
+
+use nalgebra::{Matrix4, Vector4};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use super::polar::span_basis;
+use super::{Hs4, Poly4};
+
+/// Relative tolerance for vertex–facet saturation and rank decisions.
+const EPS_REL: f64 = 1e-9;
+
+/// Why a volume could not be computed.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum VolumeError {
+    /// Fewer than five affinely independent vertices.
+    LowerDimensional,
+    /// Halfspace `i` carries a facet with no ridge polygons.
+    DegenerateFacet(usize),
+}
+
+impl fmt::Display for VolumeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            VolumeError::LowerDimensional => write!(f, "volume4: polytope is lower-dimensional"),
+            VolumeError::DegenerateFacet(i) => {
+                write!(f, "volume4: facet of halfspace {i} has no ridges")
+            }
+        }
+    }
+}
+
+impl std::error::Error for VolumeError {}
+
+/// 4-volume of `poly`; fills in whichever representation is missing.
+pub fn volume4(poly: &mut Poly4) -> Result<f64, VolumeError> {
+    poly.ensure_vertices_from_h();
+    poly.ensure_halfspaces_from_v();
+    let verts = &poly.v;
+    if verts.len() < 5 {
+        return Err(VolumeError::LowerDimensional);
+    }
+    let scale = verts.iter().map(|v| v.norm()).fold(1.0, f64::max);
+    let eps = EPS_REL * scale;
+    let all: Vec<usize> = (0..verts.len()).collect();
+    if span_basis(verts, &all, eps).len() < 4 {
+        return Err(VolumeError::LowerDimensional);
+    }
+
+    let mut facets: Vec<(usize, Vec<usize>)> = Vec::new();
+    for (i, h) in poly.h.iter().enumerate() {
+        let norm = h.n.norm();
+        let sat: Vec<usize> = all
+            .iter()
+            .copied()
+            .filter(|&k| (h.n.dot(&verts[k]) - h.c).abs() <= eps * norm)
+            .collect();
+        if sat.len() >= 4
+            && span_basis(verts, &sat, eps).len() == 3
+            && !facets.iter().any(|(_, s)| *s == sat)
+        {
+            facets.push((i, sat));
+        }
+    }
+
+    let anchor = verts.iter().sum::<Vector4<f64>>() / verts.len() as f64;
+    let mut volume = 0.0;
+    for (a, (index, facet)) in facets.iter().enumerate() {
+        let p_f = mean(verts, facet);
+        let mut ridges = 0;
+        for (b, (_, other)) in facets.iter().enumerate() {
+            if a == b {
+                continue;
+            }
+            let common: Vec<usize> = facet
+                .iter()
+                .copied()
+                .filter(|k| other.contains(k))
+                .collect();
+            if common.len() < 3 {
+                continue;
+            }
+            let basis = span_basis(verts, &common, eps);
+            if basis.len() != 2 {
+                continue;
+            }
+            ridges += 1;
+            let ring = ordered_ring(verts, &common, &basis);
+            for k in 1..ring.len() - 1 {
+                let m = Matrix4::from_columns(&[
+                    p_f - anchor,
+                    verts[ring[0]] - anchor,
+                    verts[ring[k]] - anchor,
+                    verts[ring[k + 1]] - anchor,
+                ]);
+                volume += m.determinant().abs() / 24.0;
+            }
+        }
+        if ridges == 0 {
+            return Err(VolumeError::DegenerateFacet(*index));
+        }
+    }
+    Ok(volume)
+}
+
+/// Volume of `{x : n_i·x ≤ c_i}`.
+pub fn volume_from_halfspaces(h: &[Hs4]) -> Result<f64, VolumeError> {
+    volume4(&mut Poly4::from_h(h.to_vec()))
+}
+
+fn mean(verts: &[Vector4<f64>], idx: &[usize]) -> Vector4<f64> {
+    idx.iter().map(|&i| verts[i]).sum::<Vector4<f64>>() / idx.len() as f64
+}
+
+/// Ridge vertices in cyclic order around their mean.
+fn ordered_ring(verts: &[Vector4<f64>], idx: &[usize], basis: &[Vector4<f64>]) -> Vec<usize> {
+    let centre = mean(verts, idx);
+    let angle = |i: usize| {
+        let d = verts[i] - centre;
+        basis[1].dot(&d).atan2(basis[0].dot(&d))
+    };
+    let mut ring = idx.to_vec();
+    ring.sort_by(|&a, &b| angle(a).total_cmp(&angle(b)));
+    ring
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rand4::j4;
+
+    fn cube(r: f64) -> Vec<Hs4> {
+        let mut h = Vec::new();
+        for k in 0..4 {
+            let mut e = Vector4::zeros();
+            e[k] = 1.0;
+            h.push(Hs4::new(e, r));
+            h.push(Hs4::new(-e, r));
+        }
+        h
+    }
+
+    #[test]
+    fn hypercube_and_cross_polytope() {
+        assert!((volume_from_halfspaces(&cube(1.0)).unwrap() - 16.0).abs() < 1e-9);
+        let cross: Vec<Hs4> = (0..16u32)
+            .map(|bits| {
+                let n = Vector4::from_fn(|k, _| if bits >> k & 1 == 1 { 1.0 } else { -1.0 });
+                Hs4::new(n, 1.0)
+            })
+            .collect();
+        assert!((volume_from_halfspaces(&cross).unwrap() - 2.0 / 3.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn invariant_under_unimodular_maps_and_redundant_rows() {
+        let mut poly = Poly4::from_h(cube(1.0));
+        poly.h.push(Hs4::new(Vector4::repeat(1.0), 4.0));
+        poly.h.push(poly.h[0]);
+        let shear = Matrix4::new(
+            1.0, 0.3, 0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 0.0, 1.0, -0.7, 0.0, 0.0, 0.0, 1.0,
+        );
+        for m in [shear, j4()] {
+            let mut img = poly
+                .push_forward(&m, &Vector4::new(0.3, 0.0, -1.0, 2.0))
+                .unwrap();
+            img.v.clear();
+            let vol = volume4(&mut img).unwrap();
+            assert!((vol - 16.0).abs() < 1e-9, "{vol}");
+        }
+    }
+
+    #[test]
+    fn flat_input_is_an_error() {
+        let flat: Vec<Vector4<f64>> = (0..8u32)
+            .map(|bits| {
+                Vector4::new(
+                    (bits & 1) as f64,
+                    (bits >> 1 & 1) as f64,
+                    (bits >> 2) as f64,
+                    0.0,
+                )
+            })
+            .collect();
+        assert_eq!(
+            volume4(&mut Poly4::from_v(flat)),
+            Err(VolumeError::LowerDimensional)
+        );
+    }
+}
@@ -0,0 +1,83 @@
+//! Monte Carlo volume estimate by rejection sampling.
+//!
+//! Why this file exists
+//! - Notebook sanity checks on bodies with many facets do not need the exact
+//!   facet-fan volume, only a number with an error bar. Rejection sampling
+//!   from the vertex bounding box gives both for the price of `n` membership
+//!   tests.
+//! - The estimate is `vol(box) · p̂` with `p̂` the hit fraction; the standard
+//!   error is the binomial one, `vol(box) · √(p̂(1 − p̂)/n)`. Thin or skewed
+//!   bodies waste samples (small `p̂`), so the error is returned rather than
+//!   promised.
+//!
+//! References
+//! - TH: docs/src/thesis/geom4d_volume.md
+
+use nalgebra::Vector4;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+use super::Poly4;
+
+/// Estimate and standard error of `vol(K)` from `n_samples` box samples;
+/// `None` for empty or flat bodies or `n_samples = 0`.
+pub fn volume4_mc(poly: &mut Poly4, n_samples: usize, seed: u64) -> Option<(f64, f64)> {
+    poly.ensure_vertices_from_h();
+    poly.ensure_halfspaces_from_v();
+    let first = *poly.v.first()?;
+    let (lo, hi) = poly
+        .v
+        .iter()
+        .fold((first, first), |(lo, hi), v| (lo.inf(v), hi.sup(v)));
+    let extent = hi - lo;
+    let box_vol = extent.iter().product::<f64>();
+    if n_samples == 0 || box_vol <= 0.0 {
+        return None;
+    }
+    let mut rng = StdRng::seed_from_u64(seed);
+    let hits = (0..n_samples)
+        .filter(|_| {
+            let x = lo + extent.component_mul(&Vector4::from_fn(|_, _| rng.gen::<f64>()));
+            poly.h.iter().all(|h| h.n.dot(&x) <= h.c)
+        })
+        .count();
+    let p = hits as f64 / n_samples as f64;
+    Some((
+        box_vol * p,
+        box_vol * (p * (1.0 - p) / n_samples as f64).sqrt(),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::geom4::Hs4;
+
+    #[test]
+    fn box_fills_its_own_bounding_box() {
+        let mut h = Vec::new();
+        for k in 0..4 {
+            let mut e = Vector4::zeros();
+            e[k] = 1.0;
+            h.push(Hs4::new(e, 1.0));
+            h.push(Hs4::new(-e, 1.0));
+        }
+        let (vol, se) = volume4_mc(&mut Poly4::from_h(h), 100, 7).unwrap();
+        assert_eq!((vol, se), (16.0, 0.0));
+    }
+
+    #[test]
+    fn cross_polytope_estimate_is_within_a_few_errors() {
+        let h = (0..16)
+            .map(|mask| {
+                let n = Vector4::from_fn(|k, _| if mask >> k & 1 == 1 { -1.0 } else { 1.0 });
+                Hs4::new(n, 1.0)
+            })
+            .collect();
+        let (vol, se) = volume4_mc(&mut Poly4::from_h(h), 50_000, 11).unwrap();
+        assert!(
+            se > 0.0 && (vol - 2.0 / 3.0).abs() < 4.0 * se,
+            "{vol} ± {se}"
+        );
+    }
+}
@@ -0,0 +1,41 @@
+//! Symplectic capacities and random polytopes in R⁴.
+//!
+//! Why this crate exists
+//! - The thesis asks whether the systolic ratio `c_EHZ² / (2·vol)` of convex
+//!   bodies in R⁴ can exceed 1. This crate holds the geometry (`geom2`,
+//!   `geom4`), the oriented-edge capacity solver (`oriented_edge`), the
+//!   replayable sample generators (`rand4`) and the optimizers (`optimize`)
+//!   behind that search; `viterbo-py` binds them for the pipelines.
+//! - `api` and `prelude` are curated convenience re-exports for internal
+//!   callers (see AGENTS.md, “API Policy”).
+//!
+//! References
+//! - TH: docs/src/thesis/capacity-algorithm-oriented-edge-graph.md
+//! - Code: crates/viterbo/src/spec.md
+
+use nalgebra::Vector2;
+
+pub mod api;
+pub mod geom2;
+pub mod geom4;
+pub mod optimize;
+pub mod oriented_edge;
+pub mod prelude;
+pub mod rand4;
+
+/// Signed area of the parallelogram spanned by `a` and `b` (`ω₀(a, b)` in the plane).
+pub fn parallelogram_area(a: Vector2<f64>, b: Vector2<f64>) -> f64 {
+    a.x * b.y - a.y * b.x
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parallelogram_area_is_signed() {
+        let (e1, e2) = (Vector2::new(1.0, 0.0), Vector2::new(0.0, 1.0));
+        assert_eq!(parallelogram_area(e1, e2), 1.0);
+        assert_eq!(parallelogram_area(e2, e1), -1.0);
+    }
+}
@@ -0,0 +1,322 @@
+//! Simulated annealing over facet offsets (and optionally normals).
+//!
+//! Why this file exists
+//! - Counterexample search: maximize the systolic ratio `c_EHZ² / (2·vol)`
+//!   within a fixed combinatorial type, starting from a hand-picked or
+//!   sampled body. Annealing copes with the non-smooth, plateau-ridden
+//!   landscape the capacity solver produces.
+//! - Each step perturbs one facet: its offset by a log-normal factor and,
+//!   if `normal_step > 0`, its unit normal by a Gaussian kick followed by
+//!   renormalization (a small geodesic move on S³). Moves that change the
+//!   combinatorial type (vertex–facet incidences) are rejected, as are moves
+//!   where the oracle fails. Otherwise Metropolis at temperature
+//!   `T_k = t_start · (t_end / t_start)^{k / (iterations − 1)}`.
+//! - The ratio is scale invariant, so offsets are rescaled after every move
+//!   to keep their geometric mean at 1.
+//!
+//! Replay
+//! - The run is deterministic in `(initial body, params)`; `AnnealTrace`
+//!   additionally records every proposal, so `state_at(k)` rebuilds the body
+//!   after iteration `k` without oracle calls. Traces serialize to JSON.
+//!
+//! References
+//! - TH: docs/src/thesis/random-polytopes.md (“Optimization”)
+//! - Code: crates/viterbo/src/rand4/systolic_mcmc.rs (ScoreOracle)
+
+use nalgebra::Vector4;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::geom4::{Hs4, Poly4};
+use crate::rand4::dirs::standard_normal4;
+use crate::rand4::{tight_indices, AcceptanceStats, GeneratorError, ScoreOracle};
+
+/// Parameters of [`anneal`].
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct AnnealParams {
+    pub iterations: u64,
+    /// Initial and final temperature of the geometric schedule.
+    pub t_start: f64,
+    pub t_end: f64,
+    /// Standard deviation of the log-offset kick.
+    pub offset_step: f64,
+    /// Standard deviation of the normal kick (`0` keeps normals fixed).
+    pub normal_step: f64,
+    pub seed: u64,
+}
+
+impl Default for AnnealParams {
+    fn default() -> Self {
+        Self {
+            iterations: 1_000,
+            t_start: 1e-2,
+            t_end: 1e-5,
+            offset_step: 0.05,
+            normal_step: 0.0,
+            seed: 0,
+        }
+    }
+}
+
+impl AnnealParams {
+    pub fn validate(&self) -> Result<(), GeneratorError> {
+        let temps_ok = self.t_start > 0.0 && self.t_end > 0.0 && self.t_start.is_finite();
+        let steps_ok = self.offset_step > 0.0 && self.normal_step >= 0.0;
+        if !temps_ok || !steps_ok || self.iterations == 0 {
+            return Err(GeneratorError::InvalidParams(
+                "anneal: need iterations > 0, positive temperatures, offset_step > 0, \
+                 normal_step >= 0"
+                    .into(),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Temperature at iteration `k`.
+    pub fn temperature(&self, k: u64) -> f64 {
+        if self.iterations == 1 {
+            return self.t_start;
+        }
+        let s = k as f64 / (self.iterations - 1) as f64;
+        self.t_start * (self.t_end / self.t_start).powf(s)
+    }
+}
+
+/// A halfspace in serializable form.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Facet {
+    pub n: [f64; 4],
+    pub c: f64,
+}
+
+impl From<&Hs4> for Facet {
+    fn from(h: &Hs4) -> Self {
+        Facet {
+            n: h.n.into(),
+            c: h.c,
+        }
+    }
+}
+
+impl From<Facet> for Hs4 {
+    fn from(f: Facet) -> Self {
+        Hs4::new(Vector4::from(f.n), f.c)
+    }
+}
+
+/// One proposal of the run.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct AnnealMove {
+    /// Index of the perturbed facet.
+    pub facet: usize,
+    /// Proposed halfspace, before the global rescaling.
+    pub proposal: Facet,
+    /// Oracle value of the proposal (`None` if rejected before scoring).
+    pub score: Option<f64>,
+    pub accepted: bool,
+}
+
+/// Full record of a run: start, every proposal, and the best body found.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct AnnealTrace {
+    pub params: AnnealParams,
+    pub initial: Vec<Facet>,
+    pub initial_score: f64,
+    pub moves: Vec<AnnealMove>,
+    pub best: Vec<Facet>,
+    pub best_score: f64,
+    /// Iterations completed when the best body was reached (`0` = initial).
+    pub best_iteration: u64,
+    pub stats: AcceptanceStats,
+}
+
+impl AnnealTrace {
+    /// Body after the first `iteration` proposals.
+    pub fn state_at(&self, iteration: u64) -> Poly4 {
+        let mut h: Vec<Hs4> = self.initial.iter().map(|&f| f.into()).collect();
+        for mv in self.moves.iter().take(iteration as usize) {
+            if mv.accepted {
+                h[mv.facet] = mv.proposal.into();
+                rescale(&mut h);
+            }
+        }
+        Poly4::from_h(h)
+    }
+
+    pub fn best_poly(&self) -> Poly4 {
+        let mut poly = Poly4::from_h(self.best.iter().map(|&f| f.into()).collect());
+        poly.ensure_vertices_from_h();
+        poly
+    }
+}
+
+/// Anneal `start` towards higher `oracle` scores within its combinatorial type.
+pub fn anneal<O: ScoreOracle>(
+    start: &Poly4,
+    oracle: &O,
+    params: &AnnealParams,
+) -> Result<AnnealTrace, GeneratorError> {
+    params.validate()?;
+    if start.h.is_empty() || start.h.iter().any(|h| h.c <= 0.0) {
+        return Err(GeneratorError::InvalidParams(
+            "anneal: start body needs an H-rep with the origin in its interior".into(),
+        ));
+    }
+    let mut h: Vec<Hs4> = start
+        .h
+        .iter()
+        .map(|hs| {
+            let norm = hs.n.norm();
+            Hs4::new(hs.n / norm, hs.c / norm)
+        })
+        .collect();
+    rescale(&mut h);
+    let mut poly = Poly4::from_h(h.clone());
+    let signature = combinatorial_type(&mut poly);
+    let mut score = oracle.score(&mut poly).ok_or_else(|| {
+        GeneratorError::Degenerate("anneal: oracle failed on the start body".into())
+    })?;
+
+    let mut rng = StdRng::seed_from_u64(params.seed);
+    let mut trace = AnnealTrace {
+        params: params.clone(),
+        initial: h.iter().map(Facet::from).collect(),
+        initial_score: score,
+        moves: Vec::with_capacity(params.iterations as usize),
+        best: h.iter().map(Facet::from).collect(),
+        best_score: score,
+        best_iteration: 0,
+        stats: AcceptanceStats::default(),
+    };
+    for k in 0..params.iterations {
+        trace.stats.record_attempt();
+        let facet = rng.gen_range(0..h.len());
+        let proposal = propose(&h[facet], params, &mut rng);
+        let u: f64 = rng.gen();
+        let mut candidate = h.clone();
+        candidate[facet] = proposal;
+        rescale(&mut candidate);
+        let mut cand_poly = Poly4::from_h(candidate.clone());
+        let mut mv = AnnealMove {
+            facet,
+            proposal: Facet::from(&proposal),
+            score: None,
+            accepted: false,
+        };
+        if combinatorial_type(&mut cand_poly) != signature {
+            trace.stats.record_rejection("type_changed");
+        } else if let Some(s) = oracle.score(&mut cand_poly) {
+            mv.score = Some(s);
+            if s >= score || u < ((s - score) / params.temperature(k)).exp() {
+                mv.accepted = true;
+                trace.stats.record_accepted();
+                h = candidate;
+                score = s;
+                if s > trace.best_score {
+                    trace.best = h.iter().map(Facet::from).collect();
+                    trace.best_score = s;
+                    trace.best_iteration = k + 1;
+                }
+            } else {
+                trace.stats.record_rejection("metropolis");
+            }
+        } else {
+            trace.stats.record_rejection("oracle_failed");
+        }
+        trace.moves.push(mv);
+    }
+    Ok(trace)
+}
+
+fn propose(h: &Hs4, params: &AnnealParams, rng: &mut StdRng) -> Hs4 {
+    let z = standard_normal4(rng);
+    let c = h.c * (params.offset_step * z[0]).exp();
+    if params.normal_step == 0.0 {
+        return Hs4::new(h.n, c);
+    }
+    let n = h.n + params.normal_step * standard_normal4(rng);
+    Hs4::new(n / n.norm(), c)
+}
+
+/// Rescale offsets to geometric mean 1 (the score is scale invariant).
+fn rescale(h: &mut [Hs4]) {
+    let log_mean = h.iter().map(|hs| hs.c.ln()).sum::<f64>() / h.len() as f64;
+    let factor = (-log_mean).exp();
+    h.iter_mut().for_each(|hs| hs.c *= factor);
+}
+
+/// Sorted incidence sets `{facets through v}` over all vertices `v`.
+pub fn combinatorial_type(poly: &mut Poly4) -> Vec<Vec<usize>> {
+    let mut sets = tight_indices(poly);
+    sets.sort_unstable();
+    sets
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cube() -> Poly4 {
+        let mut hs = Vec::new();
+        for k in 0..4 {
+            let mut e = Vector4::zeros();
+            e[k] = 1.0;
+            hs.push(Hs4::new(e, 1.0 + 0.1 * k as f64));
+            hs.push(Hs4::new(-e, 1.0));
+        }
+        Poly4::from_h(hs)
+    }
+
+    /// Stand-in oracle rewarding balanced offsets: `min c / max c`.
+    fn balance(poly: &mut Poly4) -> Option<f64> {
+        let (lo, hi) = poly.h.iter().fold((f64::INFINITY, 0.0f64), |(lo, hi), h| {
+            (lo.min(h.c), hi.max(h.c))
+        });
+        Some(lo / hi)
+    }
+
+    #[test]
+    fn anneal_improves_and_trace_replays() {
+        let params = AnnealParams {
+            iterations: 200,
+            seed: 4,
+            ..AnnealParams::default()
+        };
+        let trace = anneal(&cube(), &balance, &params).unwrap();
+        assert!(trace.best_score > trace.initial_score);
+        assert_eq!(trace.moves.len(), 200);
+        let mut best = trace.state_at(trace.best_iteration);
+        assert_eq!(balance(&mut best), Some(trace.best_score));
+        let again = anneal(&cube(), &balance, &params).unwrap();
+        assert_eq!(again, trace);
+        #[cfg(feature = "serde")]
+        {
+            let json = serde_json::to_string(&trace).unwrap();
+            let back: AnnealTrace = serde_json::from_str(&json).unwrap();
+            assert_eq!(back.best_iteration, trace.best_iteration);
+        }
+    }
+
+    #[test]
+    fn normal_moves_keep_the_combinatorial_type() {
+        let params = AnnealParams {
+            iterations: 50,
+            normal_step: 0.2,
+            seed: 1,
+            ..AnnealParams::default()
+        };
+        let trace = anneal(&cube(), &balance, &params).unwrap();
+        let mut start = cube();
+        let signature = combinatorial_type(&mut start);
+        for k in [10, 25, 50] {
+            assert_eq!(combinatorial_type(&mut trace.state_at(k)), signature);
+        }
+    }
+}
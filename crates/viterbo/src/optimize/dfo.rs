@@ -0,0 +1,387 @@
+//! Derivative-free optimizers (CMA-ES, Nelder–Mead) behind one harness.
+//!
+//! Why this file exists
+//! - Optimizers behave very differently on the systolic-ratio landscape
+//!   (plateaus, kinks where the minimal orbit switches), so we need to run
+//!   several on the same problem and compare their best-so-far curves.
+//! - Problems are a fixed-dimension parameterization plus an `ObjectiveFn`
+//!   (`Fn(&Poly4) -> f64`, maximized). `OffsetParameterization` fixes the
+//!   unit normals of a start body and optimizes log-offsets, so offsets stay
+//!   positive and the origin interior. Bodies whose objective is NaN count as
+//!   `−∞`.
+//! - Optimizers implement `DerivativeFreeOptimizer` on plain vectors; the
+//!   harness enforces the evaluation budget and records the history.
+//!
+//! References
+//! - TH: docs/src/thesis/random-polytopes.md (“Optimization”)
+//! - N. Hansen, "The CMA Evolution Strategy: A Tutorial", arXiv:1604.00772.
+//! - J. A. Nelder, R. Mead, "A simplex method for function minimization", 1965.
+
+use nalgebra::{DMatrix, DVector, Vector4};
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use super::Facet;
+use crate::geom4::{Hs4, Poly4};
+use crate::rand4::dirs::standard_normal4;
+use crate::rand4::{systolic_ratio4, GeneratorError};
+
+/// Objectives to maximize over bodies.
+pub trait ObjectiveFn: Fn(&Poly4) -> f64 {}
+
+impl<F: Fn(&Poly4) -> f64> ObjectiveFn for F {}
+
+/// The systolic ratio as an objective (`−∞` where it is undefined).
+pub fn systolic_objective(poly: &Poly4) -> f64 {
+    systolic_ratio4(&mut poly.clone()).unwrap_or(f64::NEG_INFINITY)
+}
+
+/// Bodies `{⟨n_i, x⟩ ≤ exp(x_i)}` with fixed unit normals `n_i`.
+#[derive(Clone, Debug)]
+pub struct OffsetParameterization {
+    normals: Vec<Vector4<f64>>,
+}
+
+impl OffsetParameterization {
+    /// Parameterize around `poly`; returns the parameterization and the start point.
+    pub fn from_poly(poly: &Poly4) -> Result<(Self, DVector<f64>), GeneratorError> {
+        if poly.h.is_empty() || poly.h.iter().any(|h| h.c <= 0.0) {
+            return Err(GeneratorError::InvalidParams(
+                "optimize: start body needs an H-rep with the origin in its interior".into(),
+            ));
+        }
+        let normals = poly.h.iter().map(|h| h.n / h.n.norm()).collect();
+        let x0 =
+            DVector::from_iterator(poly.h.len(), poly.h.iter().map(|h| (h.c / h.n.norm()).ln()));
+        Ok((Self { normals }, x0))
+    }
+
+    pub fn dim(&self) -> usize {
+        self.normals.len()
+    }
+
+    pub fn build(&self, x: &DVector<f64>) -> Poly4 {
+        let h = self
+            .normals
+            .iter()
+            .zip(x.iter())
+            .map(|(n, l)| Hs4::new(*n, l.exp()))
+            .collect();
+        Poly4::from_h(h)
+    }
+}
+
+/// A maximizer over `R^n` that spends at most `budget` evaluations.
+pub trait DerivativeFreeOptimizer {
+    fn name(&self) -> &'static str;
+
+    /// Maximize `f` from `x0`; the harness caps evaluations at `budget` anyway.
+    fn maximize(
+        &mut self,
+        f: &mut dyn FnMut(&DVector<f64>) -> f64,
+        x0: &DVector<f64>,
+        budget: usize,
+    );
+}
+
+/// Outcome of one optimizer run.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct OptimizeReport {
+    pub optimizer: String,
+    pub evaluations: usize,
+    pub best_value: f64,
+    pub best_x: Vec<f64>,
+    pub best: Vec<Facet>,
+    /// Best value seen after each evaluation.
+    pub history: Vec<f64>,
+}
+
+/// Run `optimizer` on the offsets of `start` for at most `budget` objective evaluations.
+pub fn optimize_offsets<O: ObjectiveFn>(
+    start: &Poly4,
+    objective: &O,
+    optimizer: &mut dyn DerivativeFreeOptimizer,
+    budget: usize,
+) -> Result<OptimizeReport, GeneratorError> {
+    let (param, x0) = OffsetParameterization::from_poly(start)?;
+    let mut best_value = f64::NEG_INFINITY;
+    let mut best_x = x0.clone();
+    let mut history = Vec::with_capacity(budget);
+    let mut f = |x: &DVector<f64>| {
+        if history.len() >= budget {
+            return f64::NEG_INFINITY;
+        }
+        let value = objective(&param.build(x));
+        let value = if value.is_nan() {
+            f64::NEG_INFINITY
+        } else {
+            value
+        };
+        if value > best_value {
+            best_value = value;
+            best_x = x.clone();
+        }
+        history.push(best_value);
+        value
+    };
+    optimizer.maximize(&mut f, &x0, budget);
+    let best = param.build(&best_x).h.iter().map(Facet::from).collect();
+    Ok(OptimizeReport {
+        optimizer: optimizer.name().to_owned(),
+        evaluations: history.len(),
+        best_value,
+        best_x: best_x.iter().copied().collect(),
+        best,
+        history,
+    })
+}
+
+/// (μ/μ_w, λ)-CMA-ES with cumulative step-size adaptation (Hansen's tutorial defaults).
+#[derive(Clone, Debug)]
+pub struct CmaEs {
+    /// Initial step size.
+    pub sigma0: f64,
+    /// Population size; `None` uses `4 + ⌊3 ln n⌋`.
+    pub lambda: Option<usize>,
+    pub seed: u64,
+}
+
+impl Default for CmaEs {
+    fn default() -> Self {
+        Self {
+            sigma0: 0.1,
+            lambda: None,
+            seed: 0,
+        }
+    }
+}
+
+impl DerivativeFreeOptimizer for CmaEs {
+    fn name(&self) -> &'static str {
+        "cma_es"
+    }
+
+    fn maximize(
+        &mut self,
+        f: &mut dyn FnMut(&DVector<f64>) -> f64,
+        x0: &DVector<f64>,
+        budget: usize,
+    ) {
+        let n = x0.len();
+        let nf = n as f64;
+        let lambda = self
+            .lambda
+            .unwrap_or(4 + (3.0 * nf.ln()).floor() as usize)
+            .max(2);
+        let mu = lambda / 2;
+        let raw: Vec<f64> = (1..=mu)
+            .map(|i| (mu as f64 + 0.5).ln() - (i as f64).ln())
+            .collect();
+        let total: f64 = raw.iter().sum();
+        let w: Vec<f64> = raw.iter().map(|r| r / total).collect();
+        let mu_eff = 1.0 / w.iter().map(|x| x * x).sum::<f64>();
+        let c_sigma = (mu_eff + 2.0) / (nf + mu_eff + 5.0);
+        let d_sigma = 1.0 + 2.0 * (((mu_eff - 1.0) / (nf + 1.0)).sqrt() - 1.0).max(0.0) + c_sigma;
+        let c_c = (4.0 + mu_eff / nf) / (nf + 4.0 + 2.0 * mu_eff / nf);
+        let c_1 = 2.0 / ((nf + 1.3).powi(2) + mu_eff);
+        let c_mu =
+            (1.0 - c_1).min(2.0 * (mu_eff - 2.0 + 1.0 / mu_eff) / ((nf + 2.0).powi(2) + mu_eff));
+        let chi_n = nf.sqrt() * (1.0 - 1.0 / (4.0 * nf) + 1.0 / (21.0 * nf * nf));
+
+        let mut rng = StdRng::seed_from_u64(self.seed);
+        let mut mean = x0.clone();
+        let mut sigma = self.sigma0;
+        let mut cov = DMatrix::<f64>::identity(n, n);
+        let mut p_sigma = DVector::<f64>::zeros(n);
+        let mut p_c = DVector::<f64>::zeros(n);
+        let mut used = 0;
+        let mut generation = 0;
+        while used + lambda <= budget {
+            generation += 1;
+            let eig = cov.clone().symmetric_eigen();
+            let d = eig.eigenvalues.map(|l| l.max(1e-20).sqrt());
+            let b = eig.eigenvectors;
+            let mut pop: Vec<(f64, DVector<f64>)> = (0..lambda)
+                .map(|_| {
+                    let z = gaussian_vector(&mut rng, n);
+                    let y = &b * z.component_mul(&d);
+                    let value = f(&(&mean + sigma * &y));
+                    (value, y)
+                })
+                .collect();
+            used += lambda;
+            pop.sort_by(|a, b| b.0.total_cmp(&a.0));
+            let y_w = pop
+                .iter()
+                .zip(&w)
+                .fold(DVector::zeros(n), |acc, ((_, y), wi)| acc + *wi * y);
+            mean += sigma * &y_w;
+
+            let inv_sqrt = &b * DMatrix::from_diagonal(&d.map(|x| 1.0 / x)) * b.transpose();
+            p_sigma = (1.0 - c_sigma) * &p_sigma
+                + (c_sigma * (2.0 - c_sigma) * mu_eff).sqrt() * (inv_sqrt * &y_w);
+            let ps_norm = p_sigma.norm();
+            let decay = 1.0 - (1.0 - c_sigma).powi(2 * generation);
+            let h_sigma = ps_norm / decay.sqrt() < (1.4 + 2.0 / (nf + 1.0)) * chi_n;
+            let h = if h_sigma { 1.0 } else { 0.0 };
+            p_c = (1.0 - c_c) * &p_c + h * (c_c * (2.0 - c_c) * mu_eff).sqrt() * &y_w;
+            let rank_mu = pop
+                .iter()
+                .zip(&w)
+                .fold(DMatrix::zeros(n, n), |acc, ((_, y), wi)| {
+                    acc + *wi * y * y.transpose()
+                });
+            cov = (1.0 - c_1 - c_mu) * &cov
+                + c_1 * (&p_c * p_c.transpose() + (1.0 - h) * c_c * (2.0 - c_c) * &cov)
+                + c_mu * rank_mu;
+            cov = 0.5 * (&cov + cov.transpose());
+            sigma *= ((c_sigma / d_sigma) * (ps_norm / chi_n - 1.0)).exp();
+        }
+    }
+}
+
+/// Nelder–Mead simplex search (reflection 1, expansion 2, contraction ½, shrink ½).
+#[derive(Clone, Debug)]
+pub struct NelderMead {
+    /// Edge length of the initial axis-aligned simplex.
+    pub step: f64,
+}
+
+impl Default for NelderMead {
+    fn default() -> Self {
+        Self { step: 0.1 }
+    }
+}
+
+impl DerivativeFreeOptimizer for NelderMead {
+    fn name(&self) -> &'static str {
+        "nelder_mead"
+    }
+
+    fn maximize(
+        &mut self,
+        f: &mut dyn FnMut(&DVector<f64>) -> f64,
+        x0: &DVector<f64>,
+        budget: usize,
+    ) {
+        let n = x0.len();
+        let mut simplex: Vec<(f64, DVector<f64>)> = Vec::with_capacity(n + 1);
+        simplex.push((f(x0), x0.clone()));
+        for i in 0..n {
+            let mut x = x0.clone();
+            x[i] += self.step;
+            simplex.push((f(&x), x));
+        }
+        let mut used = n + 1;
+        // Each iteration spends at most n + 2 evaluations (shrink after a failed contraction).
+        while used + n + 2 <= budget {
+            simplex.sort_by(|a, b| b.0.total_cmp(&a.0));
+            let centroid = simplex[..n]
+                .iter()
+                .fold(DVector::zeros(n), |acc, (_, x)| acc + x)
+                / n as f64;
+            let worst = simplex[n].clone();
+            let reflected = &centroid + (&centroid - &worst.1);
+            let f_r = f(&reflected);
+            used += 1;
+            if f_r > simplex[0].0 {
+                let expanded = &centroid + 2.0 * (&centroid - &worst.1);
+                let f_e = f(&expanded);
+                used += 1;
+                simplex[n] = if f_e > f_r {
+                    (f_e, expanded)
+                } else {
+                    (f_r, reflected)
+                };
+            } else if f_r > simplex[n - 1].0 {
+                simplex[n] = (f_r, reflected);
+            } else {
+                let contracted = &centroid + 0.5 * (&worst.1 - &centroid);
+                let f_c = f(&contracted);
+                used += 1;
+                if f_c > worst.0 {
+                    simplex[n] = (f_c, contracted);
+                } else {
+                    let best = simplex[0].1.clone();
+                    for (value, x) in simplex.iter_mut().skip(1) {
+                        *x = &best + 0.5 * (&*x - &best);
+                        *value = f(x);
+                    }
+                    used += n;
+                }
+            }
+        }
+    }
+}
+
+fn gaussian_vector(rng: &mut StdRng, n: usize) -> DVector<f64> {
+    let mut out = DVector::zeros(n);
+    for chunk in 0..n.div_ceil(4) {
+        let z = standard_normal4(rng);
+        for k in 0..4 {
+            if let Some(slot) = out.get_mut(4 * chunk + k) {
+                *slot = z[k];
+            }
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn start() -> Poly4 {
+        let mut hs = Vec::new();
+        for k in 0..4 {
+            let mut e = Vector4::zeros();
+            e[k] = 1.0;
+            hs.push(Hs4::new(e, 0.5 + 0.3 * k as f64));
+            hs.push(Hs4::new(-e, 1.0));
+        }
+        Poly4::from_h(hs)
+    }
+
+    /// Smooth stand-in objective with its maximum at all offsets equal to 1.
+    fn target(poly: &Poly4) -> f64 {
+        -poly.h.iter().map(|h| h.c.ln().powi(2)).sum::<f64>()
+    }
+
+    #[test]
+    fn optimizers_improve_within_budget() {
+        let initial = target(&start());
+        for optimizer in [
+            &mut CmaEs::default() as &mut dyn DerivativeFreeOptimizer,
+            &mut NelderMead::default(),
+        ] {
+            let report = optimize_offsets(&start(), &target, optimizer, 400).unwrap();
+            assert!(report.evaluations <= 400);
+            assert_eq!(report.history.len(), report.evaluations);
+            assert!(report.history.windows(2).all(|w| w[0] <= w[1]));
+            assert!(
+                report.best_value > 0.1 * initial,
+                "{}: {} vs {initial}",
+                report.optimizer,
+                report.best_value
+            );
+            let best = Poly4::from_h(report.best.iter().map(|&f| f.into()).collect());
+            assert_eq!(target(&best), report.best_value);
+        }
+    }
+
+    #[test]
+    fn cma_es_is_seeded() {
+        let run = |seed| {
+            let mut cma = CmaEs {
+                seed,
+                ..CmaEs::default()
+            };
+            optimize_offsets(&start(), &target, &mut cma, 100).unwrap()
+        };
+        assert_eq!(run(3), run(3));
+        assert_ne!(run(3).history, run(4).history);
+    }
+}
@@ -0,0 +1,16 @@
+//! Searches for bodies with extreme systolic ratio.
+//!
+//! Why this module exists
+//! - Sampling (`rand4`) explores distributions; this module exploits them:
+//!   seeded, fully replayable optimizers that push a start body towards
+//!   higher `c_EHZ² / (2·vol)` — the counterexample search the project is
+//!   ultimately about.
+//!
+//! References
+//! - TH: docs/src/thesis/random-polytopes.md (“Optimization”)
+
+mod anneal;
+mod dfo;
+
+pub use anneal::*;
+pub use dfo::*;
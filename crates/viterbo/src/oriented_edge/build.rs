@@ -0,0 +1,278 @@
+//! Build the ridge digraph: charts, first-hit maps, domains and increments.
+//!
+//! Why this file exists
+//! - Every quantity the search needs per edge is affine on the chart of the
+//!   source ridge, so it is computed once here and the DFS only composes.
+//! - Ridge `i = F ∩ G` gets the chart `U_i` (orthonormal, `ω(u₁, u₂) > 0`) and
+//!   the offset `c_i ∈ span{n_F, n_G}` on both facet planes. A point flows
+//!   from `i` into `F` iff `⟨J n_F, n_G⟩ < 0`; Lagrangian ridges (`≈ 0`) keep
+//!   their node but get no edges, which loses no minimizer.
+//! - Along `F`, the exit time to the plane of `j = F ∩ H` is
+//!   `τ_j(x) = (b_H − n_H·x) / d_j` with `d_j = ⟨n_H, J n_F⟩ > 0`. The domain
+//!   asks `τ_j ≥ 0` and `τ_j ≤ τ_k` for every other facet with `d_k > 0`,
+//!   which is linear in `x = U_iᵀ y + c_i`. Edges with an empty domain are
+//!   omitted; a non-positive `det D ψ_ij` on a non-empty domain contradicts
+//!   the orientation lemma and panics.
+//!
+//! References
+//! - TH: docs/src/thesis/capacity-algorithm-oriented-edge-graph.md (“Per-edge Maps and Polyhedral Domains”)
+//! - Code: crates/viterbo/src/geom4/faces.rs (ridge enumeration)
+
+use nalgebra::{Matrix2, Matrix2x4, Matrix4, Vector2, Vector4};
+
+use super::types::{Aff1, EdgeData, FacetId, GeomCfg, Graph, Ridge, RidgeId};
+use crate::geom2::{Aff2, HalfspaceIntersection, Hs2, Poly2};
+use crate::geom4::{face_lattice4, Hs4, Poly4};
+use crate::rand4::j4;
+
+/// Build the ridge digraph of `poly` (vertices are filled in if missing).
+pub fn build_graph(poly: &mut Poly4, cfg: GeomCfg) -> Graph {
+    let lattice = face_lattice4(poly);
+    let h: Vec<Hs4> = poly
+        .h
+        .iter()
+        .map(|h| {
+            let norm = h.n.norm();
+            Hs4::new(h.n / norm, h.c / norm)
+        })
+        .collect();
+    let j = j4();
+    let reeb: Vec<Vector4<f64>> = h.iter().map(|hs| j * hs.n).collect();
+
+    let ridges: Vec<Ridge> = lattice
+        .ridges
+        .iter()
+        .map(|r| {
+            let [a, b] = r.facets;
+            let (chart_u, chart_offset) = ridge_chart(&h[a], &h[b], &j);
+            let points: Vec<Vector2<f64>> =
+                r.vertices.iter().map(|&k| chart_u * poly.v[k]).collect();
+            Ridge {
+                facets: (FacetId(a), FacetId(b)),
+                chart_u,
+                chart_ut: chart_u.transpose(),
+                chart_offset,
+                poly: Poly2::from_points_convex_hull(&points),
+            }
+        })
+        .collect();
+
+    let mut by_facet: Vec<Vec<usize>> = vec![Vec::new(); h.len()];
+    for (i, r) in ridges.iter().enumerate() {
+        by_facet[r.facets.0 .0].push(i);
+        by_facet[r.facets.1 .0].push(i);
+    }
+    let live: Vec<usize> = (0..h.len())
+        .filter(|&k| !lattice.facets[k].is_empty())
+        .collect();
+
+    let mut edges = Vec::new();
+    let mut out_edges = vec![Vec::new(); ridges.len()];
+    for (i, ri) in ridges.iter().enumerate() {
+        let Some(f) = exit_facet(ri, &h, &reeb, cfg) else {
+            continue;
+        };
+        let v = reeb[f];
+        for &jdx in &by_facet[f] {
+            if jdx == i {
+                continue;
+            }
+            let target = other_facet(&ridges[jdx], f);
+            let d_j = h[target].n.dot(&v);
+            if d_j <= cfg.eps_tau {
+                continue;
+            }
+            let ctx = EdgeCtx {
+                h: &h,
+                live: &live,
+                f,
+                target,
+                v,
+                d_j,
+            };
+            let dom_in = ri.poly.intersect(&domain_rows(ri, &ctx, cfg));
+            if !matches!(
+                dom_in.halfspace_intersection(),
+                HalfspaceIntersection::Bounded(_)
+            ) {
+                continue;
+            }
+            let rj = &ridges[jdx];
+            let map_ij = first_hit_map(ri, rj, &ctx);
+            let det = map_ij.m.determinant();
+            assert!(
+                det > cfg.eps_det,
+                "oriented edge {i} -> {jdx} across facet {f}: det Dψ = {det} violates the \
+                 orientation lemma"
+            );
+            let img_out = dom_in
+                .push_forward(&map_ij)
+                .expect("det Dψ > eps_det, so ψ is invertible");
+            let action_inc = action_increment(ri, &ctx);
+            let rotation_inc =
+                rotation_angle(&map_ij.m).map_or(0.0, f64::abs) / std::f64::consts::PI;
+            out_edges[i].push(edges.len());
+            edges.push(EdgeData {
+                from: RidgeId(i),
+                to: RidgeId(jdx),
+                facet: FacetId(f),
+                map_ij,
+                action_inc,
+                rotation_inc,
+                dom_in,
+                img_out,
+            });
+        }
+    }
+    // Cheapest edges first, so the DFS finds a good bound early.
+    let lower_bounds: Vec<f64> = edges.iter().map(action_lower_bound).collect();
+    for out in &mut out_edges {
+        out.sort_by(|&a, &b| lower_bounds[a].total_cmp(&lower_bounds[b]));
+    }
+
+    Graph {
+        num_facets: h.len(),
+        ridges,
+        edges,
+        out_edges,
+    }
+}
+
+/// Signed angle in `(−π, π]` of the orthogonal polar factor of `m`; `None`
+/// unless `m` preserves orientation.
+pub fn rotation_angle(m: &Matrix2<f64>) -> Option<f64> {
+    if m.determinant() <= 0.0 {
+        return None;
+    }
+    let svd = m.svd(true, true);
+    let r = svd.u? * svd.v_t?;
+    Some(r[(1, 0)].atan2(r[(0, 0)]))
+}
+
+/// Minimum of `action_inc` over `dom_in` (`+∞` for an empty domain).
+fn action_lower_bound(e: &EdgeData) -> f64 {
+    match e.dom_in.halfspace_intersection() {
+        HalfspaceIntersection::Bounded(verts) => verts
+            .into_iter()
+            .map(|z| e.action_inc.eval(z))
+            .fold(f64::INFINITY, f64::min),
+        _ => f64::INFINITY,
+    }
+}
+
+/// Facet data of one candidate edge `i →F j` with `j = F ∩ target`.
+struct EdgeCtx<'a> {
+    h: &'a [Hs4],
+    live: &'a [usize],
+    f: usize,
+    target: usize,
+    v: Vector4<f64>,
+    d_j: f64,
+}
+
+/// Canonical chart of the plane `{n_a·x = c_a, n_b·x = c_b}` and its offset.
+fn ridge_chart(a: &Hs4, b: &Hs4, j: &Matrix4<f64>) -> (Matrix2x4<f64>, Vector4<f64>) {
+    let mut basis: Vec<Vector4<f64>> = Vec::with_capacity(4);
+    let candidates = [a.n, b.n]
+        .into_iter()
+        .chain((0..4).map(|k| Vector4::from_fn(|r, _| if r == k { 1.0 } else { 0.0 })));
+    for w in candidates {
+        let mut w = w;
+        for e in &basis {
+            w -= e * e.dot(&w);
+        }
+        let norm = w.norm();
+        if norm > 1e-6 {
+            basis.push(w / norm);
+        }
+        if basis.len() == 4 {
+            break;
+        }
+    }
+    let (u1, mut u2) = (basis[2], basis[3]);
+    if (j * u1).dot(&u2) < 0.0 {
+        u2 = -u2;
+    }
+    let gram = Matrix2::new(a.n.dot(&a.n), a.n.dot(&b.n), b.n.dot(&a.n), b.n.dot(&b.n));
+    let coef = gram
+        .try_inverse()
+        .expect("facets of a ridge have independent normals")
+        * Vector2::new(a.c, b.c);
+    let offset = a.n * coef.x + b.n * coef.y;
+    (
+        Matrix2x4::from_rows(&[u1.transpose(), u2.transpose()]),
+        offset,
+    )
+}
+
+/// The facet the Reeb flow enters through ridge `r`; `None` for Lagrangian ridges.
+fn exit_facet(r: &Ridge, h: &[Hs4], reeb: &[Vector4<f64>], cfg: GeomCfg) -> Option<usize> {
+    let (a, b) = (r.facets.0 .0, r.facets.1 .0);
+    let s = reeb[a].dot(&h[b].n);
+    if s < -cfg.eps_tau {
+        Some(a)
+    } else if s > cfg.eps_tau {
+        Some(b)
+    } else {
+        None
+    }
+}
+
+fn other_facet(r: &Ridge, f: usize) -> usize {
+    if r.facets.0 .0 == f {
+        r.facets.1 .0
+    } else {
+        r.facets.0 .0
+    }
+}
+
+/// `τ_j ≥ 0` and `τ_j ≤ τ_k` for every admissible `k`, as rows on the chart of `ri`.
+fn domain_rows(ri: &Ridge, ctx: &EdgeCtx<'_>, cfg: GeomCfg) -> Poly2 {
+    let hj = &ctx.h[ctx.target];
+    // `w·x ≤ c` on the ridge plane becomes `(U w)·y ≤ c − w·c_i` on the chart.
+    // Rows with `w ⊥ ridge` are constant there; keep only their verdict, as
+    // their normalized offsets would swamp the polygon's tolerances.
+    let mut rows = Vec::new();
+    let mut push = |w: Vector4<f64>, c: f64| {
+        let (n, c) = (ri.chart_u * w, c - w.dot(&ri.chart_offset));
+        if n.norm() > cfg.eps_tau * w.norm() {
+            rows.push(Hs2::new(n, c));
+        } else if c < -cfg.eps_feas * w.norm() {
+            rows.push(Hs2::new(n * 0.0, -1.0));
+        }
+    };
+    push(hj.n, hj.c);
+    for &k in ctx.live {
+        if k == ctx.f || k == ctx.target {
+            continue;
+        }
+        let hk = &ctx.h[k];
+        let d_k = hk.n.dot(&ctx.v);
+        if d_k <= cfg.eps_tau {
+            continue;
+        }
+        // d_k (b_j − n_j·x) ≤ d_j (b_k − n_k·x)
+        push(hk.n * ctx.d_j - hj.n * d_k, hk.c * ctx.d_j - hj.c * d_k);
+    }
+    Poly2::from_halfspaces(rows)
+}
+
+/// `ψ_ij(y) = U_j (P (U_iᵀ y + c_i) + v b_j / d_j)` with `P = I − v n_jᵀ / d_j`.
+fn first_hit_map(ri: &Ridge, rj: &Ridge, ctx: &EdgeCtx<'_>) -> Aff2 {
+    let hj = &ctx.h[ctx.target];
+    let p = Matrix4::identity() - ctx.v * hj.n.transpose() / ctx.d_j;
+    Aff2 {
+        m: rj.chart_u * p * ri.chart_ut,
+        t: rj.chart_u * (p * ri.chart_offset + ctx.v * (hj.c / ctx.d_j)),
+    }
+}
+
+/// `(b_F / 2)·τ_j(U_iᵀ y + c_i)` as an affine functional of `y`.
+fn action_increment(ri: &Ridge, ctx: &EdgeCtx<'_>) -> Aff1 {
+    let hj = &ctx.h[ctx.target];
+    let half_b = 0.5 * ctx.h[ctx.f].c;
+    Aff1 {
+        a: -(ri.chart_u * hj.n) * (half_b / ctx.d_j),
+        b: half_b * (hj.c - hj.n.dot(&ri.chart_offset)) / ctx.d_j,
+    }
+}
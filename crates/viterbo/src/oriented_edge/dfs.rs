@@ -0,0 +1,290 @@
+//! Depth-first cycle search on the ridge digraph.
+//!
+//! Why this file exists
+//! - A minimum-action orbit visits every facet at most once (HK2017, CH2021),
+//!   so cycles are searched as simple paths from their smallest ridge, never
+//!   re-entering a traversed facet. This is the pseudo code of `spec.md`.
+//! - Per path we carry the accumulated first-hit map `Ψ` (chart of the start
+//!   to chart of the end), the accumulated action as a functional on the end
+//!   chart and the candidate set `C` of endpoints whose action stays below
+//!   the incumbent. Subtrees die as soon as the minimum action over `C`
+//!   reaches the incumbent or `C` becomes empty.
+//! - No rotation pruning yet: `EdgeData::rotation_inc` is the polar angle of
+//!   `D ψ_ij`, not the CZ rotation increment of `spec.md`, and cutting at
+//!   `ρ > 2` on that proxy drops genuine minimizers.
+//! - Closing a path solves `Ψ(z) = z` on `C`. Identity-like returns (`Ψ ≈ id`,
+//!   e.g. the cube) admit a whole family of fixed points; rank-one returns a
+//!   segment. Both are handled by minimising the affine action over the
+//!   fixed-point set inside `C`.
+//! - Closures with (numerically) zero action are rejected: they are points
+//!   of a lower-dimensional face shared by every ridge of the path.
+//! - `solve_with_budget` caps the number of expanded path nodes and reports
+//!   `BudgetExceeded` instead of a capacity once the cap is hit: a partial
+//!   search only yields an upper bound, never the minimum.
+//! - The initial incumbent is `π R²` for the circumradius `R` (monotonicity
+//!   of `c_EHZ` under inclusion in the ball), padded so ball-like bodies
+//!   still close.
+//!
+//! References
+//! - TH: docs/src/thesis/capacity-algorithm-oriented-edge-graph.md (“Search”)
+//! - Code: crates/viterbo/src/spec.md (“Algorithm”)
+
+use std::fmt;
+
+use nalgebra::{Matrix2, Vector2};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use super::build::build_graph;
+use super::types::{Aff1, GeomCfg, Graph, RidgeId};
+use crate::geom2::{Aff2, HalfspaceIntersection, Hs2, Poly2};
+use crate::geom4::Poly4;
+
+/// Relative padding of the `π R²` starting bound.
+const INITIAL_BOUND_SLACK: f64 = 1e-9;
+
+/// The search expanded `max_nodes` path nodes without finishing.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct BudgetExceeded {
+    pub max_nodes: usize,
+}
+
+impl fmt::Display for BudgetExceeded {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "capacity search exceeded its budget of {} nodes",
+            self.max_nodes
+        )
+    }
+}
+
+impl std::error::Error for BudgetExceeded {}
+
+/// Minimum action over the closed characteristics found by the search, and
+/// the ridges of the minimizing cycle (starting at its smallest ridge).
+///
+/// `None` if no cycle closes below `π R²` (lower-dimensional or non-generic
+/// input).
+pub fn solve(poly: &mut Poly4, cfg: GeomCfg) -> Option<(f64, Vec<RidgeId>)> {
+    solve_with_budget(poly, cfg, usize::MAX)
+        .unwrap_or_else(|_| unreachable!("the search cannot expand usize::MAX nodes"))
+}
+
+/// [`solve`] that gives up after expanding `max_nodes` path nodes.
+pub fn solve_with_budget(
+    poly: &mut Poly4,
+    cfg: GeomCfg,
+    max_nodes: usize,
+) -> Result<Option<(f64, Vec<RidgeId>)>, BudgetExceeded> {
+    let graph = build_graph(poly, cfg);
+    let radius = poly.v.iter().map(|v| v.norm()).fold(0.0, f64::max);
+    let ball = std::f64::consts::PI * radius * radius;
+    let mut search = Search {
+        graph: &graph,
+        cfg,
+        start: 0,
+        min_action: cfg.eps_feas * ball,
+        best: ball * (1.0 + INITIAL_BOUND_SLACK),
+        best_cycle: None,
+        facet_seen: vec![false; graph.num_facets],
+        ridge_seen: vec![false; graph.ridges.len()],
+        path: Vec::new(),
+        nodes_left: Some(max_nodes),
+    };
+    for start in 0..graph.ridges.len() {
+        let HalfspaceIntersection::Bounded(_) = graph.ridges[start].poly.halfspace_intersection()
+        else {
+            continue;
+        };
+        search.start = start;
+        search.ridge_seen[start] = true;
+        search.path.push(RidgeId(start));
+        search.extend(
+            start,
+            &PathState {
+                candidate: graph.ridges[start].poly.clone(),
+                action: Aff1::zero(),
+                phi: Aff2::identity(),
+            },
+        );
+        search.path.pop();
+        search.ridge_seen[start] = false;
+        if search.out_of_budget() {
+            return Err(BudgetExceeded { max_nodes });
+        }
+    }
+    let best = search.best;
+    Ok(search.best_cycle.map(|cycle| (best, cycle)))
+}
+
+/// [`solve`] with `GeomCfg::default()`.
+pub fn solve_with_defaults(poly: &mut Poly4) -> Option<(f64, Vec<RidgeId>)> {
+    solve(poly, GeomCfg::default())
+}
+
+/// Accumulated data of the current path, all on the chart of its last ridge.
+struct PathState {
+    /// Endpoints of admissible trajectories with action below the incumbent.
+    candidate: Poly2,
+    action: Aff1,
+    /// Chart of the start ridge to chart of the last ridge.
+    phi: Aff2,
+}
+
+struct Search<'g> {
+    graph: &'g Graph,
+    cfg: GeomCfg,
+    start: usize,
+    /// Closures at or below this action run through a lower-dimensional face
+    /// with zero-length segments; they are not orbits.
+    min_action: f64,
+    best: f64,
+    best_cycle: Option<Vec<RidgeId>>,
+    facet_seen: Vec<bool>,
+    ridge_seen: Vec<bool>,
+    path: Vec<RidgeId>,
+    /// `None` once the node budget is spent; the search then unwinds.
+    nodes_left: Option<usize>,
+}
+
+impl Search<'_> {
+    fn out_of_budget(&self) -> bool {
+        self.nodes_left.is_none()
+    }
+
+    fn extend(&mut self, at: usize, state: &PathState) {
+        self.nodes_left = self.nodes_left.and_then(|n| n.checked_sub(1));
+        if self.out_of_budget() {
+            return;
+        }
+        let graph = self.graph;
+        let out = &graph.out_edges[at];
+        // Every out-edge of `at` crosses the same facet, and so does the one
+        // closing edge into `start`: if the path can close, nothing else in
+        // this subtree can without re-entering that facet.
+        if let Some(&k) = out.iter().find(|&&k| graph.edges[k].to.0 == self.start) {
+            if let Some(next) = self.step(state, k) {
+                if let Some(action) =
+                    fixed_point_action(&next.phi, &next.action, &next.candidate, self.cfg)
+                {
+                    if action > self.min_action && action < self.best {
+                        self.best = action;
+                        let mut cycle = self.path.clone();
+                        cycle.push(RidgeId(self.start));
+                        self.best_cycle = Some(cycle);
+                    }
+                }
+            }
+            return;
+        }
+        for &k in out {
+            let e = &graph.edges[k];
+            let to = e.to.0;
+            if to < self.start || self.ridge_seen[to] || self.facet_seen[e.facet.0] {
+                continue;
+            }
+            let Some(next) = self.step(state, k) else {
+                continue;
+            };
+            self.facet_seen[e.facet.0] = true;
+            self.ridge_seen[to] = true;
+            self.path.push(e.to);
+            self.extend(to, &next);
+            self.path.pop();
+            self.ridge_seen[to] = false;
+            self.facet_seen[e.facet.0] = false;
+            if self.out_of_budget() {
+                return;
+            }
+        }
+    }
+
+    /// The path state after edge `k`; `None` if the subtree can be pruned.
+    fn step(&self, state: &PathState, k: usize) -> Option<PathState> {
+        let e = &self.graph.edges[k];
+        if self.facet_seen[e.facet.0] {
+            return None;
+        }
+        let mut gated = state.candidate.intersect(&e.dom_in);
+        let HalfspaceIntersection::Bounded(verts) = gated.halfspace_intersection() else {
+            return None;
+        };
+        let total = state.action.add(&e.action_inc);
+        let (lower, upper) = verts
+            .iter()
+            .map(|&z| total.eval(z))
+            .fold((f64::INFINITY, f64::NEG_INFINITY), |(lo, hi), a| {
+                (lo.min(a), hi.max(a))
+            });
+        if lower >= self.best {
+            return None;
+        }
+        // Only cut when the bound bites: a near-constant action has a
+        // near-zero gradient, which would blow up the normalized row.
+        if upper > self.best {
+            gated.insert_halfspace(Hs2::new(total.a, self.best - total.b));
+        }
+        let candidate = gated
+            .push_forward(&e.map_ij)?
+            .intersect(&self.graph.ridges[e.to.0].poly);
+        let HalfspaceIntersection::Bounded(_) = candidate.halfspace_intersection() else {
+            return None;
+        };
+        Some(PathState {
+            candidate,
+            action: total.compose(&e.map_ij.inverse()?),
+            phi: e.map_ij.compose(&state.phi),
+        })
+    }
+}
+
+/// Minimum of `action` over the fixed points of `phi` in `candidate`, for a
+/// path that has just returned to its start ridge.
+pub(super) fn fixed_point_action(
+    phi: &Aff2,
+    action: &Aff1,
+    candidate: &Poly2,
+    cfg: GeomCfg,
+) -> Option<f64> {
+    let HalfspaceIntersection::Bounded(verts) = candidate.halfspace_intersection() else {
+        return None;
+    };
+    let d = Matrix2::identity() - phi.m;
+    let t = phi.t;
+    if d.determinant().abs() > cfg.eps_det {
+        let z = d.try_inverse()? * t;
+        return candidate.contains(&z, cfg.eps_feas).then(|| action.eval(z));
+    }
+    let rows = [d.row(0).transpose(), d.row(1).transpose()];
+    let r = usize::from(rows[1].norm() > rows[0].norm());
+    if rows[r].norm() <= cfg.eps_feas {
+        // Ψ ≈ id: every candidate point closes up.
+        return (t.norm() <= cfg.eps_feas).then(|| {
+            verts
+                .iter()
+                .map(|&z| action.eval(z))
+                .fold(f64::INFINITY, f64::min)
+        });
+    }
+    // Rank one: the fixed points lie on `rows[r]·z = t[r]`; on its chord
+    // through the candidate the action is affine, so check the chord ends.
+    let (n, c) = (rows[r], t[r]);
+    let on_line = |z: Vector2<f64>| (d * z - t).norm() <= cfg.eps_feas;
+    let mut best = f64::INFINITY;
+    for (p, q) in verts.iter().zip(verts.iter().cycle().skip(1)) {
+        let (fp, fq) = (n.dot(p) - c, n.dot(q) - c);
+        let hit = if fp.abs() <= cfg.eps_feas {
+            *p
+        } else if fp * fq < 0.0 {
+            p + (q - p) * (fp / (fp - fq))
+        } else {
+            continue;
+        };
+        if on_line(hit) {
+            best = best.min(action.eval(hit));
+        }
+    }
+    best.is_finite().then_some(best)
+}
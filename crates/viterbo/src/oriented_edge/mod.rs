@@ -0,0 +1,26 @@
+//! Oriented-edge graph algorithm for the EHZ capacity of 4D polytopes.
+//!
+//! Why this module exists
+//! - Closed characteristics on a generic convex polytope travel straight
+//!   along Reeb directions `J n_F` inside facets and cross ridges at single
+//!   points. Recording which ridge follows which turns the variational
+//!   problem into a cycle search on the ridge digraph with 2D affine data per
+//!   edge (first-hit map, domain, action and rotation increments).
+//! - `build` turns a `Poly4` into that graph; `dfs` searches it with
+//!   push-forward candidate sets, action/rotation pruning and a fixed-point
+//!   closure; `types` holds the shared data.
+//!
+//! References
+//! - TH: docs/src/thesis/capacity-algorithm-oriented-edge-graph.md
+//! - Code: crates/viterbo/src/spec.md (contracts and pruning lemmas)
+
+mod build;
+pub(super) mod dfs;
+mod types;
+
+#[cfg(test)]
+mod tests;
+
+pub use build::*;
+pub use dfs::*;
+pub use types::*;
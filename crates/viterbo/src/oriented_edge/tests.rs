@@ -0,0 +1,245 @@
+use nalgebra::{Matrix2, Matrix4, Vector4};
+
+use super::*;
+use crate::geom2::HalfspaceIntersection;
+use crate::geom4::{volume4, Hs4, Poly4};
+use crate::rand4::{
+    is_symplectic, random_symplectic4, DirectionSampler, SymmetricHalfspaceGenerator,
+    SymmetricHalfspaceParams,
+};
+
+/// `[-a, a]²` in the `(x1, y1)` plane times `[-b, b]²` in the `(x2, y2)` plane.
+fn symplectic_product_of_squares(a: f64, b: f64) -> Poly4 {
+    let mut hs = Vec::new();
+    for (k, r) in [(0, a), (2, a), (1, b), (3, b)] {
+        let e = Vector4::from_fn(|i, _| if i == k { 1.0 } else { 0.0 });
+        hs.push(Hs4::new(e, r));
+        hs.push(Hs4::new(-e, r));
+    }
+    Poly4::from_h(hs)
+}
+
+fn vertices(poly: &crate::geom2::Poly2) -> Vec<nalgebra::Vector2<f64>> {
+    match poly.halfspace_intersection() {
+        HalfspaceIntersection::Bounded(vs) => vs,
+        other => panic!("expected a bounded polygon, got {other:?}"),
+    }
+}
+
+#[test]
+fn smoke_graph_build_cube_edges_exist() {
+    let mut cube = symplectic_product_of_squares(1.0, 1.0);
+    let g = build_graph(&mut cube, GeomCfg::default());
+    assert_eq!(g.num_facets, 8);
+    // 28 facet pairs minus the 4 opposite ones.
+    assert_eq!(g.ridges.len(), 24);
+    assert!(!g.edges.is_empty());
+    for e in &g.edges {
+        assert!(e.map_ij.m.determinant() > 0.0);
+        assert!(g.out_edges[e.from.0].iter().any(|&k| g.edges[k].to == e.to));
+    }
+    // Lagrangian ridges (both facets in one Lagrangian coordinate plane) have no out-edges.
+    for (i, r) in g.ridges.iter().enumerate() {
+        let (a, b) = (r.facets.0 .0 / 2, r.facets.1 .0 / 2);
+        let symplectic_pair = (a / 2 == b / 2) && a != b;
+        assert_eq!(!g.out_edges[i].is_empty(), symplectic_pair, "ridge {i}");
+    }
+}
+
+#[test]
+fn tau_domain_basic_properties_on_cube() {
+    let mut cube = symplectic_product_of_squares(1.0, 1.0);
+    let cfg = GeomCfg::default();
+    let g = build_graph(&mut cube, cfg);
+    for e in &g.edges {
+        let from = &g.ridges[e.from.0];
+        let to = &g.ridges[e.to.0];
+        let n_f = cube.h[e.facet.0].n;
+        for z in vertices(&e.dom_in) {
+            assert!(from.poly.contains(&z, cfg.eps_feas));
+            // The exit time is non-negative, and the flow stays on the facet.
+            let tau = e.action_inc.eval(z) * 2.0 / cube.h[e.facet.0].c;
+            assert!(tau >= -cfg.eps_feas);
+            let x = from.lift(&z);
+            let hit = x + crate::rand4::j4() * n_f * tau;
+            assert!(cube.h.iter().all(|h| h.n.dot(&hit) <= h.c + 1e-9));
+            let w = e.map_ij.apply(&z);
+            assert!((to.lift(&w) - hit).norm() <= 1e-9);
+            assert!(to.poly.contains(&w, cfg.eps_feas));
+        }
+        for w in vertices(&e.img_out) {
+            assert!(to.poly.contains(&w, cfg.eps_feas));
+        }
+    }
+}
+
+#[test]
+fn golden_capacity_hypercube_minus1_1_pow4_is_4() {
+    let mut cube = symplectic_product_of_squares(1.0, 1.0);
+    let (capacity, cycle) = solve_with_defaults(&mut cube).expect("the cube has a closed orbit");
+    assert!((capacity - 4.0).abs() < 1e-9, "capacity {capacity}");
+    assert_eq!(cycle.first(), cycle.last());
+    assert_eq!(cycle.len(), 5);
+}
+
+#[test]
+fn golden_capacity_product_of_squares_matches_min_area() {
+    // Siburg: c(K × L) = min(area K, area L) for a symplectic product.
+    let mut poly = symplectic_product_of_squares(1.0, 2.0);
+    let (capacity, _) = solve_with_defaults(&mut poly).expect("products have closed orbits");
+    assert!((capacity - 4.0).abs() < 1e-9, "capacity {capacity}");
+}
+
+#[test]
+fn invariance_under_block_rotation_symplectomorphism() {
+    let (c, s) = (0.3f64.cos(), 0.3f64.sin());
+    // Rotation by 0.3 in the (x1, y1) plane.
+    let mut rot = Matrix4::identity();
+    rot[(0, 0)] = c;
+    rot[(0, 2)] = -s;
+    rot[(2, 0)] = s;
+    rot[(2, 2)] = c;
+    assert!(is_symplectic(&rot));
+    let poly = symplectic_product_of_squares(1.0, 2.0);
+    let mut rotated = poly
+        .push_forward(&rot, &Vector4::zeros())
+        .expect("rotations are invertible");
+    let (capacity, _) = solve_with_defaults(&mut rotated).expect("products have closed orbits");
+    assert!((capacity - 4.0).abs() < 1e-9, "capacity {capacity}");
+}
+
+#[test]
+fn capacity_is_invariant_under_random_symplectic_maps() {
+    let mut cube = symplectic_product_of_squares(1.0, 1.0);
+    let a = random_symplectic4(7, 1.5).expect("valid squeeze bound");
+    let mut image = cube
+        .push_forward(&a, &Vector4::zeros())
+        .expect("symplectic maps are invertible");
+    let (reference, _) = solve_with_defaults(&mut cube).expect("the cube has a closed orbit");
+    let (capacity, _) = solve_with_defaults(&mut image).expect("images have closed orbits");
+    assert!(
+        (capacity - reference).abs() < 1e-6,
+        "{capacity} vs {reference}"
+    );
+}
+
+#[test]
+fn rotation_angle_reads_the_polar_factor() {
+    let theta = 0.7f64;
+    let r = Matrix2::new(theta.cos(), -theta.sin(), theta.sin(), theta.cos());
+    let stretched = r * Matrix2::new(3.0, 0.0, 0.0, 0.5);
+    assert!((rotation_angle(&stretched).unwrap() - theta).abs() < 1e-12);
+    assert_eq!(rotation_angle(&Matrix2::new(1.0, 0.0, 0.0, -1.0)), None);
+}
+
+#[test]
+fn scaling_scales_capacity_quadratically() {
+    let mut small = symplectic_product_of_squares(1.0, 2.0);
+    let mut large = symplectic_product_of_squares(3.0, 6.0);
+    let (c_small, _) = solve_with_defaults(&mut small).unwrap();
+    let (c_large, _) = solve_with_defaults(&mut large).unwrap();
+    assert!((c_large - 9.0 * c_small).abs() < 1e-6);
+}
+
+#[test]
+fn generic_bodies_close_below_the_ball_bound() {
+    let params = SymmetricHalfspaceParams {
+        directions: 6,
+        radius_min: 0.8,
+        radius_max: 1.2,
+        anisotropy: None,
+        sampler: DirectionSampler::default(),
+    };
+    for seed in 0..5 {
+        let mut poly = SymmetricHalfspaceGenerator::generate_single(&params, seed).unwrap();
+        let (capacity, cycle) =
+            solve_with_defaults(&mut poly).unwrap_or_else(|| panic!("seed {seed} did not close"));
+        let radius = poly.v.iter().map(|v| v.norm()).fold(0.0, f64::max);
+        assert!(
+            capacity > 0.0 && capacity <= std::f64::consts::PI * radius * radius,
+            "seed {seed}: {capacity} {cycle:?} R={radius}"
+        );
+        let volume = volume4(&mut poly).unwrap();
+        assert!(capacity * capacity / (2.0 * volume) <= 1.0, "seed {seed}");
+        let mut inner = cycle[..cycle.len() - 1].to_vec();
+        inner.sort();
+        inner.dedup();
+        assert_eq!(
+            inner.len(),
+            cycle.len() - 1,
+            "cycle {cycle:?} revisits a ridge"
+        );
+    }
+}
+
+#[test]
+fn cycle_closure_unique_fixed_point_on_tiny_graph() {
+    use crate::geom2::{Aff2, Poly2};
+    use nalgebra::Vector2;
+
+    let square = Poly2::from_points_convex_hull(&[
+        Vector2::new(-1.0, -1.0),
+        Vector2::new(1.0, -1.0),
+        Vector2::new(1.0, 1.0),
+        Vector2::new(-1.0, 1.0),
+    ]);
+    // Ψ(z) = z / 2 + (0.1, 0) contracts onto z* = (0.2, 0).
+    let phi = Aff2 {
+        m: Matrix2::identity() * 0.5,
+        t: Vector2::new(0.1, 0.0),
+    };
+    let cfg = GeomCfg::default();
+    let zero = dfs::fixed_point_action(&phi, &Aff1::zero(), &square, cfg).unwrap();
+    assert!(zero.abs() < 1e-12);
+    let action = Aff1 {
+        a: Vector2::new(2.0, 1.0),
+        b: 3.0,
+    };
+    let value = dfs::fixed_point_action(&phi, &action, &square, cfg).unwrap();
+    assert!((value - 3.4).abs() < 1e-12);
+    // Moving z* outside the candidate set leaves no closure.
+    let far = Aff2 {
+        t: Vector2::new(1.5, 0.0),
+        ..phi
+    };
+    assert_eq!(dfs::fixed_point_action(&far, &action, &square, cfg), None);
+}
+
+#[test]
+fn cross_polytope_and_simplex_smoke_capacities() {
+    let mut cross = Poly4::from_h(
+        (0..16)
+            .map(|mask| {
+                let n = Vector4::from_fn(|i, _| if mask >> i & 1 == 1 { -1.0 } else { 1.0 });
+                Hs4::new(n, 1.0)
+            })
+            .collect(),
+    );
+    let centroid = Vector4::repeat(0.2);
+    let simplex_vertices = (0..5)
+        .map(|k| Vector4::from_fn(|i, _| if i == k { 1.0 } else { 0.0 }) - centroid)
+        .collect();
+    let mut simplex = Poly4::from_v(simplex_vertices);
+    simplex.ensure_halfspaces_from_v();
+    let (capacity, _) = solve_with_defaults(&mut cross).expect("the cross-polytope closes");
+    let radius = cross.v.iter().map(|v| v.norm()).fold(0.0, f64::max);
+    assert!(capacity > 0.0 && capacity <= std::f64::consts::PI * radius * radius);
+    // Every closure on the simplex runs along an edge shared by three facets
+    // (zero action), so the generic-cycle search must report none rather
+    // than a spurious value.
+    assert_eq!(solve_with_defaults(&mut simplex), None);
+}
+
+#[test]
+fn node_budget_is_reported_not_truncated() {
+    let cfg = GeomCfg::default();
+    let mut cube = symplectic_product_of_squares(1.0, 1.0);
+    assert_eq!(
+        solve_with_budget(&mut cube, cfg, 3),
+        Err(BudgetExceeded { max_nodes: 3 })
+    );
+    let (capacity, _) = solve_with_budget(&mut cube, cfg, 100_000)
+        .expect("the cube needs far fewer nodes")
+        .expect("the cube has a closed orbit");
+    assert!((capacity - 4.0).abs() < 1e-9, "capacity {capacity}");
+}
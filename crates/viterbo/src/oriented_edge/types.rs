@@ -0,0 +1,138 @@
+//! Graph data shared by the builder and the search.
+//!
+//! Why this file exists
+//! - The builder, the DFS, the benches and the Python inspection handle all
+//!   read the same per-ridge charts and per-edge maps. Field names follow the
+//!   symbol map of the design doc (`map_ij`, `dom_in`, `img_out`, ...).
+//!
+//! References
+//! - TH: docs/src/thesis/capacity-algorithm-oriented-edge-graph.md (“Symbol map”)
+
+use nalgebra::{Matrix2x4, Matrix4x2, Vector2, Vector4};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::geom2::{Aff2, Poly2};
+
+/// Numerical tolerances of the graph builder and the fixed-point closure.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct GeomCfg {
+    /// Determinants at or below this are treated as singular.
+    pub eps_det: f64,
+    /// Slack for feasibility and membership tests.
+    pub eps_feas: f64,
+    /// Slack for exit-time denominators and tie-breaking.
+    pub eps_tau: f64,
+}
+
+impl Default for GeomCfg {
+    fn default() -> Self {
+        Self {
+            eps_det: 1e-12,
+            eps_feas: 1e-9,
+            eps_tau: 1e-9,
+        }
+    }
+}
+
+/// Index into `Graph::ridges`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct RidgeId(pub usize);
+
+/// Index into the polytope's halfspaces.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct FacetId(pub usize);
+
+/// Affine functional `z ↦ a·z + b` on a ridge chart.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Aff1 {
+    pub a: Vector2<f64>,
+    pub b: f64,
+}
+
+impl Aff1 {
+    pub fn zero() -> Self {
+        Self {
+            a: Vector2::zeros(),
+            b: 0.0,
+        }
+    }
+
+    pub fn eval(&self, z: Vector2<f64>) -> f64 {
+        self.a.dot(&z) + self.b
+    }
+
+    pub fn add(&self, other: &Aff1) -> Aff1 {
+        Aff1 {
+            a: self.a + other.a,
+            b: self.b + other.b,
+        }
+    }
+
+    /// `self ∘ f`.
+    pub fn compose(&self, f: &Aff2) -> Aff1 {
+        Aff1 {
+            a: f.m.transpose() * self.a,
+            b: self.a.dot(&f.t) + self.b,
+        }
+    }
+}
+
+/// A 2-face `F ∩ G` with its canonical chart.
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Ridge {
+    /// The two facets, lower index first.
+    pub facets: (FacetId, FacetId),
+    /// Chart `U_i`: rows are an orthonormal basis of the ridge plane with `ω(u₁, u₂) ≥ 0`.
+    pub chart_u: Matrix2x4<f64>,
+    /// `U_iᵀ`, the left inverse of the chart on the plane.
+    pub chart_ut: Matrix4x2<f64>,
+    /// `c_i ∈ span{n_F, n_G}` on both facet planes, so `Ξ_i(y) = U_iᵀ y + c_i`.
+    pub chart_offset: Vector4<f64>,
+    /// The ridge polygon `A_i` in chart coordinates.
+    pub poly: Poly2,
+}
+
+impl Ridge {
+    /// The point of the ridge plane with chart coordinates `y`.
+    pub fn lift(&self, y: &Vector2<f64>) -> Vector4<f64> {
+        self.chart_ut * y + self.chart_offset
+    }
+}
+
+/// Oriented edge `i →F j`: points of `dom_in ⊂ A_i` flow along `J n_F` and hit `j` first.
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct EdgeData {
+    pub from: RidgeId,
+    pub to: RidgeId,
+    pub facet: FacetId,
+    /// First-hit map `ψ_ij` between the charts of `from` and `to`.
+    pub map_ij: Aff2,
+    /// Action `(b_F / 2)·τ_ij` of the segment, as a functional on chart `from`.
+    pub action_inc: Aff1,
+    /// `|angle of the polar factor of D ψ_ij| / π ∈ [0, 1]`; a diagnostic
+    /// proxy, not the CZ rotation increment, so the search does not prune on it.
+    pub rotation_inc: f64,
+    /// `dom ψ_ij ⊂ A_i`.
+    pub dom_in: Poly2,
+    /// `im ψ_ij ⊂ A_j`.
+    pub img_out: Poly2,
+}
+
+/// Ridge digraph of a polytope.
+#[derive(Clone, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Graph {
+    /// Number of halfspaces of the polytope (facet ids index them).
+    pub num_facets: usize,
+    pub ridges: Vec<Ridge>,
+    pub edges: Vec<EdgeData>,
+    /// Indices into `edges` leaving each ridge.
+    pub out_edges: Vec<Vec<usize>>,
+}
@@ -0,0 +1,9 @@
+//! Common types for glob import (`use viterbo::prelude::*`).
+//!
+//! Why this file exists
+//! - The 2D/4D value types and the affine helpers show up in nearly every
+//!   module that touches the solver; the prelude keeps those imports short.
+
+pub use crate::api::*;
+pub use crate::geom2::{Aff2, HalfspaceIntersection, Hs2, Poly2};
+pub use crate::oriented_edge::{Aff1, Ridge};
@@ -0,0 +1,258 @@
+//! Per-sample random anisotropy: wrap any 4D generator and apply a random linear map.
+//!
+//! Why this file exists
+//! - The `anisotropy` params of the older families take one fixed matrix for
+//!   the whole stream. Studying how eccentricity correlates with the systolic
+//!   ratio needs a fresh map per row, drawn from a stated distribution.
+//! - `RandomAnisotropyGenerator` maps each base sample `K` to `A K` with
+//!   `A = U · diag(σ) · Vᵀ`: singular values log-uniform in
+//!   `[sigma_min, sigma_max]`, `U, V` Haar-random in O(4) (QR of a Gaussian
+//!   matrix with the sign fix), or the identity when `rotate = false`.
+//!   With `unit_determinant` (default) `σ` is divided by its geometric mean,
+//!   so only the shape changes, not the volume.
+//!
+//! Replay
+//! - Row `i` draws `A` from `StdRng(derive_seed(seed ^ ANISOTROPY_SALT, i))`.
+//!   The salt keeps the map stream independent of base families that use
+//!   the same master seed. `AnisotropyReplay` stores that derived seed next
+//!   to the base replay value, so rows regenerate without the row index.
+//!   The drawn singular values are recorded too, ready for eccentricity plots.
+//!
+//! References
+//! - F. Mezzadri, "How to generate random matrices from the classical compact groups" (2007).
+//! - TH: docs/src/thesis/random-polytopes.md (“Random Anisotropy (adapter)”)
+
+use nalgebra::{Matrix4, Vector4};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use super::dirs::standard_normal4;
+use super::seeding::derive_seed;
+use super::{GeneratorError, PolytopeGenerator4, PolytopeSample4};
+use crate::geom4::{Hs4, Poly4};
+
+/// Decorrelates the map stream from base streams sharing the master seed.
+const ANISOTROPY_SALT: u64 = 0xA215_0720_C0DE_5EED;
+
+/// Distribution of the per-row linear map.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct AnisotropyDist {
+    /// Singular values are log-uniform in `[sigma_min, sigma_max]`.
+    pub sigma_min: f64,
+    pub sigma_max: f64,
+    /// Draw Haar-random `U, V`; otherwise the map is axis-aligned.
+    pub rotate: bool,
+    /// Normalize `σ` to geometric mean 1 (`|det A| = 1`).
+    pub unit_determinant: bool,
+}
+
+impl Default for AnisotropyDist {
+    fn default() -> Self {
+        Self {
+            sigma_min: 1.0,
+            sigma_max: 4.0,
+            rotate: true,
+            unit_determinant: true,
+        }
+    }
+}
+
+impl AnisotropyDist {
+    pub fn validate(&self) -> Result<(), GeneratorError> {
+        let ok =
+            self.sigma_min > 0.0 && self.sigma_min <= self.sigma_max && self.sigma_max.is_finite();
+        if !ok {
+            return Err(GeneratorError::InvalidParams(
+                "anisotropy: need 0 < sigma_min <= sigma_max < inf".into(),
+            ));
+        }
+        Ok(())
+    }
+
+    /// The map drawn from `seed`, with its singular values (descending).
+    pub fn sample(&self, seed: u64) -> (Matrix4<f64>, [f64; 4]) {
+        let mut rng = StdRng::seed_from_u64(seed);
+        let (lo, hi) = (self.sigma_min.ln(), self.sigma_max.ln());
+        let mut sigma: [f64; 4] = std::array::from_fn(|_| {
+            let u: f64 = rng.gen();
+            (lo + (hi - lo) * u).exp()
+        });
+        if self.unit_determinant {
+            let log_mean = sigma.iter().map(|s| s.ln()).sum::<f64>() / 4.0;
+            sigma.iter_mut().for_each(|s| *s /= log_mean.exp());
+        }
+        sigma.sort_by(|a, b| b.total_cmp(a));
+        let d = Matrix4::from_diagonal(&Vector4::from(sigma));
+        let a = if self.rotate {
+            let u = haar_orthogonal4(&mut rng);
+            let v = haar_orthogonal4(&mut rng);
+            u * d * v.transpose()
+        } else {
+            d
+        };
+        (a, sigma)
+    }
+}
+
+/// Seed of the map of row `index` in a stream with master seed `seed`.
+pub(super) fn map_seed(seed: u64, index: u64) -> u64 {
+    derive_seed(seed ^ ANISOTROPY_SALT, index)
+}
+
+/// Batch params of an anisotropic family: base params plus the map distribution.
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct AnisotropyParams<P> {
+    pub base: P,
+    pub dist: AnisotropyDist,
+}
+
+/// Replay value of an anisotropic row.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct AnisotropyReplay<R> {
+    pub base: R,
+    /// Seed of the map (already derived from the master seed and row index).
+    pub seed: u64,
+    /// Singular values of the map, descending (informational).
+    pub singular_values: [f64; 4],
+}
+
+/// Generator adapter mapping each base sample `K` to `A K` with a fresh random `A`.
+#[derive(Clone, Debug)]
+pub struct RandomAnisotropyGenerator<G> {
+    base: G,
+    dist: AnisotropyDist,
+    seed: u64,
+    next_index: u64,
+}
+
+impl<G: PolytopeGenerator4> RandomAnisotropyGenerator<G> {
+    pub fn new(base: G, dist: AnisotropyDist, seed: u64) -> Result<Self, GeneratorError> {
+        dist.validate()?;
+        Ok(Self {
+            base,
+            dist,
+            seed,
+            next_index: 0,
+        })
+    }
+
+    pub fn base(&self) -> &G {
+        &self.base
+    }
+
+    pub fn dist(&self) -> &AnisotropyDist {
+        &self.dist
+    }
+}
+
+impl<G: PolytopeGenerator4> PolytopeGenerator4 for RandomAnisotropyGenerator<G> {
+    type Replay = AnisotropyReplay<G::Replay>;
+
+    fn generate_next(&mut self) -> Result<Option<PolytopeSample4<Self::Replay>>, GeneratorError> {
+        let Some(sample) = self.base.generate_next()? else {
+            return Ok(None);
+        };
+        let seed = map_seed(self.seed, self.next_index);
+        self.next_index += 1;
+        let (a, singular_values) = self.dist.sample(seed);
+        Ok(Some(PolytopeSample4 {
+            polytope: linear_image4(&sample.polytope, &a)?,
+            replay: AnisotropyReplay {
+                base: sample.replay,
+                seed,
+                singular_values,
+            },
+        }))
+    }
+
+    fn regenerate(&self, replay: &Self::Replay) -> Result<Poly4, GeneratorError> {
+        let base = self.base.regenerate(&replay.base)?;
+        linear_image4(&base, &self.dist.sample(replay.seed).0)
+    }
+}
+
+/// `A K` for invertible `A`: normals map by `A⁻ᵀ`, vertices by `A`.
+pub fn linear_image4(poly: &Poly4, a: &Matrix4<f64>) -> Result<Poly4, GeneratorError> {
+    let inv_t = a
+        .try_inverse()
+        .ok_or_else(|| GeneratorError::Degenerate("anisotropy: singular map".into()))?
+        .transpose();
+    let mut image = Poly4::from_h(poly.h.iter().map(|h| Hs4::new(inv_t * h.n, h.c)).collect());
+    image.v = poly.v.iter().map(|v| a * v).collect();
+    Ok(image)
+}
+
+/// Haar-distributed orthogonal 4×4 matrix (Mezzadri's QR sign fix).
+fn haar_orthogonal4(rng: &mut StdRng) -> Matrix4<f64> {
+    let g = Matrix4::from_columns(&std::array::from_fn::<_, 4, _>(|_| standard_normal4(rng)));
+    let qr = g.qr();
+    let (mut q, r) = (qr.q(), qr.r());
+    for k in 0..4 {
+        if r[(k, k)] < 0.0 {
+            q.column_mut(k).neg_mut();
+        }
+    }
+    q
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn maps_have_the_requested_spectrum() {
+        let dist = AnisotropyDist {
+            sigma_min: 0.5,
+            sigma_max: 8.0,
+            ..AnisotropyDist::default()
+        };
+        for seed in 0..20 {
+            let (a, sigma) = dist.sample(seed);
+            assert!((a.determinant().abs() - 1.0).abs() < 1e-9);
+            let mut svd: Vec<f64> = a
+                .svd(false, false)
+                .singular_values
+                .iter()
+                .copied()
+                .collect();
+            svd.sort_by(|x, y| y.total_cmp(x));
+            for (s, t) in svd.iter().zip(sigma) {
+                assert!((s - t).abs() < 1e-9 * t);
+            }
+            assert!(sigma[0] / sigma[3] <= 16.0 + 1e-9);
+        }
+        assert_eq!(dist.sample(3), dist.sample(3));
+        assert!(AnisotropyDist {
+            sigma_min: 2.0,
+            sigma_max: 1.0,
+            ..AnisotropyDist::default()
+        }
+        .validate()
+        .is_err());
+    }
+
+    #[test]
+    fn linear_image_keeps_incidences() {
+        let mut hs = Vec::new();
+        for k in 0..4 {
+            let mut e = Vector4::zeros();
+            e[k] = 1.0;
+            hs.push(Hs4::new(e, 1.0));
+            hs.push(Hs4::new(-e, 1.0));
+        }
+        let mut cube = Poly4::from_h(hs);
+        cube.ensure_vertices_from_h();
+        let (a, _) = AnisotropyDist::default().sample(7);
+        let image = linear_image4(&cube, &a).unwrap();
+        for v in &image.v {
+            let tight = image.h.iter().filter(|h| (h.n.dot(v) - h.c).abs() < 1e-9);
+            assert_eq!(tight.count(), 4);
+        }
+    }
+}
@@ -0,0 +1,387 @@
+//! Deterministic batch sampling: row `i` depends only on `(params, master_seed, i)`.
+//!
+//! Why this file exists
+//! - Streaming generators walk their index sequentially; large dataset runs
+//!   want to split the index space across threads and machines instead.
+//!   `generate_batch(&params, master_seed, range)` builds every row of `range`
+//!   independently (on rayon with the default `parallel` feature, sequentially
+//!   without it), so any sharding of the index space reproduces the same rows
+//!   bit for bit, in index order.
+//! - Every row coincides with row `index` of the stream seeded with
+//!   `master_seed`: token-addressed families reuse their replay path
+//!   (`ReplayToken { seed: master_seed, index }`), `SymmetricHalfspaceParams`
+//!   uses the stream's per-row seed `master_seed.wrapping_add(index)`,
+//!   `ExactFacetsParams` runs the same row-local resample loop, and
+//!   `AnisotropyParams` maps the base row with the stream's map seed.
+//! - `RegularProductEnumerator` (the enumeration, not its params, since it
+//!   holds the built polygons) ignores `master_seed`; indices past its end
+//!   are `InvalidParams`.
+//! - Out of scope: `MixtureGenerator`. Its row `i` is the next row of the
+//!   component picked at `i`, i.e. it depends on how often each component
+//!   was picked before, so rows are not addressable by index alone.
+//!
+//! References
+//! - TH: docs/src/thesis/random-polytopes.md (“Reproducibility”)
+
+use std::ops::Range;
+
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+
+use super::anisotropy::map_seed;
+use super::exact_facets::sample_row;
+use super::{
+    linear_image4, AcceptanceStats, AnisotropyParams, AnisotropyReplay, ExactFacetsParams,
+    ExactFacetsReplay, GaussianVerticesGenerator, GaussianVerticesParams, GeneratorError,
+    GroupSymmetricGenerator, GroupSymmetricParams, MahlerProductGenerator, MahlerProductParams,
+    PolytopeSample4, RandomFacesGenerator, RandomFacesParams, RandomVerticesGenerator,
+    RandomVerticesParams, RegularProductEnumerator, RegularProductReplay, SimpleParams,
+    SimplePolytopeGenerator, SimplicialParams, SimplicialPolytopeGenerator,
+    SymmetricHalfspaceGenerator, SymmetricHalfspaceParams, ZonotopeGenerator, ZonotopeParams,
+};
+use crate::geom2::rand::ReplayToken;
+
+/// Params of a family whose rows can be built from `(master_seed, index)` alone.
+pub trait BatchFamily: Sync {
+    type Replay: Send;
+
+    /// Build row `index`; must not depend on any other row.
+    fn sample_index(
+        &self,
+        master_seed: u64,
+        index: u64,
+    ) -> Result<PolytopeSample4<Self::Replay>, GeneratorError>;
+}
+
+/// Rows `range` of the family, in parallel under `parallel`, returned in index order.
+pub fn generate_batch<P: BatchFamily>(
+    params: &P,
+    master_seed: u64,
+    range: Range<u64>,
+) -> Vec<Result<PolytopeSample4<P::Replay>, GeneratorError>> {
+    #[cfg(feature = "parallel")]
+    let indices = range.into_par_iter();
+    #[cfg(not(feature = "parallel"))]
+    let indices = range;
+    indices
+        .map(|index| params.sample_index(master_seed, index))
+        .collect()
+}
+
+fn token_row(
+    master_seed: u64,
+    index: u64,
+    build: impl FnOnce(ReplayToken) -> Result<crate::geom4::Poly4, GeneratorError>,
+) -> Result<PolytopeSample4<ReplayToken>, GeneratorError> {
+    let replay = ReplayToken {
+        seed: master_seed,
+        index,
+    };
+    Ok(PolytopeSample4 {
+        polytope: build(replay)?,
+        replay,
+    })
+}
+
+impl BatchFamily for GaussianVerticesParams {
+    type Replay = ReplayToken;
+
+    fn sample_index(
+        &self,
+        master_seed: u64,
+        index: u64,
+    ) -> Result<PolytopeSample4<ReplayToken>, GeneratorError> {
+        token_row(master_seed, index, |tok| {
+            GaussianVerticesGenerator::sample_with_token(self, tok)
+        })
+    }
+}
+
+impl BatchFamily for ZonotopeParams {
+    type Replay = ReplayToken;
+
+    fn sample_index(
+        &self,
+        master_seed: u64,
+        index: u64,
+    ) -> Result<PolytopeSample4<ReplayToken>, GeneratorError> {
+        token_row(master_seed, index, |tok| {
+            ZonotopeGenerator::sample_with_token(self, tok)
+        })
+    }
+}
+
+impl BatchFamily for GroupSymmetricParams {
+    type Replay = ReplayToken;
+
+    fn sample_index(
+        &self,
+        master_seed: u64,
+        index: u64,
+    ) -> Result<PolytopeSample4<ReplayToken>, GeneratorError> {
+        token_row(master_seed, index, |tok| {
+            GroupSymmetricGenerator::sample_with_token(self, tok)
+        })
+    }
+}
+
+impl BatchFamily for SimpleParams {
+    type Replay = ReplayToken;
+
+    fn sample_index(
+        &self,
+        master_seed: u64,
+        index: u64,
+    ) -> Result<PolytopeSample4<ReplayToken>, GeneratorError> {
+        token_row(master_seed, index, |tok| {
+            SimplePolytopeGenerator::sample_with_token(self, tok)
+        })
+    }
+}
+
+impl BatchFamily for SimplicialParams {
+    type Replay = ReplayToken;
+
+    fn sample_index(
+        &self,
+        master_seed: u64,
+        index: u64,
+    ) -> Result<PolytopeSample4<ReplayToken>, GeneratorError> {
+        token_row(master_seed, index, |tok| {
+            SimplicialPolytopeGenerator::sample_with_token(self, tok)
+        })
+    }
+}
+
+impl BatchFamily for MahlerProductParams {
+    type Replay = ReplayToken;
+
+    fn sample_index(
+        &self,
+        master_seed: u64,
+        index: u64,
+    ) -> Result<PolytopeSample4<ReplayToken>, GeneratorError> {
+        token_row(master_seed, index, |tok| {
+            MahlerProductGenerator::sample_with_token(self, tok)
+        })
+    }
+}
+
+impl BatchFamily for RandomVerticesParams {
+    type Replay = ReplayToken;
+
+    fn sample_index(
+        &self,
+        master_seed: u64,
+        index: u64,
+    ) -> Result<PolytopeSample4<ReplayToken>, GeneratorError> {
+        token_row(master_seed, index, |tok| {
+            RandomVerticesGenerator::sample_with_token(self, tok)
+        })
+    }
+}
+
+impl BatchFamily for RandomFacesParams {
+    type Replay = ReplayToken;
+
+    fn sample_index(
+        &self,
+        master_seed: u64,
+        index: u64,
+    ) -> Result<PolytopeSample4<ReplayToken>, GeneratorError> {
+        token_row(master_seed, index, |tok| {
+            RandomFacesGenerator::sample_with_token(self, tok)
+        })
+    }
+}
+
+impl BatchFamily for SymmetricHalfspaceParams {
+    /// The per-row seed passed to `generate_single`, as in the stream.
+    type Replay = u64;
+
+    fn sample_index(
+        &self,
+        master_seed: u64,
+        index: u64,
+    ) -> Result<PolytopeSample4<u64>, GeneratorError> {
+        let seed = master_seed.wrapping_add(index);
+        Ok(PolytopeSample4 {
+            polytope: SymmetricHalfspaceGenerator::generate_single(self, seed)?,
+            replay: seed,
+        })
+    }
+}
+
+impl BatchFamily for ExactFacetsParams {
+    type Replay = ExactFacetsReplay;
+
+    fn sample_index(
+        &self,
+        master_seed: u64,
+        index: u64,
+    ) -> Result<PolytopeSample4<ExactFacetsReplay>, GeneratorError> {
+        self.validate()?;
+        sample_row(self, master_seed, index, &mut AcceptanceStats::default())
+    }
+}
+
+impl BatchFamily for RegularProductEnumerator {
+    type Replay = RegularProductReplay;
+
+    fn sample_index(
+        &self,
+        _master_seed: u64,
+        index: u64,
+    ) -> Result<PolytopeSample4<RegularProductReplay>, GeneratorError> {
+        let replay = usize::try_from(index)
+            .ok()
+            .and_then(|i| self.pair(i))
+            .ok_or_else(|| {
+                GeneratorError::InvalidParams(format!(
+                    "regular_products: row {index} out of range ({} rows)",
+                    self.len()
+                ))
+            })?;
+        Ok(PolytopeSample4 {
+            polytope: self.build_poly(&replay)?,
+            replay,
+        })
+    }
+}
+
+impl<P: BatchFamily> BatchFamily for AnisotropyParams<P> {
+    type Replay = AnisotropyReplay<P::Replay>;
+
+    fn sample_index(
+        &self,
+        master_seed: u64,
+        index: u64,
+    ) -> Result<PolytopeSample4<Self::Replay>, GeneratorError> {
+        self.dist.validate()?;
+        let sample = self.base.sample_index(master_seed, index)?;
+        let seed = map_seed(master_seed, index);
+        let (a, singular_values) = self.dist.sample(seed);
+        Ok(PolytopeSample4 {
+            polytope: linear_image4(&sample.polytope, &a)?,
+            replay: AnisotropyReplay {
+                base: sample.replay,
+                seed,
+                singular_values,
+            },
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rand4::seeding::derive_seed;
+    use crate::rand4::{
+        AnisotropyDist, DirectionSampler, ExactFacetsGenerator, PolytopeGenerator4,
+        RandomAnisotropyGenerator, RegularPolygonSpec, RegularProductEnumParams,
+    };
+
+    fn assert_matches_stream<P: BatchFamily, G: PolytopeGenerator4<Replay = P::Replay>>(
+        params: &P,
+        mut gen: G,
+        rows: u64,
+    ) where
+        P::Replay: PartialEq + std::fmt::Debug,
+    {
+        for (index, row) in generate_batch(params, 5, 0..rows).into_iter().enumerate() {
+            let row = row.unwrap();
+            let streamed = gen.generate_next().unwrap().unwrap();
+            assert_eq!(row.replay, streamed.replay, "row {index}");
+            assert_eq!(row.polytope.h, streamed.polytope.h, "row {index}");
+        }
+    }
+
+    #[test]
+    fn shards_reproduce_the_stream() {
+        let params = ZonotopeParams {
+            segments_min: 4,
+            segments_max: 5,
+            ..ZonotopeParams::default()
+        };
+        let whole = generate_batch(&params, 42, 0..6);
+        let mut shards = generate_batch(&params, 42, 0..2);
+        shards.extend(generate_batch(&params, 42, 2..6));
+        let mut gen = ZonotopeGenerator::new(params, 42).unwrap();
+        for (a, b) in whole.iter().zip(&shards) {
+            let (a, b) = (a.as_ref().unwrap(), b.as_ref().unwrap());
+            let streamed = gen.generate_next().unwrap().unwrap();
+            assert_eq!(a.replay, b.replay);
+            assert_eq!(a.polytope.h, b.polytope.h);
+            assert_eq!(a.polytope.h, streamed.polytope.h);
+        }
+    }
+
+    #[test]
+    fn derived_seeds_differ_per_index() {
+        let seeds: Vec<u64> = (0..64).map(|i| derive_seed(7, i)).collect();
+        let mut unique = seeds.clone();
+        unique.sort_unstable();
+        unique.dedup();
+        assert_eq!(unique.len(), seeds.len());
+        assert_ne!(derive_seed(7, 0), derive_seed(8, 0));
+    }
+
+    #[test]
+    fn batch_rows_match_the_streams() {
+        let symmetric = SymmetricHalfspaceParams {
+            directions: 5,
+            radius_min: 0.8,
+            radius_max: 1.2,
+            anisotropy: None,
+            sampler: DirectionSampler::default(),
+        };
+        let gen = SymmetricHalfspaceGenerator::new(symmetric.clone(), 5).unwrap();
+        assert_matches_stream(&symmetric, gen, 3);
+
+        let exact = ExactFacetsParams {
+            faces: RandomFacesParams {
+                facets_min: 8,
+                facets_max: 10,
+                radius_min: 0.8,
+                radius_max: 1.2,
+                anisotropy: None,
+                sampler: DirectionSampler::default(),
+                max_attempts: 20,
+            },
+            facets: 8,
+            max_resamples: 200,
+        };
+        let gen = ExactFacetsGenerator::new(exact.clone(), 5).unwrap();
+        assert_matches_stream(&exact, gen, 2);
+
+        let zonotopes = ZonotopeParams {
+            segments_min: 4,
+            segments_max: 5,
+            ..ZonotopeParams::default()
+        };
+        let anisotropic = AnisotropyParams {
+            base: zonotopes.clone(),
+            dist: AnisotropyDist::default(),
+        };
+        let base = ZonotopeGenerator::new(zonotopes, 5).unwrap();
+        let gen = RandomAnisotropyGenerator::new(base, AnisotropyDist::default(), 5).unwrap();
+        assert_matches_stream(&anisotropic, gen, 3);
+    }
+
+    #[test]
+    fn regular_products_are_indexed_pairs() {
+        let square = RegularPolygonSpec::new(4, 0.0, 1.0).unwrap();
+        let hexagon = RegularPolygonSpec::new(6, 0.0, 1.0).unwrap();
+        let enumeration = RegularProductEnumerator::new(RegularProductEnumParams {
+            factors_a: vec![square, hexagon],
+            factors_b: vec![square, hexagon],
+            max_pairs: Some(3),
+        })
+        .unwrap();
+        assert_matches_stream(&enumeration, enumeration.clone(), 3);
+        assert!(matches!(
+            enumeration.sample_index(0, 3),
+            Err(GeneratorError::InvalidParams(_))
+        ));
+    }
+}
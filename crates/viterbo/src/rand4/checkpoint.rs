@@ -0,0 +1,104 @@
+//! Checkpoint/resume of generator streams.
+//!
+//! Why this file exists
+//! - Long dataset runs must survive crashes. Our generators are index
+//!   addressed (row `k` depends only on `(params, seed, k)`), so the whole
+//!   stream position is the next index plus bookkeeping; saving that is
+//!   enough to resume at the exact row, without replaying earlier rows.
+//! - States serialize with serde (default `serde` feature) so a runner can
+//!   write them next to the partial output. Params are *not* part of the state: resume with the same
+//!   config. `restore_state` rejects a state recorded under a different seed.
+//!
+//! References
+//! - TH: docs/src/thesis/random-polytopes.md (“Reproducibility”)
+
+#[cfg(feature = "serde")]
+use serde::de::DeserializeOwned;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use super::{AcceptanceStats, GeneratorError, RejectionStats};
+
+/// Streams whose position can be saved and restored.
+pub trait Checkpoint {
+    #[cfg(feature = "serde")]
+    type State: Clone + Serialize + DeserializeOwned;
+    #[cfg(not(feature = "serde"))]
+    type State: Clone;
+
+    fn save_state(&self) -> Self::State;
+    fn restore_state(&mut self, state: Self::State) -> Result<(), GeneratorError>;
+}
+
+/// Position of a seeded, index-addressed stream.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct StreamState {
+    pub seed: u64,
+    pub next_index: u64,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub stats: AcceptanceStats,
+}
+
+impl StreamState {
+    /// Error unless the state was recorded for a stream with `seed`.
+    pub fn check_seed(&self, seed: u64) -> Result<(), GeneratorError> {
+        if self.seed == seed {
+            Ok(())
+        } else {
+            Err(GeneratorError::InvalidParams(format!(
+                "checkpoint: state was recorded for seed {}, generator uses {seed}",
+                self.seed
+            )))
+        }
+    }
+}
+
+/// State of a `FilteredGenerator`: its base plus the rejection counters.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct FilteredState<S> {
+    pub base: S,
+    pub rejections: RejectionStats,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rand4::{
+        FacetCountRange, FilteredGenerator, PolytopeGenerator4, ZonotopeGenerator, ZonotopeParams,
+    };
+
+    #[test]
+    fn resume_continues_at_the_saved_row() {
+        let params = ZonotopeParams::default();
+        let mut gen = ZonotopeGenerator::new(params.clone(), 5).unwrap();
+        gen.generate_next().unwrap();
+        gen.generate_next().unwrap();
+        let state = gen.save_state();
+        #[cfg(feature = "serde")]
+        let state: StreamState =
+            serde_json::from_str(&serde_json::to_string(&state).unwrap()).unwrap();
+        let expected = gen.generate_next().unwrap().unwrap();
+
+        let mut resumed = ZonotopeGenerator::new(params, 5).unwrap();
+        resumed.restore_state(state).unwrap();
+        let got = resumed.generate_next().unwrap().unwrap();
+        assert_eq!(got.replay, expected.replay);
+        assert_eq!(got.polytope.h, expected.polytope.h);
+        assert_eq!(resumed.save_state().stats.accepted, 3);
+    }
+
+    #[test]
+    fn restore_rejects_foreign_state() {
+        let base = ZonotopeGenerator::new(ZonotopeParams::default(), 1).unwrap();
+        let mut filtered =
+            FilteredGenerator::new(base).with_filter(FacetCountRange { min: 8, max: 99 });
+        let mut state = filtered.save_state();
+        state.base.seed = 2;
+        assert!(filtered.restore_state(state.clone()).is_err());
+        state.base.seed = 1;
+        state.rejections.rejected.clear();
+        assert!(filtered.restore_state(state).is_err());
+    }
+}
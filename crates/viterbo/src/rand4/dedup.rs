@@ -0,0 +1,308 @@
+//! Duplicate detection across streams by canonical polytope hashes.
+//!
+//! Why this file exists
+//! - Enumerative and symmetric families emit many congruent samples (same
+//!   body, different placement), which over-weights them in statistics.
+//!   `DedupSink` keeps a set of canonical hashes and drops repeats; one sink
+//!   can be shared by several streams.
+//!
+//! Canonical form
+//! - Normalize first: translate the vertex centroid to the origin
+//!   (`Normalization::Congruence`), optionally also whiten the vertex
+//!   covariance to the identity (`Normalization::Affine`, which identifies all
+//!   affine images — note that capacity ratios are only invariant under
+//!   symplectic maps, so this mode is for combinatorial studies).
+//! - Then hash invariants that survive the remaining orthogonal freedom:
+//!   vertex/facet counts, the multisets of facet sizes and vertex degrees
+//!   (combinatorial type), and the quantized multisets of vertex norms and
+//!   pairwise vertex distances (geometry). This is not a complete invariant,
+//!   but non-congruent bodies colliding needs a coincidence at every level.
+//! - Quantization uses a relative step; values straddling a rounding
+//!   boundary can still hash apart, so dedup is conservative (it may keep a
+//!   near-duplicate, it does not merge clearly different bodies).
+//! - Hashing is FNV-1a over the integer invariants, so hashes are stable
+//!   across processes and Rust versions.
+//!
+//! References
+//! - TH: docs/src/thesis/random-polytopes.md (“Deduplication”)
+
+use std::collections::HashSet;
+
+use nalgebra::{Matrix4, Vector4};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use super::{GeneratorError, PolytopeSample4};
+use crate::geom4::Poly4;
+
+/// Which transformations map a body onto "the same" body.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Normalization {
+    /// Rigid motions (translations and orthogonal maps).
+    #[default]
+    Congruence,
+    /// All invertible affine maps.
+    Affine,
+}
+
+/// Canonical-form settings.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct CanonicalCfg {
+    pub normalization: Normalization,
+    /// Quantization step relative to the body's radius after normalization.
+    pub rel_quantum: f64,
+}
+
+impl Default for CanonicalCfg {
+    fn default() -> Self {
+        Self {
+            normalization: Normalization::default(),
+            rel_quantum: 1e-6,
+        }
+    }
+}
+
+/// Canonical hash of `poly`; `None` for empty or lower-dimensional input.
+pub fn canonical_hash(poly: &mut Poly4, cfg: &CanonicalCfg) -> Option<u64> {
+    poly.ensure_vertices_from_h();
+    poly.ensure_halfspaces_from_v();
+    let nv = poly.v.len();
+    if nv < 5 || poly.h.is_empty() {
+        return None;
+    }
+    let mean = poly.v.iter().sum::<Vector4<f64>>() / nv as f64;
+    let mut pts: Vec<Vector4<f64>> = poly.v.iter().map(|v| v - mean).collect();
+    if cfg.normalization == Normalization::Affine {
+        let w = whitening(&pts)?;
+        pts.iter_mut().for_each(|p| *p = w * *p);
+    }
+    let radius = pts.iter().map(|p| p.norm()).fold(0.0, f64::max);
+    if radius <= 0.0 {
+        return None;
+    }
+    let quantum = cfg.rel_quantum * radius;
+    let q = |x: f64| (x / quantum).round() as i64;
+
+    // Combinatorial type via vertex-facet incidences of the original body.
+    let scale = poly.v.iter().map(|v| v.norm()).fold(1.0, f64::max);
+    let eps = 1e-9 * scale;
+    let mut facet_sizes = Vec::with_capacity(poly.h.len());
+    let mut degrees = vec![0i64; nv];
+    for h in &poly.h {
+        let norm = h.n.norm();
+        let mut size = 0;
+        for (i, v) in poly.v.iter().enumerate() {
+            if (h.n.dot(v) - h.c).abs() <= eps * norm {
+                size += 1;
+                degrees[i] += 1;
+            }
+        }
+        facet_sizes.push(size);
+    }
+    facet_sizes.sort_unstable();
+    degrees.sort_unstable();
+
+    let mut norms: Vec<i64> = pts.iter().map(|p| q(p.norm())).collect();
+    norms.sort_unstable();
+    let mut dists = Vec::with_capacity(nv * (nv - 1) / 2);
+    for i in 0..nv {
+        for j in i + 1..nv {
+            dists.push(q((pts[i] - pts[j]).norm()));
+        }
+    }
+    dists.sort_unstable();
+
+    let mut h = Fnv64::new();
+    h.write(nv as i64);
+    h.write(poly.h.len() as i64);
+    for part in [&facet_sizes, &degrees, &norms, &dists] {
+        h.write(part.len() as i64);
+        part.iter().for_each(|&x| h.write(x));
+    }
+    Some(h.finish())
+}
+
+/// `Σ^{-1/2}` for the vertex covariance `Σ`; `None` if it is singular.
+fn whitening(pts: &[Vector4<f64>]) -> Option<Matrix4<f64>> {
+    let cov = pts.iter().map(|p| p * p.transpose()).sum::<Matrix4<f64>>() / pts.len() as f64;
+    let eig = cov.symmetric_eigen();
+    let max = eig.eigenvalues.max();
+    if eig.eigenvalues.iter().any(|&l| l <= 1e-12 * max) {
+        return None;
+    }
+    let inv_sqrt = Matrix4::from_diagonal(&eig.eigenvalues.map(|l| 1.0 / l.sqrt()));
+    Some(eig.eigenvectors * inv_sqrt * eig.eigenvectors.transpose())
+}
+
+struct Fnv64(u64);
+
+impl Fnv64 {
+    fn new() -> Self {
+        Fnv64(0xcbf2_9ce4_8422_2325)
+    }
+
+    fn write(&mut self, x: i64) {
+        for b in x.to_le_bytes() {
+            self.0 ^= u64::from(b);
+            self.0 = self.0.wrapping_mul(0x0000_0100_0000_01b3);
+        }
+    }
+
+    fn finish(&self) -> u64 {
+        self.0
+    }
+}
+
+/// Set of canonical hashes seen so far, shared across streams.
+#[derive(Clone, Debug, Default)]
+pub struct DedupSink {
+    cfg: CanonicalCfg,
+    seen: HashSet<u64>,
+    duplicates: u64,
+    unhashable: u64,
+}
+
+impl DedupSink {
+    pub fn new(cfg: CanonicalCfg) -> Self {
+        Self {
+            cfg,
+            ..Self::default()
+        }
+    }
+
+    /// Record `poly`; `true` if it is new (or cannot be hashed), `false` for a duplicate.
+    pub fn insert(&mut self, poly: &mut Poly4) -> bool {
+        match canonical_hash(poly, &self.cfg) {
+            Some(hash) => {
+                let new = self.seen.insert(hash);
+                if !new {
+                    self.duplicates += 1;
+                }
+                new
+            }
+            None => {
+                self.unhashable += 1;
+                true
+            }
+        }
+    }
+
+    /// Drop duplicates from a sample stream; errors pass through untouched.
+    pub fn dedup<'a, R: 'a>(
+        &'a mut self,
+        samples: impl Iterator<Item = Result<PolytopeSample4<R>, GeneratorError>> + 'a,
+    ) -> impl Iterator<Item = Result<PolytopeSample4<R>, GeneratorError>> + 'a {
+        samples.filter_map(move |item| match item {
+            Ok(mut sample) => self.insert(&mut sample.polytope).then_some(Ok(sample)),
+            Err(err) => Some(Err(err)),
+        })
+    }
+
+    pub fn unique(&self) -> usize {
+        self.seen.len()
+    }
+
+    pub fn duplicates(&self) -> u64 {
+        self.duplicates
+    }
+
+    /// Samples passed through because no canonical form exists (degenerate input).
+    pub fn unhashable(&self) -> u64 {
+        self.unhashable
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::geom4::Hs4;
+
+    fn box4(half: [f64; 4]) -> Poly4 {
+        let mut hs = Vec::new();
+        for k in 0..4 {
+            let mut e = Vector4::zeros();
+            e[k] = 1.0;
+            hs.push(Hs4::new(e, half[k]));
+            hs.push(Hs4::new(-e, half[k]));
+        }
+        let mut p = Poly4::from_h(hs);
+        p.ensure_vertices_from_h();
+        p
+    }
+
+    /// Rotate in the (x₀, x₂) plane and translate.
+    fn moved(p: &Poly4) -> Poly4 {
+        let (s, c) = 0.7f64.sin_cos();
+        let mut r = Matrix4::identity();
+        r[(0, 0)] = c;
+        r[(0, 2)] = -s;
+        r[(2, 0)] = s;
+        r[(2, 2)] = c;
+        let t = Vector4::new(0.3, -1.0, 2.0, 0.5);
+        let h =
+            p.h.iter()
+                .map(|h| {
+                    let n = r * h.n;
+                    Hs4::new(n, h.c + n.dot(&t))
+                })
+                .collect();
+        let mut out = Poly4::from_h(h);
+        out.v = p.v.iter().map(|v| r * v + t).collect();
+        out
+    }
+
+    #[test]
+    fn congruent_copies_are_dropped() {
+        let mut sink = DedupSink::default();
+        let mut a = box4([1.0, 1.0, 1.0, 2.0]);
+        let mut b = moved(&a);
+        let mut c = box4([1.0, 1.0, 1.5, 2.0]);
+        assert!(sink.insert(&mut a));
+        assert!(!sink.insert(&mut b));
+        assert!(sink.insert(&mut c));
+        assert_eq!((sink.unique(), sink.duplicates()), (2, 1));
+    }
+
+    #[test]
+    fn affine_mode_identifies_boxes() {
+        let cfg = CanonicalCfg {
+            normalization: Normalization::Affine,
+            ..CanonicalCfg::default()
+        };
+        let mut cube = box4([1.0; 4]);
+        let mut slab = box4([1.0, 3.0, 1.0, 0.5]);
+        assert_eq!(
+            canonical_hash(&mut cube, &cfg),
+            canonical_hash(&mut slab, &cfg)
+        );
+        let congruence = CanonicalCfg::default();
+        assert_ne!(
+            canonical_hash(&mut cube, &congruence),
+            canonical_hash(&mut slab, &congruence)
+        );
+    }
+
+    #[test]
+    fn dedup_filters_streams() {
+        let mut sink = DedupSink::default();
+        let rows = |k: u32| -> Vec<Result<PolytopeSample4<u32>, GeneratorError>> {
+            (0..3)
+                .map(|i| {
+                    Ok(PolytopeSample4 {
+                        polytope: box4([1.0, 1.0, 1.0, 1.0 + f64::from(i % 2)]),
+                        replay: k * 10 + i,
+                    })
+                })
+                .collect()
+        };
+        let first: Vec<u32> = sink
+            .dedup(rows(1).into_iter())
+            .map(|s| s.unwrap().replay)
+            .collect();
+        let second = sink.dedup(rows(2).into_iter()).count();
+        assert_eq!(first, vec![10, 11]);
+        assert_eq!(second, 0);
+    }
+}
@@ -0,0 +1,223 @@
+//! Shared direction sampling on the 3-sphere S³ ⊂ R⁴.
+//!
+//! Why this file exists
+//! - Normalizing a uniform sample from the cube `[-1,1]⁴` is *not* uniform on
+//!   S³: directions towards the cube's corners are over-represented. All 4D
+//!   generators draw facet normals / vertex directions through this module so
+//!   the distribution is fixed in one place.
+//! - `DirectionSampler::Gaussian` normalizes four independent standard normals
+//!   (rotation invariant, hence exactly uniform on S³).
+//! - `DirectionSampler::LegacyCube` keeps the historical cube-normalization so
+//!   replay tokens recorded before the switch still regenerate bit-identically.
+//!   Do not use it for new datasets.
+//! - `DirectionMode::Sobol` replaces the RNG by a scrambled Sobol sequence
+//!   addressed by the replay token (quasi-Monte-Carlo coverage studies).
+//!
+//! References
+//! - Marsaglia (1972), "Choosing a point from the surface of a sphere".
+//! - TH: docs/src/thesis/random-polytopes.md (“Centrally Symmetric Random Halfspaces”)
+
+use nalgebra::Vector4;
+use rand::Rng;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use super::sobol::{unit_vector_from_cube, SobolSequence};
+use crate::geom2::rand::ReplayToken;
+
+/// Below this norm a raw sample is redrawn to avoid amplifying rounding noise.
+const MIN_RAW_NORM: f64 = 1e-9;
+
+/// Which distribution a generator uses for unit directions.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum DirectionSampler {
+    /// Uniform on S³ via normalized Gaussians.
+    #[default]
+    Gaussian,
+    /// Normalized uniform cube sample (biased towards diagonals); replay compatibility only.
+    LegacyCube,
+}
+
+/// Where a generator's unit directions come from.
+///
+/// Serialized as a flat name: `"gaussian"`, `"legacy_cube"` or `"sobol"`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(into = "String", try_from = "String"))]
+pub enum DirectionMode {
+    /// Independent draws from the row RNG.
+    Iid(DirectionSampler),
+    /// Scrambled Sobol points `index·count .. index·count + count`, seeded by `token.seed`.
+    Sobol,
+}
+
+impl Default for DirectionMode {
+    fn default() -> Self {
+        DirectionMode::Iid(DirectionSampler::default())
+    }
+}
+
+impl DirectionMode {
+    pub fn name(self) -> &'static str {
+        match self {
+            DirectionMode::Iid(DirectionSampler::Gaussian) => "gaussian",
+            DirectionMode::Iid(DirectionSampler::LegacyCube) => "legacy_cube",
+            DirectionMode::Sobol => "sobol",
+        }
+    }
+}
+
+impl From<DirectionMode> for String {
+    fn from(mode: DirectionMode) -> Self {
+        mode.name().to_owned()
+    }
+}
+
+impl TryFrom<String> for DirectionMode {
+    type Error = String;
+
+    fn try_from(name: String) -> Result<Self, String> {
+        match name.as_str() {
+            "gaussian" => Ok(DirectionMode::Iid(DirectionSampler::Gaussian)),
+            "legacy_cube" => Ok(DirectionMode::Iid(DirectionSampler::LegacyCube)),
+            "sobol" => Ok(DirectionMode::Sobol),
+            other => Err(format!(
+                "direction mode must be 'gaussian', 'legacy_cube' or 'sobol', got {other}"
+            )),
+        }
+    }
+}
+
+/// Draw the `count` unit directions of the row addressed by `token`.
+///
+/// In `Sobol` mode the RNG is left untouched, so any remaining randomness of
+/// the row (radii, counts) stays aligned with the IID mode.
+pub fn directions_for_row<R: Rng + ?Sized>(
+    mode: DirectionMode,
+    rng: &mut R,
+    token: ReplayToken,
+    count: usize,
+) -> Vec<Vector4<f64>> {
+    match mode {
+        DirectionMode::Iid(sampler) => (0..count)
+            .map(|_| sample_unit_vector(rng, sampler))
+            .collect(),
+        DirectionMode::Sobol => {
+            let seq = SobolSequence::new(3, token.seed).expect("3 <= SOBOL_MAX_DIMS");
+            let base = token.index.wrapping_mul(count as u64);
+            (0..count as u64)
+                .map(|j| {
+                    let i = base.wrapping_add(j);
+                    unit_vector_from_cube([seq.coord(i, 0), seq.coord(i, 1), seq.coord(i, 2)])
+                })
+                .collect()
+        }
+    }
+}
+
+/// Draw a unit vector in R⁴ with the chosen sampler.
+pub fn sample_unit_vector<R: Rng + ?Sized>(rng: &mut R, sampler: DirectionSampler) -> Vector4<f64> {
+    loop {
+        let raw = match sampler {
+            DirectionSampler::Gaussian => standard_normal4(rng),
+            DirectionSampler::LegacyCube => Vector4::new(
+                rng.gen_range(-1.0..1.0),
+                rng.gen_range(-1.0..1.0),
+                rng.gen_range(-1.0..1.0),
+                rng.gen_range(-1.0..1.0),
+            ),
+        };
+        let norm = raw.norm();
+        if norm > MIN_RAW_NORM {
+            return raw / norm;
+        }
+    }
+}
+
+/// Four independent standard normals via Box–Muller.
+pub fn standard_normal4<R: Rng + ?Sized>(rng: &mut R) -> Vector4<f64> {
+    let (a, b) = standard_normal_pair(rng);
+    let (c, d) = standard_normal_pair(rng);
+    Vector4::new(a, b, c, d)
+}
+
+fn standard_normal_pair<R: Rng + ?Sized>(rng: &mut R) -> (f64, f64) {
+    // `gen::<f64>()` is in [0,1); shift to (0,1] so `ln` stays finite.
+    let u1: f64 = 1.0 - rng.gen::<f64>();
+    let u2: f64 = rng.gen::<f64>();
+    let r = (-2.0 * u1.ln()).sqrt();
+    let theta = std::f64::consts::TAU * u2;
+    (r * theta.cos(), r * theta.sin())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    fn mean_fourth_moment(sampler: DirectionSampler) -> f64 {
+        let mut rng = StdRng::seed_from_u64(17);
+        let n = 20_000;
+        (0..n)
+            .map(|_| sample_unit_vector(&mut rng, sampler)[0].powi(4))
+            .sum::<f64>()
+            / n as f64
+    }
+
+    #[test]
+    fn unit_vectors_have_unit_norm() {
+        let mut rng = StdRng::seed_from_u64(3);
+        for sampler in [DirectionSampler::Gaussian, DirectionSampler::LegacyCube] {
+            for _ in 0..100 {
+                let v = sample_unit_vector(&mut rng, sampler);
+                assert!((v.norm() - 1.0).abs() < 1e-12);
+            }
+        }
+    }
+
+    #[test]
+    fn gaussian_sampler_matches_sphere_moment() {
+        // Uniform on S³: E[x₀⁴] = 3 / (n (n + 2)) = 1/8 for n = 4.
+        let m = mean_fourth_moment(DirectionSampler::Gaussian);
+        assert!((m - 0.125).abs() < 6e-3, "E[x^4] = {m}");
+    }
+
+    #[test]
+    fn sobol_rows_are_token_addressed() {
+        let mut rng = StdRng::seed_from_u64(0);
+        let tok = ReplayToken { seed: 8, index: 3 };
+        let a = directions_for_row(DirectionMode::Sobol, &mut rng, tok, 6);
+        let b = directions_for_row(DirectionMode::Sobol, &mut StdRng::seed_from_u64(1), tok, 6);
+        assert_eq!(a, b);
+        assert!(a.iter().all(|v| (v.norm() - 1.0).abs() < 1e-12));
+        let next = ReplayToken { index: 4, ..tok };
+        assert_ne!(
+            a,
+            directions_for_row(DirectionMode::Sobol, &mut rng, next, 6)
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn direction_modes_serialize_by_name() {
+        for mode in [
+            DirectionMode::Iid(DirectionSampler::Gaussian),
+            DirectionMode::Iid(DirectionSampler::LegacyCube),
+            DirectionMode::Sobol,
+        ] {
+            let json = serde_json::to_value(mode).unwrap();
+            assert_eq!(json, serde_json::Value::from(mode.name()));
+            assert_eq!(serde_json::from_value::<DirectionMode>(json).unwrap(), mode);
+        }
+        assert!(serde_json::from_str::<DirectionMode>("\"halton\"").is_err());
+    }
+
+    #[test]
+    fn legacy_sampler_is_biased_towards_diagonals() {
+        let m = mean_fourth_moment(DirectionSampler::LegacyCube);
+        assert!(m < 0.115, "E[x^4] = {m}");
+    }
+}
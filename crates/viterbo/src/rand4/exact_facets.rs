@@ -0,0 +1,269 @@
+//! Random-faces polytopes with exactly `m` facets after reduction.
+//!
+//! Why this file exists
+//! - `RandomFacesGenerator` draws `facets_min..=facets_max` halfspaces, but
+//!   redundant ones disappear when the H-rep is reduced, so the facet count
+//!   of a row is unpredictable. Facet-count-stratified atlases then end up
+//!   with ragged strata.
+//! - `ExactFacetsGenerator` resamples the base family until the reduced body
+//!   has exactly `facets` facets. Attempt `k` of row `i` is the base row
+//!   `ReplayToken { seed: derive_seed(seed, i), index: k }`, so rejected
+//!   attempts never shift later rows.
+//! - A halfspace counts as a facet if it is tight on at least four vertices;
+//!   redundant halfspaces are dropped from the emitted H-rep.
+//!
+//! Replay
+//! - `ExactFacetsReplay { seed, index, attempt }` addresses the accepted base
+//!   attempt directly; regeneration costs one base sample, not a rerun of the
+//!   rejection loop.
+//!
+//! References
+//! - TH: docs/src/thesis/random-polytopes.md (“Exact Facet Counts (adapter)”)
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use super::seeding::derive_seed;
+use super::{
+    AcceptanceStats, AcceptanceStatsSource, Checkpoint, GeneratorError, PolytopeGenerator4,
+    PolytopeSample4, RandomFacesGenerator, RandomFacesParams, StreamState,
+};
+use crate::geom2::rand::ReplayToken;
+use crate::geom4::Poly4;
+
+/// Relative tolerance for vertex–facet incidence.
+const EPS_INCIDENCE: f64 = 1e-9;
+
+/// Parameters for [`ExactFacetsGenerator`].
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ExactFacetsParams {
+    /// Base family; `facets_max` must be at least `facets`.
+    pub faces: RandomFacesParams,
+    /// Required facet count of every emitted body.
+    pub facets: usize,
+    /// Base samples tried per row before giving up.
+    pub max_resamples: u64,
+}
+
+impl ExactFacetsParams {
+    pub fn validate(&self) -> Result<(), GeneratorError> {
+        if self.facets < 5 {
+            return Err(GeneratorError::InvalidParams(
+                "exact_facets: a bounded 4-polytope has at least 5 facets".into(),
+            ));
+        }
+        if self.faces.facets_max < self.facets {
+            return Err(GeneratorError::InvalidParams(format!(
+                "exact_facets: facets_max = {} can never yield {} facets",
+                self.faces.facets_max, self.facets
+            )));
+        }
+        if self.max_resamples == 0 {
+            return Err(GeneratorError::InvalidParams(
+                "exact_facets: max_resamples must be positive".into(),
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// Replay value: the accepted base attempt of a row.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ExactFacetsReplay {
+    pub seed: u64,
+    pub index: u64,
+    pub attempt: u64,
+}
+
+impl ExactFacetsReplay {
+    /// Base-family token of this attempt.
+    pub fn base_token(&self) -> ReplayToken {
+        ReplayToken {
+            seed: derive_seed(self.seed, self.index),
+            index: self.attempt,
+        }
+    }
+}
+
+/// Random-faces stream whose rows all have exactly `params.facets` facets.
+pub struct ExactFacetsGenerator {
+    params: ExactFacetsParams,
+    base: RandomFacesGenerator,
+    seed: u64,
+    next_index: u64,
+    stats: AcceptanceStats,
+}
+
+impl ExactFacetsGenerator {
+    pub fn new(params: ExactFacetsParams, seed: u64) -> Result<Self, GeneratorError> {
+        params.validate()?;
+        let base = RandomFacesGenerator::new(params.faces.clone(), seed)?;
+        Ok(Self {
+            params,
+            base,
+            seed,
+            next_index: 0,
+            stats: AcceptanceStats::default(),
+        })
+    }
+
+    pub fn params(&self) -> &ExactFacetsParams {
+        &self.params
+    }
+}
+
+/// Resample row `index` of the stream seeded with `seed` until the facet
+/// count matches; shared by the stream and the batch path.
+pub(super) fn sample_row(
+    params: &ExactFacetsParams,
+    seed: u64,
+    index: u64,
+    stats: &mut AcceptanceStats,
+) -> Result<PolytopeSample4<ExactFacetsReplay>, GeneratorError> {
+    for attempt in 0..params.max_resamples {
+        stats.record_attempt();
+        let replay = ExactFacetsReplay {
+            seed,
+            index,
+            attempt,
+        };
+        let poly = match RandomFacesGenerator::sample_with_token(&params.faces, replay.base_token())
+        {
+            Ok(poly) => poly,
+            Err(GeneratorError::Degenerate(_)) => {
+                stats.record_rejection("base_degenerate");
+                continue;
+            }
+            Err(err) => return Err(err),
+        };
+        let reduced = reduce_facets(poly);
+        if reduced.h.len() == params.facets {
+            stats.record_accepted();
+            return Ok(PolytopeSample4 {
+                polytope: reduced,
+                replay,
+            });
+        }
+        stats.record_rejection("facet_count");
+    }
+    stats.record_exhausted();
+    Err(GeneratorError::Degenerate(format!(
+        "exact_facets: no body with {} facets after {} resamples (seed={seed}, index={index})",
+        params.facets, params.max_resamples
+    )))
+}
+
+impl PolytopeGenerator4 for ExactFacetsGenerator {
+    type Replay = ExactFacetsReplay;
+
+    fn generate_next(
+        &mut self,
+    ) -> Result<Option<PolytopeSample4<ExactFacetsReplay>>, GeneratorError> {
+        let sample = sample_row(&self.params, self.seed, self.next_index, &mut self.stats)?;
+        self.next_index += 1;
+        Ok(Some(sample))
+    }
+
+    fn regenerate(&self, replay: &ExactFacetsReplay) -> Result<Poly4, GeneratorError> {
+        let reduced = reduce_facets(self.base.regenerate(&replay.base_token())?);
+        if reduced.h.len() != self.params.facets {
+            return Err(GeneratorError::InvalidParams(format!(
+                "exact_facets: replay yields {} facets, expected {}",
+                reduced.h.len(),
+                self.params.facets
+            )));
+        }
+        Ok(reduced)
+    }
+}
+
+impl AcceptanceStatsSource for ExactFacetsGenerator {
+    fn acceptance_stats(&self) -> &AcceptanceStats {
+        &self.stats
+    }
+}
+
+impl Checkpoint for ExactFacetsGenerator {
+    type State = StreamState;
+
+    fn save_state(&self) -> StreamState {
+        StreamState {
+            seed: self.seed,
+            next_index: self.next_index,
+            stats: self.stats.clone(),
+        }
+    }
+
+    fn restore_state(&mut self, state: StreamState) -> Result<(), GeneratorError> {
+        state.check_seed(self.seed)?;
+        self.next_index = state.next_index;
+        self.stats = state.stats;
+        Ok(())
+    }
+}
+
+/// Drop halfspaces that are tight on fewer than four vertices (redundant in 4D).
+pub fn reduce_facets(mut poly: Poly4) -> Poly4 {
+    poly.ensure_vertices_from_h();
+    let scale = poly.v.iter().map(|v| v.norm()).fold(1.0, f64::max);
+    let h = poly
+        .h
+        .iter()
+        .filter(|h| {
+            let tol = EPS_INCIDENCE * scale * h.n.norm();
+            poly.v
+                .iter()
+                .filter(|v| (h.n.dot(v) - h.c).abs() <= tol)
+                .count()
+                >= 4
+        })
+        .copied()
+        .collect();
+    let v = std::mem::take(&mut poly.v);
+    let mut reduced = Poly4::from_h(h);
+    reduced.v = v;
+    reduced
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::geom4::Hs4;
+    use nalgebra::Vector4;
+
+    #[test]
+    fn reduction_drops_redundant_halfspaces() {
+        let mut hs = Vec::new();
+        for k in 0..4 {
+            let mut e = Vector4::zeros();
+            e[k] = 1.0;
+            hs.push(Hs4::new(e, 1.0));
+            hs.push(Hs4::new(-e, 1.0));
+        }
+        // Touches the cube only at one vertex.
+        hs.push(Hs4::new(Vector4::repeat(1.0), 4.0));
+        // Misses the cube entirely.
+        hs.push(Hs4::new(Vector4::new(1.0, 0.0, 0.0, 0.0), 3.0));
+        let reduced = reduce_facets(Poly4::from_h(hs));
+        assert_eq!(reduced.h.len(), 8);
+        assert_eq!(reduced.v.len(), 16);
+    }
+
+    #[test]
+    fn replay_tokens_are_row_local() {
+        let r = ExactFacetsReplay {
+            seed: 9,
+            index: 4,
+            attempt: 2,
+        };
+        let t = r.base_token();
+        assert_eq!(t.index, 2);
+        assert_eq!(t.seed, derive_seed(9, 4));
+        assert_ne!(
+            t.seed,
+            ExactFacetsReplay { index: 5, ..r }.base_token().seed
+        );
+    }
+}
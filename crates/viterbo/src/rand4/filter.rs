@@ -0,0 +1,312 @@
+//! Composable sample filters with per-predicate rejection accounting.
+//!
+//! Why this file exists
+//! - Experiments used to post-filter generator output ad hoc (volume window,
+//!   facet counts, "not too thin"), which silently biases the stream and
+//!   leaves no record of how much was thrown away. `FilteredGenerator` applies
+//!   an explicit filter chain and counts rejections per predicate so the
+//!   acceptance rate can be reported next to the data.
+//! - Filters run in order and stop at the first failure; each rejection is
+//!   charged to that filter only. Cheap predicates should come first.
+//! - Replay delegates to the base generator: accepted rows keep their base
+//!   replay value, and `regenerate` does not re-check the filters.
+//!
+//! References
+//! - TH: docs/src/thesis/random-polytopes.md (“Filters”)
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use super::{Checkpoint, FilteredState, GeneratorError, PolytopeGenerator4, PolytopeSample4};
+use crate::geom4::{volume4, Poly4};
+
+/// A predicate on samples; `accept` may fill in missing representations.
+pub trait SampleFilter {
+    /// Short label used in rejection statistics.
+    fn name(&self) -> String;
+    fn accept(&self, poly: &mut Poly4) -> bool;
+}
+
+/// Keep samples with `vol ≥ min` (failed volume computations are rejected).
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct MinVolume(pub f64);
+
+/// Keep samples with `vol ≤ max` (failed volume computations are rejected).
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct MaxVolume(pub f64);
+
+/// Keep samples whose facet count lies in `min..=max`.
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct FacetCountRange {
+    pub min: usize,
+    pub max: usize,
+}
+
+/// Keep samples whose facets all stay at distance `≥ margin` from the origin.
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct MinOriginMargin(pub f64);
+
+/// Keep samples with `R_out / r_in ≤ max` about the origin, where `R_out` is the
+/// largest vertex norm and `r_in` the smallest facet distance.
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct MaxAspectRatio(pub f64);
+
+impl SampleFilter for MinVolume {
+    fn name(&self) -> String {
+        format!("min_volume({})", self.0)
+    }
+
+    fn accept(&self, poly: &mut Poly4) -> bool {
+        volume4(poly).is_ok_and(|v| v >= self.0)
+    }
+}
+
+impl SampleFilter for MaxVolume {
+    fn name(&self) -> String {
+        format!("max_volume({})", self.0)
+    }
+
+    fn accept(&self, poly: &mut Poly4) -> bool {
+        volume4(poly).is_ok_and(|v| v <= self.0)
+    }
+}
+
+impl SampleFilter for FacetCountRange {
+    fn name(&self) -> String {
+        format!("facets({}..={})", self.min, self.max)
+    }
+
+    fn accept(&self, poly: &mut Poly4) -> bool {
+        poly.ensure_halfspaces_from_v();
+        (self.min..=self.max).contains(&poly.h.len())
+    }
+}
+
+impl SampleFilter for MinOriginMargin {
+    fn name(&self) -> String {
+        format!("min_origin_margin({})", self.0)
+    }
+
+    fn accept(&self, poly: &mut Poly4) -> bool {
+        origin_inradius(poly).is_some_and(|r| r >= self.0)
+    }
+}
+
+impl SampleFilter for MaxAspectRatio {
+    fn name(&self) -> String {
+        format!("max_aspect_ratio({})", self.0)
+    }
+
+    fn accept(&self, poly: &mut Poly4) -> bool {
+        let Some(r_in) = origin_inradius(poly).filter(|&r| r > 0.0) else {
+            return false;
+        };
+        poly.ensure_vertices_from_h();
+        let r_out = poly.v.iter().map(|v| v.norm()).fold(0.0, f64::max);
+        r_out / r_in <= self.0
+    }
+}
+
+/// Smallest facet distance from the origin (negative if the origin is outside).
+pub(crate) fn origin_inradius(poly: &mut Poly4) -> Option<f64> {
+    poly.ensure_halfspaces_from_v();
+    poly.h
+        .iter()
+        .map(|h| h.c / h.n.norm())
+        .min_by(f64::total_cmp)
+}
+
+/// Accepted/rejected counts of a [`FilteredGenerator`].
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct RejectionStats {
+    pub accepted: u64,
+    /// `(filter name, rejections)` in chain order.
+    pub rejected: Vec<(String, u64)>,
+}
+
+impl RejectionStats {
+    pub fn total_rejected(&self) -> u64 {
+        self.rejected.iter().map(|(_, n)| n).sum()
+    }
+
+    /// Fraction of base samples that passed every filter (`None` before any draw).
+    pub fn acceptance_rate(&self) -> Option<f64> {
+        let total = self.accepted + self.total_rejected();
+        (total > 0).then(|| self.accepted as f64 / total as f64)
+    }
+}
+
+/// Generator wrapper that only emits samples passing every filter.
+pub struct FilteredGenerator<G> {
+    base: G,
+    filters: Vec<Box<dyn SampleFilter>>,
+    stats: RejectionStats,
+    max_consecutive_rejections: u64,
+}
+
+impl<G: PolytopeGenerator4> FilteredGenerator<G> {
+    /// Default cap on back-to-back rejections before reporting `Degenerate`.
+    pub const DEFAULT_MAX_CONSECUTIVE_REJECTIONS: u64 = 10_000;
+
+    pub fn new(base: G) -> Self {
+        Self {
+            base,
+            filters: Vec::new(),
+            stats: RejectionStats::default(),
+            max_consecutive_rejections: Self::DEFAULT_MAX_CONSECUTIVE_REJECTIONS,
+        }
+    }
+
+    /// Append a filter to the chain.
+    pub fn with_filter(mut self, filter: impl SampleFilter + 'static) -> Self {
+        self.stats.rejected.push((filter.name(), 0));
+        self.filters.push(Box::new(filter));
+        self
+    }
+
+    /// Give up with `GeneratorError::Degenerate` after this many rejections in a row.
+    pub fn with_max_consecutive_rejections(mut self, cap: u64) -> Self {
+        self.max_consecutive_rejections = cap.max(1);
+        self
+    }
+
+    pub fn stats(&self) -> &RejectionStats {
+        &self.stats
+    }
+
+    pub fn base(&self) -> &G {
+        &self.base
+    }
+}
+
+impl<G: PolytopeGenerator4> PolytopeGenerator4 for FilteredGenerator<G> {
+    type Replay = G::Replay;
+
+    fn generate_next(&mut self) -> Result<Option<PolytopeSample4<G::Replay>>, GeneratorError> {
+        let mut streak = 0;
+        loop {
+            let Some(mut sample) = self.base.generate_next()? else {
+                return Ok(None);
+            };
+            let failed = self
+                .filters
+                .iter()
+                .position(|f| !f.accept(&mut sample.polytope));
+            let Some(i) = failed else {
+                self.stats.accepted += 1;
+                return Ok(Some(sample));
+            };
+            self.stats.rejected[i].1 += 1;
+            streak += 1;
+            if streak >= self.max_consecutive_rejections {
+                return Err(GeneratorError::Degenerate(format!(
+                    "filter: {streak} consecutive rejections (last by {})",
+                    self.stats.rejected[i].0
+                )));
+            }
+        }
+    }
+
+    fn regenerate(&self, replay: &G::Replay) -> Result<Poly4, GeneratorError> {
+        self.base.regenerate(replay)
+    }
+}
+
+impl<G: PolytopeGenerator4 + Checkpoint> Checkpoint for FilteredGenerator<G> {
+    type State = FilteredState<G::State>;
+
+    fn save_state(&self) -> Self::State {
+        FilteredState {
+            base: self.base.save_state(),
+            rejections: self.stats.clone(),
+        }
+    }
+
+    fn restore_state(&mut self, state: Self::State) -> Result<(), GeneratorError> {
+        let names = |s: &RejectionStats| {
+            s.rejected
+                .iter()
+                .map(|(n, _)| n.clone())
+                .collect::<Vec<_>>()
+        };
+        if names(&state.rejections) != names(&self.stats) {
+            return Err(GeneratorError::InvalidParams(
+                "checkpoint: filter chain differs from the recorded one".into(),
+            ));
+        }
+        self.base.restore_state(state.base)?;
+        self.stats = state.rejections;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::geom4::Hs4;
+    use nalgebra::Vector4;
+
+    /// Cubes `[-s, s]⁴` with `s = 1, 2, 3, ...`.
+    struct GrowingCubes {
+        next: u32,
+    }
+
+    impl PolytopeGenerator4 for GrowingCubes {
+        type Replay = u32;
+
+        fn generate_next(&mut self) -> Result<Option<PolytopeSample4<u32>>, GeneratorError> {
+            self.next += 1;
+            Ok(Some(PolytopeSample4 {
+                polytope: self.regenerate(&self.next)?,
+                replay: self.next,
+            }))
+        }
+
+        fn regenerate(&self, replay: &u32) -> Result<Poly4, GeneratorError> {
+            let s = f64::from(*replay);
+            let mut hs = Vec::new();
+            for k in 0..4 {
+                let mut e = Vector4::zeros();
+                e[k] = 1.0;
+                hs.push(Hs4::new(e, s));
+                hs.push(Hs4::new(-e, s));
+            }
+            let mut p = Poly4::from_h(hs);
+            p.ensure_vertices_from_h();
+            Ok(p)
+        }
+    }
+
+    #[test]
+    fn counts_rejections_per_filter() {
+        let mut gen = FilteredGenerator::new(GrowingCubes { next: 0 })
+            .with_filter(FacetCountRange { min: 8, max: 8 })
+            .with_filter(MinOriginMargin(2.5));
+        let sample = gen.generate_next().unwrap().unwrap();
+        assert_eq!(sample.replay, 3);
+        let stats = gen.stats();
+        assert_eq!(stats.accepted, 1);
+        assert_eq!(stats.rejected[0].1, 0);
+        assert_eq!(stats.rejected[1].1, 2);
+        assert_eq!(stats.acceptance_rate(), Some(1.0 / 3.0));
+    }
+
+    #[test]
+    fn impossible_chain_reports_degenerate() {
+        // Cubes have R_out / r_in = 2 about their centre.
+        let mut gen = FilteredGenerator::new(GrowingCubes { next: 0 })
+            .with_filter(MaxAspectRatio(1.5))
+            .with_max_consecutive_rejections(5);
+        assert!(matches!(
+            gen.generate_next(),
+            Err(GeneratorError::Degenerate(_))
+        ));
+        assert_eq!(gen.stats().total_rejected(), 5);
+    }
+}
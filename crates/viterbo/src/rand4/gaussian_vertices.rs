@@ -0,0 +1,305 @@
+//! Gaussian-vertex polytopes: convex hulls of `N(0, Σ)` point clouds.
+//!
+//! Why this family exists
+//! - `RandomVerticesGenerator` draws vertices as `direction × radius`, which
+//!   concentrates mass on a shell and yields a characteristic facet-count
+//!   distribution. Gaussian clouds put mass near the centre, so fewer points
+//!   survive hull reduction and the facet statistics differ noticeably. We keep
+//!   both families side by side for comparison studies.
+//! - The covariance `Σ` is user supplied; we factor it once (Cholesky) and map
+//!   standard normal samples through the lower factor `L` (`x = L z`).
+//!
+//! Replay
+//! - Rows are addressed by `ReplayToken { seed, index }`; each token seeds its
+//!   own `StdRng`, so regenerating row `k` does not require replaying rows `< k`.
+//! - Rejections (origin not interior, degenerate hull) retry with the same RNG,
+//!   so the accepted attempt is part of the deterministic replay.
+//!
+//! References
+//! - TH: docs/src/thesis/random-polytopes.md
+//! - Code: crates/viterbo/src/rand4/mod.rs (`RandomVerticesGenerator`)
+
+use nalgebra::{Cholesky, Matrix4, Vector4};
+use rand::Rng;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use super::dirs::standard_normal4;
+use super::seeding::token_rng;
+use super::{
+    AcceptanceStats, AcceptanceStatsSource, Checkpoint, GeneratorError, IndexedGenerator,
+    PolytopeGenerator4, PolytopeSample4, StreamState,
+};
+use crate::geom2::rand::ReplayToken;
+use crate::geom4::Poly4;
+
+/// Minimum slack `c` a facet must keep so the origin is strictly interior.
+const ORIGIN_MARGIN: f64 = 1e-9;
+
+/// Parameters for [`GaussianVerticesGenerator`]; missing JSON fields take the defaults.
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct GaussianVerticesParams {
+    /// Inclusive lower bound on the number of drawn points (before hull reduction).
+    pub vertices_min: usize,
+    /// Inclusive upper bound on the number of drawn points (before hull reduction).
+    pub vertices_max: usize,
+    /// Covariance `Σ` of the vertex distribution; must be symmetric positive definite.
+    #[cfg_attr(feature = "serde", serde(with = "super::serde_matrix"))]
+    pub covariance: Matrix4<f64>,
+    /// Number of draws per row before giving up with `GeneratorError::Degenerate`.
+    pub max_attempts: usize,
+}
+
+impl Default for GaussianVerticesParams {
+    fn default() -> Self {
+        Self {
+            vertices_min: 8,
+            vertices_max: 24,
+            covariance: Matrix4::identity(),
+            max_attempts: 20,
+        }
+    }
+}
+
+impl GaussianVerticesParams {
+    /// Check counts and covariance; returns the Cholesky factor `L` with `Σ = L Lᵀ`.
+    pub fn validate(&self) -> Result<Matrix4<f64>, GeneratorError> {
+        if self.vertices_min < 5 {
+            return Err(GeneratorError::InvalidParams(
+                "gaussian vertices: vertices_min must be >= 5 for a full-dimensional hull".into(),
+            ));
+        }
+        if self.vertices_min > self.vertices_max {
+            return Err(GeneratorError::InvalidParams(format!(
+                "gaussian vertices: vertices_min ({}) > vertices_max ({})",
+                self.vertices_min, self.vertices_max
+            )));
+        }
+        if self.max_attempts == 0 {
+            return Err(GeneratorError::InvalidParams(
+                "gaussian vertices: max_attempts must be positive".into(),
+            ));
+        }
+        let cov = self.covariance;
+        let asym = (cov - cov.transpose()).abs().max();
+        if !cov.iter().all(|x| x.is_finite()) || asym > 1e-12 * cov.abs().max().max(1.0) {
+            return Err(GeneratorError::InvalidParams(
+                "gaussian vertices: covariance must be finite and symmetric".into(),
+            ));
+        }
+        Cholesky::new(cov).map(|ch| ch.l()).ok_or_else(|| {
+            GeneratorError::InvalidParams(
+                "gaussian vertices: covariance must be positive definite".into(),
+            )
+        })
+    }
+}
+
+/// Streaming generator over Gaussian-vertex polytopes.
+#[derive(Clone, Debug)]
+pub struct GaussianVerticesGenerator {
+    params: GaussianVerticesParams,
+    chol: Matrix4<f64>,
+    seed: u64,
+    next_index: u64,
+    stats: AcceptanceStats,
+}
+
+impl GaussianVerticesGenerator {
+    pub fn new(params: GaussianVerticesParams, seed: u64) -> Result<Self, GeneratorError> {
+        let chol = params.validate()?;
+        Ok(Self {
+            params,
+            chol,
+            seed,
+            next_index: 0,
+            stats: AcceptanceStats::default(),
+        })
+    }
+
+    pub fn params(&self) -> &GaussianVerticesParams {
+        &self.params
+    }
+
+    /// Build the row addressed by `token` without constructing a generator.
+    pub fn sample_with_token(
+        params: &GaussianVerticesParams,
+        token: ReplayToken,
+    ) -> Result<Poly4, GeneratorError> {
+        let chol = params.validate()?;
+        sample_impl(params, &chol, token, &mut AcceptanceStats::default())
+    }
+}
+
+impl PolytopeGenerator4 for GaussianVerticesGenerator {
+    type Replay = ReplayToken;
+
+    fn generate_next(&mut self) -> Result<Option<PolytopeSample4<ReplayToken>>, GeneratorError> {
+        let token = ReplayToken {
+            seed: self.seed,
+            index: self.next_index,
+        };
+        let polytope = sample_impl(&self.params, &self.chol, token, &mut self.stats)?;
+        self.next_index += 1;
+        Ok(Some(PolytopeSample4 {
+            polytope,
+            replay: token,
+        }))
+    }
+
+    fn regenerate(&self, replay: &ReplayToken) -> Result<Poly4, GeneratorError> {
+        sample_impl(
+            &self.params,
+            &self.chol,
+            *replay,
+            &mut AcceptanceStats::default(),
+        )
+    }
+}
+
+impl AcceptanceStatsSource for GaussianVerticesGenerator {
+    fn acceptance_stats(&self) -> &AcceptanceStats {
+        &self.stats
+    }
+}
+
+impl Checkpoint for GaussianVerticesGenerator {
+    type State = StreamState;
+
+    fn save_state(&self) -> StreamState {
+        StreamState {
+            seed: self.seed,
+            next_index: self.next_index,
+            stats: self.stats.clone(),
+        }
+    }
+
+    fn restore_state(&mut self, state: StreamState) -> Result<(), GeneratorError> {
+        state.check_seed(self.seed)?;
+        self.next_index = state.next_index;
+        self.stats = state.stats;
+        Ok(())
+    }
+}
+
+impl IndexedGenerator for GaussianVerticesGenerator {
+    fn row_count(&self) -> Option<u64> {
+        None
+    }
+
+    fn replay_at(&self, index: u64) -> Option<ReplayToken> {
+        Some(ReplayToken {
+            seed: self.seed,
+            index,
+        })
+    }
+}
+
+fn sample_impl(
+    params: &GaussianVerticesParams,
+    chol: &Matrix4<f64>,
+    token: ReplayToken,
+    stats: &mut AcceptanceStats,
+) -> Result<Poly4, GeneratorError> {
+    let mut rng = token_rng(token);
+    for _ in 0..params.max_attempts {
+        stats.record_attempt();
+        let count = rng.gen_range(params.vertices_min..=params.vertices_max);
+        let points: Vec<Vector4<f64>> = (0..count)
+            .map(|_| chol * standard_normal4(&mut rng))
+            .collect();
+        let mut poly = Poly4::from_v(points);
+        poly.ensure_halfspaces_from_v();
+        if poly.h.len() < 5 {
+            stats.record_rejection("low_dimensional");
+            continue;
+        }
+        if poly.h.iter().any(|h| h.c <= ORIGIN_MARGIN) {
+            stats.record_rejection("origin_not_interior");
+            continue;
+        }
+        // Drop interior points: rebuild the vertex list from the reduced H-rep.
+        poly.v.clear();
+        poly.ensure_vertices_from_h();
+        if poly.check_canonical().is_ok() {
+            stats.record_accepted();
+            return Ok(poly);
+        }
+        stats.record_rejection("not_canonical");
+    }
+    stats.record_exhausted();
+    Err(GeneratorError::Degenerate(format!(
+        "gaussian vertices: no valid hull after {} attempts (seed={}, index={})",
+        params.max_attempts, token.seed, token.index
+    )))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rejects_non_positive_definite_covariance() {
+        let params = GaussianVerticesParams {
+            covariance: Matrix4::from_diagonal(&Vector4::new(1.0, 1.0, 1.0, 0.0)),
+            ..GaussianVerticesParams::default()
+        };
+        assert!(matches!(
+            GaussianVerticesGenerator::new(params, 1),
+            Err(GeneratorError::InvalidParams(_))
+        ));
+    }
+
+    #[test]
+    fn rejects_asymmetric_covariance() {
+        let mut cov = Matrix4::identity();
+        cov[(0, 1)] = 0.3;
+        let params = GaussianVerticesParams {
+            covariance: cov,
+            ..GaussianVerticesParams::default()
+        };
+        assert!(params.validate().is_err());
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn params_json_round_trip() {
+        let params: GaussianVerticesParams = serde_json::from_value(serde_json::json!({
+            "vertices_max": 12,
+            "covariance": [
+                [2.0, 0.5, 0.0, 0.0],
+                [0.5, 1.0, 0.0, 0.0],
+                [0.0, 0.0, 1.0, 0.0],
+                [0.0, 0.0, 0.0, 1.0],
+            ],
+        }))
+        .unwrap();
+        assert_eq!(params.vertices_min, 8);
+        assert_eq!(params.covariance[(0, 1)], 0.5);
+        let back: GaussianVerticesParams =
+            serde_json::from_value(serde_json::to_value(&params).unwrap()).unwrap();
+        assert_eq!(back.covariance, params.covariance);
+        assert_eq!(back.vertices_max, 12);
+    }
+
+    #[test]
+    fn gaussian_vertices_contain_origin_and_replay() {
+        let params = GaussianVerticesParams {
+            covariance: Matrix4::from_diagonal(&Vector4::new(2.0, 1.0, 0.5, 0.25)),
+            ..GaussianVerticesParams::default()
+        };
+        let mut gen = GaussianVerticesGenerator::new(params.clone(), 2025).unwrap();
+        for _ in 0..3 {
+            let sample = gen.generate_next().unwrap().unwrap();
+            assert!(sample.polytope.h.iter().all(|h| h.c > 0.0));
+            assert!(sample.polytope.v.len() <= params.vertices_max);
+            let again = gen.regenerate(&sample.replay).unwrap();
+            assert_eq!(again.v, sample.polytope.v);
+            let direct =
+                GaussianVerticesGenerator::sample_with_token(&params, sample.replay).unwrap();
+            assert_eq!(direct.h, sample.polytope.h);
+        }
+    }
+}
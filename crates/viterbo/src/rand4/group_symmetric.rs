@@ -0,0 +1,456 @@
+//! Polytopes invariant under a finite subgroup of Sp(4).
+//!
+//! Why this family exists
+//! - The symmetric Viterbo experiments need bodies with a prescribed
+//!   symplectic symmetry (e.g. the block rotation `C_k` or the factor swap),
+//!   and building them by hand does not scale. Here we sample `m` orbit
+//!   representatives `(n, c)` and close the facet set under the group, so the
+//!   result is `G`-invariant by construction.
+//! - Coordinates are `(x₁, x₂, y₁, y₂)` with `J = [[0, −I], [I, 0]]`
+//!   (geom4 convention). An element `g` maps the halfspace `⟨n, x⟩ ≤ c` to
+//!   `⟨g^{−T} n, x⟩ ≤ c`; orbits are deduplicated, so facets fixed by a
+//!   stabilizer appear once.
+//! - Offsets are positive, so the origin is interior whenever the body is
+//!   bounded. Boundedness (normals positively span R⁴) is checked by clipping
+//!   with a huge box; unbounded (or absurdly elongated) draws are retried.
+//!
+//! Groups
+//! - `BlockRotation { k }`: rotation by `2π/k` in both `(x_i, y_i)` planes,
+//!   i.e. multiplication by `e^{2πi/k}` on C². `k = 2` is central symmetry.
+//! - `FactorSwap`: `(x₁, y₁) ↔ (x₂, y₂)`.
+//! - `Generated { generators }`: the group generated by user matrices
+//!   (row-major); each must be symplectic and the closure finite.
+//!
+//! Replay
+//! - Rows are addressed by `ReplayToken { seed, index }` (per-row RNG, see
+//!   `rand4::seeding`).
+//!
+//! References
+//! - TH: docs/src/thesis/random-polytopes.md (“Group-Symmetric Polytopes”)
+//! - TH: docs/src/thesis/geom4d_polytopes.md (J-matrix convention)
+
+use std::f64::consts::PI;
+
+use nalgebra::{Matrix4, Vector4};
+use rand::Rng;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use super::dirs::{directions_for_row, DirectionMode};
+use super::seeding::token_rng;
+use super::symplectic::is_symplectic;
+use super::{
+    AcceptanceStats, AcceptanceStatsSource, Checkpoint, GeneratorError, IndexedGenerator,
+    PolytopeGenerator4, PolytopeSample4, StreamState,
+};
+use crate::geom2::rand::ReplayToken;
+use crate::geom4::{Hs4, Poly4};
+
+/// Closures larger than this are reported as (probably) infinite groups.
+pub const MAX_GROUP_ORDER: usize = 1024;
+/// Entrywise tolerance for matrix equality.
+const EPS_MATRIX: f64 = 1e-9;
+/// Unit normals closer than this are the same facet direction.
+const EPS_NORMAL: f64 = 1e-9;
+/// Bodies reaching beyond this multiple of the largest offset count as unbounded.
+const BOUND_FACTOR: f64 = 1e6;
+
+/// A finite subgroup of Sp(4), given by generators.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(tag = "kind", rename_all = "snake_case"))]
+pub enum SymmetryGroup {
+    /// Cyclic group of order `k` rotating both `(x_i, y_i)` planes by `2π/k`.
+    BlockRotation { k: u32 },
+    /// Swap of the two symplectic factors, `(x₁, y₁) ↔ (x₂, y₂)`.
+    FactorSwap,
+    /// Group generated by the given symplectic matrices (row-major).
+    Generated { generators: Vec<[[f64; 4]; 4]> },
+}
+
+impl Default for SymmetryGroup {
+    fn default() -> Self {
+        SymmetryGroup::BlockRotation { k: 3 }
+    }
+}
+
+impl SymmetryGroup {
+    /// Generator matrices; errors on `k = 0` or non-symplectic input.
+    pub fn generators(&self) -> Result<Vec<Matrix4<f64>>, GeneratorError> {
+        let gens = match self {
+            SymmetryGroup::BlockRotation { k } => {
+                if *k == 0 {
+                    return Err(GeneratorError::InvalidParams(
+                        "group_symmetric: block rotation order k must be positive".into(),
+                    ));
+                }
+                let (s, c) = (2.0 * PI / f64::from(*k)).sin_cos();
+                let mut r = Matrix4::zeros();
+                for i in 0..2 {
+                    r[(i, i)] = c;
+                    r[(i, i + 2)] = -s;
+                    r[(i + 2, i)] = s;
+                    r[(i + 2, i + 2)] = c;
+                }
+                vec![r]
+            }
+            SymmetryGroup::FactorSwap => {
+                let mut p = Matrix4::zeros();
+                for (i, j) in [(0, 1), (1, 0), (2, 3), (3, 2)] {
+                    p[(i, j)] = 1.0;
+                }
+                vec![p]
+            }
+            SymmetryGroup::Generated { generators } => generators
+                .iter()
+                .map(|rows| Matrix4::from_fn(|i, j| rows[i][j]))
+                .collect(),
+        };
+        if let Some(i) = gens.iter().position(|g| !is_symplectic(g)) {
+            return Err(GeneratorError::InvalidParams(format!(
+                "group_symmetric: generator {i} is not symplectic (g^T J g != J)"
+            )));
+        }
+        Ok(gens)
+    }
+
+    /// All group elements (identity first), by closing the generators under products.
+    pub fn elements(&self) -> Result<Vec<Matrix4<f64>>, GeneratorError> {
+        let gens = self.generators()?;
+        let mut elems = vec![Matrix4::identity()];
+        let mut frontier = 0;
+        while frontier < elems.len() {
+            let a = elems[frontier];
+            frontier += 1;
+            for g in &gens {
+                let prod = g * a;
+                if elems.iter().any(|e| (e - prod).amax() < EPS_MATRIX) {
+                    continue;
+                }
+                if elems.len() == MAX_GROUP_ORDER {
+                    return Err(GeneratorError::InvalidParams(format!(
+                        "group_symmetric: generated group exceeds {MAX_GROUP_ORDER} elements \
+                         (infinite or too large)"
+                    )));
+                }
+                elems.push(prod);
+            }
+        }
+        Ok(elems)
+    }
+}
+
+/// Parameters for [`GroupSymmetricGenerator`]; missing JSON fields take the defaults.
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct GroupSymmetricParams {
+    pub group: SymmetryGroup,
+    /// Inclusive range for the number of orbit representatives.
+    pub orbits_min: usize,
+    pub orbits_max: usize,
+    /// Offset range of the representative halfspaces `⟨n, x⟩ ≤ c` (unit `n`).
+    pub offset_min: f64,
+    pub offset_max: f64,
+    /// Source of the representative normals.
+    pub directions: DirectionMode,
+    /// Number of draws per row before giving up with `GeneratorError::Degenerate`.
+    pub max_attempts: usize,
+}
+
+impl Default for GroupSymmetricParams {
+    fn default() -> Self {
+        Self {
+            group: SymmetryGroup::default(),
+            orbits_min: 2,
+            orbits_max: 6,
+            offset_min: 0.8,
+            offset_max: 1.2,
+            directions: DirectionMode::default(),
+            max_attempts: 20,
+        }
+    }
+}
+
+impl GroupSymmetricParams {
+    pub fn validate(&self) -> Result<(), GeneratorError> {
+        if self.orbits_min == 0 || self.orbits_min > self.orbits_max {
+            return Err(GeneratorError::InvalidParams(format!(
+                "group_symmetric: need 1 <= orbits_min ({}) <= orbits_max ({})",
+                self.orbits_min, self.orbits_max
+            )));
+        }
+        let offsets_ok = self.offset_min > 0.0
+            && self.offset_min <= self.offset_max
+            && self.offset_max.is_finite();
+        if !offsets_ok {
+            return Err(GeneratorError::InvalidParams(
+                "group_symmetric: need 0 < offset_min <= offset_max < inf".into(),
+            ));
+        }
+        if self.max_attempts == 0 {
+            return Err(GeneratorError::InvalidParams(
+                "group_symmetric: max_attempts must be positive".into(),
+            ));
+        }
+        self.group.elements().map(|_| ())
+    }
+}
+
+/// Streaming generator over `G`-invariant polytopes.
+#[derive(Clone, Debug)]
+pub struct GroupSymmetricGenerator {
+    params: GroupSymmetricParams,
+    /// Cached `g^{−T}` for every group element.
+    normal_maps: Vec<Matrix4<f64>>,
+    seed: u64,
+    next_index: u64,
+    stats: AcceptanceStats,
+}
+
+impl GroupSymmetricGenerator {
+    pub fn new(params: GroupSymmetricParams, seed: u64) -> Result<Self, GeneratorError> {
+        let normal_maps = normal_maps(&params)?;
+        Ok(Self {
+            params,
+            normal_maps,
+            seed,
+            next_index: 0,
+            stats: AcceptanceStats::default(),
+        })
+    }
+
+    pub fn params(&self) -> &GroupSymmetricParams {
+        &self.params
+    }
+
+    /// Order of the symmetry group.
+    pub fn group_order(&self) -> usize {
+        self.normal_maps.len()
+    }
+
+    /// Build the row addressed by `token` without constructing a generator.
+    pub fn sample_with_token(
+        params: &GroupSymmetricParams,
+        token: ReplayToken,
+    ) -> Result<Poly4, GeneratorError> {
+        let maps = normal_maps(params)?;
+        sample_impl(params, &maps, token, &mut AcceptanceStats::default())
+    }
+}
+
+impl PolytopeGenerator4 for GroupSymmetricGenerator {
+    type Replay = ReplayToken;
+
+    fn generate_next(&mut self) -> Result<Option<PolytopeSample4<ReplayToken>>, GeneratorError> {
+        let token = ReplayToken {
+            seed: self.seed,
+            index: self.next_index,
+        };
+        let polytope = sample_impl(&self.params, &self.normal_maps, token, &mut self.stats)?;
+        self.next_index += 1;
+        Ok(Some(PolytopeSample4 {
+            polytope,
+            replay: token,
+        }))
+    }
+
+    fn regenerate(&self, replay: &ReplayToken) -> Result<Poly4, GeneratorError> {
+        sample_impl(
+            &self.params,
+            &self.normal_maps,
+            *replay,
+            &mut AcceptanceStats::default(),
+        )
+    }
+}
+
+impl AcceptanceStatsSource for GroupSymmetricGenerator {
+    fn acceptance_stats(&self) -> &AcceptanceStats {
+        &self.stats
+    }
+}
+
+impl Checkpoint for GroupSymmetricGenerator {
+    type State = StreamState;
+
+    fn save_state(&self) -> StreamState {
+        StreamState {
+            seed: self.seed,
+            next_index: self.next_index,
+            stats: self.stats.clone(),
+        }
+    }
+
+    fn restore_state(&mut self, state: StreamState) -> Result<(), GeneratorError> {
+        state.check_seed(self.seed)?;
+        self.next_index = state.next_index;
+        self.stats = state.stats;
+        Ok(())
+    }
+}
+
+impl IndexedGenerator for GroupSymmetricGenerator {
+    fn row_count(&self) -> Option<u64> {
+        None
+    }
+
+    fn replay_at(&self, index: u64) -> Option<ReplayToken> {
+        Some(ReplayToken {
+            seed: self.seed,
+            index,
+        })
+    }
+}
+
+/// Validate `params` and return `g^{−T}` for every group element.
+fn normal_maps(params: &GroupSymmetricParams) -> Result<Vec<Matrix4<f64>>, GeneratorError> {
+    params.validate()?;
+    params
+        .group
+        .elements()?
+        .into_iter()
+        .map(|g| {
+            g.try_inverse().map(|inv| inv.transpose()).ok_or_else(|| {
+                GeneratorError::InvalidParams("group_symmetric: singular group element".into())
+            })
+        })
+        .collect()
+}
+
+/// Close the representative halfspaces under the group (unit normals, deduplicated).
+pub fn orbit_halfspaces(reps: &[Hs4], normal_maps: &[Matrix4<f64>]) -> Vec<Hs4> {
+    let mut out: Vec<Hs4> = Vec::with_capacity(reps.len() * normal_maps.len());
+    for rep in reps {
+        for m in normal_maps {
+            let n = m * rep.n;
+            let norm = n.norm();
+            let (n, c) = (n / norm, rep.c / norm);
+            match out.iter_mut().find(|h| (h.n - n).norm() < EPS_NORMAL) {
+                Some(h) => h.c = h.c.min(c),
+                None => out.push(Hs4::new(n, c)),
+            }
+        }
+    }
+    out
+}
+
+/// Whether the H-rep is bounded: clipped to the box `[−R, R]⁴` with
+/// `R = BOUND_FACTOR · max c`, no vertex may come near the box.
+///
+/// Also `false` when `max c ≤ 0` (including an empty H-rep): the box test
+/// needs the origin inside. Callers that must tell unbounded from
+/// degenerate check that case first.
+pub fn is_bounded(h: &[Hs4]) -> bool {
+    let r = BOUND_FACTOR * h.iter().map(|hs| hs.c).fold(0.0, f64::max);
+    let mut clipped = h.to_vec();
+    for k in 0..4 {
+        let mut e = Vector4::zeros();
+        e[k] = 1.0;
+        clipped.push(Hs4::new(e, r));
+        clipped.push(Hs4::new(-e, r));
+    }
+    let mut poly = Poly4::from_h(clipped);
+    poly.ensure_vertices_from_h();
+    !poly.v.is_empty() && poly.v.iter().all(|v| v.amax() < 0.5 * r)
+}
+
+fn sample_impl(
+    params: &GroupSymmetricParams,
+    normal_maps: &[Matrix4<f64>],
+    token: ReplayToken,
+    stats: &mut AcceptanceStats,
+) -> Result<Poly4, GeneratorError> {
+    let mut rng = token_rng(token);
+    for _ in 0..params.max_attempts {
+        stats.record_attempt();
+        let m = rng.gen_range(params.orbits_min..=params.orbits_max);
+        let dirs = directions_for_row(params.directions, &mut rng, token, m);
+        let reps: Vec<Hs4> = dirs
+            .into_iter()
+            .map(|n: Vector4<f64>| {
+                Hs4::new(n, rng.gen_range(params.offset_min..=params.offset_max))
+            })
+            .collect();
+        let h = orbit_halfspaces(&reps, normal_maps);
+        if !is_bounded(&h) {
+            stats.record_rejection("unbounded");
+            continue;
+        }
+        let mut poly = Poly4::from_h(h);
+        poly.ensure_vertices_from_h();
+        if poly.check_canonical().is_ok() {
+            stats.record_accepted();
+            return Ok(poly);
+        }
+        stats.record_rejection("not_canonical");
+    }
+    stats.record_exhausted();
+    Err(GeneratorError::Degenerate(format!(
+        "group_symmetric: no bounded invariant body after {} attempts (seed={}, index={})",
+        params.max_attempts, token.seed, token.index
+    )))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn builtin_groups_are_symplectic_with_expected_order() {
+        let order = |g: SymmetryGroup| g.elements().unwrap().len();
+        assert_eq!(order(SymmetryGroup::BlockRotation { k: 5 }), 5);
+        assert_eq!(order(SymmetryGroup::FactorSwap), 2);
+        let rot = SymmetryGroup::BlockRotation { k: 4 }.generators().unwrap()[0];
+        let swap = SymmetryGroup::FactorSwap.generators().unwrap()[0];
+        let to_rows = |m: Matrix4<f64>| std::array::from_fn(|i| std::array::from_fn(|j| m[(i, j)]));
+        let product = SymmetryGroup::Generated {
+            generators: vec![to_rows(rot), to_rows(swap)],
+        };
+        // Block rotations are central, so ⟨C₄, swap⟩ ≅ C₄ × C₂.
+        assert_eq!(order(product), 8);
+    }
+
+    #[test]
+    fn rejects_non_symplectic_and_infinite_groups() {
+        let mut scale = [[0.0; 4]; 4];
+        for (i, row) in scale.iter_mut().enumerate() {
+            row[i] = 2.0;
+        }
+        let err = SymmetryGroup::Generated {
+            generators: vec![scale],
+        };
+        assert!(err.generators().is_err());
+        // diag(2, 1, 1/2, 1) is symplectic but of infinite order.
+        let mut stretch = [[0.0; 4]; 4];
+        for (i, s) in [2.0, 1.0, 0.5, 1.0].into_iter().enumerate() {
+            stretch[i][i] = s;
+        }
+        let infinite = SymmetryGroup::Generated {
+            generators: vec![stretch],
+        };
+        assert!(infinite.generators().is_ok());
+        assert!(infinite.elements().is_err());
+    }
+
+    #[test]
+    fn samples_are_invariant_and_replay() {
+        let params = GroupSymmetricParams {
+            group: SymmetryGroup::BlockRotation { k: 3 },
+            orbits_min: 3,
+            orbits_max: 3,
+            ..GroupSymmetricParams::default()
+        };
+        let mut gen = GroupSymmetricGenerator::new(params.clone(), 11).unwrap();
+        let sample = gen.generate_next().unwrap().unwrap();
+        let p = &sample.polytope;
+        let g = SymmetryGroup::BlockRotation { k: 3 }.generators().unwrap()[0];
+        for v in &p.v {
+            let w = g * v;
+            assert!(p.v.iter().any(|u| (u - w).norm() < 1e-9));
+        }
+        assert_eq!(p.h.len() % 3, 0);
+        let again = GroupSymmetricGenerator::sample_with_token(&params, sample.replay).unwrap();
+        assert_eq!(again.h, p.h);
+    }
+}
@@ -0,0 +1,265 @@
+//! Iterator adapters and random access over `PolytopeGenerator4` streams.
+//!
+//! Why this file exists
+//! - `generate_next -> Result<Option<..>>` forces hand-written `while let`
+//!   loops. `into_samples()` turns any generator into a standard `Iterator`
+//!   of `Result<PolytopeSample4<_>, _>` so `take`, `filter_map`, `zip` etc.
+//!   compose while every item keeps its replay value.
+//! - The iterator is fused after the first error: generators do not advance
+//!   their index on failure, so polling again would retry the same row forever.
+//! - `IndexedGenerator` gives random access (`sample_at(index)`) for streams
+//!   whose rows are addressed by an integer: token-addressed families
+//!   (`ReplayToken { seed, index }`) and the regular-product enumeration.
+//!   The trait is an extension of `PolytopeGenerator4` because `rand4/mod.rs`
+//!   owns the core trait and a blanket `IntoIterator` impl is not allowed.
+//!
+//! References
+//! - TH: docs/src/thesis/random-polytopes.md (“Architecture”)
+
+use super::{
+    Checkpoint, GeneratorError, PolytopeGenerator4, PolytopeSample4, RegularProductEnumParams,
+    RegularProductEnumerator, RegularProductReplay,
+};
+use crate::geom4::Poly4;
+
+/// Iterator over a generator's samples; see [`GeneratorIterExt::into_samples`].
+#[derive(Clone, Debug)]
+pub struct Samples<G> {
+    gen: G,
+    done: bool,
+}
+
+impl<G> Samples<G> {
+    /// Give the generator back (e.g. to read its params or continue manually).
+    pub fn into_inner(self) -> G {
+        self.gen
+    }
+}
+
+impl<G: PolytopeGenerator4> Iterator for Samples<G> {
+    type Item = Result<PolytopeSample4<G::Replay>, GeneratorError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        match self.gen.generate_next() {
+            Ok(Some(sample)) => Some(Ok(sample)),
+            Ok(None) => {
+                self.done = true;
+                None
+            }
+            Err(err) => {
+                self.done = true;
+                Some(Err(err))
+            }
+        }
+    }
+}
+
+impl<G: PolytopeGenerator4> std::iter::FusedIterator for Samples<G> {}
+
+/// Iterator conversion for every generator.
+pub trait GeneratorIterExt: PolytopeGenerator4 + Sized {
+    /// Consume the generator as an iterator of samples (fused after the first error).
+    fn into_samples(self) -> Samples<Self> {
+        Samples {
+            gen: self,
+            done: false,
+        }
+    }
+
+    /// Iterate by reference, leaving the generator usable afterwards.
+    fn samples(&mut self) -> Samples<&mut Self> {
+        Samples {
+            gen: self,
+            done: false,
+        }
+    }
+}
+
+impl<G: PolytopeGenerator4> GeneratorIterExt for G {}
+
+impl<G: PolytopeGenerator4> PolytopeGenerator4 for &mut G {
+    type Replay = G::Replay;
+
+    fn generate_next(&mut self) -> Result<Option<PolytopeSample4<G::Replay>>, GeneratorError> {
+        (**self).generate_next()
+    }
+
+    fn regenerate(&self, replay: &G::Replay) -> Result<Poly4, GeneratorError> {
+        (**self).regenerate(replay)
+    }
+}
+
+/// Generators whose rows are addressed by a flat index.
+pub trait IndexedGenerator: PolytopeGenerator4 {
+    /// Number of rows, or `None` for unbounded streams.
+    fn row_count(&self) -> Option<u64>;
+
+    /// Replay value of row `index`; `None` past the end.
+    fn replay_at(&self, index: u64) -> Option<Self::Replay>;
+
+    /// Build row `index` directly, independent of the streaming position.
+    fn sample_at(
+        &self,
+        index: u64,
+    ) -> Result<Option<PolytopeSample4<Self::Replay>>, GeneratorError> {
+        let Some(replay) = self.replay_at(index) else {
+            return Ok(None);
+        };
+        let polytope = self.regenerate(&replay)?;
+        Ok(Some(PolytopeSample4 { polytope, replay }))
+    }
+}
+
+/// Regular-product enumeration with flat pair indices (`index = index_a · |B| + index_b`).
+///
+/// Streams in the same order as the atlas `pair_index`, truncated to `max_pairs`.
+pub struct IndexedRegularProducts {
+    enumerator: RegularProductEnumerator,
+    len_b: u64,
+    rows: u64,
+    next_index: u64,
+}
+
+impl IndexedRegularProducts {
+    pub fn new(params: RegularProductEnumParams) -> Result<Self, GeneratorError> {
+        let len_a = params.factors_a.len() as u64;
+        let len_b = params.factors_b.len() as u64;
+        let mut rows = len_a * len_b;
+        if let Some(max) = params.max_pairs {
+            rows = rows.min(max as u64);
+        }
+        Ok(Self {
+            enumerator: RegularProductEnumerator::new(params)?,
+            len_b,
+            rows,
+            next_index: 0,
+        })
+    }
+}
+
+impl PolytopeGenerator4 for IndexedRegularProducts {
+    type Replay = RegularProductReplay;
+
+    fn generate_next(
+        &mut self,
+    ) -> Result<Option<PolytopeSample4<RegularProductReplay>>, GeneratorError> {
+        let sample = self.sample_at(self.next_index)?;
+        if sample.is_some() {
+            self.next_index += 1;
+        }
+        Ok(sample)
+    }
+
+    fn regenerate(&self, replay: &RegularProductReplay) -> Result<Poly4, GeneratorError> {
+        self.enumerator.build_poly(replay)
+    }
+}
+
+impl Checkpoint for IndexedRegularProducts {
+    /// The next pair index.
+    type State = u64;
+
+    fn save_state(&self) -> u64 {
+        self.next_index
+    }
+
+    fn restore_state(&mut self, next_index: u64) -> Result<(), GeneratorError> {
+        if next_index > self.rows {
+            return Err(GeneratorError::InvalidParams(format!(
+                "checkpoint: pair index {next_index} beyond {} rows",
+                self.rows
+            )));
+        }
+        self.next_index = next_index;
+        Ok(())
+    }
+}
+
+impl IndexedGenerator for IndexedRegularProducts {
+    fn row_count(&self) -> Option<u64> {
+        Some(self.rows)
+    }
+
+    fn replay_at(&self, index: u64) -> Option<RegularProductReplay> {
+        (index < self.rows).then(|| RegularProductReplay {
+            index_a: (index / self.len_b) as usize,
+            index_b: (index % self.len_b) as usize,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rand4::{ZonotopeGenerator, ZonotopeParams};
+
+    /// Yields `len` rows, failing at row `fail_at` without advancing.
+    struct Scripted {
+        next: u32,
+        len: u32,
+        fail_at: Option<u32>,
+    }
+
+    impl PolytopeGenerator4 for Scripted {
+        type Replay = u32;
+
+        fn generate_next(&mut self) -> Result<Option<PolytopeSample4<u32>>, GeneratorError> {
+            if Some(self.next) == self.fail_at {
+                return Err(GeneratorError::Degenerate("scripted".into()));
+            }
+            if self.next == self.len {
+                return Ok(None);
+            }
+            self.next += 1;
+            Ok(Some(PolytopeSample4 {
+                polytope: Poly4::default(),
+                replay: self.next - 1,
+            }))
+        }
+
+        fn regenerate(&self, _replay: &u32) -> Result<Poly4, GeneratorError> {
+            Ok(Poly4::default())
+        }
+    }
+
+    #[test]
+    fn iterator_keeps_replay_and_ends() {
+        let gen = Scripted {
+            next: 0,
+            len: 4,
+            fail_at: None,
+        };
+        let replays: Vec<u32> = gen.into_samples().map(|s| s.unwrap().replay).collect();
+        assert_eq!(replays, vec![0, 1, 2, 3]);
+    }
+
+    #[test]
+    fn iterator_fuses_after_error() {
+        let mut gen = Scripted {
+            next: 0,
+            len: 10,
+            fail_at: Some(2),
+        };
+        let items: Vec<_> = gen.samples().collect();
+        assert_eq!(items.len(), 3);
+        assert!(items[2].is_err());
+        assert_eq!(gen.next, 2);
+    }
+
+    #[test]
+    fn sample_at_matches_stream() {
+        let params = ZonotopeParams {
+            segments_min: 4,
+            segments_max: 5,
+            ..ZonotopeParams::default()
+        };
+        let gen = ZonotopeGenerator::new(params, 3).unwrap();
+        let direct = gen.sample_at(2).unwrap().unwrap();
+        let streamed = gen.into_samples().nth(2).unwrap().unwrap();
+        assert_eq!(direct.replay, streamed.replay);
+        assert_eq!(direct.polytope.h, streamed.polytope.h);
+    }
+}
@@ -0,0 +1,403 @@
+//! Choice of centre for the Mahler product family `K × (K − s)°`.
+//!
+//! Why this file exists
+//! - `MahlerProductGenerator` always recentres `K` at its area centroid
+//!   before taking the polar. The polar factor, and with it the capacity
+//!   statistics of `K × K°`, depend measurably on that choice, so studies
+//!   comparing centres need it as an explicit, recorded parameter.
+//! - `CenteredMahlerGenerator` draws exactly the sample of the base family
+//!   for the same `ReplayToken`, reads `K` back from the `q`-factor, moves the
+//!   origin to the requested centre `s`, and rebuilds `(K − s) × (K − s)°`.
+//!   `CentroidKind::AreaCentroid` (the default) returns the base sample
+//!   unchanged, so existing configs and atlas rows keep their meaning.
+//! - No rescaling after the shift: `λK × (λK)° = diag(λ, λ, 1/λ, 1/λ)(K × K°)`
+//!   is a symplectic image, so capacities and systolic ratios are unaffected.
+//!
+//! Centres (all for a convex polygon with the vertices in ccw order)
+//! - Area centroid: the usual shoelace formula.
+//! - Chebyshev centre: centre of a largest inscribed disk (LP over triples of
+//!   active edges; ties, e.g. for rectangles, are averaged).
+//! - Santaló point: minimizer of `area((K − s)°) = ½ Σ sin θᵢ / (hᵢ hᵢ₊₁)`
+//!   with `hᵢ = cᵢ − nᵢ·s` and `θᵢ` the turning angle between consecutive unit
+//!   edge normals. The function is strictly convex, so damped Newton with the
+//!   closed-form gradient and Hessian converges in a handful of steps.
+//!
+//! References
+//! - TH: docs/src/thesis/random-polytopes.md (“Mahler Product Sampler”)
+//! - Code: crates/viterbo/src/geom4/polar.rs (4D centroid and Santaló point)
+
+use nalgebra::{Matrix2, Matrix3, Vector2, Vector3, Vector4};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use super::{
+    GeneratorError, MahlerProductGenerator, MahlerProductParams, PolytopeGenerator4,
+    PolytopeSample4,
+};
+use crate::geom2::rand::ReplayToken;
+use crate::geom4::{Hs4, Poly4};
+
+/// Relative tolerance for feasibility and for treating normals as planar.
+const EPS_REL: f64 = 1e-9;
+/// Newton iterations for the Santaló point.
+const SANTALO_MAX_ITERS: usize = 100;
+
+/// Which interior point of `K` becomes the origin before taking the polar.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum CentroidKind {
+    /// Area centroid (the behaviour of `MahlerProductGenerator`).
+    #[default]
+    AreaCentroid,
+    /// Centre of a largest inscribed disk.
+    Chebyshev,
+    /// Minimizer of the polar area.
+    Santalo,
+}
+
+/// Parameters for [`CenteredMahlerGenerator`].
+#[derive(Clone, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct CenteredMahlerParams {
+    pub mahler: MahlerProductParams,
+    pub centering: CentroidKind,
+}
+
+/// Mahler products `(K − s) × (K − s)°` with a configurable centre `s`.
+pub struct CenteredMahlerGenerator {
+    params: CenteredMahlerParams,
+    seed: u64,
+    next_index: u64,
+}
+
+impl CenteredMahlerGenerator {
+    pub fn new(params: CenteredMahlerParams, seed: u64) -> Result<Self, GeneratorError> {
+        // Validates the base params.
+        MahlerProductGenerator::new(params.mahler.clone(), seed)?;
+        Ok(Self {
+            params,
+            seed,
+            next_index: 0,
+        })
+    }
+
+    pub fn params(&self) -> &CenteredMahlerParams {
+        &self.params
+    }
+
+    pub fn sample_with_token(
+        params: &CenteredMahlerParams,
+        token: ReplayToken,
+    ) -> Result<Poly4, GeneratorError> {
+        let base = MahlerProductGenerator::sample_with_token(&params.mahler, token)?;
+        if params.centering == CentroidKind::AreaCentroid {
+            return Ok(base);
+        }
+        let k = q_factor_vertices(&base)?;
+        let s = centre2(&k, params.centering).ok_or_else(|| {
+            GeneratorError::Degenerate(format!(
+                "mahler: {:?} centre undefined for the drawn polygon",
+                params.centering
+            ))
+        })?;
+        mahler_product(&k, &s)
+    }
+}
+
+impl PolytopeGenerator4 for CenteredMahlerGenerator {
+    type Replay = ReplayToken;
+
+    fn generate_next(&mut self) -> Result<Option<PolytopeSample4<ReplayToken>>, GeneratorError> {
+        let token = ReplayToken {
+            seed: self.seed,
+            index: self.next_index,
+        };
+        let polytope = Self::sample_with_token(&self.params, token)?;
+        self.next_index += 1;
+        Ok(Some(PolytopeSample4 {
+            polytope,
+            replay: token,
+        }))
+    }
+
+    fn regenerate(&self, replay: &ReplayToken) -> Result<Poly4, GeneratorError> {
+        Self::sample_with_token(&self.params, *replay)
+    }
+}
+
+/// The requested centre of the ccw polygon `k`.
+pub fn centre2(k: &[Vector2<f64>], kind: CentroidKind) -> Option<Vector2<f64>> {
+    match kind {
+        CentroidKind::AreaCentroid => area_centroid2(k),
+        CentroidKind::Chebyshev => chebyshev_center2(k),
+        CentroidKind::Santalo => santalo_point2(k),
+    }
+}
+
+/// Area centroid of the ccw polygon `k`.
+pub fn area_centroid2(k: &[Vector2<f64>]) -> Option<Vector2<f64>> {
+    let mut area2 = 0.0;
+    let mut acc = Vector2::zeros();
+    for (a, b) in cyclic_pairs(k) {
+        let w = cross(a, b);
+        area2 += w;
+        acc += (a + b) * w;
+    }
+    (area2 > 0.0).then(|| acc / (3.0 * area2))
+}
+
+/// Centre of a largest disk inside the ccw polygon `k`.
+pub fn chebyshev_center2(k: &[Vector2<f64>]) -> Option<Vector2<f64>> {
+    let edges = edges2(k)?;
+    let tol = EPS_REL * diameter(k);
+    let mut best_r = 0.0;
+    let mut centres = Vec::new();
+    for i in 0..edges.len() {
+        for j in i + 1..edges.len() {
+            for l in j + 1..edges.len() {
+                let (a, b, c) = (edges[i], edges[j], edges[l]);
+                let m = Matrix3::new(
+                    a.0.x, a.0.y, 1.0, //
+                    b.0.x, b.0.y, 1.0, //
+                    c.0.x, c.0.y, 1.0,
+                );
+                let Some(sol) = m.try_inverse().map(|inv| inv * Vector3::new(a.1, b.1, c.1)) else {
+                    continue;
+                };
+                let (x, r) = (Vector2::new(sol.x, sol.y), sol.z);
+                if r <= 0.0 || edges.iter().any(|(n, c)| n.dot(&x) + r > c + tol) {
+                    continue;
+                }
+                if r > best_r + tol {
+                    best_r = r;
+                    centres.clear();
+                }
+                if r >= best_r - tol {
+                    centres.push(x);
+                }
+            }
+        }
+    }
+    if centres.is_empty() {
+        return None;
+    }
+    Some(centres.iter().sum::<Vector2<f64>>() / centres.len() as f64)
+}
+
+/// Santaló point of the ccw polygon `k`: the interior `s` minimizing `area((K − s)°)`.
+pub fn santalo_point2(k: &[Vector2<f64>]) -> Option<Vector2<f64>> {
+    let edges = edges2(k)?;
+    let mut s = area_centroid2(k)?;
+    let (mut f, mut g, mut h) = polar_area2(&edges, &s)?;
+    for _ in 0..SANTALO_MAX_ITERS {
+        let dir = -h.try_inverse()? * g;
+        // Squared Newton decrement, relative to the objective.
+        if -g.dot(&dir) <= EPS_REL * EPS_REL * f {
+            break;
+        }
+        let mut t = 1.0;
+        loop {
+            let trial = s + dir * t;
+            match polar_area2(&edges, &trial) {
+                Some(next) if next.0 <= f + 1e-4 * t * g.dot(&dir) => {
+                    s = trial;
+                    (f, g, h) = next;
+                    break;
+                }
+                _ => {
+                    t *= 0.5;
+                    if t <= EPS_REL {
+                        return Some(s);
+                    }
+                }
+            }
+        }
+    }
+    Some(s)
+}
+
+/// `area((K − s)°)` with gradient and Hessian in `s`; `None` unless `s` is interior.
+fn polar_area2(
+    edges: &[(Vector2<f64>, f64)],
+    s: &Vector2<f64>,
+) -> Option<(f64, Vector2<f64>, Matrix2<f64>)> {
+    let h: Vec<f64> = edges.iter().map(|(n, c)| c - n.dot(s)).collect();
+    if h.iter().any(|&hi| hi <= 0.0) {
+        return None;
+    }
+    let mut f = 0.0;
+    let mut g = Vector2::zeros();
+    let mut hess = Matrix2::zeros();
+    for i in 0..edges.len() {
+        let j = (i + 1) % edges.len();
+        let (ni, nj) = (edges[i].0, edges[j].0);
+        let w = 0.5 * cross(&ni, &nj) / (h[i] * h[j]);
+        let (pi, pj) = (ni / h[i], nj / h[j]);
+        let a = pi + pj;
+        f += w;
+        g += a * w;
+        hess += (a * a.transpose() + pi * pi.transpose() + pj * pj.transpose()) * w;
+    }
+    Some((f, g, hess))
+}
+
+/// `(K − s) × (K − s)°` for the ccw polygon `k`, with `K` in the `q`-plane.
+pub fn mahler_product(k: &[Vector2<f64>], s: &Vector2<f64>) -> Result<Poly4, GeneratorError> {
+    let edges = edges2(k)
+        .ok_or_else(|| GeneratorError::Degenerate("mahler: polygon has no interior".into()))?;
+    let mut h: Vec<Hs4> = edges
+        .iter()
+        .map(|(n, c)| Hs4::new(Vector4::new(n.x, n.y, 0.0, 0.0), c - n.dot(s)))
+        .collect();
+    h.extend(k.iter().map(|v| {
+        let w = v - s;
+        Hs4::new(Vector4::new(0.0, 0.0, w.x, w.y), 1.0)
+    }));
+    if h.iter().any(|hs| hs.c <= 0.0) {
+        return Err(GeneratorError::Degenerate(
+            "mahler: centre is not interior to the polygon".into(),
+        ));
+    }
+    let mut poly = Poly4::from_h(h);
+    poly.ensure_vertices_from_h();
+    poly.check_canonical()
+        .map_err(|e| GeneratorError::Degenerate(format!("mahler: product invalid: {e}")))?;
+    Ok(poly)
+}
+
+/// Vertices (ccw) of the `q`-factor of a Lagrangian product `K × T`.
+fn q_factor_vertices(poly: &Poly4) -> Result<Vec<Vector2<f64>>, GeneratorError> {
+    let layout_err = || GeneratorError::Degenerate("mahler: sample is not a q × p product".into());
+    let mut hs = Vec::new();
+    for h in &poly.h {
+        let (q, p) = (h.n.fixed_rows::<2>(0), h.n.fixed_rows::<2>(2));
+        if p.norm() <= EPS_REL * h.n.norm() {
+            hs.push((q.into_owned(), h.c));
+        } else if q.norm() > EPS_REL * h.n.norm() {
+            return Err(layout_err());
+        }
+    }
+    polygon_from_halfspaces(&hs).ok_or_else(layout_err)
+}
+
+/// Vertices (ccw) of `{x : n·x ≤ c}`; `None` if it is not a bounded polygon.
+fn polygon_from_halfspaces(hs: &[(Vector2<f64>, f64)]) -> Option<Vec<Vector2<f64>>> {
+    let scale = hs
+        .iter()
+        .map(|(n, c)| c.abs() / n.norm())
+        .fold(0.0, f64::max);
+    let tol = EPS_REL * scale.max(1.0);
+    let mut verts: Vec<Vector2<f64>> = Vec::new();
+    for i in 0..hs.len() {
+        for j in i + 1..hs.len() {
+            let m = Matrix2::from_rows(&[hs[i].0.transpose(), hs[j].0.transpose()]);
+            let Some(x) = m
+                .try_inverse()
+                .map(|inv| inv * Vector2::new(hs[i].1, hs[j].1))
+            else {
+                continue;
+            };
+            let feasible = hs.iter().all(|(n, c)| n.dot(&x) <= c + tol * n.norm());
+            if feasible && verts.iter().all(|v| (v - x).norm() > tol) {
+                verts.push(x);
+            }
+        }
+    }
+    if verts.len() < 3 {
+        return None;
+    }
+    let mid = verts.iter().sum::<Vector2<f64>>() / verts.len() as f64;
+    verts.sort_by(|a, b| {
+        let (ta, tb) = (
+            (a - mid).y.atan2((a - mid).x),
+            (b - mid).y.atan2((b - mid).x),
+        );
+        ta.total_cmp(&tb)
+    });
+    (area_centroid2(&verts).is_some()).then_some(verts)
+}
+
+/// Unit outward normals and offsets of the edges of the ccw polygon `k`.
+fn edges2(k: &[Vector2<f64>]) -> Option<Vec<(Vector2<f64>, f64)>> {
+    if k.len() < 3 {
+        return None;
+    }
+    cyclic_pairs(k)
+        .map(|(a, b)| {
+            let d = b - a;
+            let len = d.norm();
+            (len > 0.0).then(|| {
+                let n = Vector2::new(d.y, -d.x) / len;
+                (n, n.dot(a))
+            })
+        })
+        .collect()
+}
+
+fn cyclic_pairs(k: &[Vector2<f64>]) -> impl Iterator<Item = (&Vector2<f64>, &Vector2<f64>)> {
+    k.iter().zip(k.iter().cycle().skip(1))
+}
+
+fn cross(a: &Vector2<f64>, b: &Vector2<f64>) -> f64 {
+    a.x * b.y - a.y * b.x
+}
+
+fn diameter(k: &[Vector2<f64>]) -> f64 {
+    k.iter()
+        .flat_map(|a| k.iter().map(move |b| (a - b).norm()))
+        .fold(0.0, f64::max)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn v(x: f64, y: f64) -> Vector2<f64> {
+        Vector2::new(x, y)
+    }
+
+    #[test]
+    fn centres_of_a_right_triangle() {
+        let k = [v(0.0, 0.0), v(1.0, 0.0), v(0.0, 1.0)];
+        let third = v(1.0, 1.0) / 3.0;
+        assert!((area_centroid2(&k).unwrap() - third).norm() < 1e-12);
+        // Triangles are affine images of each other: Santaló point = centroid.
+        assert!((santalo_point2(&k).unwrap() - third).norm() < 1e-9);
+        let r = 1.0 - std::f64::consts::FRAC_1_SQRT_2;
+        assert!((chebyshev_center2(&k).unwrap() - v(r, r)).norm() < 1e-12);
+        let rect = [v(0.0, 0.0), v(4.0, 0.0), v(4.0, 1.0), v(0.0, 1.0)];
+        assert!((chebyshev_center2(&rect).unwrap() - v(2.0, 0.5)).norm() < 1e-12);
+    }
+
+    #[test]
+    fn centres_differ_on_an_asymmetric_quadrilateral() {
+        let k = [v(-1.0, -1.0), v(3.0, -1.0), v(0.0, 1.0), v(-1.0, 1.0)];
+        let kinds = [
+            CentroidKind::AreaCentroid,
+            CentroidKind::Chebyshev,
+            CentroidKind::Santalo,
+        ];
+        let centres: Vec<_> = kinds.iter().map(|&c| centre2(&k, c).unwrap()).collect();
+        assert!((centres[0] - centres[2]).norm() > 1e-3);
+        assert!((centres[1] - centres[2]).norm() > 1e-3);
+        // The Santaló point is where the polar's centroid sits at the origin.
+        let s = centres[2];
+        let polar: Vec<_> = edges2(&k)
+            .unwrap()
+            .iter()
+            .map(|(n, c)| n / (c - n.dot(&s)))
+            .collect();
+        assert!(area_centroid2(&polar).unwrap().norm() < 1e-9);
+        let q = q_factor_vertices(&Poly4::from_h(
+            edges2(&k)
+                .unwrap()
+                .iter()
+                .map(|(n, c)| Hs4::new(Vector4::new(n.x, n.y, 0.0, 0.0), *c))
+                .chain([Hs4::new(Vector4::new(0.0, 0.0, 1.0, 0.0), 1.0)])
+                .collect(),
+        ))
+        .unwrap();
+        assert_eq!(q.len(), 4);
+    }
+}
@@ -0,0 +1,257 @@
+//! Weighted mixture of several generator families under one master seed.
+//!
+//! Why this file exists
+//! - Balanced multi-family atlases used to be stitched together from
+//!   separately seeded runs, so no single seed reproduced the dataset.
+//!   `MixtureGenerator` owns the children and draws the family of each row
+//!   from the master seed, so `(config, seed)` fixes the whole stream.
+//! - Row `i` picks child `k` with probability `weight_k / Σ weights` using
+//!   `token_rng(ReplayToken { seed: seed ^ MIXTURE_SALT, index: i })`; the
+//!   child then emits its own next sample. Children are erased streams
+//!   (`Box<dyn ErasedGenerator>`), so any registry family can take part.
+//!   Their seeds are `derive_seed(seed, k)` when built through the registry.
+//! - Finite children (enumerations) drop out when exhausted; the draw is
+//!   repeated among the remaining children with the same row RNG. The
+//!   mixture ends once every child is exhausted.
+//!
+//! Replay
+//! - `MixtureReplay { family, replay }`: the child index and the child's own
+//!   replay value, which regenerates the row without touching other children.
+//!
+//! References
+//! - TH: docs/src/thesis/random-polytopes.md (“Mixtures”)
+
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use super::seeding::token_rng;
+use super::{ErasedGenerator, GeneratorError, PolytopeGenerator4, PolytopeSample4};
+use crate::geom2::rand::ReplayToken;
+use crate::geom4::Poly4;
+
+/// Decorrelates the family draws from child streams sharing the master seed.
+const MIXTURE_SALT: u64 = 0x6D49_7874_7572_6531;
+
+/// One weighted child of a [`MixtureGenerator`].
+pub struct MixtureComponent {
+    pub weight: f64,
+    pub generator: Box<dyn ErasedGenerator>,
+}
+
+/// Replay value of a mixture row.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct MixtureReplay {
+    /// Index of the child in the mixture.
+    pub family: usize,
+    /// The child's own replay value.
+    pub replay: Value,
+}
+
+/// Rows drawn from weighted child generators.
+pub struct MixtureGenerator {
+    components: Vec<MixtureComponent>,
+    exhausted: Vec<bool>,
+    /// Rows emitted per child.
+    counts: Vec<u64>,
+    seed: u64,
+    next_index: u64,
+}
+
+impl MixtureGenerator {
+    pub fn new(components: Vec<MixtureComponent>, seed: u64) -> Result<Self, GeneratorError> {
+        if components.is_empty() {
+            return Err(GeneratorError::InvalidParams(
+                "mixture: need at least one component".into(),
+            ));
+        }
+        let weights_ok = components
+            .iter()
+            .all(|c| c.weight.is_finite() && c.weight >= 0.0)
+            && components.iter().any(|c| c.weight > 0.0);
+        if !weights_ok {
+            return Err(GeneratorError::InvalidParams(
+                "mixture: weights must be finite, non-negative and not all zero".into(),
+            ));
+        }
+        let n = components.len();
+        Ok(Self {
+            // Zero-weight children can never be drawn.
+            exhausted: components.iter().map(|c| c.weight == 0.0).collect(),
+            components,
+            counts: vec![0; n],
+            seed,
+            next_index: 0,
+        })
+    }
+
+    pub fn len(&self) -> usize {
+        self.components.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.components.is_empty()
+    }
+
+    /// Rows emitted so far, per child.
+    pub fn counts(&self) -> &[u64] {
+        &self.counts
+    }
+
+    pub fn component(&self, family: usize) -> Option<&MixtureComponent> {
+        self.components.get(family)
+    }
+
+    /// Weighted draw among the children that are not exhausted.
+    fn draw<R: Rng>(&self, rng: &mut R) -> Option<usize> {
+        let total: f64 = self
+            .components
+            .iter()
+            .zip(&self.exhausted)
+            .filter(|(_, &done)| !done)
+            .map(|(c, _)| c.weight)
+            .sum();
+        if total <= 0.0 {
+            return None;
+        }
+        let mut u = rng.gen::<f64>() * total;
+        let active = || {
+            self.components
+                .iter()
+                .enumerate()
+                .filter(|(k, c)| !self.exhausted[*k] && c.weight > 0.0)
+        };
+        for (k, c) in active() {
+            if u < c.weight {
+                return Some(k);
+            }
+            u -= c.weight;
+        }
+        // Rounding left `u` just above the last weight.
+        active().next_back().map(|(k, _)| k)
+    }
+}
+
+impl PolytopeGenerator4 for MixtureGenerator {
+    type Replay = MixtureReplay;
+
+    fn generate_next(&mut self) -> Result<Option<PolytopeSample4<MixtureReplay>>, GeneratorError> {
+        let mut rng = token_rng(ReplayToken {
+            seed: self.seed ^ MIXTURE_SALT,
+            index: self.next_index,
+        });
+        while let Some(family) = self.draw(&mut rng) {
+            match self.components[family].generator.next_sample()? {
+                Some(sample) => {
+                    self.next_index += 1;
+                    self.counts[family] += 1;
+                    return Ok(Some(PolytopeSample4 {
+                        polytope: sample.polytope,
+                        replay: MixtureReplay {
+                            family,
+                            replay: sample.replay,
+                        },
+                    }));
+                }
+                None => self.exhausted[family] = true,
+            }
+        }
+        Ok(None)
+    }
+
+    fn regenerate(&self, replay: &MixtureReplay) -> Result<Poly4, GeneratorError> {
+        let child = self.components.get(replay.family).ok_or_else(|| {
+            GeneratorError::InvalidParams(format!(
+                "mixture: family {} out of range ({} components)",
+                replay.family,
+                self.components.len()
+            ))
+        })?;
+        child.generator.regenerate_json(&replay.replay)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rand4::{ErasedSample, GeneratorKind};
+    use serde_json::json;
+
+    /// Emits `len` copies of an empty body with replay `{"tag", "index"}`.
+    struct Tagged {
+        tag: u64,
+        len: u64,
+        next: u64,
+    }
+
+    impl ErasedGenerator for Tagged {
+        fn kind(&self) -> GeneratorKind {
+            GeneratorKind::RegularProducts
+        }
+
+        fn next_sample(&mut self) -> Result<Option<ErasedSample>, GeneratorError> {
+            if self.next == self.len {
+                return Ok(None);
+            }
+            self.next += 1;
+            Ok(Some(ErasedSample {
+                polytope: Poly4::from_h(Vec::new()),
+                replay: json!({ "tag": self.tag, "index": self.next - 1 }),
+            }))
+        }
+
+        fn regenerate_json(&self, _replay: &Value) -> Result<Poly4, GeneratorError> {
+            Ok(Poly4::from_h(Vec::new()))
+        }
+    }
+
+    fn mixture(weights: &[f64], len: u64, seed: u64) -> MixtureGenerator {
+        let components = weights
+            .iter()
+            .enumerate()
+            .map(|(k, &weight)| MixtureComponent {
+                weight,
+                generator: Box::new(Tagged {
+                    tag: k as u64,
+                    len,
+                    next: 0,
+                }),
+            })
+            .collect();
+        MixtureGenerator::new(components, seed).unwrap()
+    }
+
+    fn families(gen: &mut MixtureGenerator, rows: usize) -> Vec<usize> {
+        (0..rows)
+            .map_while(|_| gen.generate_next().unwrap())
+            .map(|s| s.replay.family)
+            .collect()
+    }
+
+    #[test]
+    fn draws_follow_weights_and_the_seed() {
+        let mut gen = mixture(&[1.0, 3.0, 0.0], u64::MAX, 7);
+        let rows = families(&mut gen, 4000);
+        let share = rows.iter().filter(|&&f| f == 1).count() as f64 / 4000.0;
+        assert!((share - 0.75).abs() < 0.03, "{share}");
+        assert_eq!(gen.counts()[2], 0);
+        assert_eq!(
+            families(&mut mixture(&[1.0, 3.0, 0.0], u64::MAX, 7), 4000),
+            rows
+        );
+        assert_ne!(
+            families(&mut mixture(&[1.0, 3.0, 0.0], u64::MAX, 8), 4000),
+            rows
+        );
+    }
+
+    #[test]
+    fn exhausted_children_drop_out() {
+        let mut gen = mixture(&[1.0, 1.0], 3, 0);
+        let rows = families(&mut gen, 10);
+        assert_eq!(rows.len(), 6);
+        assert_eq!(gen.counts(), &[3, 3]);
+        assert!(gen.generate_next().unwrap().is_none());
+        assert!(MixtureGenerator::new(Vec::new(), 0).is_err());
+    }
+}
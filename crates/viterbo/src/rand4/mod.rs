@@ -0,0 +1,1002 @@
+//! Random and enumerative 4D polytope generators.
+//!
+//! Why this file exists
+//! - Every family emits `Poly4` rows through one trait, `PolytopeGenerator4`:
+//!   `generate_next` streams rows and `regenerate` rebuilds any row from its
+//!   replay value alone. Atlas stages, the registry and the Python bindings
+//!   only ever talk to that trait.
+//! - The five original families live here; later families, adapters
+//!   (filters, anisotropy, mixtures, exact facet counts) and infrastructure
+//!   (seeding, checkpoints, batches, the registry) each have their own
+//!   submodule and are re-exported, so callers import from `rand4` only.
+//! - Directions `u` come from `dirs::sample_unit_vector` with the params'
+//!   `sampler`: uniform on S³ by default, `LegacyCube` to replay rows drawn
+//!   before that default.
+//! - Errors split into `InvalidParams` (the config can never work) and
+//!   `Degenerate` (this row ran out of retries). Callers retry or skip rows on
+//!   the latter and fail fast on the former.
+//!
+//! Families defined here
+//! - `RandomVerticesGenerator`: hull of `k` points `r·u` (V→H).
+//! - `RandomFacesGenerator`: `m` halfspaces `u·x ≤ r`, reduced to facets (H→V→H).
+//! - `SymmetricHalfspaceGenerator`: paired halfspaces `±u·x ≤ r`, seed-addressed.
+//! - `MahlerProductGenerator`: `K × K°` for a random polygon `K`.
+//! - `RegularProductEnumerator`: lagrangian products of regular polygons.
+//!
+//! References
+//! - TH: docs/src/thesis/random-polytopes.md
+//! - Code: crates/viterbo/src/geom2/rand.rs (2D steps of the Mahler sampler)
+
+pub mod anisotropy;
+pub mod batch;
+pub mod checkpoint;
+pub mod dedup;
+pub mod dirs;
+pub mod exact_facets;
+pub mod filter;
+pub mod gaussian_vertices;
+pub mod group_symmetric;
+pub mod iter;
+pub mod mahler_centering;
+#[cfg(feature = "serde")]
+pub mod mixture;
+pub mod polar_dual;
+#[cfg(feature = "serde")]
+pub mod registry;
+pub mod regular_sweep;
+pub(crate) mod seeding;
+#[cfg(feature = "serde")]
+mod serde_matrix;
+pub mod simple;
+pub mod simplicial;
+pub mod sobol;
+pub mod stats;
+pub mod stream_stats;
+pub mod sweep;
+pub mod symplectic;
+pub mod systolic_mcmc;
+pub mod zonotope;
+
+pub use anisotropy::*;
+pub use batch::*;
+pub use checkpoint::*;
+pub use dedup::*;
+pub use dirs::*;
+pub use exact_facets::*;
+pub use filter::*;
+pub use gaussian_vertices::*;
+pub use group_symmetric::*;
+pub use iter::*;
+pub use mahler_centering::*;
+#[cfg(feature = "serde")]
+pub use mixture::*;
+pub use polar_dual::*;
+#[cfg(feature = "serde")]
+pub use registry::*;
+pub use regular_sweep::*;
+pub use seeding::derive_seed;
+pub use simple::*;
+pub use simplicial::*;
+pub use sobol::*;
+pub use stats::*;
+pub use stream_stats::*;
+pub use sweep::*;
+pub use symplectic::*;
+pub use systolic_mcmc::*;
+pub use zonotope::*;
+
+use std::f64::consts::PI;
+use std::fmt;
+
+use nalgebra::{Matrix4, Vector2, Vector4};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::geom2::rand::{
+    draw_polygon_radial, polar, recenter_rescale, Bounds2, RadialCfg, ReplayToken,
+};
+use crate::geom2::Poly2;
+use crate::geom4::{lagrangian_product, Hs4, Poly4};
+use seeding::token_rng;
+
+/// Redraws of a seed-addressed symmetric body before reporting `Degenerate`.
+const MAX_SYMMETRIC_DRAWS: usize = 100;
+
+/// Why a generator could not produce a row.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum GeneratorError {
+    /// The parameters can never yield a valid polytope.
+    InvalidParams(String),
+    /// This row exhausted its retries; other rows may still succeed.
+    Degenerate(String),
+}
+
+impl fmt::Display for GeneratorError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GeneratorError::InvalidParams(msg) => write!(f, "invalid generator params: {msg}"),
+            GeneratorError::Degenerate(msg) => write!(f, "degenerate sample: {msg}"),
+        }
+    }
+}
+
+impl std::error::Error for GeneratorError {}
+
+/// One generated row: the polytope and the value that rebuilds it.
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct PolytopeSample4<R> {
+    pub polytope: Poly4,
+    pub replay: R,
+}
+
+/// A stream of valid polytopes whose rows can be rebuilt from their replay values.
+pub trait PolytopeGenerator4 {
+    type Replay: Clone;
+
+    /// Next row; `Ok(None)` once a finite family is exhausted.
+    fn generate_next(&mut self) -> Result<Option<PolytopeSample4<Self::Replay>>, GeneratorError>;
+
+    /// Rebuild the row behind `replay`, independent of the stream position.
+    fn regenerate(&self, replay: &Self::Replay) -> Result<Poly4, GeneratorError>;
+}
+
+/// Parameters for [`RandomVerticesGenerator`].
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct RandomVerticesParams {
+    /// Inclusive range of the number of drawn points (at least 5).
+    pub vertices_min: usize,
+    pub vertices_max: usize,
+    /// Points lie at distance `r ∈ [radius_min, radius_max]` from the origin.
+    pub radius_min: f64,
+    pub radius_max: f64,
+    /// Optional linear map applied to every point.
+    #[cfg_attr(feature = "serde", serde(default, with = "serde_matrix::option"))]
+    pub anisotropy: Option<Matrix4<f64>>,
+    /// Distribution of the unit directions; `LegacyCube` replays rows drawn
+    /// before the Gaussian default.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub sampler: DirectionSampler,
+    /// Number of draws per row before giving up with `GeneratorError::Degenerate`.
+    pub max_attempts: usize,
+}
+
+impl RandomVerticesParams {
+    pub fn validate(&self) -> Result<(), GeneratorError> {
+        if self.vertices_min < 5 {
+            return Err(GeneratorError::InvalidParams(
+                "random_vertices: vertices_min must be >= 5".into(),
+            ));
+        }
+        if self.vertices_min > self.vertices_max {
+            return Err(GeneratorError::InvalidParams(format!(
+                "random_vertices: vertices_min ({}) > vertices_max ({})",
+                self.vertices_min, self.vertices_max
+            )));
+        }
+        validate_radii("random_vertices", self.radius_min, self.radius_max)?;
+        validate_anisotropy("random_vertices", self.anisotropy.as_ref())?;
+        validate_attempts("random_vertices", self.max_attempts)
+    }
+}
+
+/// Hulls of random point clouds around the origin.
+#[derive(Clone, Debug)]
+pub struct RandomVerticesGenerator {
+    params: RandomVerticesParams,
+    seed: u64,
+    next_index: u64,
+    stats: AcceptanceStats,
+}
+
+impl RandomVerticesGenerator {
+    pub fn new(params: RandomVerticesParams, seed: u64) -> Result<Self, GeneratorError> {
+        params.validate()?;
+        Ok(Self {
+            params,
+            seed,
+            next_index: 0,
+            stats: AcceptanceStats::default(),
+        })
+    }
+
+    pub fn params(&self) -> &RandomVerticesParams {
+        &self.params
+    }
+
+    /// Build the row addressed by `token` without constructing a generator.
+    pub fn sample_with_token(
+        params: &RandomVerticesParams,
+        token: ReplayToken,
+    ) -> Result<Poly4, GeneratorError> {
+        params.validate()?;
+        random_vertices_impl(params, token, &mut AcceptanceStats::default())
+    }
+}
+
+/// Parameters for [`RandomFacesGenerator`].
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct RandomFacesParams {
+    /// Inclusive range of the number of drawn halfspaces (at least 5).
+    pub facets_min: usize,
+    pub facets_max: usize,
+    /// Offsets `r ∈ [radius_min, radius_max]` of the halfspaces `u·x ≤ r`.
+    pub radius_min: f64,
+    pub radius_max: f64,
+    /// Optional linear map applied to the body.
+    #[cfg_attr(feature = "serde", serde(default, with = "serde_matrix::option"))]
+    pub anisotropy: Option<Matrix4<f64>>,
+    /// Distribution of the unit directions; `LegacyCube` replays rows drawn
+    /// before the Gaussian default.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub sampler: DirectionSampler,
+    /// Number of draws per row before giving up with `GeneratorError::Degenerate`.
+    pub max_attempts: usize,
+}
+
+impl RandomFacesParams {
+    pub fn validate(&self) -> Result<(), GeneratorError> {
+        if self.facets_min < 5 {
+            return Err(GeneratorError::InvalidParams(
+                "random_faces: facets_min must be >= 5 for a bounded body".into(),
+            ));
+        }
+        if self.facets_min > self.facets_max {
+            return Err(GeneratorError::InvalidParams(format!(
+                "random_faces: facets_min ({}) > facets_max ({})",
+                self.facets_min, self.facets_max
+            )));
+        }
+        validate_radii("random_faces", self.radius_min, self.radius_max)?;
+        validate_anisotropy("random_faces", self.anisotropy.as_ref())?;
+        validate_attempts("random_faces", self.max_attempts)
+    }
+}
+
+/// Intersections of random halfspaces, reduced to their facets.
+#[derive(Clone, Debug)]
+pub struct RandomFacesGenerator {
+    params: RandomFacesParams,
+    seed: u64,
+    next_index: u64,
+    stats: AcceptanceStats,
+}
+
+impl RandomFacesGenerator {
+    pub fn new(params: RandomFacesParams, seed: u64) -> Result<Self, GeneratorError> {
+        params.validate()?;
+        Ok(Self {
+            params,
+            seed,
+            next_index: 0,
+            stats: AcceptanceStats::default(),
+        })
+    }
+
+    pub fn params(&self) -> &RandomFacesParams {
+        &self.params
+    }
+
+    /// Build the row addressed by `token` without constructing a generator.
+    pub fn sample_with_token(
+        params: &RandomFacesParams,
+        token: ReplayToken,
+    ) -> Result<Poly4, GeneratorError> {
+        params.validate()?;
+        random_faces_impl(params, token, &mut AcceptanceStats::default())
+    }
+}
+
+/// Parameters for [`SymmetricHalfspaceGenerator`].
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct SymmetricHalfspaceParams {
+    /// Number of directions `d`; the body has `2d` halfspaces (at least 4).
+    pub directions: usize,
+    /// Offsets `r ∈ [radius_min, radius_max]` shared by each pair `±u·x ≤ r`.
+    pub radius_min: f64,
+    pub radius_max: f64,
+    /// Optional linear map applied to the body.
+    #[cfg_attr(feature = "serde", serde(default, with = "serde_matrix::option"))]
+    pub anisotropy: Option<Matrix4<f64>>,
+    /// Distribution of the unit directions; `LegacyCube` replays rows drawn
+    /// before the Gaussian default.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub sampler: DirectionSampler,
+}
+
+impl SymmetricHalfspaceParams {
+    pub fn validate(&self) -> Result<(), GeneratorError> {
+        if self.directions < 4 {
+            return Err(GeneratorError::InvalidParams(
+                "symmetric_halfspaces: need at least 4 directions to bound R^4".into(),
+            ));
+        }
+        validate_radii("symmetric_halfspaces", self.radius_min, self.radius_max)?;
+        validate_anisotropy("symmetric_halfspaces", self.anisotropy.as_ref())
+    }
+}
+
+/// Centrally symmetric bodies `{|u_k·x| ≤ r_k}`, addressed by a plain seed.
+#[derive(Clone, Debug)]
+pub struct SymmetricHalfspaceGenerator {
+    params: SymmetricHalfspaceParams,
+    seed: u64,
+    next_index: u64,
+}
+
+impl SymmetricHalfspaceGenerator {
+    /// Stream whose row `i` is `generate_single(params, seed + i)`.
+    pub fn new(params: SymmetricHalfspaceParams, seed: u64) -> Result<Self, GeneratorError> {
+        params.validate()?;
+        Ok(Self {
+            params,
+            seed,
+            next_index: 0,
+        })
+    }
+
+    pub fn params(&self) -> &SymmetricHalfspaceParams {
+        &self.params
+    }
+
+    /// The body seeded by `seed`; draws whose directions fail to span R⁴ are redrawn.
+    pub fn generate_single(
+        params: &SymmetricHalfspaceParams,
+        seed: u64,
+    ) -> Result<Poly4, GeneratorError> {
+        params.validate()?;
+        let mut rng = StdRng::seed_from_u64(seed);
+        for _ in 0..MAX_SYMMETRIC_DRAWS {
+            let mut h = Vec::with_capacity(2 * params.directions);
+            for _ in 0..params.directions {
+                let u = sample_unit_vector(&mut rng, params.sampler);
+                let r = rng.gen_range(params.radius_min..=params.radius_max);
+                h.push(Hs4::new(u, r));
+                h.push(Hs4::new(-u, r));
+            }
+            let mut poly = apply_anisotropy(Poly4::from_h(h), params.anisotropy.as_ref())?;
+            if poly.check_canonical().is_ok() {
+                return Ok(poly);
+            }
+        }
+        Err(GeneratorError::Degenerate(format!(
+            "symmetric_halfspaces: directions failed to span R^4 after {MAX_SYMMETRIC_DRAWS} \
+             draws (seed={seed})"
+        )))
+    }
+}
+
+/// Parameters for [`MahlerProductGenerator`].
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct MahlerProductParams {
+    /// Radial model of the polygon `K`.
+    pub radial_cfg: RadialCfg,
+    /// In-/out-radius window `K` is rescaled into.
+    pub bounds: Bounds2,
+    /// Polygons drawn per row before giving up with `GeneratorError::Degenerate`.
+    pub max_attempts: usize,
+}
+
+impl Default for MahlerProductParams {
+    fn default() -> Self {
+        Self {
+            radial_cfg: RadialCfg::default(),
+            bounds: Bounds2::default(),
+            max_attempts: 20,
+        }
+    }
+}
+
+impl MahlerProductParams {
+    pub fn validate(&self) -> Result<(), GeneratorError> {
+        if !self.radial_cfg.is_valid() {
+            return Err(GeneratorError::InvalidParams(
+                "mahler_products: radial_cfg needs >= 3 vertices, angle jitter in [0, 0.5), \
+                 radial jitter in [0, 1) and a positive base radius"
+                    .into(),
+            ));
+        }
+        let b = self.bounds;
+        if !(b.r_in_min > 0.0 && b.r_in_min <= b.r_out_max && b.r_out_max.is_finite()) {
+            return Err(GeneratorError::InvalidParams(
+                "mahler_products: need 0 < r_in_min <= r_out_max < inf".into(),
+            ));
+        }
+        validate_attempts("mahler_products", self.max_attempts)
+    }
+}
+
+/// Mahler products `K × K°` of random polygons centred at their area centroid.
+#[derive(Clone, Debug)]
+pub struct MahlerProductGenerator {
+    params: MahlerProductParams,
+    seed: u64,
+    next_index: u64,
+    stats: AcceptanceStats,
+}
+
+impl MahlerProductGenerator {
+    pub fn new(params: MahlerProductParams, seed: u64) -> Result<Self, GeneratorError> {
+        params.validate()?;
+        Ok(Self {
+            params,
+            seed,
+            next_index: 0,
+            stats: AcceptanceStats::default(),
+        })
+    }
+
+    pub fn params(&self) -> &MahlerProductParams {
+        &self.params
+    }
+
+    /// Build the row addressed by `token` without constructing a generator.
+    pub fn sample_with_token(
+        params: &MahlerProductParams,
+        token: ReplayToken,
+    ) -> Result<Poly4, GeneratorError> {
+        params.validate()?;
+        mahler_impl(params, token, &mut AcceptanceStats::default())
+    }
+}
+
+/// A regular `sides`-gon with circumradius `scale`, first vertex at angle `rotation`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct RegularPolygonSpec {
+    pub sides: u32,
+    pub rotation: f64,
+    pub scale: f64,
+}
+
+impl RegularPolygonSpec {
+    pub fn new(sides: u32, rotation: f64, scale: f64) -> Result<Self, GeneratorError> {
+        if sides < 3 {
+            return Err(GeneratorError::InvalidParams(format!(
+                "regular polygon needs >= 3 sides, got {sides}"
+            )));
+        }
+        if !(rotation.is_finite() && scale > 0.0 && scale.is_finite()) {
+            return Err(GeneratorError::InvalidParams(format!(
+                "regular polygon needs a finite rotation and 0 < scale < inf, got \
+                 rotation={rotation}, scale={scale}"
+            )));
+        }
+        Ok(Self {
+            sides,
+            rotation,
+            scale,
+        })
+    }
+
+    /// The polygon as a `Poly2`.
+    pub fn polygon(&self) -> Poly2 {
+        let step = 2.0 * PI / self.sides as f64;
+        let points: Vec<Vector2<f64>> = (0..self.sides)
+            .map(|k| {
+                let theta = self.rotation + k as f64 * step;
+                self.scale * Vector2::new(theta.cos(), theta.sin())
+            })
+            .collect();
+        Poly2::from_points_convex_hull(&points)
+    }
+}
+
+/// Parameters for [`RegularProductEnumerator`].
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct RegularProductEnumParams {
+    /// Factors in the `q`-plane.
+    pub factors_a: Vec<RegularPolygonSpec>,
+    /// Factors in the `p`-plane.
+    pub factors_b: Vec<RegularPolygonSpec>,
+    /// Stop after this many pairs (in `index_a`-major order).
+    pub max_pairs: Option<usize>,
+}
+
+/// Replay value of a regular product: indices into `factors_a` and `factors_b`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct RegularProductReplay {
+    pub index_a: usize,
+    pub index_b: usize,
+}
+
+/// Lagrangian products `A × B` of regular polygons, `index_a`-major.
+#[derive(Clone, Debug)]
+pub struct RegularProductEnumerator {
+    params: RegularProductEnumParams,
+    polys_a: Vec<Poly2>,
+    polys_b: Vec<Poly2>,
+    next_pair: usize,
+}
+
+impl RegularProductEnumerator {
+    pub fn new(params: RegularProductEnumParams) -> Result<Self, GeneratorError> {
+        if params.factors_a.is_empty() || params.factors_b.is_empty() {
+            return Err(GeneratorError::InvalidParams(
+                "regular_products: factors_a and factors_b must be non-empty".into(),
+            ));
+        }
+        let polys_a = params.factors_a.iter().map(|s| s.polygon()).collect();
+        let polys_b = params.factors_b.iter().map(|s| s.polygon()).collect();
+        Ok(Self {
+            params,
+            polys_a,
+            polys_b,
+            next_pair: 0,
+        })
+    }
+
+    pub fn params(&self) -> &RegularProductEnumParams {
+        &self.params
+    }
+
+    /// Number of rows the enumeration yields.
+    pub fn len(&self) -> usize {
+        let total = self.polys_a.len() * self.polys_b.len();
+        self.params.max_pairs.map_or(total, |max| total.min(max))
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Replay value of row `index`; `None` past the end of the enumeration.
+    pub fn pair(&self, index: usize) -> Option<RegularProductReplay> {
+        let len_b = self.polys_b.len();
+        (index < self.len()).then(|| RegularProductReplay {
+            index_a: index / len_b,
+            index_b: index % len_b,
+        })
+    }
+
+    /// The product addressed by `replay`.
+    pub fn build_poly(&self, replay: &RegularProductReplay) -> Result<Poly4, GeneratorError> {
+        let (Some(a), Some(b)) = (
+            self.polys_a.get(replay.index_a),
+            self.polys_b.get(replay.index_b),
+        ) else {
+            return Err(GeneratorError::InvalidParams(format!(
+                "regular_products: pair ({}, {}) out of range ({} x {})",
+                replay.index_a,
+                replay.index_b,
+                self.polys_a.len(),
+                self.polys_b.len()
+            )));
+        };
+        let mut poly = lagrangian_product(a, b).ok_or_else(|| {
+            GeneratorError::Degenerate("regular_products: flat polygon factor".into())
+        })?;
+        poly.check_canonical()
+            .map_err(|err| GeneratorError::Degenerate(format!("regular_products: {err}")))?;
+        Ok(poly)
+    }
+}
+
+impl PolytopeGenerator4 for RegularProductEnumerator {
+    type Replay = RegularProductReplay;
+
+    fn generate_next(
+        &mut self,
+    ) -> Result<Option<PolytopeSample4<RegularProductReplay>>, GeneratorError> {
+        let Some(replay) = self.pair(self.next_pair) else {
+            return Ok(None);
+        };
+        let polytope = self.build_poly(&replay)?;
+        self.next_pair += 1;
+        Ok(Some(PolytopeSample4 { polytope, replay }))
+    }
+
+    fn regenerate(&self, replay: &RegularProductReplay) -> Result<Poly4, GeneratorError> {
+        self.build_poly(replay)
+    }
+}
+
+impl PolytopeGenerator4 for SymmetricHalfspaceGenerator {
+    /// The seed passed to `generate_single`.
+    type Replay = u64;
+
+    fn generate_next(&mut self) -> Result<Option<PolytopeSample4<u64>>, GeneratorError> {
+        let seed = self.seed.wrapping_add(self.next_index);
+        let polytope = Self::generate_single(&self.params, seed)?;
+        self.next_index += 1;
+        Ok(Some(PolytopeSample4 {
+            polytope,
+            replay: seed,
+        }))
+    }
+
+    fn regenerate(&self, replay: &u64) -> Result<Poly4, GeneratorError> {
+        Self::generate_single(&self.params, *replay)
+    }
+}
+
+/// Stream, checkpoint and index plumbing shared by the token-addressed families.
+macro_rules! token_addressed {
+    ($gen:ty, $sample:ident) => {
+        impl PolytopeGenerator4 for $gen {
+            type Replay = ReplayToken;
+
+            fn generate_next(
+                &mut self,
+            ) -> Result<Option<PolytopeSample4<ReplayToken>>, GeneratorError> {
+                let token = ReplayToken {
+                    seed: self.seed,
+                    index: self.next_index,
+                };
+                let polytope = $sample(&self.params, token, &mut self.stats)?;
+                self.next_index += 1;
+                Ok(Some(PolytopeSample4 {
+                    polytope,
+                    replay: token,
+                }))
+            }
+
+            fn regenerate(&self, replay: &ReplayToken) -> Result<Poly4, GeneratorError> {
+                $sample(&self.params, *replay, &mut AcceptanceStats::default())
+            }
+        }
+
+        impl AcceptanceStatsSource for $gen {
+            fn acceptance_stats(&self) -> &AcceptanceStats {
+                &self.stats
+            }
+        }
+
+        impl Checkpoint for $gen {
+            type State = StreamState;
+
+            fn save_state(&self) -> StreamState {
+                StreamState {
+                    seed: self.seed,
+                    next_index: self.next_index,
+                    stats: self.stats.clone(),
+                }
+            }
+
+            fn restore_state(&mut self, state: StreamState) -> Result<(), GeneratorError> {
+                state.check_seed(self.seed)?;
+                self.next_index = state.next_index;
+                self.stats = state.stats;
+                Ok(())
+            }
+        }
+
+        impl IndexedGenerator for $gen {
+            fn row_count(&self) -> Option<u64> {
+                None
+            }
+
+            fn replay_at(&self, index: u64) -> Option<ReplayToken> {
+                Some(ReplayToken {
+                    seed: self.seed,
+                    index,
+                })
+            }
+        }
+    };
+}
+
+token_addressed!(RandomVerticesGenerator, random_vertices_impl);
+token_addressed!(RandomFacesGenerator, random_faces_impl);
+token_addressed!(MahlerProductGenerator, mahler_impl);
+
+fn random_vertices_impl(
+    params: &RandomVerticesParams,
+    token: ReplayToken,
+    stats: &mut AcceptanceStats,
+) -> Result<Poly4, GeneratorError> {
+    let mut rng = token_rng(token);
+    let map = params.anisotropy.unwrap_or_else(Matrix4::identity);
+    for _ in 0..params.max_attempts {
+        stats.record_attempt();
+        let count = rng.gen_range(params.vertices_min..=params.vertices_max);
+        let points: Vec<Vector4<f64>> = (0..count)
+            .map(|_| {
+                let u = sample_unit_vector(&mut rng, params.sampler);
+                map * (u * rng.gen_range(params.radius_min..=params.radius_max))
+            })
+            .collect();
+        let mut poly = Poly4::from_v(points);
+        poly.ensure_halfspaces_from_v();
+        if poly.h.len() < 5 {
+            stats.record_rejection("rank_deficient");
+            continue;
+        }
+        if poly.h.iter().any(|h| h.c <= 0.0) {
+            stats.record_rejection("origin_not_interior");
+            continue;
+        }
+        // Drop interior points: rebuild the vertex list from the H-rep.
+        poly.v.clear();
+        if poly.check_canonical().is_ok() {
+            stats.record_accepted();
+            return Ok(poly);
+        }
+        stats.record_rejection("not_canonical");
+    }
+    stats.record_exhausted();
+    Err(GeneratorError::Degenerate(format!(
+        "random_vertices: no valid hull after {} attempts (seed={}, index={})",
+        params.max_attempts, token.seed, token.index
+    )))
+}
+
+fn random_faces_impl(
+    params: &RandomFacesParams,
+    token: ReplayToken,
+    stats: &mut AcceptanceStats,
+) -> Result<Poly4, GeneratorError> {
+    let mut rng = token_rng(token);
+    for _ in 0..params.max_attempts {
+        stats.record_attempt();
+        let m = rng.gen_range(params.facets_min..=params.facets_max);
+        let h: Vec<Hs4> = (0..m)
+            .map(|_| {
+                let u = sample_unit_vector(&mut rng, params.sampler);
+                Hs4::new(u, rng.gen_range(params.radius_min..=params.radius_max))
+            })
+            .collect();
+        let mut poly = apply_anisotropy(Poly4::from_h(h), params.anisotropy.as_ref())?;
+        if poly.check_canonical().is_err() {
+            stats.record_rejection("unbounded");
+            continue;
+        }
+        stats.record_accepted();
+        return Ok(reduce_facets(poly));
+    }
+    stats.record_exhausted();
+    Err(GeneratorError::Degenerate(format!(
+        "random_faces: halfspaces failed to bound a body after {} attempts (seed={}, index={})",
+        params.max_attempts, token.seed, token.index
+    )))
+}
+
+/// Attempt `k` of a row draws its polygon from `(derive_seed(seed, index), k)`,
+/// so rows stay independent and a rejected polygon never shifts later rows.
+fn mahler_impl(
+    params: &MahlerProductParams,
+    token: ReplayToken,
+    stats: &mut AcceptanceStats,
+) -> Result<Poly4, GeneratorError> {
+    let row_seed = derive_seed(token.seed, token.index);
+    for attempt in 0..params.max_attempts as u64 {
+        stats.record_attempt();
+        let draw = ReplayToken {
+            seed: row_seed,
+            index: attempt,
+        };
+        let Some(k) = draw_polygon_radial(params.radial_cfg, draw) else {
+            stats.record_rejection("polygon");
+            continue;
+        };
+        let Some((k, _, _)) = recenter_rescale(&k, params.bounds) else {
+            stats.record_rejection("bounds");
+            continue;
+        };
+        let Some(mut poly) = polar(&k).and_then(|kp| lagrangian_product(&k, &kp)) else {
+            stats.record_rejection("polar");
+            continue;
+        };
+        if poly.check_canonical().is_ok() {
+            stats.record_accepted();
+            return Ok(poly);
+        }
+        stats.record_rejection("not_canonical");
+    }
+    stats.record_exhausted();
+    Err(GeneratorError::Degenerate(format!(
+        "mahler_products: no admissible polygon after {} attempts (seed={}, index={})",
+        params.max_attempts, token.seed, token.index
+    )))
+}
+
+/// `A·K`, or `K` unchanged without a map.
+fn apply_anisotropy(poly: Poly4, map: Option<&Matrix4<f64>>) -> Result<Poly4, GeneratorError> {
+    match map {
+        None => Ok(poly),
+        Some(a) => poly
+            .push_forward(a, &Vector4::zeros())
+            .ok_or_else(|| GeneratorError::InvalidParams("anisotropy map is singular".into())),
+    }
+}
+
+fn validate_radii(family: &str, min: f64, max: f64) -> Result<(), GeneratorError> {
+    if min > 0.0 && min <= max && max.is_finite() {
+        Ok(())
+    } else {
+        Err(GeneratorError::InvalidParams(format!(
+            "{family}: need 0 < radius_min <= radius_max < inf, got [{min}, {max}]"
+        )))
+    }
+}
+
+fn validate_anisotropy(family: &str, map: Option<&Matrix4<f64>>) -> Result<(), GeneratorError> {
+    match map {
+        Some(a) if a.try_inverse().is_none() => Err(GeneratorError::InvalidParams(format!(
+            "{family}: anisotropy map is singular"
+        ))),
+        _ => Ok(()),
+    }
+}
+
+fn validate_attempts(family: &str, max_attempts: usize) -> Result<(), GeneratorError> {
+    if max_attempts == 0 {
+        return Err(GeneratorError::InvalidParams(format!(
+            "{family}: max_attempts must be positive"
+        )));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    fn faces_params(facets_min: usize, facets_max: usize) -> RandomFacesParams {
+        RandomFacesParams {
+            facets_min,
+            facets_max,
+            radius_min: 0.4,
+            radius_max: 1.2,
+            anisotropy: None,
+            max_attempts: 50,
+            sampler: DirectionSampler::default(),
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn params_and_polytopes_round_trip_through_json() {
+        let mut map = Matrix4::identity();
+        map[(0, 1)] = 0.5;
+        let params = RandomFacesParams {
+            anisotropy: Some(map),
+            ..faces_params(6, 8)
+        };
+        let json = serde_json::to_value(&params).unwrap();
+        // Rows, as in configs: entry (0, 1) is the second number of the first row.
+        assert_eq!(json["anisotropy"][0][1], 0.5);
+        assert_eq!(json["sampler"], "gaussian");
+        let back: RandomFacesParams = serde_json::from_value(json).unwrap();
+        assert_eq!(back.anisotropy, params.anisotropy);
+        let bare: RandomFacesParams = serde_json::from_value(serde_json::json!({
+            "facets_min": 6, "facets_max": 8, "radius_min": 0.4, "radius_max": 1.2,
+            "max_attempts": 50,
+        }))
+        .unwrap();
+        assert_eq!(
+            (bare.anisotropy, bare.sampler),
+            (None, DirectionSampler::Gaussian)
+        );
+
+        let poly = RandomFacesGenerator::new(params, 3)
+            .unwrap()
+            .generate_next()
+            .unwrap()
+            .unwrap()
+            .polytope;
+        let back: Poly4 = serde_json::from_str(&serde_json::to_string(&poly).unwrap()).unwrap();
+        // serde_json's default float parser may be off by one ulp.
+        assert_eq!((back.h.len(), back.v.len()), (poly.h.len(), poly.v.len()));
+        for (a, b) in back.h.iter().zip(&poly.h) {
+            assert!((a.n - b.n).amax() < 1e-12 && (a.c - b.c).abs() < 1e-12);
+        }
+        for (a, b) in back.v.iter().zip(&poly.v) {
+            assert!((a - b).amax() < 1e-12 * b.amax().max(1.0));
+        }
+    }
+
+    proptest! {
+        #![proptest_config(ProptestConfig::with_cases(16))]
+
+        #[test]
+        fn symmetric_halfspaces_even_and_bounded(d in 4usize..9, seed in any::<u64>()) {
+            let params = SymmetricHalfspaceParams {
+                directions: d,
+                radius_min: 0.3,
+                radius_max: 1.0,
+                anisotropy: None,
+                sampler: DirectionSampler::default(),
+            };
+            let mut poly = SymmetricHalfspaceGenerator::generate_single(&params, seed).unwrap();
+            prop_assert_eq!(poly.h.len(), 2 * d);
+            prop_assert!(poly.check_canonical().is_ok());
+            prop_assert!(poly.v.iter().all(|v| poly.v.iter().any(|w| (v + w).norm() < 1e-9)));
+        }
+
+        #[test]
+        fn random_faces_facets_in_range(seed in any::<u64>()) {
+            let mut gen = RandomFacesGenerator::new(faces_params(6, 12), seed).unwrap();
+            let sample = gen.generate_next().unwrap().unwrap();
+            prop_assert!((5..=12).contains(&sample.polytope.h.len()));
+            let again = gen.regenerate(&sample.replay).unwrap();
+            prop_assert_eq!(again.h, sample.polytope.h);
+        }
+    }
+
+    #[test]
+    fn random_vertices_are_hull_vertices_and_replay() {
+        let params = RandomVerticesParams {
+            vertices_min: 8,
+            vertices_max: 20,
+            radius_min: 0.5,
+            radius_max: 1.5,
+            anisotropy: Some(Matrix4::from_diagonal(&Vector4::new(2.0, 1.0, 1.0, 0.5))),
+            sampler: DirectionSampler::default(),
+            max_attempts: 10,
+        };
+        let mut gen = RandomVerticesGenerator::new(params.clone(), 3).unwrap();
+        for _ in 0..3 {
+            let sample = gen.generate_next().unwrap().unwrap();
+            let p = &sample.polytope;
+            assert!(p.v.iter().all(|v| p
+                .h
+                .iter()
+                .filter(|h| (h.n.dot(v) - h.c).abs() < 1e-9)
+                .count()
+                >= 4));
+            let again = RandomVerticesGenerator::sample_with_token(&params, sample.replay).unwrap();
+            assert_eq!(again.h, p.h);
+        }
+        assert_eq!(gen.acceptance_stats().accepted, 3);
+    }
+
+    #[test]
+    fn mahler_products_are_products_with_the_polar() {
+        let mut gen = MahlerProductGenerator::new(MahlerProductParams::default(), 2025).unwrap();
+        let sample = gen.generate_next().unwrap().unwrap();
+        let p = &sample.polytope;
+        // Every vertex pairs a q-vertex of K with a p-vertex of K°: ⟨q, p⟩ ≤ 1.
+        assert!(p.v.iter().all(|v| v[0] * v[2] + v[1] * v[3] <= 1.0 + 1e-9));
+        let again = gen.regenerate(&sample.replay).unwrap();
+        assert_eq!(again.h, p.h);
+    }
+
+    #[test]
+    fn regular_products_enumerate_in_pair_order() {
+        let spec = |n| RegularPolygonSpec::new(n, 0.1, 1.0).unwrap();
+        let params = RegularProductEnumParams {
+            factors_a: vec![spec(3), spec(4)],
+            factors_b: vec![spec(5), spec(6), spec(8)],
+            max_pairs: Some(4),
+        };
+        let mut gen = RegularProductEnumerator::new(params).unwrap();
+        let mut replays = Vec::new();
+        while let Some(sample) = gen.generate_next().unwrap() {
+            let r = sample.replay;
+            let (na, nb) = ([3, 4][r.index_a], [5, 6, 8][r.index_b]);
+            assert_eq!(sample.polytope.h.len(), na + nb);
+            assert_eq!(sample.polytope.v.len(), na * nb);
+            replays.push((r.index_a, r.index_b));
+        }
+        assert_eq!(replays, vec![(0, 0), (0, 1), (0, 2), (1, 0)]);
+        assert!(RegularPolygonSpec::new(2, 0.0, 1.0).is_err());
+    }
+
+    #[test]
+    fn invalid_params_are_rejected_up_front() {
+        assert!(matches!(
+            RandomFacesGenerator::new(faces_params(4, 8), 0),
+            Err(GeneratorError::InvalidParams(_))
+        ));
+        let singular = RandomFacesParams {
+            anisotropy: Some(Matrix4::zeros()),
+            sampler: DirectionSampler::default(),
+            ..faces_params(6, 8)
+        };
+        assert!(RandomFacesGenerator::new(singular, 0).is_err());
+        let mut bad = MahlerProductParams::default();
+        bad.bounds.r_in_min = 3.0;
+        assert!(MahlerProductGenerator::new(bad, 0).is_err());
+    }
+}
@@ -0,0 +1,216 @@
+//! Polar-dual stream adapter: wrap any 4D generator and emit `K°` per row.
+//!
+//! Why this adapter exists
+//! - Mahler-volume vs capacity scatter plots need paired rows `(K, K°)`. The
+//!   dual depends on the chosen origin, so each primal sample is first
+//!   recentred (volume centroid or Santaló point) and then dualized.
+//! - Replay delegates to the wrapped generator: the adapter adds no randomness,
+//!   so the base replay token fully determines the dual row and we reuse it
+//!   unchanged as `Self::Replay`.
+//!
+//! References
+//! - TH: docs/src/thesis/random-polytopes.md (“Polar Duals”)
+//! - Code: crates/viterbo/src/geom4/polar.rs (`polar4`, `santalo_point4`)
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use super::{
+    AcceptanceStats, AcceptanceStatsSource, Checkpoint, GeneratorError, IndexedGenerator,
+    PolytopeGenerator4, PolytopeSample4,
+};
+use crate::geom4::{centroid4, polar4, santalo_point4, translate4, Poly4};
+
+/// Which interior point becomes the origin before taking the polar.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum DualCentering {
+    /// Dualize about the origin as sampled (it must be interior).
+    None,
+    /// Dualize about the volume centroid.
+    Centroid,
+    /// Dualize about the Santaló point (minimizes `vol(K°)`, so `vol(K)·vol(K°)` is the Mahler volume).
+    #[default]
+    Santalo,
+}
+
+/// A base sample together with its polar dual.
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct DualPair<R> {
+    pub primal: PolytopeSample4<R>,
+    pub dual: Poly4,
+}
+
+/// Generator adapter mapping each base sample `K` to `(K − s)°`.
+#[derive(Clone, Debug)]
+pub struct PolarDualGenerator<G> {
+    base: G,
+    centering: DualCentering,
+}
+
+impl<G: PolytopeGenerator4> PolarDualGenerator<G> {
+    pub fn new(base: G, centering: DualCentering) -> Self {
+        Self { base, centering }
+    }
+
+    pub fn base(&self) -> &G {
+        &self.base
+    }
+
+    pub fn centering(&self) -> DualCentering {
+        self.centering
+    }
+
+    /// Next base sample together with its dual (for paired `(K, K°)` rows).
+    pub fn next_pair(&mut self) -> Result<Option<DualPair<G::Replay>>, GeneratorError> {
+        let Some(mut sample) = self.base.generate_next()? else {
+            return Ok(None);
+        };
+        let dual = dual_of(&mut sample.polytope, self.centering)?;
+        Ok(Some(DualPair {
+            primal: sample,
+            dual,
+        }))
+    }
+}
+
+impl<G: PolytopeGenerator4> PolytopeGenerator4 for PolarDualGenerator<G> {
+    type Replay = G::Replay;
+
+    fn generate_next(&mut self) -> Result<Option<PolytopeSample4<G::Replay>>, GeneratorError> {
+        Ok(self.next_pair()?.map(|pair| PolytopeSample4 {
+            polytope: pair.dual,
+            replay: pair.primal.replay,
+        }))
+    }
+
+    fn regenerate(&self, replay: &G::Replay) -> Result<Poly4, GeneratorError> {
+        let mut primal = self.base.regenerate(replay)?;
+        dual_of(&mut primal, self.centering)
+    }
+}
+
+impl<G: IndexedGenerator> IndexedGenerator for PolarDualGenerator<G> {
+    fn row_count(&self) -> Option<u64> {
+        self.base.row_count()
+    }
+
+    fn replay_at(&self, index: u64) -> Option<G::Replay> {
+        self.base.replay_at(index)
+    }
+}
+
+impl<G: Checkpoint> Checkpoint for PolarDualGenerator<G> {
+    type State = G::State;
+
+    fn save_state(&self) -> G::State {
+        self.base.save_state()
+    }
+
+    fn restore_state(&mut self, state: G::State) -> Result<(), GeneratorError> {
+        self.base.restore_state(state)
+    }
+}
+
+impl<G: AcceptanceStatsSource> AcceptanceStatsSource for PolarDualGenerator<G> {
+    fn acceptance_stats(&self) -> &AcceptanceStats {
+        self.base.acceptance_stats()
+    }
+}
+
+/// Polar dual of `poly` about the centre selected by `centering`.
+pub fn dual_of(poly: &mut Poly4, centering: DualCentering) -> Result<Poly4, GeneratorError> {
+    let centre = match centering {
+        DualCentering::None => None,
+        DualCentering::Centroid => Some(centroid4(poly).ok_or_else(|| {
+            GeneratorError::Degenerate("polar dual: centroid undefined (degenerate body)".into())
+        })?),
+        DualCentering::Santalo => Some(santalo_point4(poly).ok_or_else(|| {
+            GeneratorError::Degenerate("polar dual: Santaló point undefined".into())
+        })?),
+    };
+    let mut shifted = match centre {
+        Some(s) => translate4(poly, &s),
+        None => poly.clone(),
+    };
+    polar4(&mut shifted).ok_or_else(|| {
+        GeneratorError::Degenerate("polar dual: origin is not an interior point".into())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::geom4::{volume_centroid4, Hs4};
+    use nalgebra::Vector4;
+
+    /// Axis-aligned boxes `[k, k + 2]⁴`, replayed by `k`; the origin is never interior.
+    struct ShiftedBoxes {
+        next: u32,
+        len: u32,
+    }
+
+    fn shifted_box(k: u32) -> Poly4 {
+        let lo = f64::from(k);
+        let mut hs = Vec::new();
+        for i in 0..4 {
+            let mut e = Vector4::zeros();
+            e[i] = 1.0;
+            hs.push(Hs4::new(e, lo + 2.0));
+            hs.push(Hs4::new(-e, -lo));
+        }
+        let mut p = Poly4::from_h(hs);
+        p.ensure_vertices_from_h();
+        p
+    }
+
+    impl PolytopeGenerator4 for ShiftedBoxes {
+        type Replay = u32;
+
+        fn generate_next(&mut self) -> Result<Option<PolytopeSample4<u32>>, GeneratorError> {
+            if self.next == self.len {
+                return Ok(None);
+            }
+            let k = self.next;
+            self.next += 1;
+            Ok(Some(PolytopeSample4 {
+                polytope: shifted_box(k),
+                replay: k,
+            }))
+        }
+
+        fn regenerate(&self, replay: &u32) -> Result<Poly4, GeneratorError> {
+            Ok(shifted_box(*replay))
+        }
+    }
+
+    #[test]
+    fn pairs_recentre_and_replay() {
+        let base = ShiftedBoxes { next: 0, len: 2 };
+        let mut gen = PolarDualGenerator::new(base, DualCentering::Centroid);
+        let mut rows = 0;
+        while let Some(DualPair { primal, mut dual }) = gen.next_pair().unwrap() {
+            // [−1,1]⁴ after recentring; its polar is the unit cross-polytope.
+            assert_eq!(dual.v.len(), 8);
+            let (vol, c) = volume_centroid4(&mut dual).unwrap();
+            assert!((vol - 2.0 / 3.0).abs() < 1e-9);
+            assert!(c.norm() < 1e-9);
+            let again = gen.regenerate(&primal.replay).unwrap();
+            assert_eq!(again.h, dual.h);
+            rows += 1;
+        }
+        assert_eq!(rows, 2);
+        assert!(gen.generate_next().unwrap().is_none());
+    }
+
+    #[test]
+    fn uncentred_dual_requires_interior_origin() {
+        let base = ShiftedBoxes { next: 0, len: 1 };
+        let mut gen = PolarDualGenerator::new(base, DualCentering::None);
+        assert!(matches!(
+            gen.generate_next(),
+            Err(GeneratorError::Degenerate(_))
+        ));
+    }
+}
@@ -0,0 +1,789 @@
+//! Generator registry: build any 4D family from a JSON config.
+//!
+//! Why this file exists
+//! - The atlas sources (Python), the PyO3 bindings and the benches each kept
+//!   their own `match` over generator families. Adding a family meant touching
+//!   all three. Here the family list lives in one enum (`GeneratorKind`) and
+//!   `from_config` returns a type-erased stream, so callers only forward JSON.
+//! - Configs use the atlas source schema: `{"family": ..., "params": {...},
+//!   "seed": u64}`. Family names match `src/viterbo/atlas/sources.py`.
+//! - Params of families with serde derives deserialize directly; the older
+//!   families are still read field by field, mirroring the PyO3 dict parsing.
+//! - Replay values are erased to JSON as well (`{"seed", "index"}`,
+//!   `{"index_a", "index_b"}`, ...), which is exactly what atlas rows store in
+//!   `replay_token`; `regenerate_json` accepts the same value back.
+//!
+//! References
+//! - TH: docs/src/thesis/random-polytopes.md (“Integration with the Atlas Dataset”)
+//! - Code: crates/viterbo-py/src/rand4.rs (dict parsing this mirrors)
+
+use nalgebra::Matrix4;
+use serde::de::DeserializeOwned;
+use serde_json::{json, Map, Value};
+
+use super::seeding::derive_seed;
+use super::{
+    AnisotropyDist, AnisotropyReplay, CenteredMahlerGenerator, CenteredMahlerParams, CentroidKind,
+    DirectionMode, DirectionSampler, ExactFacetsGenerator, ExactFacetsParams, ExactFacetsReplay,
+    GaussianVerticesGenerator, GaussianVerticesParams, GeneratorError, GroupSymmetricGenerator,
+    GroupSymmetricParams, MahlerProductGenerator, MahlerProductParams, MixtureComponent,
+    MixtureGenerator, MixtureReplay, PolygonSweep, PolytopeGenerator4, PolytopeSample4,
+    RandomAnisotropyGenerator, RandomFacesGenerator, RandomFacesParams, RandomVerticesGenerator,
+    RandomVerticesParams, RegularPolygonSpec, RegularProductEnumParams, RegularProductEnumerator,
+    RegularProductReplay, SimpleParams, SimplePolytopeGenerator, SimplicialParams,
+    SimplicialPolytopeGenerator, SymmetricHalfspaceGenerator, SymmetricHalfspaceParams,
+    ZonotopeGenerator, ZonotopeParams,
+};
+use crate::geom2::rand::{Bounds2, RadialCfg, ReplayToken, VertexCount};
+use crate::geom4::Poly4;
+
+/// Every generator family reachable through [`from_config`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum GeneratorKind {
+    RandomVertices,
+    RandomFaces,
+    SymmetricHalfspaces,
+    MahlerProducts,
+    RegularProducts,
+    GaussianVertices,
+    Zonotopes,
+    GroupSymmetric,
+    ExactFacets,
+    SimplePolytopes,
+    SimplicialPolytopes,
+    Mixture,
+}
+
+impl GeneratorKind {
+    pub const ALL: [GeneratorKind; 12] = [
+        GeneratorKind::RandomVertices,
+        GeneratorKind::RandomFaces,
+        GeneratorKind::SymmetricHalfspaces,
+        GeneratorKind::MahlerProducts,
+        GeneratorKind::RegularProducts,
+        GeneratorKind::GaussianVertices,
+        GeneratorKind::Zonotopes,
+        GeneratorKind::GroupSymmetric,
+        GeneratorKind::ExactFacets,
+        GeneratorKind::SimplePolytopes,
+        GeneratorKind::SimplicialPolytopes,
+        GeneratorKind::Mixture,
+    ];
+
+    /// Config / atlas family name.
+    pub fn name(self) -> &'static str {
+        match self {
+            GeneratorKind::RandomVertices => "random_vertices",
+            GeneratorKind::RandomFaces => "random_faces",
+            GeneratorKind::SymmetricHalfspaces => "symmetric_halfspaces",
+            GeneratorKind::MahlerProducts => "mahler_products",
+            GeneratorKind::RegularProducts => "regular_products",
+            GeneratorKind::GaussianVertices => "gaussian_vertices",
+            GeneratorKind::Zonotopes => "zonotopes",
+            GeneratorKind::GroupSymmetric => "group_symmetric",
+            GeneratorKind::ExactFacets => "exact_facets",
+            GeneratorKind::SimplePolytopes => "simple_polytopes",
+            GeneratorKind::SimplicialPolytopes => "simplicial_polytopes",
+            GeneratorKind::Mixture => "mixture",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|k| k.name() == name)
+    }
+}
+
+/// A sample with its replay value serialized to JSON.
+#[derive(Clone, Debug)]
+pub struct ErasedSample {
+    pub polytope: Poly4,
+    pub replay: Value,
+}
+
+/// Object-safe view of a `PolytopeGenerator4` with JSON replay values.
+pub trait ErasedGenerator {
+    fn kind(&self) -> GeneratorKind;
+    fn next_sample(&mut self) -> Result<Option<ErasedSample>, GeneratorError>;
+    fn regenerate_json(&self, replay: &Value) -> Result<Poly4, GeneratorError>;
+}
+
+/// Build the stream described by `{"family", "params", "seed"}`.
+pub fn from_config(config: &Value) -> Result<Box<dyn ErasedGenerator>, GeneratorError> {
+    let cfg = as_object(config, "config")?;
+    let family: String = get_required(cfg, "family")?;
+    let kind = GeneratorKind::from_name(&family).ok_or_else(|| {
+        GeneratorError::InvalidParams(format!("unknown generator family '{family}'"))
+    })?;
+    let empty = Value::Object(Map::new());
+    let params = as_object(cfg.get("params").unwrap_or(&empty), "params")?;
+    let seed: u64 = get_with_default(cfg, "seed", 0)?;
+    let gen = build(kind, params, seed)?;
+    match cfg.get("random_anisotropy") {
+        None => Ok(gen),
+        Some(dist) => {
+            let dist: AnisotropyDist = deserialize_params(as_object(dist, "random_anisotropy")?)?;
+            Ok(erase(
+                kind,
+                RandomAnisotropyGenerator::new(gen, dist, seed)?,
+            ))
+        }
+    }
+}
+
+fn build(
+    kind: GeneratorKind,
+    p: &Map<String, Value>,
+    seed: u64,
+) -> Result<Box<dyn ErasedGenerator>, GeneratorError> {
+    Ok(match kind {
+        GeneratorKind::RandomVertices => {
+            let params = RandomVerticesParams {
+                vertices_min: get_required(p, "vertices_min")?,
+                vertices_max: get_required(p, "vertices_max")?,
+                radius_min: get_required(p, "radius_min")?,
+                radius_max: get_required(p, "radius_max")?,
+                anisotropy: optional_matrix4(p, "anisotropy")?,
+                sampler: get_with_default(p, "sampler", DirectionSampler::default())?,
+                max_attempts: get_required(p, "max_attempts")?,
+            };
+            erase(kind, RandomVerticesGenerator::new(params, seed)?)
+        }
+        GeneratorKind::RandomFaces => erase(
+            kind,
+            RandomFacesGenerator::new(random_faces_params(p)?, seed)?,
+        ),
+        GeneratorKind::SymmetricHalfspaces => {
+            let params = SymmetricHalfspaceParams {
+                directions: get_required(p, "directions")?,
+                radius_min: get_required(p, "radius_min")?,
+                radius_max: get_required(p, "radius_max")?,
+                anisotropy: optional_matrix4(p, "anisotropy")?,
+                sampler: get_with_default(p, "sampler", DirectionSampler::default())?,
+            };
+            Box::new(SymmetricStream {
+                params,
+                seed,
+                next_index: 0,
+            })
+        }
+        GeneratorKind::MahlerProducts => {
+            let mut params = MahlerProductParams::default();
+            if let Some(radial) = p.get("radial_cfg") {
+                params.radial_cfg = radial_cfg(as_object(radial, "radial_cfg")?)?;
+            }
+            if let Some(bounds) = p.get("bounds") {
+                let b = as_object(bounds, "bounds")?;
+                params.bounds = Bounds2 {
+                    r_in_min: get_with_default(b, "r_in_min", 0.1)?,
+                    r_out_max: get_with_default(b, "r_out_max", 2.0)?,
+                };
+            }
+            params.max_attempts = get_with_default(p, "max_attempts", params.max_attempts)?;
+            let centering: CentroidKind = match p.get("centering") {
+                None => CentroidKind::default(),
+                Some(v) => serde_json::from_value(v.clone())
+                    .map_err(|err| GeneratorError::InvalidParams(format!("centering: {err}")))?,
+            };
+            if centering == CentroidKind::AreaCentroid {
+                erase(kind, MahlerProductGenerator::new(params, seed)?)
+            } else {
+                let params = CenteredMahlerParams {
+                    mahler: params,
+                    centering,
+                };
+                erase(kind, CenteredMahlerGenerator::new(params, seed)?)
+            }
+        }
+        GeneratorKind::RegularProducts => {
+            let params = RegularProductEnumParams {
+                factors_a: polygon_specs(p, "factors_a")?,
+                factors_b: polygon_specs(p, "factors_b")?,
+                max_pairs: get_with_default(p, "max_pairs", None)?,
+            };
+            erase(kind, RegularProductEnumerator::new(params)?)
+        }
+        GeneratorKind::GaussianVertices => {
+            let params: GaussianVerticesParams = deserialize_params(p)?;
+            erase(kind, GaussianVerticesGenerator::new(params, seed)?)
+        }
+        GeneratorKind::Zonotopes => {
+            let params: ZonotopeParams = deserialize_params(p)?;
+            erase(kind, ZonotopeGenerator::new(params, seed)?)
+        }
+        GeneratorKind::GroupSymmetric => {
+            let params: GroupSymmetricParams = deserialize_params(p)?;
+            erase(kind, GroupSymmetricGenerator::new(params, seed)?)
+        }
+        GeneratorKind::ExactFacets => {
+            let params = ExactFacetsParams {
+                faces: random_faces_params(p)?,
+                facets: get_required(p, "facets")?,
+                max_resamples: get_with_default(p, "max_resamples", 100)?,
+            };
+            erase(kind, ExactFacetsGenerator::new(params, seed)?)
+        }
+        GeneratorKind::SimplePolytopes => {
+            let params: SimpleParams = deserialize_params(p)?;
+            erase(kind, SimplePolytopeGenerator::new(params, seed)?)
+        }
+        GeneratorKind::SimplicialPolytopes => {
+            let params: SimplicialParams = deserialize_params(p)?;
+            erase(kind, SimplicialPolytopeGenerator::new(params, seed)?)
+        }
+        GeneratorKind::Mixture => erase(kind, mixture(p, seed)?),
+    })
+}
+
+/// Replay values that round-trip through the JSON stored in atlas rows.
+pub trait ReplayJson: Sized {
+    fn to_json(&self) -> Value;
+    fn from_json(value: &Value) -> Result<Self, GeneratorError>;
+}
+
+impl ReplayJson for ReplayToken {
+    fn to_json(&self) -> Value {
+        json!({ "seed": self.seed, "index": self.index })
+    }
+
+    fn from_json(value: &Value) -> Result<Self, GeneratorError> {
+        let m = as_object(value, "replay")?;
+        Ok(ReplayToken {
+            seed: get_required(m, "seed")?,
+            index: get_required(m, "index")?,
+        })
+    }
+}
+
+impl ReplayJson for ExactFacetsReplay {
+    fn to_json(&self) -> Value {
+        json!({ "seed": self.seed, "index": self.index, "attempt": self.attempt })
+    }
+
+    fn from_json(value: &Value) -> Result<Self, GeneratorError> {
+        let m = as_object(value, "replay")?;
+        Ok(ExactFacetsReplay {
+            seed: get_required(m, "seed")?,
+            index: get_required(m, "index")?,
+            attempt: get_required(m, "attempt")?,
+        })
+    }
+}
+
+impl ReplayJson for MixtureReplay {
+    fn to_json(&self) -> Value {
+        json!({ "family": self.family, "replay": self.replay })
+    }
+
+    fn from_json(value: &Value) -> Result<Self, GeneratorError> {
+        let m = as_object(value, "replay")?;
+        Ok(MixtureReplay {
+            family: get_required(m, "family")?,
+            replay: m
+                .get("replay")
+                .cloned()
+                .ok_or_else(|| GeneratorError::InvalidParams("missing 'replay'".into()))?,
+        })
+    }
+}
+
+impl ReplayJson for RegularProductReplay {
+    fn to_json(&self) -> Value {
+        json!({ "index_a": self.index_a, "index_b": self.index_b })
+    }
+
+    fn from_json(value: &Value) -> Result<Self, GeneratorError> {
+        let m = as_object(value, "replay")?;
+        Ok(RegularProductReplay {
+            index_a: get_required(m, "index_a")?,
+            index_b: get_required(m, "index_b")?,
+        })
+    }
+}
+
+impl<R: ReplayJson> ReplayJson for AnisotropyReplay<R> {
+    fn to_json(&self) -> Value {
+        json!({
+            "base": self.base.to_json(),
+            "anisotropy_seed": self.seed,
+            "singular_values": self.singular_values,
+        })
+    }
+
+    fn from_json(value: &Value) -> Result<Self, GeneratorError> {
+        let m = as_object(value, "replay")?;
+        let base = m
+            .get("base")
+            .ok_or_else(|| GeneratorError::InvalidParams("missing 'base'".into()))?;
+        let sv: Vec<f64> = get_required(m, "singular_values")?;
+        Ok(AnisotropyReplay {
+            base: R::from_json(base)?,
+            seed: get_required(m, "anisotropy_seed")?,
+            singular_values: sv.try_into().map_err(|_| {
+                GeneratorError::InvalidParams("'singular_values' needs four entries".into())
+            })?,
+        })
+    }
+}
+
+/// Erased replay values are already JSON.
+impl ReplayJson for Value {
+    fn to_json(&self) -> Value {
+        self.clone()
+    }
+
+    fn from_json(value: &Value) -> Result<Self, GeneratorError> {
+        Ok(value.clone())
+    }
+}
+
+/// Lets typed adapters (e.g. random anisotropy) wrap an erased stream.
+impl PolytopeGenerator4 for Box<dyn ErasedGenerator> {
+    type Replay = Value;
+
+    fn generate_next(&mut self) -> Result<Option<PolytopeSample4<Value>>, GeneratorError> {
+        Ok(self.next_sample()?.map(|s| PolytopeSample4 {
+            polytope: s.polytope,
+            replay: s.replay,
+        }))
+    }
+
+    fn regenerate(&self, replay: &Value) -> Result<Poly4, GeneratorError> {
+        self.regenerate_json(replay)
+    }
+}
+
+struct Erased<G> {
+    kind: GeneratorKind,
+    inner: G,
+}
+
+fn erase<G>(kind: GeneratorKind, inner: G) -> Box<dyn ErasedGenerator>
+where
+    G: PolytopeGenerator4 + 'static,
+    G::Replay: ReplayJson,
+{
+    Box::new(Erased { kind, inner })
+}
+
+impl<G> ErasedGenerator for Erased<G>
+where
+    G: PolytopeGenerator4,
+    G::Replay: ReplayJson,
+{
+    fn kind(&self) -> GeneratorKind {
+        self.kind
+    }
+
+    fn next_sample(&mut self) -> Result<Option<ErasedSample>, GeneratorError> {
+        Ok(self.inner.generate_next()?.map(|s| ErasedSample {
+            polytope: s.polytope,
+            replay: s.replay.to_json(),
+        }))
+    }
+
+    fn regenerate_json(&self, replay: &Value) -> Result<Poly4, GeneratorError> {
+        self.inner.regenerate(&G::Replay::from_json(replay)?)
+    }
+}
+
+/// Symmetric halfspaces are seed-addressed (`generate_single`); rows use `seed + index`
+/// exactly like the atlas source, and replay as `{"seed": seed + index}`.
+struct SymmetricStream {
+    params: SymmetricHalfspaceParams,
+    seed: u64,
+    next_index: u64,
+}
+
+impl ErasedGenerator for SymmetricStream {
+    fn kind(&self) -> GeneratorKind {
+        GeneratorKind::SymmetricHalfspaces
+    }
+
+    fn next_sample(&mut self) -> Result<Option<ErasedSample>, GeneratorError> {
+        let sample_seed = self.seed.wrapping_add(self.next_index);
+        let polytope = SymmetricHalfspaceGenerator::generate_single(&self.params, sample_seed)?;
+        self.next_index += 1;
+        Ok(Some(ErasedSample {
+            polytope,
+            replay: json!({ "seed": sample_seed }),
+        }))
+    }
+
+    fn regenerate_json(&self, replay: &Value) -> Result<Poly4, GeneratorError> {
+        let seed = get_required(as_object(replay, "replay")?, "seed")?;
+        SymmetricHalfspaceGenerator::generate_single(&self.params, seed)
+    }
+}
+
+/// `{"components": [{"weight", "config": {"family", "params"}}]}`; child `k` is
+/// seeded with `derive_seed(seed, k)`, so component configs must not carry a seed.
+fn mixture(p: &Map<String, Value>, seed: u64) -> Result<MixtureGenerator, GeneratorError> {
+    let components = p
+        .get("components")
+        .and_then(Value::as_array)
+        .ok_or_else(|| {
+            GeneratorError::InvalidParams("mixture: 'components' must be a list".into())
+        })?;
+    let components = components
+        .iter()
+        .enumerate()
+        .map(|(k, c)| {
+            let c = as_object(c, "mixture component")?;
+            let mut config = as_object(
+                c.get("config").ok_or_else(|| {
+                    GeneratorError::InvalidParams("mixture: component without 'config'".into())
+                })?,
+                "config",
+            )?
+            .clone();
+            if config.contains_key("seed") {
+                return Err(GeneratorError::InvalidParams(
+                    "mixture: component seeds are derived from the mixture seed".into(),
+                ));
+            }
+            config.insert("seed".into(), json!(derive_seed(seed, k as u64)));
+            Ok(MixtureComponent {
+                weight: get_with_default(c, "weight", 1.0)?,
+                generator: from_config(&Value::Object(config))?,
+            })
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    MixtureGenerator::new(components, seed)
+}
+
+fn random_faces_params(p: &Map<String, Value>) -> Result<RandomFacesParams, GeneratorError> {
+    Ok(RandomFacesParams {
+        facets_min: get_required(p, "facets_min")?,
+        facets_max: get_required(p, "facets_max")?,
+        radius_min: get_required(p, "radius_min")?,
+        radius_max: get_required(p, "radius_max")?,
+        anisotropy: optional_matrix4(p, "anisotropy")?,
+        sampler: get_with_default(p, "sampler", DirectionSampler::default())?,
+        max_attempts: get_required(p, "max_attempts")?,
+    })
+}
+
+fn radial_cfg(m: &Map<String, Value>) -> Result<RadialCfg, GeneratorError> {
+    let mut cfg = RadialCfg::default();
+    if let Some(vc) = m.get("vertex_count") {
+        cfg.vertex_count = vertex_count(vc)?;
+    }
+    cfg.angle_jitter_frac = get_with_default(m, "angle_jitter_frac", cfg.angle_jitter_frac)?;
+    cfg.radial_jitter = get_with_default(m, "radial_jitter", cfg.radial_jitter)?;
+    cfg.base_radius = get_with_default(m, "base_radius", cfg.base_radius)?;
+    cfg.random_phase = get_with_default(m, "random_phase", cfg.random_phase)?;
+    Ok(cfg)
+}
+
+fn vertex_count(value: &Value) -> Result<VertexCount, GeneratorError> {
+    if let Some(fixed) = value.as_u64() {
+        return Ok(VertexCount::Fixed(fixed as usize));
+    }
+    let m = as_object(value, "vertex_count")?;
+    let kind: String = get_required(m, "kind")?;
+    match kind.as_str() {
+        "fixed" => Ok(VertexCount::Fixed(get_required(m, "value")?)),
+        "uniform" => Ok(VertexCount::Uniform {
+            min: get_required(m, "min")?,
+            max: get_required(m, "max")?,
+        }),
+        other => Err(GeneratorError::InvalidParams(format!(
+            "vertex_count.kind must be 'fixed' or 'uniform', got {other}"
+        ))),
+    }
+}
+
+fn polygon_specs(
+    m: &Map<String, Value>,
+    key: &str,
+) -> Result<Vec<RegularPolygonSpec>, GeneratorError> {
+    // A sweep spec object instead of an explicit list (see `rand4::regular_sweep`).
+    if let Some(sweep) = m.get(key).and_then(Value::as_object) {
+        let sweep: PolygonSweep = deserialize_params(sweep)?;
+        return sweep.expand();
+    }
+    let list = m
+        .get(key)
+        .and_then(Value::as_array)
+        .ok_or_else(|| GeneratorError::InvalidParams(format!("missing '{key}' list")))?;
+    if list.is_empty() {
+        return Err(GeneratorError::InvalidParams(format!(
+            "{key} must contain at least one polygon"
+        )));
+    }
+    list.iter()
+        .map(|item| {
+            let spec = as_object(item, key)?;
+            RegularPolygonSpec::new(
+                get_required(spec, "sides")?,
+                get_with_default(spec, "rotation", 0.0)?,
+                get_with_default(spec, "scale", 1.0)?,
+            )
+        })
+        .collect()
+}
+
+fn optional_matrix4(
+    m: &Map<String, Value>,
+    key: &str,
+) -> Result<Option<Matrix4<f64>>, GeneratorError> {
+    let Some(rows) = get_with_default::<Option<Vec<Vec<f64>>>>(m, key, None)? else {
+        return Ok(None);
+    };
+    if rows.len() != 4 || rows.iter().any(|r| r.len() != 4) {
+        return Err(GeneratorError::InvalidParams(format!(
+            "{key} must be a 4x4 matrix (list of four rows)"
+        )));
+    }
+    let data: Vec<f64> = rows.into_iter().flatten().collect();
+    Ok(Some(Matrix4::from_row_slice(&data)))
+}
+
+/// Scalar-ish JSON values we read from configs.
+trait FromJson: Sized {
+    fn from_json(value: &Value) -> Option<Self>;
+}
+
+impl FromJson for f64 {
+    fn from_json(value: &Value) -> Option<Self> {
+        value.as_f64()
+    }
+}
+
+impl FromJson for u64 {
+    fn from_json(value: &Value) -> Option<Self> {
+        value.as_u64()
+    }
+}
+
+impl FromJson for usize {
+    fn from_json(value: &Value) -> Option<Self> {
+        value.as_u64().and_then(|x| usize::try_from(x).ok())
+    }
+}
+
+impl FromJson for u32 {
+    fn from_json(value: &Value) -> Option<Self> {
+        value.as_u64().and_then(|x| u32::try_from(x).ok())
+    }
+}
+
+impl FromJson for bool {
+    fn from_json(value: &Value) -> Option<Self> {
+        value.as_bool()
+    }
+}
+
+impl FromJson for DirectionSampler {
+    fn from_json(value: &Value) -> Option<Self> {
+        match DirectionMode::try_from(value.as_str()?.to_owned()).ok()? {
+            DirectionMode::Iid(sampler) => Some(sampler),
+            DirectionMode::Sobol => None,
+        }
+    }
+}
+
+impl FromJson for String {
+    fn from_json(value: &Value) -> Option<Self> {
+        value.as_str().map(str::to_owned)
+    }
+}
+
+impl<T: FromJson> FromJson for Vec<T> {
+    fn from_json(value: &Value) -> Option<Self> {
+        value.as_array()?.iter().map(T::from_json).collect()
+    }
+}
+
+impl<T: FromJson> FromJson for Option<T> {
+    fn from_json(value: &Value) -> Option<Self> {
+        if value.is_null() {
+            Some(None)
+        } else {
+            T::from_json(value).map(Some)
+        }
+    }
+}
+
+/// Params types with serde derives (missing fields fall back to their `Default`).
+fn deserialize_params<T: DeserializeOwned>(m: &Map<String, Value>) -> Result<T, GeneratorError> {
+    serde_json::from_value(Value::Object(m.clone()))
+        .map_err(|err| GeneratorError::InvalidParams(format!("params: {err}")))
+}
+
+fn as_object<'a>(value: &'a Value, what: &str) -> Result<&'a Map<String, Value>, GeneratorError> {
+    value
+        .as_object()
+        .ok_or_else(|| GeneratorError::InvalidParams(format!("{what} must be a JSON object")))
+}
+
+fn get_required<T: FromJson>(m: &Map<String, Value>, key: &str) -> Result<T, GeneratorError> {
+    let value = m
+        .get(key)
+        .ok_or_else(|| GeneratorError::InvalidParams(format!("missing '{key}'")))?;
+    T::from_json(value)
+        .ok_or_else(|| GeneratorError::InvalidParams(format!("'{key}' has the wrong type")))
+}
+
+fn get_with_default<T: FromJson>(
+    m: &Map<String, Value>,
+    key: &str,
+    default: T,
+) -> Result<T, GeneratorError> {
+    match m.get(key) {
+        None => Ok(default),
+        Some(_) => get_required(m, key),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn family_names_round_trip() {
+        for kind in GeneratorKind::ALL {
+            assert_eq!(GeneratorKind::from_name(kind.name()), Some(kind));
+        }
+        assert_eq!(GeneratorKind::from_name("nope"), None);
+    }
+
+    #[test]
+    fn rejects_unknown_family_and_bad_params() {
+        let err = |cfg: Value| from_config(&cfg).err().expect("config should be rejected");
+        assert!(matches!(
+            err(json!({ "family": "hypercubes" })),
+            GeneratorError::InvalidParams(_)
+        ));
+        assert!(matches!(
+            err(json!({ "family": "zonotopes", "params": { "segments_min": "four" } })),
+            GeneratorError::InvalidParams(_)
+        ));
+        assert!(matches!(
+            err(json!({ "family": "zonotopes", "params": { "directions": "halton" } })),
+            GeneratorError::InvalidParams(_)
+        ));
+    }
+
+    #[test]
+    fn random_anisotropy_wraps_any_family() {
+        let cfg = json!({
+            "family": "zonotopes",
+            "seed": 5,
+            "params": { "segments_min": 5, "segments_max": 5 },
+            "random_anisotropy": { "sigma_max": 10.0 },
+        });
+        let mut gen = from_config(&cfg).unwrap();
+        let sample = gen.next_sample().unwrap().unwrap();
+        assert_eq!(sample.replay["base"], json!({ "seed": 5, "index": 0 }));
+        let sv = sample.replay["singular_values"].as_array().unwrap();
+        assert!(sv[0].as_f64().unwrap() >= sv[3].as_f64().unwrap());
+        let again = gen.regenerate_json(&sample.replay).unwrap();
+        assert_eq!(again.h, sample.polytope.h);
+    }
+
+    #[test]
+    fn anisotropy_replay_survives_repeated_json_round_trips() {
+        let cfg = json!({
+            "family": "zonotopes",
+            "seed": 5,
+            "params": { "segments_min": 5, "segments_max": 5 },
+            "random_anisotropy": { "sigma_max": 10.0 },
+        });
+        let mut gen = from_config(&cfg).unwrap();
+        let replay = gen.next_sample().unwrap().unwrap().replay;
+        let parsed = AnisotropyReplay::<Value>::from_json(&replay).unwrap();
+        assert_eq!(parsed.to_json(), replay);
+        let reparsed = AnisotropyReplay::<Value>::from_json(&parsed.to_json()).unwrap();
+        assert_eq!(reparsed.to_json(), replay);
+        let mut partial = replay.clone();
+        partial.as_object_mut().unwrap().remove("singular_values");
+        assert!(AnisotropyReplay::<Value>::from_json(&partial).is_err());
+    }
+
+    #[test]
+    fn mixture_config_tags_rows_with_the_family() {
+        let zonotopes = json!({ "family": "zonotopes", "params": { "segments_min": 5 } });
+        let cfg = json!({
+            "family": "mixture",
+            "seed": 2,
+            "params": { "components": [
+                { "weight": 1.0, "config": zonotopes },
+                { "weight": 2.0, "config": zonotopes },
+            ] },
+        });
+        let mut gen = from_config(&cfg).unwrap();
+        for _ in 0..3 {
+            let row = gen.next_sample().unwrap().unwrap();
+            let family = row.replay["family"].as_u64().unwrap();
+            assert!(family < 2);
+            let child_seed = derive_seed(2, family);
+            assert_eq!(row.replay["replay"]["seed"], json!(child_seed));
+            assert_eq!(gen.regenerate_json(&row.replay).unwrap().h, row.polytope.h);
+        }
+        let mut seeded = cfg.clone();
+        seeded["params"]["components"][0]["config"]["seed"] = json!(1);
+        assert!(from_config(&seeded).is_err());
+    }
+
+    #[test]
+    fn zonotope_config_streams_and_replays() {
+        let cfg = json!({
+            "family": "zonotopes",
+            "seed": 11,
+            "params": { "segments_min": 5, "segments_max": 5, "directions": "sobol" },
+        });
+        let mut gen = from_config(&cfg).unwrap();
+        assert_eq!(gen.kind(), GeneratorKind::Zonotopes);
+        let _ = gen.next_sample().unwrap().unwrap();
+        let second = gen.next_sample().unwrap().unwrap();
+        assert_eq!(second.replay, json!({ "seed": 11, "index": 1 }));
+        let again = gen.regenerate_json(&second.replay).unwrap();
+        assert_eq!(again.h, second.polytope.h);
+    }
+
+    #[test]
+    fn core_families_take_a_direction_sampler() {
+        let cfg = |sampler: Value| {
+            json!({
+                "family": "random_faces",
+                "seed": 4,
+                "params": {
+                    "facets_min": 8, "facets_max": 8, "radius_min": 1.0, "radius_max": 1.0,
+                    "max_attempts": 20, "sampler": sampler,
+                },
+            })
+        };
+        let row = |cfg: Value| from_config(&cfg).unwrap().next_sample().unwrap().unwrap();
+        let legacy = row(cfg(json!("legacy_cube")));
+        let params = RandomFacesParams {
+            facets_min: 8,
+            facets_max: 8,
+            radius_min: 1.0,
+            radius_max: 1.0,
+            anisotropy: None,
+            sampler: DirectionSampler::LegacyCube,
+            max_attempts: 20,
+        };
+        let token = ReplayToken { seed: 4, index: 0 };
+        let direct = RandomFacesGenerator::sample_with_token(&params, token).unwrap();
+        assert_eq!(legacy.polytope.h, direct.h);
+        assert_ne!(row(cfg(json!("gaussian"))).polytope.h, legacy.polytope.h);
+        assert!(from_config(&cfg(json!("sobol"))).is_err());
+    }
+
+    #[test]
+    fn replay_tokens_round_trip() {
+        let tok = ReplayToken { seed: 3, index: 9 };
+        let back = ReplayToken::from_json(&tok.to_json()).unwrap();
+        assert_eq!((back.seed, back.index), (3, 9));
+        let pair = RegularProductReplay {
+            index_a: 1,
+            index_b: 2,
+        };
+        assert_eq!(
+            RegularProductReplay::from_json(&pair.to_json()).unwrap(),
+            pair
+        );
+    }
+}
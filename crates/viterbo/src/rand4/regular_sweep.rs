@@ -0,0 +1,326 @@
+//! Compact sweep specs for the regular-polygon product enumeration.
+//!
+//! Why this file exists
+//! - Regular-product studies sweep `sides × rotation × scale` grids with
+//!   hundreds of points per factor; writing `factors_a/factors_b` out by hand
+//!   is error-prone. `PolygonSweep` expands `sides ∈ {min..max}`,
+//!   `rotation ∈ linspace`, `scale ∈ logspace` into `RegularPolygonSpec`s in a
+//!   fixed order: sides outermost, then rotation, then scale (innermost).
+//! - `RegularProductSweepGenerator` streams the product in atlas `pair_index`
+//!   order (via `IndexedRegularProducts`) and extends the replay value with
+//!   the sweep coordinates of both factors, so rows can be grouped by
+//!   `(sides, rotation, scale)` without reverse-engineering the indices.
+//!
+//! References
+//! - TH: docs/src/thesis/random-polytopes.md (“Regular Polygon Product Enumerator”)
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use super::{
+    Checkpoint, GeneratorError, IndexedGenerator, IndexedRegularProducts, PolytopeGenerator4,
+    PolytopeSample4, RegularPolygonSpec, RegularProductEnumParams, RegularProductReplay,
+};
+use crate::geom4::Poly4;
+
+/// Side counts of a sweep: an inclusive range or an explicit list.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(untagged))]
+pub enum SidesSpec {
+    Range { min: u32, max: u32 },
+    List(Vec<u32>),
+}
+
+impl SidesSpec {
+    pub fn values(&self) -> Vec<u32> {
+        match self {
+            SidesSpec::Range { min, max } => (*min..=*max).collect(),
+            SidesSpec::List(list) => list.clone(),
+        }
+    }
+}
+
+/// `count` evenly spaced values from `start` to `end` inclusive (`count = 1` gives `start`).
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Linspace {
+    pub start: f64,
+    pub end: f64,
+    pub count: usize,
+}
+
+impl Linspace {
+    pub fn value(&self, i: usize) -> f64 {
+        if self.count <= 1 {
+            return self.start;
+        }
+        self.start + (self.end - self.start) * i as f64 / (self.count - 1) as f64
+    }
+}
+
+/// `count` geometrically spaced values from `start` to `end` inclusive (both positive).
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Logspace {
+    pub start: f64,
+    pub end: f64,
+    pub count: usize,
+}
+
+impl Logspace {
+    pub fn value(&self, i: usize) -> f64 {
+        if self.count <= 1 {
+            return self.start;
+        }
+        self.start * (self.end / self.start).powf(i as f64 / (self.count - 1) as f64)
+    }
+}
+
+/// Grid of regular polygons `sides × rotations × scales`.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct PolygonSweep {
+    pub sides: SidesSpec,
+    #[cfg_attr(feature = "serde", serde(default = "PolygonSweep::default_rotations"))]
+    pub rotations: Linspace,
+    #[cfg_attr(feature = "serde", serde(default = "PolygonSweep::default_scales"))]
+    pub scales: Logspace,
+}
+
+/// Position of one polygon within its [`PolygonSweep`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct SweepCoords {
+    pub sides: u32,
+    pub rotation_index: usize,
+    pub rotation: f64,
+    pub scale_index: usize,
+    pub scale: f64,
+}
+
+impl PolygonSweep {
+    fn default_rotations() -> Linspace {
+        Linspace {
+            start: 0.0,
+            end: 0.0,
+            count: 1,
+        }
+    }
+
+    fn default_scales() -> Logspace {
+        Logspace {
+            start: 1.0,
+            end: 1.0,
+            count: 1,
+        }
+    }
+
+    /// Sweep over side counts only (rotation 0, scale 1).
+    pub fn sides(sides: SidesSpec) -> Self {
+        Self {
+            sides,
+            rotations: Self::default_rotations(),
+            scales: Self::default_scales(),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.sides.values().len() * self.rotations.count.max(1) * self.scales.count.max(1)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Coordinates of entry `index` of [`expand`](Self::expand).
+    pub fn coords(&self, index: usize) -> Option<SweepCoords> {
+        let sides = self.sides.values();
+        let n_rot = self.rotations.count.max(1);
+        let n_scale = self.scales.count.max(1);
+        let side = *sides.get(index / (n_rot * n_scale))?;
+        let rotation_index = index / n_scale % n_rot;
+        let scale_index = index % n_scale;
+        Some(SweepCoords {
+            sides: side,
+            rotation_index,
+            rotation: self.rotations.value(rotation_index),
+            scale_index,
+            scale: self.scales.value(scale_index),
+        })
+    }
+
+    /// All polygons, sides outermost and scale innermost.
+    pub fn expand(&self) -> Result<Vec<RegularPolygonSpec>, GeneratorError> {
+        let scales_ok = self.scales.start > 0.0 && self.scales.end > 0.0;
+        if !scales_ok {
+            return Err(GeneratorError::InvalidParams(
+                "regular sweep: logspace scales must be positive".into(),
+            ));
+        }
+        if self.is_empty() {
+            return Err(GeneratorError::InvalidParams(
+                "regular sweep: sweep contains no polygons".into(),
+            ));
+        }
+        (0..self.len())
+            .map(|i| {
+                let c = self.coords(i).expect("index below len");
+                RegularPolygonSpec::new(c.sides, c.rotation, c.scale)
+            })
+            .collect()
+    }
+}
+
+/// Product sweep: every polygon of `a` times every polygon of `b`.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct RegularProductSweepParams {
+    pub a: PolygonSweep,
+    pub b: PolygonSweep,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub max_pairs: Option<usize>,
+}
+
+impl RegularProductSweepParams {
+    /// The equivalent explicit enumeration params.
+    pub fn to_enum_params(&self) -> Result<RegularProductEnumParams, GeneratorError> {
+        Ok(RegularProductEnumParams {
+            factors_a: self.a.expand()?,
+            factors_b: self.b.expand()?,
+            max_pairs: self.max_pairs,
+        })
+    }
+}
+
+/// Replay value of a sweep row: enumeration indices plus sweep coordinates.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct RegularSweepReplay {
+    pub index_a: usize,
+    pub index_b: usize,
+    pub a: SweepCoords,
+    pub b: SweepCoords,
+}
+
+impl From<RegularSweepReplay> for RegularProductReplay {
+    fn from(r: RegularSweepReplay) -> Self {
+        RegularProductReplay {
+            index_a: r.index_a,
+            index_b: r.index_b,
+        }
+    }
+}
+
+/// Regular-product enumeration over a [`RegularProductSweepParams`] grid.
+pub struct RegularProductSweepGenerator {
+    params: RegularProductSweepParams,
+    inner: IndexedRegularProducts,
+}
+
+impl RegularProductSweepGenerator {
+    pub fn new(params: RegularProductSweepParams) -> Result<Self, GeneratorError> {
+        let inner = IndexedRegularProducts::new(params.to_enum_params()?)?;
+        Ok(Self { params, inner })
+    }
+
+    pub fn params(&self) -> &RegularProductSweepParams {
+        &self.params
+    }
+
+    fn with_coords(&self, r: RegularProductReplay) -> RegularSweepReplay {
+        RegularSweepReplay {
+            index_a: r.index_a,
+            index_b: r.index_b,
+            a: self
+                .params
+                .a
+                .coords(r.index_a)
+                .expect("index from enumeration"),
+            b: self
+                .params
+                .b
+                .coords(r.index_b)
+                .expect("index from enumeration"),
+        }
+    }
+}
+
+impl PolytopeGenerator4 for RegularProductSweepGenerator {
+    type Replay = RegularSweepReplay;
+
+    fn generate_next(
+        &mut self,
+    ) -> Result<Option<PolytopeSample4<RegularSweepReplay>>, GeneratorError> {
+        Ok(self.inner.generate_next()?.map(|s| PolytopeSample4 {
+            replay: self.with_coords(s.replay),
+            polytope: s.polytope,
+        }))
+    }
+
+    fn regenerate(&self, replay: &RegularSweepReplay) -> Result<Poly4, GeneratorError> {
+        self.inner.regenerate(&(*replay).into())
+    }
+}
+
+impl IndexedGenerator for RegularProductSweepGenerator {
+    fn row_count(&self) -> Option<u64> {
+        self.inner.row_count()
+    }
+
+    fn replay_at(&self, index: u64) -> Option<RegularSweepReplay> {
+        self.inner.replay_at(index).map(|r| self.with_coords(r))
+    }
+}
+
+impl Checkpoint for RegularProductSweepGenerator {
+    /// The next pair index.
+    type State = u64;
+
+    fn save_state(&self) -> u64 {
+        self.inner.save_state()
+    }
+
+    fn restore_state(&mut self, next_index: u64) -> Result<(), GeneratorError> {
+        self.inner.restore_state(next_index)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn expansion_order_and_coords() {
+        let sweep = PolygonSweep {
+            sides: SidesSpec::Range { min: 3, max: 5 },
+            rotations: Linspace {
+                start: 0.0,
+                end: 0.5,
+                count: 2,
+            },
+            scales: Logspace {
+                start: 1.0,
+                end: 4.0,
+                count: 3,
+            },
+        };
+        assert_eq!(sweep.len(), 18);
+        let c = sweep.coords(9).unwrap();
+        assert_eq!((c.sides, c.rotation_index, c.scale_index), (4, 1, 0));
+        assert_eq!((c.rotation, c.scale), (0.5, 1.0));
+        assert_eq!(sweep.coords(2).unwrap().scale, 4.0);
+        assert!((sweep.coords(1).unwrap().scale - 2.0).abs() < 1e-12);
+        assert!(sweep.coords(18).is_none());
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn sweep_json_defaults() {
+        let sweep: PolygonSweep =
+            serde_json::from_value(serde_json::json!({ "sides": [6, 8] })).unwrap();
+        assert_eq!(sweep, PolygonSweep::sides(SidesSpec::List(vec![6, 8])));
+        let c = sweep.coords(1).unwrap();
+        assert_eq!((c.sides, c.rotation, c.scale), (8, 0.0, 1.0));
+    }
+}
@@ -0,0 +1,34 @@
+//! Per-row seed derivation shared by the token-addressed generators.
+//!
+//! Why this file exists
+//! - Every generator that replays rows by `ReplayToken { seed, index }` needs
+//!   the same mixing step, and replay compatibility depends on it never
+//!   drifting between families. One definition keeps it honest.
+//! - SplitMix64 is a cheap bijective mixer; feeding it `seed ^ mix(index)`
+//!   decorrelates neighbouring indices before seeding `StdRng`.
+//!
+//! References
+//! - S. Vigna, "Further scramblings of Marsaglia's xorshift generators" (SplitMix64 finalizer).
+
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+
+use crate::geom2::rand::ReplayToken;
+
+/// Per-row RNG; mixing keeps neighbouring indices decorrelated.
+pub(crate) fn token_rng(token: ReplayToken) -> StdRng {
+    StdRng::seed_from_u64(derive_seed(token.seed, token.index))
+}
+
+/// SplitMix64 finalizer (bijective 64-bit mix).
+pub(crate) fn splitmix64(mut z: u64) -> u64 {
+    z = z.wrapping_add(0x9E37_79B9_7F4A_7C15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^ (z >> 31)
+}
+
+/// Seed of row `index` for seed-addressed families (`generate_single(params, seed)`).
+pub fn derive_seed(master_seed: u64, index: u64) -> u64 {
+    splitmix64(master_seed ^ splitmix64(index))
+}
@@ -0,0 +1,49 @@
+//! Serde helper: `Matrix4` as a list of four rows.
+//!
+//! Why this file exists
+//! - Configs and atlas rows write matrices as `[[a, b, c, d], ...]` (rows),
+//!   matching the PyO3 `anisotropy` convention. nalgebra's own serde support
+//!   emits a flat column-major array, which is easy to misread by hand and
+//!   would silently transpose non-symmetric inputs.
+//!
+//! Usage: `#[serde(with = "super::serde_matrix")]` on a `Matrix4<f64>` field,
+//! `serde_matrix::option` on an `Option<Matrix4<f64>>` (`null` for `None`).
+
+use nalgebra::Matrix4;
+use serde::de::Error as _;
+use serde::{Deserialize, Deserializer, Serializer};
+
+pub fn serialize<S: Serializer>(m: &Matrix4<f64>, s: S) -> Result<S::Ok, S::Error> {
+    let rows: [[f64; 4]; 4] = std::array::from_fn(|i| std::array::from_fn(|j| m[(i, j)]));
+    serde::Serialize::serialize(&rows, s)
+}
+
+pub fn deserialize<'de, D: Deserializer<'de>>(d: D) -> Result<Matrix4<f64>, D::Error> {
+    let rows: Vec<Vec<f64>> = Vec::deserialize(d)?;
+    if rows.len() != 4 || rows.iter().any(|r| r.len() != 4) {
+        return Err(D::Error::custom(
+            "expected a 4x4 matrix (list of four rows)",
+        ));
+    }
+    let data: Vec<f64> = rows.into_iter().flatten().collect();
+    Ok(Matrix4::from_row_slice(&data))
+}
+
+/// The same row layout for `Option<Matrix4<f64>>`.
+pub mod option {
+    use nalgebra::Matrix4;
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(m: &Option<Matrix4<f64>>, s: S) -> Result<S::Ok, S::Error> {
+        match m {
+            Some(m) => super::serialize(m, s),
+            None => s.serialize_none(),
+        }
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(d: D) -> Result<Option<Matrix4<f64>>, D::Error> {
+        #[derive(Deserialize)]
+        struct Rows(#[serde(with = "super")] Matrix4<f64>);
+        Ok(Option::<Rows>::deserialize(d)?.map(|Rows(m)| m))
+    }
+}
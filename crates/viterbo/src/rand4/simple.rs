@@ -0,0 +1,331 @@
+//! Simple polytopes: every vertex lies on exactly four facets.
+//!
+//! Why this family exists
+//! - The oriented-edge algorithm assumes generic position (each vertex on
+//!   four facets, each ridge on two). Those assumptions hold exactly on
+//!   simple polytopes, and tests of the solver want a clean stream of them
+//!   rather than filtering degenerate rows after the fact.
+//! - A row draws `m ∈ [facets_min, facets_max]` unit normals (or takes the
+//!   fixed `normals` list) and independent offsets in
+//!   `[offset_min, offset_max]`. Random offsets put the arrangement in
+//!   general position almost surely; redundant halfspaces are dropped.
+//! - Simplicity is verified from the tight-index sets of the vertices
+//!   (`tight_indices`): exactly four facets within `EPS_TIGHT`, and the fifth
+//!   smallest slack at least `min_slack · scale`. The margin keeps rows away
+//!   from near-degenerate vertices that rounding could make non-simple.
+//!
+//! Replay
+//! - Rows are addressed by `ReplayToken { seed, index }` (per-row RNG, see
+//!   `rand4::seeding`).
+//!
+//! References
+//! - TH: docs/src/thesis/random-polytopes.md (“Simple Polytopes”)
+//! - Code: crates/viterbo/src/spec.md (generic-position assumptions)
+
+use nalgebra::Vector4;
+use rand::Rng;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use super::dirs::{directions_for_row, DirectionMode};
+use super::group_symmetric::is_bounded;
+use super::seeding::token_rng;
+use super::{
+    reduce_facets, AcceptanceStats, AcceptanceStatsSource, Checkpoint, GeneratorError,
+    IndexedGenerator, PolytopeGenerator4, PolytopeSample4, StreamState,
+};
+use crate::geom2::rand::ReplayToken;
+use crate::geom4::{Hs4, Poly4};
+
+/// Relative slack below which a vertex counts as lying on a facet.
+pub const EPS_TIGHT: f64 = 1e-9;
+
+/// Parameters for [`SimplePolytopeGenerator`].
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct SimpleParams {
+    /// Halfspaces drawn per attempt (before redundant ones are dropped).
+    pub facets_min: usize,
+    pub facets_max: usize,
+    pub offset_min: f64,
+    pub offset_max: f64,
+    pub directions: DirectionMode,
+    /// Fixed normal set; overrides `facets_*` and `directions` when given.
+    pub normals: Option<Vec<[f64; 4]>>,
+    /// Required relative slack of the fifth-nearest facet at every vertex.
+    pub min_slack: f64,
+    pub max_attempts: usize,
+}
+
+impl Default for SimpleParams {
+    fn default() -> Self {
+        Self {
+            facets_min: 8,
+            facets_max: 16,
+            offset_min: 0.8,
+            offset_max: 1.2,
+            directions: DirectionMode::default(),
+            normals: None,
+            min_slack: 1e-6,
+            max_attempts: 50,
+        }
+    }
+}
+
+impl SimpleParams {
+    pub fn validate(&self) -> Result<(), GeneratorError> {
+        let counts_ok = 5 <= self.facets_min && self.facets_min <= self.facets_max;
+        let offsets_ok = 0.0 < self.offset_min && self.offset_min <= self.offset_max;
+        if !counts_ok || !offsets_ok {
+            return Err(GeneratorError::InvalidParams(
+                "simple: need 5 <= facets_min <= facets_max and 0 < offset_min <= offset_max"
+                    .into(),
+            ));
+        }
+        if let Some(normals) = &self.normals {
+            let nonzero = normals.iter().all(|n| Vector4::from(*n).norm() > 0.0);
+            if normals.len() < 5 || !nonzero {
+                return Err(GeneratorError::InvalidParams(
+                    "simple: fixed normals need at least 5 nonzero vectors".into(),
+                ));
+            }
+        }
+        if self.min_slack < 0.0 || self.max_attempts == 0 {
+            return Err(GeneratorError::InvalidParams(
+                "simple: need min_slack >= 0 and max_attempts > 0".into(),
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// Streaming generator over simple polytopes.
+#[derive(Clone, Debug)]
+pub struct SimplePolytopeGenerator {
+    params: SimpleParams,
+    seed: u64,
+    next_index: u64,
+    stats: AcceptanceStats,
+}
+
+impl SimplePolytopeGenerator {
+    pub fn new(params: SimpleParams, seed: u64) -> Result<Self, GeneratorError> {
+        params.validate()?;
+        Ok(Self {
+            params,
+            seed,
+            next_index: 0,
+            stats: AcceptanceStats::default(),
+        })
+    }
+
+    pub fn params(&self) -> &SimpleParams {
+        &self.params
+    }
+
+    /// Build the row addressed by `token` without constructing a generator.
+    pub fn sample_with_token(
+        params: &SimpleParams,
+        token: ReplayToken,
+    ) -> Result<Poly4, GeneratorError> {
+        params.validate()?;
+        sample_impl(params, token, &mut AcceptanceStats::default())
+    }
+}
+
+impl PolytopeGenerator4 for SimplePolytopeGenerator {
+    type Replay = ReplayToken;
+
+    fn generate_next(&mut self) -> Result<Option<PolytopeSample4<ReplayToken>>, GeneratorError> {
+        let token = ReplayToken {
+            seed: self.seed,
+            index: self.next_index,
+        };
+        let polytope = sample_impl(&self.params, token, &mut self.stats)?;
+        self.next_index += 1;
+        Ok(Some(PolytopeSample4 {
+            polytope,
+            replay: token,
+        }))
+    }
+
+    fn regenerate(&self, replay: &ReplayToken) -> Result<Poly4, GeneratorError> {
+        sample_impl(&self.params, *replay, &mut AcceptanceStats::default())
+    }
+}
+
+impl AcceptanceStatsSource for SimplePolytopeGenerator {
+    fn acceptance_stats(&self) -> &AcceptanceStats {
+        &self.stats
+    }
+}
+
+impl Checkpoint for SimplePolytopeGenerator {
+    type State = StreamState;
+
+    fn save_state(&self) -> StreamState {
+        StreamState {
+            seed: self.seed,
+            next_index: self.next_index,
+            stats: self.stats.clone(),
+        }
+    }
+
+    fn restore_state(&mut self, state: StreamState) -> Result<(), GeneratorError> {
+        state.check_seed(self.seed)?;
+        self.next_index = state.next_index;
+        self.stats = state.stats;
+        Ok(())
+    }
+}
+
+impl IndexedGenerator for SimplePolytopeGenerator {
+    fn row_count(&self) -> Option<u64> {
+        None
+    }
+
+    fn replay_at(&self, index: u64) -> Option<ReplayToken> {
+        Some(ReplayToken {
+            seed: self.seed,
+            index,
+        })
+    }
+}
+
+/// Indices of the facets through each vertex (slack within `EPS_TIGHT`, relative).
+pub fn tight_indices(poly: &mut Poly4) -> Vec<Vec<usize>> {
+    poly.ensure_vertices_from_h();
+    let scale = vertex_scale(poly);
+    poly.v
+        .iter()
+        .map(|v| {
+            poly.h
+                .iter()
+                .enumerate()
+                .filter(|(_, h)| (h.c - h.n.dot(v)).abs() <= EPS_TIGHT * scale * h.n.norm())
+                .map(|(i, _)| i)
+                .collect()
+        })
+        .collect()
+}
+
+/// Whether every vertex lies on exactly four facets and at least
+/// `min_slack · scale` away from all others.
+pub fn is_simple(poly: &mut Poly4, min_slack: f64) -> bool {
+    let tight = tight_indices(poly);
+    let scale = vertex_scale(poly);
+    !tight.is_empty()
+        && tight.iter().zip(&poly.v).all(|(t, v)| {
+            t.len() == 4
+                && poly
+                    .h
+                    .iter()
+                    .enumerate()
+                    .filter(|(i, _)| !t.contains(i))
+                    .all(|(_, h)| h.c - h.n.dot(v) >= min_slack * scale * h.n.norm())
+        })
+}
+
+fn vertex_scale(poly: &Poly4) -> f64 {
+    poly.v.iter().map(|v| v.norm()).fold(1.0, f64::max)
+}
+
+fn sample_impl(
+    params: &SimpleParams,
+    token: ReplayToken,
+    stats: &mut AcceptanceStats,
+) -> Result<Poly4, GeneratorError> {
+    let mut rng = token_rng(token);
+    for _ in 0..params.max_attempts {
+        stats.record_attempt();
+        let normals: Vec<Vector4<f64>> = match &params.normals {
+            Some(fixed) => fixed
+                .iter()
+                .map(|n| Vector4::from(*n).normalize())
+                .collect(),
+            None => {
+                let m = rng.gen_range(params.facets_min..=params.facets_max);
+                directions_for_row(params.directions, &mut rng, token, m)
+            }
+        };
+        let h: Vec<Hs4> = normals
+            .into_iter()
+            .map(|n| Hs4::new(n, rng.gen_range(params.offset_min..=params.offset_max)))
+            .collect();
+        if !is_bounded(&h) {
+            stats.record_rejection("unbounded");
+            continue;
+        }
+        let mut poly = reduce_facets(Poly4::from_h(h));
+        if poly.check_canonical().is_err() {
+            stats.record_rejection("not_canonical");
+            continue;
+        }
+        if !is_simple(&mut poly, params.min_slack) {
+            stats.record_rejection("not_simple");
+            continue;
+        }
+        stats.record_accepted();
+        return Ok(poly);
+    }
+    stats.record_exhausted();
+    Err(GeneratorError::Degenerate(format!(
+        "simple: no simple polytope after {} attempts (seed={}, index={})",
+        params.max_attempts, token.seed, token.index
+    )))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cut_cube(offset: f64) -> Poly4 {
+        let mut hs = Vec::new();
+        for k in 0..4 {
+            let mut e = Vector4::zeros();
+            e[k] = 1.0;
+            hs.push(Hs4::new(e, 1.0));
+            hs.push(Hs4::new(-e, 1.0));
+        }
+        // Cuts one corner of the cube: simple iff it avoids the other vertices.
+        hs.push(Hs4::new(Vector4::repeat(1.0), offset));
+        Poly4::from_h(hs)
+    }
+
+    #[test]
+    fn simplicity_check_on_cut_cubes() {
+        assert!(is_simple(&mut cut_cube(10.0), 1e-6));
+        assert!(is_simple(&mut cut_cube(3.5), 1e-6));
+        // Passes through the four neighbours of the cut corner.
+        let mut degenerate = cut_cube(2.0);
+        let tight = tight_indices(&mut degenerate);
+        assert!(tight.iter().any(|t| t.len() == 5));
+        assert!(!is_simple(&mut degenerate, 1e-6));
+    }
+
+    #[test]
+    fn stream_is_simple_and_replays() {
+        let params = SimpleParams {
+            facets_min: 9,
+            facets_max: 10,
+            ..SimpleParams::default()
+        };
+        let mut gen = SimplePolytopeGenerator::new(params, 3).unwrap();
+        for _ in 0..3 {
+            let mut s = gen.generate_next().unwrap().unwrap();
+            assert!(tight_indices(&mut s.polytope).iter().all(|t| t.len() == 4));
+            assert_eq!(gen.regenerate(&s.replay).unwrap().h, s.polytope.h);
+        }
+    }
+
+    #[test]
+    fn fixed_normals_are_validated() {
+        let params = SimpleParams {
+            normals: Some(vec![[1.0, 0.0, 0.0, 0.0]; 4]),
+            ..SimpleParams::default()
+        };
+        assert!(params.validate().is_err());
+        assert!(SimpleParams::default().validate().is_ok());
+    }
+}
@@ -0,0 +1,319 @@
+//! Simplicial polytopes: every facet is a 3-simplex.
+//!
+//! Why this family exists
+//! - Capacity–duality hypotheses compare a body with its polar. Polars of
+//!   simple polytopes are simplicial and vice versa, so paired streams of
+//!   both kinds (this file and `rand4::simple`) let those tests run on clean
+//!   combinatorics rather than on whatever degeneracies a generic sampler
+//!   produces.
+//! - A row draws `m ∈ [vertices_min, vertices_max]` points `r·u` (unit
+//!   direction `u`, radius `r ∈ [radius_min, radius_max]`) and kicks each by
+//!   an isotropic Gaussian of size `perturbation · radius_max`. The kick
+//!   puts the points in general position, so their hull is simplicial almost
+//!   surely; points that end up interior are dropped.
+//! - Simpliciality is verified from the facet vertex counts
+//!   (`facet_vertex_counts`): exactly four vertices within `EPS_TIGHT` of
+//!   every facet, and every other vertex at least `min_slack · scale` away.
+//!
+//! Replay
+//! - Rows are addressed by `ReplayToken { seed, index }` (per-row RNG, see
+//!   `rand4::seeding`).
+//!
+//! References
+//! - TH: docs/src/thesis/random-polytopes.md (“Simplicial Polytopes”)
+//! - Code: crates/viterbo/src/rand4/simple.rs (the dual notion)
+
+use nalgebra::Vector4;
+use rand::Rng;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use super::dirs::{directions_for_row, standard_normal4, DirectionMode};
+use super::seeding::token_rng;
+use super::{
+    AcceptanceStats, AcceptanceStatsSource, Checkpoint, GeneratorError, IndexedGenerator,
+    PolytopeGenerator4, PolytopeSample4, StreamState, EPS_TIGHT,
+};
+use crate::geom2::rand::ReplayToken;
+use crate::geom4::Poly4;
+
+/// Parameters for [`SimplicialPolytopeGenerator`].
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct SimplicialParams {
+    /// Points drawn per attempt (interior ones are dropped).
+    pub vertices_min: usize,
+    pub vertices_max: usize,
+    pub radius_min: f64,
+    pub radius_max: f64,
+    /// Gaussian kick per point, relative to `radius_max`.
+    pub perturbation: f64,
+    pub directions: DirectionMode,
+    /// Required relative slack of every non-incident vertex at every facet.
+    pub min_slack: f64,
+    pub max_attempts: usize,
+}
+
+impl Default for SimplicialParams {
+    fn default() -> Self {
+        Self {
+            vertices_min: 10,
+            vertices_max: 20,
+            radius_min: 0.5,
+            radius_max: 1.0,
+            perturbation: 1e-3,
+            directions: DirectionMode::default(),
+            min_slack: 1e-6,
+            max_attempts: 50,
+        }
+    }
+}
+
+impl SimplicialParams {
+    pub fn validate(&self) -> Result<(), GeneratorError> {
+        let counts_ok = 5 <= self.vertices_min && self.vertices_min <= self.vertices_max;
+        let radii_ok = 0.0 < self.radius_min && self.radius_min <= self.radius_max;
+        if !counts_ok || !radii_ok {
+            return Err(GeneratorError::InvalidParams(
+                "simplicial: need 5 <= vertices_min <= vertices_max and 0 < radius_min <= radius_max"
+                    .into(),
+            ));
+        }
+        if self.perturbation < 0.0 || self.min_slack < 0.0 || self.max_attempts == 0 {
+            return Err(GeneratorError::InvalidParams(
+                "simplicial: need perturbation >= 0, min_slack >= 0 and max_attempts > 0".into(),
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// Streaming generator over simplicial polytopes.
+#[derive(Clone, Debug)]
+pub struct SimplicialPolytopeGenerator {
+    params: SimplicialParams,
+    seed: u64,
+    next_index: u64,
+    stats: AcceptanceStats,
+}
+
+impl SimplicialPolytopeGenerator {
+    pub fn new(params: SimplicialParams, seed: u64) -> Result<Self, GeneratorError> {
+        params.validate()?;
+        Ok(Self {
+            params,
+            seed,
+            next_index: 0,
+            stats: AcceptanceStats::default(),
+        })
+    }
+
+    pub fn params(&self) -> &SimplicialParams {
+        &self.params
+    }
+
+    /// Build the row addressed by `token` without constructing a generator.
+    pub fn sample_with_token(
+        params: &SimplicialParams,
+        token: ReplayToken,
+    ) -> Result<Poly4, GeneratorError> {
+        params.validate()?;
+        sample_impl(params, token, &mut AcceptanceStats::default())
+    }
+}
+
+impl PolytopeGenerator4 for SimplicialPolytopeGenerator {
+    type Replay = ReplayToken;
+
+    fn generate_next(&mut self) -> Result<Option<PolytopeSample4<ReplayToken>>, GeneratorError> {
+        let token = ReplayToken {
+            seed: self.seed,
+            index: self.next_index,
+        };
+        let polytope = sample_impl(&self.params, token, &mut self.stats)?;
+        self.next_index += 1;
+        Ok(Some(PolytopeSample4 {
+            polytope,
+            replay: token,
+        }))
+    }
+
+    fn regenerate(&self, replay: &ReplayToken) -> Result<Poly4, GeneratorError> {
+        sample_impl(&self.params, *replay, &mut AcceptanceStats::default())
+    }
+}
+
+impl AcceptanceStatsSource for SimplicialPolytopeGenerator {
+    fn acceptance_stats(&self) -> &AcceptanceStats {
+        &self.stats
+    }
+}
+
+impl Checkpoint for SimplicialPolytopeGenerator {
+    type State = StreamState;
+
+    fn save_state(&self) -> StreamState {
+        StreamState {
+            seed: self.seed,
+            next_index: self.next_index,
+            stats: self.stats.clone(),
+        }
+    }
+
+    fn restore_state(&mut self, state: StreamState) -> Result<(), GeneratorError> {
+        state.check_seed(self.seed)?;
+        self.next_index = state.next_index;
+        self.stats = state.stats;
+        Ok(())
+    }
+}
+
+impl IndexedGenerator for SimplicialPolytopeGenerator {
+    fn row_count(&self) -> Option<u64> {
+        None
+    }
+
+    fn replay_at(&self, index: u64) -> Option<ReplayToken> {
+        Some(ReplayToken {
+            seed: self.seed,
+            index,
+        })
+    }
+}
+
+/// Number of vertices on each facet (slack within `EPS_TIGHT`, relative).
+pub fn facet_vertex_counts(poly: &mut Poly4) -> Vec<usize> {
+    poly.ensure_vertices_from_h();
+    let scale = vertex_scale(poly);
+    poly.h
+        .iter()
+        .map(|h| {
+            let tol = EPS_TIGHT * scale * h.n.norm();
+            poly.v
+                .iter()
+                .filter(|v| (h.c - h.n.dot(v)).abs() <= tol)
+                .count()
+        })
+        .collect()
+}
+
+/// Whether every facet carries exactly four vertices and every other vertex
+/// is at least `min_slack · scale` away from it.
+pub fn is_simplicial(poly: &mut Poly4, min_slack: f64) -> bool {
+    let counts = facet_vertex_counts(poly);
+    let scale = vertex_scale(poly);
+    !counts.is_empty()
+        && counts.iter().zip(&poly.h).all(|(&count, h)| {
+            let tight = EPS_TIGHT * scale * h.n.norm();
+            let far = min_slack * scale * h.n.norm();
+            count == 4
+                && poly.v.iter().all(|v| {
+                    let slack = h.c - h.n.dot(v);
+                    slack.abs() <= tight || slack >= far
+                })
+        })
+}
+
+fn vertex_scale(poly: &Poly4) -> f64 {
+    poly.v.iter().map(|v| v.norm()).fold(1.0, f64::max)
+}
+
+/// Keep only points that lie on some facet (drops interior input points).
+fn drop_interior_points(poly: &mut Poly4) {
+    let scale = vertex_scale(poly);
+    let h = &poly.h;
+    poly.v.retain(|v| {
+        h.iter()
+            .any(|hs| (hs.c - hs.n.dot(v)).abs() <= EPS_TIGHT * scale * hs.n.norm())
+    });
+}
+
+fn sample_impl(
+    params: &SimplicialParams,
+    token: ReplayToken,
+    stats: &mut AcceptanceStats,
+) -> Result<Poly4, GeneratorError> {
+    let mut rng = token_rng(token);
+    let kick = params.perturbation * params.radius_max;
+    for _ in 0..params.max_attempts {
+        stats.record_attempt();
+        let m = rng.gen_range(params.vertices_min..=params.vertices_max);
+        let dirs = directions_for_row(params.directions, &mut rng, token, m);
+        let points: Vec<Vector4<f64>> = dirs
+            .into_iter()
+            .map(|u| {
+                let r = rng.gen_range(params.radius_min..=params.radius_max);
+                u * r + standard_normal4(&mut rng) * kick
+            })
+            .collect();
+        let mut poly = Poly4::from_v(points);
+        poly.ensure_halfspaces_from_v();
+        if poly.h.is_empty() || poly.h.iter().any(|h| h.c <= 0.0) {
+            stats.record_rejection("origin_not_interior");
+            continue;
+        }
+        drop_interior_points(&mut poly);
+        if poly.check_canonical().is_err() {
+            stats.record_rejection("not_canonical");
+            continue;
+        }
+        if !is_simplicial(&mut poly, params.min_slack) {
+            stats.record_rejection("not_simplicial");
+            continue;
+        }
+        stats.record_accepted();
+        return Ok(poly);
+    }
+    stats.record_exhausted();
+    Err(GeneratorError::Degenerate(format!(
+        "simplicial: no simplicial polytope after {} attempts (seed={}, index={})",
+        params.max_attempts, token.seed, token.index
+    )))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::geom4::Hs4;
+
+    fn cross_polytope() -> Poly4 {
+        let mut hs = Vec::new();
+        for signs in 0..16u32 {
+            let n = Vector4::from_fn(|k, _| if signs >> k & 1 == 1 { -1.0 } else { 1.0 });
+            hs.push(Hs4::new(n, 1.0));
+        }
+        Poly4::from_h(hs)
+    }
+
+    fn cube() -> Poly4 {
+        let mut hs = Vec::new();
+        for k in 0..4 {
+            let mut e = Vector4::zeros();
+            e[k] = 1.0;
+            hs.push(Hs4::new(e, 1.0));
+            hs.push(Hs4::new(-e, 1.0));
+        }
+        Poly4::from_h(hs)
+    }
+
+    #[test]
+    fn cross_polytope_is_simplicial_and_cube_is_not() {
+        let mut cross = cross_polytope();
+        assert_eq!(facet_vertex_counts(&mut cross), vec![4; 16]);
+        assert!(is_simplicial(&mut cross, 1e-6));
+        let mut cube = cube();
+        assert_eq!(facet_vertex_counts(&mut cube), vec![8; 8]);
+        assert!(!is_simplicial(&mut cube, 1e-6));
+    }
+
+    #[test]
+    fn params_are_validated() {
+        assert!(SimplicialParams::default().validate().is_ok());
+        let bad = SimplicialParams {
+            vertices_min: 4,
+            ..SimplicialParams::default()
+        };
+        assert!(bad.validate().is_err());
+    }
+}
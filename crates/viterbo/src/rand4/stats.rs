@@ -0,0 +1,98 @@
+//! Acceptance-rate and degeneracy statistics of generator retry loops.
+//!
+//! Why this file exists
+//! - Generators retry degenerate draws internally (`max_attempts`), so the
+//!   effective rejection rate of a configuration was invisible. Atlas
+//!   provenance should record it: a family that discards 90% of its draws
+//!   samples a very different distribution than its parameters suggest.
+//! - Counts are kept per reason (stable snake_case labels) and serialize to
+//!   JSON for provenance sidecars.
+//! - Only streaming draws (`generate_next`) are counted; `regenerate` and the
+//!   stateless `sample_with_token` paths leave the statistics untouched.
+//!
+//! References
+//! - TH: docs/src/thesis/random-polytopes.md (“Reproducibility”)
+
+use std::collections::BTreeMap;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Attempt and rejection counters of one generator.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct AcceptanceStats {
+    /// Samples emitted.
+    pub accepted: u64,
+    /// Draws made, accepted or not.
+    pub attempts: u64,
+    /// Rejected draws by reason.
+    pub degeneracies: BTreeMap<String, u64>,
+    /// Rows abandoned after `max_attempts` (surfaced as `GeneratorError::Degenerate`).
+    pub exhausted: u64,
+}
+
+impl AcceptanceStats {
+    pub fn record_attempt(&mut self) {
+        self.attempts += 1;
+    }
+
+    pub fn record_rejection(&mut self, reason: &str) {
+        *self.degeneracies.entry(reason.to_owned()).or_default() += 1;
+    }
+
+    pub fn record_accepted(&mut self) {
+        self.accepted += 1;
+    }
+
+    pub fn record_exhausted(&mut self) {
+        self.exhausted += 1;
+    }
+
+    pub fn rejected(&self) -> u64 {
+        self.degeneracies.values().sum()
+    }
+
+    /// Fraction of draws that were rejected (`None` before the first draw).
+    pub fn rejection_rate(&self) -> Option<f64> {
+        (self.attempts > 0).then(|| self.rejected() as f64 / self.attempts as f64)
+    }
+
+    /// Add another generator's counters (e.g. merging shards).
+    pub fn merge(&mut self, other: &AcceptanceStats) {
+        self.accepted += other.accepted;
+        self.attempts += other.attempts;
+        self.exhausted += other.exhausted;
+        for (reason, n) in &other.degeneracies {
+            *self.degeneracies.entry(reason.clone()).or_default() += n;
+        }
+    }
+}
+
+/// Generators that report their acceptance statistics.
+pub trait AcceptanceStatsSource {
+    fn acceptance_stats(&self) -> &AcceptanceStats;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rates_and_merge() {
+        let mut a = AcceptanceStats::default();
+        assert_eq!(a.rejection_rate(), None);
+        for _ in 0..4 {
+            a.record_attempt();
+        }
+        a.record_rejection("origin_not_interior");
+        a.record_accepted();
+        let mut b = a.clone();
+        b.record_rejection("not_canonical");
+        a.merge(&b);
+        assert_eq!(a.attempts, 8);
+        assert_eq!(a.degeneracies["origin_not_interior"], 2);
+        assert_eq!(a.rejected(), 3);
+        assert_eq!(a.rejection_rate(), Some(3.0 / 8.0));
+    }
+}
@@ -0,0 +1,455 @@
+//! Online summary statistics of a sample stream (single pass, constant memory).
+//!
+//! Why this file exists
+//! - Atlas runs should end with a distribution summary (facet and vertex
+//!   counts, volume, inradius) without a second pass over the written rows.
+//!   `StreamStats` ingests samples as they are produced; `StatsGenerator`
+//!   does so transparently for any generator.
+//! - Counts are small integers, so they keep an exact histogram (exact
+//!   quantiles). Volume and inradius keep Welford mean/variance, min/max, and
+//!   one P² estimator per reported quantile (five markers each, no stored
+//!   samples). P² is exact for the first five observations and converges for
+//!   continuous distributions; it is not mergeable, so parallel runs should
+//!   report per-shard summaries.
+//! - The inradius is taken about the origin (smallest facet distance), as in
+//!   the filters; it is the quantity generators control directly.
+//!
+//! References
+//! - R. Jain, I. Chlamtac, "The P² algorithm for dynamic calculation of
+//!   quantiles and histograms without storing observations" (CACM 1985).
+//! - Code: crates/viterbo/src/rand4/filter.rs (origin inradius)
+
+use std::collections::BTreeMap;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+#[cfg(feature = "serde")]
+use serde_json::Value;
+
+use super::filter::origin_inradius;
+use super::{GeneratorError, PolytopeGenerator4, PolytopeSample4};
+use crate::geom4::{volume4, Poly4};
+
+/// Quantiles reported for every tracked quantity.
+pub const REPORTED_QUANTILES: [f64; 5] = [0.05, 0.25, 0.5, 0.75, 0.95];
+
+/// P² estimator of a single quantile.
+#[derive(Clone, Debug)]
+struct P2Quantile {
+    p: f64,
+    /// Marker heights; the first `count.min(5)` entries are the raw
+    /// observations until the markers are initialized.
+    q: [f64; 5],
+    /// Actual marker positions (1-based).
+    n: [f64; 5],
+    /// Desired marker positions and their increments.
+    np: [f64; 5],
+    dn: [f64; 5],
+    count: u64,
+}
+
+impl P2Quantile {
+    fn new(p: f64) -> Self {
+        Self {
+            p,
+            q: [0.0; 5],
+            n: [1.0, 2.0, 3.0, 4.0, 5.0],
+            np: [1.0, 1.0 + 2.0 * p, 1.0 + 4.0 * p, 3.0 + 2.0 * p, 5.0],
+            dn: [0.0, p / 2.0, p, (1.0 + p) / 2.0, 1.0],
+            count: 0,
+        }
+    }
+
+    fn push(&mut self, x: f64) {
+        if self.count < 5 {
+            self.q[self.count as usize] = x;
+            self.count += 1;
+            if self.count == 5 {
+                self.q.sort_by(f64::total_cmp);
+            }
+            return;
+        }
+        self.count += 1;
+        let q = &mut self.q;
+        let k = if x < q[0] {
+            q[0] = x;
+            0
+        } else if x >= q[4] {
+            q[4] = x;
+            3
+        } else {
+            (0..4).find(|&i| x < q[i + 1]).expect("q[0] <= x < q[4]")
+        };
+        self.n.iter_mut().skip(k + 1).for_each(|n| *n += 1.0);
+        for (np, dn) in self.np.iter_mut().zip(self.dn) {
+            *np += dn;
+        }
+        for i in 1..4 {
+            let d = self.np[i] - self.n[i];
+            let up = d >= 1.0 && self.n[i + 1] - self.n[i] > 1.0;
+            let down = d <= -1.0 && self.n[i - 1] - self.n[i] < -1.0;
+            if up || down {
+                let s = d.signum();
+                let candidate = self.parabolic(i, s);
+                self.q[i] = if self.q[i - 1] < candidate && candidate < self.q[i + 1] {
+                    candidate
+                } else {
+                    self.linear(i, s)
+                };
+                self.n[i] += s;
+            }
+        }
+    }
+
+    fn parabolic(&self, i: usize, d: f64) -> f64 {
+        let (q, n) = (&self.q, &self.n);
+        q[i] + d / (n[i + 1] - n[i - 1])
+            * ((n[i] - n[i - 1] + d) * (q[i + 1] - q[i]) / (n[i + 1] - n[i])
+                + (n[i + 1] - n[i] - d) * (q[i] - q[i - 1]) / (n[i] - n[i - 1]))
+    }
+
+    fn linear(&self, i: usize, d: f64) -> f64 {
+        let j = if d > 0.0 { i + 1 } else { i - 1 };
+        self.q[i] + d * (self.q[j] - self.q[i]) / (self.n[j] - self.n[i])
+    }
+
+    fn estimate(&self) -> Option<f64> {
+        match self.count {
+            0 => None,
+            1..=4 => {
+                let mut seen = self.q[..self.count as usize].to_vec();
+                seen.sort_by(f64::total_cmp);
+                Some(seen[nearest_rank(self.p, seen.len() as u64) as usize])
+            }
+            _ => Some(self.q[2]),
+        }
+    }
+}
+
+/// 0-based nearest-rank index of quantile `p` among `len` sorted values.
+fn nearest_rank(p: f64, len: u64) -> u64 {
+    ((p * len as f64).ceil() as u64).clamp(1, len) - 1
+}
+
+/// One quantile estimate.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct QuantileEstimate {
+    pub p: f64,
+    pub value: f64,
+}
+
+/// Serializable summary of one tracked quantity.
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct SummaryReport {
+    pub count: u64,
+    pub mean: Option<f64>,
+    /// Sample standard deviation (`None` below two observations).
+    pub std: Option<f64>,
+    pub min: Option<f64>,
+    pub max: Option<f64>,
+    pub quantiles: Vec<QuantileEstimate>,
+}
+
+/// Running moments, extremes and P² quantiles of a real-valued quantity.
+#[derive(Clone, Debug)]
+pub struct RunningSummary {
+    count: u64,
+    mean: f64,
+    m2: f64,
+    min: f64,
+    max: f64,
+    quantiles: Vec<P2Quantile>,
+}
+
+impl Default for RunningSummary {
+    fn default() -> Self {
+        Self {
+            count: 0,
+            mean: 0.0,
+            m2: 0.0,
+            min: f64::INFINITY,
+            max: f64::NEG_INFINITY,
+            quantiles: REPORTED_QUANTILES
+                .iter()
+                .map(|&p| P2Quantile::new(p))
+                .collect(),
+        }
+    }
+}
+
+impl RunningSummary {
+    pub fn push(&mut self, x: f64) {
+        self.count += 1;
+        let delta = x - self.mean;
+        self.mean += delta / self.count as f64;
+        self.m2 += delta * (x - self.mean);
+        self.min = self.min.min(x);
+        self.max = self.max.max(x);
+        self.quantiles.iter_mut().for_each(|q| q.push(x));
+    }
+
+    pub fn count(&self) -> u64 {
+        self.count
+    }
+
+    pub fn report(&self) -> SummaryReport {
+        let any = self.count > 0;
+        SummaryReport {
+            count: self.count,
+            mean: any.then_some(self.mean),
+            std: (self.count > 1).then(|| (self.m2 / (self.count - 1) as f64).sqrt()),
+            min: any.then_some(self.min),
+            max: any.then_some(self.max),
+            quantiles: self
+                .quantiles
+                .iter()
+                .filter_map(|q| q.estimate().map(|value| QuantileEstimate { p: q.p, value }))
+                .collect(),
+        }
+    }
+}
+
+/// Exact histogram of a small integer quantity.
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct CountHistogram {
+    pub bins: BTreeMap<usize, u64>,
+}
+
+impl CountHistogram {
+    pub fn push(&mut self, k: usize) {
+        *self.bins.entry(k).or_default() += 1;
+    }
+
+    pub fn count(&self) -> u64 {
+        self.bins.values().sum()
+    }
+
+    /// Exact nearest-rank quantile.
+    pub fn quantile(&self, p: f64) -> Option<usize> {
+        let total = self.count();
+        if total == 0 {
+            return None;
+        }
+        let rank = nearest_rank(p, total);
+        let mut seen = 0;
+        self.bins.iter().find_map(|(&k, &n)| {
+            seen += n;
+            (seen > rank).then_some(k)
+        })
+    }
+
+    pub fn report(&self) -> SummaryReport {
+        let total = self.count();
+        let moments = (total > 0).then(|| {
+            let mean = self
+                .bins
+                .iter()
+                .map(|(&k, &n)| k as f64 * n as f64)
+                .sum::<f64>()
+                / total as f64;
+            let ss: f64 = self
+                .bins
+                .iter()
+                .map(|(&k, &n)| n as f64 * (k as f64 - mean).powi(2))
+                .sum();
+            (mean, ss)
+        });
+        SummaryReport {
+            count: total,
+            mean: moments.map(|(mean, _)| mean),
+            std: moments
+                .filter(|_| total > 1)
+                .map(|(_, ss)| (ss / (total - 1) as f64).sqrt()),
+            min: self.bins.keys().next().map(|&k| k as f64),
+            max: self.bins.keys().next_back().map(|&k| k as f64),
+            quantiles: REPORTED_QUANTILES
+                .iter()
+                .filter_map(|&p| {
+                    self.quantile(p)
+                        .map(|k| QuantileEstimate { p, value: k as f64 })
+                })
+                .collect(),
+        }
+    }
+}
+
+/// Serializable end-of-run summary of a [`StreamStats`].
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct StreamStatsReport {
+    pub samples: u64,
+    pub facets: SummaryReport,
+    pub facet_histogram: CountHistogram,
+    pub vertices: SummaryReport,
+    pub vertex_histogram: CountHistogram,
+    pub volume: SummaryReport,
+    /// Samples whose volume could not be computed.
+    pub volume_failures: u64,
+    pub inradius: SummaryReport,
+}
+
+/// Single-pass accumulator over polytope samples.
+#[derive(Clone, Debug)]
+pub struct StreamStats {
+    samples: u64,
+    facets: CountHistogram,
+    vertices: CountHistogram,
+    track_volume: bool,
+    volume: RunningSummary,
+    volume_failures: u64,
+    inradius: RunningSummary,
+}
+
+impl Default for StreamStats {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl StreamStats {
+    pub fn new() -> Self {
+        Self {
+            samples: 0,
+            facets: CountHistogram::default(),
+            vertices: CountHistogram::default(),
+            track_volume: true,
+            volume: RunningSummary::default(),
+            volume_failures: 0,
+            inradius: RunningSummary::default(),
+        }
+    }
+
+    /// Skip the (comparatively expensive) volume computation.
+    pub fn without_volume(mut self) -> Self {
+        self.track_volume = false;
+        self
+    }
+
+    pub fn samples(&self) -> u64 {
+        self.samples
+    }
+
+    /// Ingest one body; fills in missing representations.
+    pub fn ingest(&mut self, poly: &mut Poly4) {
+        self.samples += 1;
+        poly.ensure_halfspaces_from_v();
+        poly.ensure_vertices_from_h();
+        self.facets.push(poly.h.len());
+        self.vertices.push(poly.v.len());
+        if self.track_volume {
+            match volume4(poly) {
+                Ok(vol) => self.volume.push(vol),
+                Err(_) => self.volume_failures += 1,
+            }
+        }
+        if let Some(r) = origin_inradius(poly) {
+            self.inradius.push(r);
+        }
+    }
+
+    pub fn ingest_sample<R>(&mut self, sample: &mut PolytopeSample4<R>) {
+        self.ingest(&mut sample.polytope);
+    }
+
+    pub fn report(&self) -> StreamStatsReport {
+        StreamStatsReport {
+            samples: self.samples,
+            facets: self.facets.report(),
+            facet_histogram: self.facets.clone(),
+            vertices: self.vertices.report(),
+            vertex_histogram: self.vertices.clone(),
+            volume: self.volume.report(),
+            volume_failures: self.volume_failures,
+            inradius: self.inradius.report(),
+        }
+    }
+
+    #[cfg(feature = "serde")]
+    pub fn to_json(&self) -> Value {
+        serde_json::to_value(self.report()).expect("report serializes")
+    }
+}
+
+/// Generator adapter feeding every emitted sample into a [`StreamStats`].
+#[derive(Clone, Debug)]
+pub struct StatsGenerator<G> {
+    base: G,
+    stats: StreamStats,
+}
+
+impl<G: PolytopeGenerator4> StatsGenerator<G> {
+    pub fn new(base: G, stats: StreamStats) -> Self {
+        Self { base, stats }
+    }
+
+    pub fn base(&self) -> &G {
+        &self.base
+    }
+
+    pub fn stats(&self) -> &StreamStats {
+        &self.stats
+    }
+
+    pub fn into_parts(self) -> (G, StreamStats) {
+        (self.base, self.stats)
+    }
+}
+
+impl<G: PolytopeGenerator4> PolytopeGenerator4 for StatsGenerator<G> {
+    type Replay = G::Replay;
+
+    fn generate_next(&mut self) -> Result<Option<PolytopeSample4<G::Replay>>, GeneratorError> {
+        let mut sample = self.base.generate_next()?;
+        if let Some(s) = sample.as_mut() {
+            self.stats.ingest_sample(s);
+        }
+        Ok(sample)
+    }
+
+    fn regenerate(&self, replay: &G::Replay) -> Result<Poly4, GeneratorError> {
+        self.base.regenerate(replay)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::rngs::StdRng;
+    use rand::{Rng, SeedableRng};
+
+    #[test]
+    fn p2_tracks_uniform_quantiles() {
+        let mut rng = StdRng::seed_from_u64(1);
+        let mut summary = RunningSummary::default();
+        for _ in 0..20_000 {
+            summary.push(rng.gen::<f64>());
+        }
+        let report = summary.report();
+        assert!((report.mean.unwrap() - 0.5).abs() < 0.01);
+        assert!((report.std.unwrap() - (1.0f64 / 12.0).sqrt()).abs() < 0.01);
+        for q in &report.quantiles {
+            assert!((q.value - q.p).abs() < 0.02, "{q:?}");
+        }
+        let mut small = RunningSummary::default();
+        [3.0, 1.0, 2.0].into_iter().for_each(|x| small.push(x));
+        assert_eq!(small.report().quantiles[2].value, 2.0);
+    }
+
+    #[test]
+    fn histogram_quantiles_are_exact() {
+        let mut hist = CountHistogram::default();
+        for k in [8, 8, 9, 10, 10, 10, 12] {
+            hist.push(k);
+        }
+        assert_eq!(hist.quantile(0.5), Some(10));
+        assert_eq!(hist.quantile(0.05), Some(8));
+        assert_eq!(hist.quantile(1.0), Some(12));
+        let report = hist.report();
+        assert_eq!((report.min, report.max), (Some(8.0), Some(12.0)));
+        #[cfg(feature = "serde")]
+        assert_eq!(serde_json::to_value(&report).unwrap()["count"], 7);
+    }
+}
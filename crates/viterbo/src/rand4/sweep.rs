@@ -0,0 +1,285 @@
+//! One-parameter families `P(t)` interpolating between two polytopes.
+//!
+//! Why this file exists
+//! - Capacity-continuity plots along 1-parameter families are a recurring
+//!   figure type. `FamilySweepGenerator` emits `P(t)` on a uniform grid
+//!   `t = 0, 1/(steps−1), …, 1` as an ordinary (finite, deterministic) stream,
+//!   so the atlas and filter tooling apply unchanged.
+//! - Facets correspond by index: facet `i` of `P(t)` interpolates facet `i` of
+//!   the endpoints. Both endpoints are normalized to unit normals; offsets
+//!   interpolate linearly (they stay positive, so the origin stays interior).
+//! - Normals either must agree (`NormalPath::Fixed`, the common "same normal
+//!   fan, different offsets" case) or follow the great-circle arc between the
+//!   endpoint normals (`NormalPath::Geodesic`, slerp). Antipodal normals have
+//!   no unique geodesic and are rejected; intermediate bodies that fail
+//!   `check_canonical` surface as `GeneratorError::Degenerate` at that row.
+//!
+//! Replay
+//! - The grid index; `SweepReplay { index, t }` also carries `t` for plots.
+//!
+//! References
+//! - TH: docs/src/thesis/random-polytopes.md (“Parametric Sweeps”)
+
+use nalgebra::Vector4;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use super::{Checkpoint, GeneratorError, IndexedGenerator, PolytopeGenerator4, PolytopeSample4};
+use crate::geom4::{Hs4, Poly4};
+
+/// Unit normals closer than this count as equal.
+const EPS_NORMAL: f64 = 1e-9;
+
+/// How facet normals move along the sweep.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum NormalPath {
+    /// Endpoint normals must coincide; only offsets move.
+    #[default]
+    Fixed,
+    /// Normals follow great-circle arcs on S³.
+    Geodesic,
+}
+
+/// Parameters for [`FamilySweepGenerator`].
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct SweepParams {
+    /// Grid points including both endpoints (at least 2).
+    pub steps: u64,
+    pub normals: NormalPath,
+}
+
+impl Default for SweepParams {
+    fn default() -> Self {
+        Self {
+            steps: 11,
+            normals: NormalPath::default(),
+        }
+    }
+}
+
+/// Replay value of a sweep row.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct SweepReplay {
+    pub index: u64,
+    pub t: f64,
+}
+
+/// Deterministic stream of `P(t)` between two polytopes with matching facets.
+#[derive(Clone, Debug)]
+pub struct FamilySweepGenerator {
+    start: Vec<Hs4>,
+    end: Vec<Hs4>,
+    params: SweepParams,
+    next_index: u64,
+}
+
+impl FamilySweepGenerator {
+    pub fn new(start: &Poly4, end: &Poly4, params: SweepParams) -> Result<Self, GeneratorError> {
+        if params.steps < 2 {
+            return Err(GeneratorError::InvalidParams(
+                "sweep: steps must be >= 2".into(),
+            ));
+        }
+        if start.h.len() != end.h.len() || start.h.is_empty() {
+            return Err(GeneratorError::InvalidParams(format!(
+                "sweep: endpoints need the same (nonzero) facet count, got {} and {}",
+                start.h.len(),
+                end.h.len()
+            )));
+        }
+        let start = unit_halfspaces(start)?;
+        let end = unit_halfspaces(end)?;
+        for (i, (a, b)) in start.iter().zip(&end).enumerate() {
+            let fixed_ok = (a.n - b.n).norm() < EPS_NORMAL;
+            let geodesic_ok = (a.n + b.n).norm() >= EPS_NORMAL;
+            let ok = match params.normals {
+                NormalPath::Fixed => fixed_ok,
+                NormalPath::Geodesic => geodesic_ok,
+            };
+            if !ok {
+                return Err(GeneratorError::InvalidParams(format!(
+                    "sweep: facet {i} normals are incompatible with {:?} interpolation",
+                    params.normals
+                )));
+            }
+        }
+        Ok(Self {
+            start,
+            end,
+            params,
+            next_index: 0,
+        })
+    }
+
+    pub fn params(&self) -> &SweepParams {
+        &self.params
+    }
+
+    /// Grid parameter of row `index`.
+    pub fn t_at(&self, index: u64) -> f64 {
+        index as f64 / (self.params.steps - 1) as f64
+    }
+
+    /// `P(t)` for any `t ∈ [0, 1]`, not only grid points.
+    pub fn poly_at(&self, t: f64) -> Result<Poly4, GeneratorError> {
+        let h = self
+            .start
+            .iter()
+            .zip(&self.end)
+            .map(|(a, b)| {
+                let n = match self.params.normals {
+                    NormalPath::Fixed => a.n,
+                    NormalPath::Geodesic => slerp(&a.n, &b.n, t),
+                };
+                Hs4::new(n, (1.0 - t) * a.c + t * b.c)
+            })
+            .collect();
+        let mut poly = Poly4::from_h(h);
+        poly.ensure_vertices_from_h();
+        poly.check_canonical()
+            .map_err(|e| GeneratorError::Degenerate(format!("sweep: P({t}) invalid: {e}")))?;
+        Ok(poly)
+    }
+}
+
+impl PolytopeGenerator4 for FamilySweepGenerator {
+    type Replay = SweepReplay;
+
+    fn generate_next(&mut self) -> Result<Option<PolytopeSample4<SweepReplay>>, GeneratorError> {
+        let sample = self.sample_at(self.next_index)?;
+        if sample.is_some() {
+            self.next_index += 1;
+        }
+        Ok(sample)
+    }
+
+    fn regenerate(&self, replay: &SweepReplay) -> Result<Poly4, GeneratorError> {
+        self.poly_at(self.t_at(replay.index))
+    }
+}
+
+impl IndexedGenerator for FamilySweepGenerator {
+    fn row_count(&self) -> Option<u64> {
+        Some(self.params.steps)
+    }
+
+    fn replay_at(&self, index: u64) -> Option<SweepReplay> {
+        (index < self.params.steps).then(|| SweepReplay {
+            index,
+            t: self.t_at(index),
+        })
+    }
+}
+
+impl Checkpoint for FamilySweepGenerator {
+    /// The next grid index.
+    type State = u64;
+
+    fn save_state(&self) -> u64 {
+        self.next_index
+    }
+
+    fn restore_state(&mut self, next_index: u64) -> Result<(), GeneratorError> {
+        if next_index > self.params.steps {
+            return Err(GeneratorError::InvalidParams(format!(
+                "checkpoint: grid index {next_index} beyond {} steps",
+                self.params.steps
+            )));
+        }
+        self.next_index = next_index;
+        Ok(())
+    }
+}
+
+fn unit_halfspaces(poly: &Poly4) -> Result<Vec<Hs4>, GeneratorError> {
+    poly.h
+        .iter()
+        .map(|h| {
+            let norm = h.n.norm();
+            if norm == 0.0 || h.c <= 0.0 {
+                return Err(GeneratorError::InvalidParams(
+                    "sweep: endpoints need nonzero normals and the origin in their interior".into(),
+                ));
+            }
+            Ok(Hs4::new(h.n / norm, h.c / norm))
+        })
+        .collect()
+}
+
+/// Point at fraction `t` along the great-circle arc from unit `a` to unit `b`.
+fn slerp(a: &Vector4<f64>, b: &Vector4<f64>, t: f64) -> Vector4<f64> {
+    let theta = a.dot(b).clamp(-1.0, 1.0).acos();
+    if theta < EPS_NORMAL {
+        return *a;
+    }
+    let s = theta.sin();
+    a * (((1.0 - t) * theta).sin() / s) + b * ((t * theta).sin() / s)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rand4::GeneratorIterExt;
+
+    fn box4(half: [f64; 4]) -> Poly4 {
+        let mut hs = Vec::new();
+        for k in 0..4 {
+            let mut e = Vector4::zeros();
+            e[k] = 1.0;
+            hs.push(Hs4::new(e, half[k]));
+            hs.push(Hs4::new(-e, half[k]));
+        }
+        Poly4::from_h(hs)
+    }
+
+    #[test]
+    fn offsets_interpolate_linearly() {
+        let gen = FamilySweepGenerator::new(
+            &box4([1.0; 4]),
+            &box4([1.0, 1.0, 1.0, 3.0]),
+            SweepParams {
+                steps: 5,
+                ..SweepParams::default()
+            },
+        )
+        .unwrap();
+        let rows: Vec<_> = gen.clone().into_samples().map(Result::unwrap).collect();
+        assert_eq!(rows.len(), 5);
+        assert_eq!(rows[2].replay.t, 0.5);
+        assert!((rows[2].polytope.h[6].c - 2.0).abs() < 1e-12);
+        assert_eq!(
+            gen.regenerate(&rows[3].replay).unwrap().h,
+            rows[3].polytope.h
+        );
+    }
+
+    #[test]
+    fn geodesic_normals_stay_unit_and_fixed_rejects_rotation() {
+        let start = box4([1.0; 4]);
+        let mut end = box4([1.0; 4]);
+        let (s, c) = 0.3f64.sin_cos();
+        for h in &mut end.h {
+            h.n = Vector4::new(
+                c * h.n[0] - s * h.n[1],
+                s * h.n[0] + c * h.n[1],
+                h.n[2],
+                h.n[3],
+            );
+        }
+        assert!(FamilySweepGenerator::new(&start, &end, SweepParams::default()).is_err());
+        let params = SweepParams {
+            steps: 3,
+            normals: NormalPath::Geodesic,
+        };
+        let gen = FamilySweepGenerator::new(&start, &end, params).unwrap();
+        let mid = gen.poly_at(0.5).unwrap();
+        let (s, c) = 0.15f64.sin_cos();
+        assert!((mid.h[0].n - Vector4::new(c, s, 0.0, 0.0)).norm() < 1e-12);
+        assert!(mid.h.iter().all(|h| (h.n.norm() - 1.0).abs() < 1e-12));
+    }
+}
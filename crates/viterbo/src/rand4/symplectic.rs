@@ -0,0 +1,115 @@
+//! Random linear symplectic maps of `R⁴ = C²` for data augmentation.
+//!
+//! Why this file exists
+//! - `c_EHZ` and the systolic ratio are invariant under linear symplectic
+//!   maps, so `Sp(4)` images are free augmentations for learned models, but
+//!   only if the map really preserves `ω`. Generic matrices built in NumPy
+//!   silently change the label.
+//! - `random_symplectic4` draws `A = K₁ · D · K₂` (Euler / Bloch–Messiah
+//!   decomposition): `K₁, K₂` Haar-random in `U(2) = Sp(4) ∩ O(4)` and
+//!   `D = diag(e^{s₁}, e^{s₂}, e^{−s₁}, e^{−s₂})` with `s_k` uniform in
+//!   `[−max_squeeze, max_squeeze]`. `max_squeeze = 0` gives a random rotation.
+//!
+//! Replay
+//! - The map is a pure function of `seed` (`StdRng::seed_from_u64`).
+//!
+//! References
+//! - TH: docs/src/thesis/random-polytopes.md (“Random Anisotropy (adapter)”)
+
+use std::f64::consts::PI;
+
+use nalgebra::Matrix4;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+use super::dirs::standard_normal4;
+use super::GeneratorError;
+
+/// Tolerance of [`is_symplectic`] (entrywise on `Aᵀ J A − J`).
+pub const EPS_SYMPLECTIC: f64 = 1e-9;
+
+/// The standard complex structure `J = [[0, −I], [I, 0]]` in `(x1, x2, y1, y2)`.
+pub fn j4() -> Matrix4<f64> {
+    let mut j = Matrix4::zeros();
+    for i in 0..2 {
+        j[(i, i + 2)] = -1.0;
+        j[(i + 2, i)] = 1.0;
+    }
+    j
+}
+
+/// `Aᵀ J A = J` up to [`EPS_SYMPLECTIC`].
+pub fn is_symplectic(a: &Matrix4<f64>) -> bool {
+    let j = j4();
+    (a.transpose() * j * a - j).amax() < EPS_SYMPLECTIC
+}
+
+/// Random element of `Sp(4)`; see the file header for the distribution.
+pub fn random_symplectic4(seed: u64, max_squeeze: f64) -> Result<Matrix4<f64>, GeneratorError> {
+    if !(max_squeeze >= 0.0 && max_squeeze.is_finite()) {
+        return Err(GeneratorError::InvalidParams(
+            "random_symplectic4: need 0 <= max_squeeze < inf".into(),
+        ));
+    }
+    let mut rng = StdRng::seed_from_u64(seed);
+    let k1 = haar_unitary2(&mut rng);
+    let k2 = haar_unitary2(&mut rng);
+    let mut d = Matrix4::identity();
+    for k in 0..2 {
+        let s = max_squeeze * (2.0 * rng.gen::<f64>() - 1.0);
+        d[(k, k)] = s.exp();
+        d[(k + 2, k + 2)] = (-s).exp();
+    }
+    Ok(k1 * d * k2)
+}
+
+/// Haar-random `U(2)` as a real 4×4 matrix `[[Re U, −Im U], [Im U, Re U]]`:
+/// a uniform unit quaternion gives `SU(2)`, times a uniform phase `e^{iφ}`.
+fn haar_unitary2(rng: &mut StdRng) -> Matrix4<f64> {
+    let q = standard_normal4(rng).normalize();
+    let phi = 2.0 * PI * rng.gen::<f64>();
+    let (s, c) = phi.sin_cos();
+    // U = e^{iφ} [[α, −β̄], [β, ᾱ]] with α = q0 + i q1, β = q2 + i q3.
+    let mul = |re: f64, im: f64| (c * re - s * im, s * re + c * im);
+    let u = [
+        [mul(q[0], q[1]), mul(-q[2], q[3])],
+        [mul(q[2], q[3]), mul(q[0], -q[1])],
+    ];
+    let mut a = Matrix4::zeros();
+    for r in 0..2 {
+        for k in 0..2 {
+            let (re, im) = u[r][k];
+            a[(r, k)] = re;
+            a[(r + 2, k + 2)] = re;
+            a[(r, k + 2)] = -im;
+            a[(r + 2, k)] = im;
+        }
+    }
+    a
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn draws_are_symplectic_and_replayable() {
+        for seed in 0..50 {
+            let a = random_symplectic4(seed, 1.5).unwrap();
+            assert!(is_symplectic(&a), "seed {seed}");
+            assert!((a.determinant() - 1.0).abs() < 1e-9);
+        }
+        assert_eq!(
+            random_symplectic4(4, 0.5).unwrap(),
+            random_symplectic4(4, 0.5).unwrap()
+        );
+        assert!(random_symplectic4(0, -1.0).is_err());
+    }
+
+    #[test]
+    fn zero_squeeze_is_orthogonal() {
+        let a = random_symplectic4(9, 0.0).unwrap();
+        assert!((a.transpose() * a - Matrix4::identity()).amax() < 1e-12);
+        assert!(!is_symplectic(&Matrix4::from_diagonal_element(2.0)));
+    }
+}
@@ -0,0 +1,442 @@
+//! Conditional sampler for bodies with high systolic ratio (Metropolis chains).
+//!
+//! Why this file exists
+//! - IID families essentially never produce near-extremal bodies
+//!   (`c_EHZ² / (2·vol)` close to 1), which are the ones the project studies.
+//!   `SystolicMcmcGenerator` runs Metropolis chains over facet offsets,
+//!   conditioned on `score ≥ threshold`, using the capacity solver as oracle.
+//! - Chain state: the unit normals of a base sample (fixed) and log-offsets.
+//!   Proposals add `N(0, step²)` to every log-offset and re-centre their mean
+//!   to 0; the systolic ratio is scale invariant, so this only removes a
+//!   useless scale drift. Offsets stay positive and the normals keep
+//!   positively spanning R⁴, so every state is bounded with the origin
+//!   interior (some facets may become redundant).
+//! - Burn-in climbs greedily from the base sample until the threshold is met
+//!   (at most `burn_in_steps` proposals, else the chain is abandoned). Above
+//!   the threshold, proposals below it are rejected and the rest accepted with
+//!   probability `min(1, exp(β·Δscore))`: `β = 0` samples the offsets
+//!   uniformly (in log coordinates) on the superlevel set, `β > 0` tilts
+//!   towards higher ratios. Every `thin`-th step is emitted, and each chain
+//!   emits `samples_per_chain` rows before restarting from the next base
+//!   sample.
+//!
+//! Replay
+//! - `McmcReplay { base, chain, step }`: chain `chain` uses the RNG of
+//!   `ReplayToken { seed, index: chain }` and starts from the base row `base`;
+//!   `regenerate` re-runs it for `step` proposals. Replay therefore costs
+//!   `step` oracle calls and assumes a deterministic oracle.
+//!
+//! References
+//! - TH: docs/src/thesis/random-polytopes.md (“Conditional Sampling”)
+//! - Code: crates/viterbo/src/oriented_edge/dfs.rs::solve_with_defaults
+
+use nalgebra::Vector4;
+use rand::rngs::StdRng;
+use rand::Rng;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use super::dirs::standard_normal4;
+use super::seeding::token_rng;
+use super::{
+    AcceptanceStats, AcceptanceStatsSource, GeneratorError, PolytopeGenerator4, PolytopeSample4,
+};
+use crate::geom2::rand::ReplayToken;
+use crate::geom4::{volume4, Hs4, Poly4};
+use crate::oriented_edge::solve_with_defaults;
+
+/// Score of a body; `None` when it cannot be evaluated.
+pub trait ScoreOracle {
+    fn score(&self, poly: &mut Poly4) -> Option<f64>;
+}
+
+impl<F: Fn(&mut Poly4) -> Option<f64>> ScoreOracle for F {
+    fn score(&self, poly: &mut Poly4) -> Option<f64> {
+        self(poly)
+    }
+}
+
+/// The systolic ratio `c_EHZ² / (2·vol)` via the oriented-edge solver.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct SystolicRatio;
+
+impl ScoreOracle for SystolicRatio {
+    fn score(&self, poly: &mut Poly4) -> Option<f64> {
+        systolic_ratio4(poly)
+    }
+}
+
+/// `c_EHZ² / (2·vol)`; `None` if the solver finds no cycle or the volume fails.
+pub fn systolic_ratio4(poly: &mut Poly4) -> Option<f64> {
+    let volume = volume4(poly).ok().filter(|&v| v > 0.0)?;
+    let (capacity, _cycle) = solve_with_defaults(poly)?;
+    Some(capacity * capacity / (2.0 * volume))
+}
+
+/// Parameters for [`SystolicMcmcGenerator`].
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct McmcParams {
+    /// Emitted rows satisfy `score ≥ threshold`.
+    pub threshold: f64,
+    /// Inverse temperature above the threshold (`0` = uniform on the superlevel set).
+    pub beta: f64,
+    /// Standard deviation of the log-offset proposals.
+    pub step: f64,
+    /// Proposals allowed to reach the threshold before a chain is abandoned.
+    pub burn_in_steps: u64,
+    /// Proposals between emitted rows.
+    pub thin: u64,
+    /// Rows per chain before restarting from the next base sample.
+    pub samples_per_chain: u64,
+    /// Consecutive abandoned chains before giving up with `GeneratorError::Degenerate`.
+    pub max_failed_chains: u64,
+}
+
+impl Default for McmcParams {
+    fn default() -> Self {
+        Self {
+            threshold: 0.9,
+            beta: 0.0,
+            step: 0.05,
+            burn_in_steps: 2_000,
+            thin: 10,
+            samples_per_chain: 20,
+            max_failed_chains: 10,
+        }
+    }
+}
+
+impl McmcParams {
+    pub fn validate(&self) -> Result<(), GeneratorError> {
+        let finite = self.threshold.is_finite() && self.beta.is_finite() && self.step.is_finite();
+        if !finite || self.beta < 0.0 || self.step <= 0.0 {
+            return Err(GeneratorError::InvalidParams(
+                "mcmc: need finite threshold, beta >= 0 and step > 0".into(),
+            ));
+        }
+        if self.thin == 0 || self.samples_per_chain == 0 || self.max_failed_chains == 0 {
+            return Err(GeneratorError::InvalidParams(
+                "mcmc: thin, samples_per_chain and max_failed_chains must be positive".into(),
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// Replay value of an MCMC row.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct McmcReplay<R> {
+    /// Replay value of the base sample the chain started from.
+    pub base: R,
+    /// Chain number (selects the chain's RNG).
+    pub chain: u64,
+    /// Proposals made in the chain up to this row, burn-in included.
+    pub step: u64,
+}
+
+/// One Metropolis chain over log-offsets with fixed normals.
+struct Chain {
+    normals: Vec<Vector4<f64>>,
+    log_offsets: Vec<f64>,
+    score: f64,
+    steps: u64,
+    rng: StdRng,
+}
+
+impl Chain {
+    fn start<O: ScoreOracle>(
+        poly: &Poly4,
+        oracle: &O,
+        rng: StdRng,
+    ) -> Result<Self, GeneratorError> {
+        if poly.h.iter().any(|h| h.c <= 0.0) {
+            return Err(GeneratorError::Degenerate(
+                "mcmc: base sample does not contain the origin in its interior".into(),
+            ));
+        }
+        let normals = poly.h.iter().map(|h| h.n / h.n.norm()).collect();
+        let mut log_offsets: Vec<f64> = poly.h.iter().map(|h| (h.c / h.n.norm()).ln()).collect();
+        recentre(&mut log_offsets);
+        let mut chain = Self {
+            normals,
+            log_offsets,
+            score: 0.0,
+            steps: 0,
+            rng,
+        };
+        chain.score = oracle.score(&mut chain.poly()).ok_or_else(|| {
+            GeneratorError::Degenerate("mcmc: oracle failed on base sample".into())
+        })?;
+        Ok(chain)
+    }
+
+    fn poly(&self) -> Poly4 {
+        let h = self
+            .normals
+            .iter()
+            .zip(&self.log_offsets)
+            .map(|(n, l)| Hs4::new(*n, l.exp()))
+            .collect();
+        Poly4::from_h(h)
+    }
+
+    /// One proposal; greedy while below `threshold`, Metropolis above.
+    fn step<O: ScoreOracle>(
+        &mut self,
+        params: &McmcParams,
+        oracle: &O,
+        stats: &mut AcceptanceStats,
+    ) {
+        self.steps += 1;
+        stats.record_attempt();
+        let mut proposal = self.log_offsets.clone();
+        for (l, z) in proposal.iter_mut().zip(normals_iter(&mut self.rng)) {
+            *l += params.step * z;
+        }
+        recentre(&mut proposal);
+        let u: f64 = self.rng.gen();
+        let saved = std::mem::replace(&mut self.log_offsets, proposal);
+        let Some(score) = oracle.score(&mut self.poly()) else {
+            self.log_offsets = saved;
+            stats.record_rejection("oracle_failed");
+            return;
+        };
+        let above = self.score >= params.threshold;
+        let accept = if !above {
+            score >= self.score
+        } else if score < params.threshold {
+            stats.record_rejection("below_threshold");
+            self.log_offsets = saved;
+            return;
+        } else {
+            u < (params.beta * (score - self.score)).exp()
+        };
+        if accept {
+            self.score = score;
+        } else {
+            self.log_offsets = saved;
+            stats.record_rejection(if above {
+                "metropolis"
+            } else {
+                "burn_in_downhill"
+            });
+        }
+    }
+}
+
+/// Standard normal draws (Box–Muller via `standard_normal4`), four at a time.
+fn normals_iter(rng: &mut StdRng) -> impl Iterator<Item = f64> + '_ {
+    std::iter::repeat_with(move || standard_normal4(rng)).flat_map(|v| [v[0], v[1], v[2], v[3]])
+}
+
+fn recentre(log_offsets: &mut [f64]) {
+    let mean = log_offsets.iter().sum::<f64>() / log_offsets.len() as f64;
+    log_offsets.iter_mut().for_each(|l| *l -= mean);
+}
+
+/// Metropolis chains started from a base generator, emitting rows with `score ≥ threshold`.
+pub struct SystolicMcmcGenerator<G: PolytopeGenerator4, O = SystolicRatio> {
+    base: G,
+    oracle: O,
+    params: McmcParams,
+    seed: u64,
+    next_chain: u64,
+    current: Option<(Chain, G::Replay, u64)>,
+    stats: AcceptanceStats,
+}
+
+impl<G: PolytopeGenerator4> SystolicMcmcGenerator<G> {
+    /// Chains scored by the systolic ratio.
+    pub fn new(base: G, params: McmcParams, seed: u64) -> Result<Self, GeneratorError> {
+        Self::with_oracle(base, SystolicRatio, params, seed)
+    }
+}
+
+impl<G: PolytopeGenerator4, O: ScoreOracle> SystolicMcmcGenerator<G, O> {
+    pub fn with_oracle(
+        base: G,
+        oracle: O,
+        params: McmcParams,
+        seed: u64,
+    ) -> Result<Self, GeneratorError> {
+        params.validate()?;
+        Ok(Self {
+            base,
+            oracle,
+            params,
+            seed,
+            next_chain: 0,
+            current: None,
+            stats: AcceptanceStats::default(),
+        })
+    }
+
+    pub fn params(&self) -> &McmcParams {
+        &self.params
+    }
+
+    pub fn base(&self) -> &G {
+        &self.base
+    }
+
+    fn chain_rng(&self, chain: u64) -> StdRng {
+        token_rng(ReplayToken {
+            seed: self.seed,
+            index: chain,
+        })
+    }
+
+    /// Start chains from fresh base samples until one reaches the threshold.
+    fn start_chain(&mut self) -> Result<Option<()>, GeneratorError> {
+        for _ in 0..self.params.max_failed_chains {
+            let Some(sample) = self.base.generate_next()? else {
+                return Ok(None);
+            };
+            let id = self.next_chain;
+            self.next_chain += 1;
+            let rng = self.chain_rng(id);
+            let Ok(mut chain) = Chain::start(&sample.polytope, &self.oracle, rng) else {
+                self.stats.record_exhausted();
+                continue;
+            };
+            while chain.score < self.params.threshold && chain.steps < self.params.burn_in_steps {
+                chain.step(&self.params, &self.oracle, &mut self.stats);
+            }
+            if chain.score >= self.params.threshold {
+                self.current = Some((chain, sample.replay, 0));
+                return Ok(Some(()));
+            }
+            self.stats.record_exhausted();
+        }
+        Err(GeneratorError::Degenerate(format!(
+            "mcmc: {} consecutive chains failed to reach threshold {}",
+            self.params.max_failed_chains, self.params.threshold
+        )))
+    }
+}
+
+impl<G: PolytopeGenerator4, O: ScoreOracle> PolytopeGenerator4 for SystolicMcmcGenerator<G, O> {
+    type Replay = McmcReplay<G::Replay>;
+
+    fn generate_next(&mut self) -> Result<Option<PolytopeSample4<Self::Replay>>, GeneratorError> {
+        if self.current.is_none() && self.start_chain()?.is_none() {
+            return Ok(None);
+        }
+        let (chain, base, emitted) = self.current.as_mut().expect("chain started above");
+        for _ in 0..self.params.thin {
+            chain.step(&self.params, &self.oracle, &mut self.stats);
+        }
+        let mut polytope = chain.poly();
+        polytope.ensure_vertices_from_h();
+        let replay = McmcReplay {
+            base: base.clone(),
+            chain: self.next_chain - 1,
+            step: chain.steps,
+        };
+        *emitted += 1;
+        if *emitted == self.params.samples_per_chain {
+            self.current = None;
+        }
+        self.stats.record_accepted();
+        Ok(Some(PolytopeSample4 { polytope, replay }))
+    }
+
+    fn regenerate(&self, replay: &Self::Replay) -> Result<Poly4, GeneratorError> {
+        let start = self.base.regenerate(&replay.base)?;
+        let mut chain = Chain::start(&start, &self.oracle, self.chain_rng(replay.chain))?;
+        let mut scratch = AcceptanceStats::default();
+        while chain.steps < replay.step {
+            chain.step(&self.params, &self.oracle, &mut scratch);
+        }
+        let mut polytope = chain.poly();
+        polytope.ensure_vertices_from_h();
+        Ok(polytope)
+    }
+}
+
+impl<G: PolytopeGenerator4, O> AcceptanceStatsSource for SystolicMcmcGenerator<G, O> {
+    /// Proposals (`attempts`), emitted rows (`accepted`), rejected proposals by
+    /// reason, and abandoned chains (`exhausted`).
+    fn acceptance_stats(&self) -> &AcceptanceStats {
+        &self.stats
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rand4::{ZonotopeGenerator, ZonotopeParams};
+
+    /// Cheap stand-in for the systolic ratio: `min c / max c` over unit normals.
+    fn offset_balance(poly: &mut Poly4) -> Option<f64> {
+        let c = poly.h.iter().map(|h| h.c / h.n.norm());
+        let (lo, hi) = c.fold((f64::INFINITY, 0.0f64), |(lo, hi), c| {
+            (lo.min(c), hi.max(c))
+        });
+        Some(lo / hi)
+    }
+
+    fn generator(
+        seed: u64,
+    ) -> SystolicMcmcGenerator<ZonotopeGenerator, fn(&mut Poly4) -> Option<f64>> {
+        let base = ZonotopeGenerator::new(
+            ZonotopeParams {
+                segments_min: 4,
+                segments_max: 4,
+                ..ZonotopeParams::default()
+            },
+            3,
+        )
+        .unwrap();
+        let params = McmcParams {
+            threshold: 0.8,
+            thin: 3,
+            samples_per_chain: 2,
+            step: 0.1,
+            ..McmcParams::default()
+        };
+        SystolicMcmcGenerator::with_oracle(
+            base,
+            offset_balance as fn(&mut Poly4) -> Option<f64>,
+            params,
+            seed,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn rows_meet_threshold_and_replay() {
+        let mut gen = generator(9);
+        let rows: Vec<_> = (0..3)
+            .map(|_| gen.generate_next().unwrap().unwrap())
+            .collect();
+        assert_eq!(
+            rows.iter().map(|r| r.replay.chain).collect::<Vec<_>>(),
+            vec![0, 0, 1]
+        );
+        for mut row in rows {
+            assert!(offset_balance(&mut row.polytope).unwrap() >= 0.8);
+            let again = gen.regenerate(&row.replay).unwrap();
+            assert_eq!(again.h, row.polytope.h);
+        }
+        let stats = gen.acceptance_stats();
+        assert_eq!(stats.accepted, 3);
+        assert!(stats.attempts >= 3 * 3);
+    }
+
+    #[test]
+    fn unreachable_threshold_is_degenerate() {
+        let mut gen = generator(1);
+        gen.params.threshold = 2.0;
+        gen.params.burn_in_steps = 5;
+        gen.params.max_failed_chains = 2;
+        assert!(matches!(
+            gen.generate_next(),
+            Err(GeneratorError::Degenerate(_))
+        ));
+        assert_eq!(gen.acceptance_stats().exhausted, 2);
+    }
+}
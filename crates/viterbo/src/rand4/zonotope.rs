@@ -0,0 +1,347 @@
+//! Random zonotopes: Minkowski sums of `m` centred segments in R⁴.
+//!
+//! Why this family exists
+//! - Zonotopes are centrally symmetric with a rich, fully predictable face
+//!   lattice (`2·C(m, 3)` facets in general position), which makes them a
+//!   standard stress family for facet-graph code and capacity solvers.
+//! - The H-rep is combinatorial: every triple of generators `{g_a, g_b, g_c}`
+//!   spanning a 3-space defines the facet normal `n ⊥ g_a, g_b, g_c` with
+//!   support `h(n) = Σ_i |⟨n, g_i⟩|` (and likewise `−n`). No hull computation
+//!   is needed; vertices follow from the H-rep as usual.
+//! - Segments are `[−g_i, g_i]` with `g_i = ℓ_i u_i`, directions `u_i` from
+//!   `rand4::dirs` and lengths `ℓ_i` uniform in `[length_min, length_max]`, so
+//!   the origin is the centre of symmetry and strictly interior.
+//!
+//! Replay
+//! - Rows are addressed by `ReplayToken { seed, index }` (per-row RNG, see
+//!   `rand4::seeding`); rank-deficient draws retry with the same RNG.
+//!
+//! References
+//! - TH: docs/src/thesis/random-polytopes.md (“Random Zonotopes”)
+//! - G. M. Ziegler, "Lectures on Polytopes", Lecture 7 (zonotopes).
+
+use nalgebra::Vector4;
+use rand::Rng;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use super::dirs::{directions_for_row, DirectionMode};
+use super::seeding::token_rng;
+use super::{
+    AcceptanceStats, AcceptanceStatsSource, Checkpoint, GeneratorError, IndexedGenerator,
+    PolytopeGenerator4, PolytopeSample4, StreamState,
+};
+use crate::geom2::rand::ReplayToken;
+use crate::geom4::{Hs4, Poly4};
+
+/// Generator triples whose normal is shorter than this (relative) span < 3 dimensions.
+const EPS_RANK: f64 = 1e-9;
+/// Normalized normals closer than this are treated as the same facet direction.
+const EPS_NORMAL: f64 = 1e-9;
+
+/// Parameters for [`ZonotopeGenerator`]; missing JSON fields take the defaults.
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct ZonotopeParams {
+    /// Inclusive lower bound on the number of segments `m` (at least 4).
+    pub segments_min: usize,
+    /// Inclusive upper bound on the number of segments `m`.
+    pub segments_max: usize,
+    /// Half-length range of the segments `[−g, g]`.
+    pub length_min: f64,
+    pub length_max: f64,
+    /// Source of the segment directions.
+    pub directions: DirectionMode,
+    /// Number of draws per row before giving up with `GeneratorError::Degenerate`.
+    pub max_attempts: usize,
+}
+
+impl Default for ZonotopeParams {
+    fn default() -> Self {
+        Self {
+            segments_min: 4,
+            segments_max: 8,
+            length_min: 0.5,
+            length_max: 1.5,
+            directions: DirectionMode::default(),
+            max_attempts: 20,
+        }
+    }
+}
+
+impl ZonotopeParams {
+    pub fn validate(&self) -> Result<(), GeneratorError> {
+        if self.segments_min < 4 {
+            return Err(GeneratorError::InvalidParams(
+                "zonotope: segments_min must be >= 4 for a full-dimensional sum".into(),
+            ));
+        }
+        if self.segments_min > self.segments_max {
+            return Err(GeneratorError::InvalidParams(format!(
+                "zonotope: segments_min ({}) > segments_max ({})",
+                self.segments_min, self.segments_max
+            )));
+        }
+        let lengths_ok = self.length_min > 0.0
+            && self.length_min <= self.length_max
+            && self.length_max.is_finite();
+        if !lengths_ok {
+            return Err(GeneratorError::InvalidParams(
+                "zonotope: need 0 < length_min <= length_max < inf".into(),
+            ));
+        }
+        if self.max_attempts == 0 {
+            return Err(GeneratorError::InvalidParams(
+                "zonotope: max_attempts must be positive".into(),
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// Streaming generator over random zonotopes.
+#[derive(Clone, Debug)]
+pub struct ZonotopeGenerator {
+    params: ZonotopeParams,
+    seed: u64,
+    next_index: u64,
+    stats: AcceptanceStats,
+}
+
+impl ZonotopeGenerator {
+    pub fn new(params: ZonotopeParams, seed: u64) -> Result<Self, GeneratorError> {
+        params.validate()?;
+        Ok(Self {
+            params,
+            seed,
+            next_index: 0,
+            stats: AcceptanceStats::default(),
+        })
+    }
+
+    pub fn params(&self) -> &ZonotopeParams {
+        &self.params
+    }
+
+    /// Build the row addressed by `token` without constructing a generator.
+    pub fn sample_with_token(
+        params: &ZonotopeParams,
+        token: ReplayToken,
+    ) -> Result<Poly4, GeneratorError> {
+        params.validate()?;
+        sample_impl(params, token, &mut AcceptanceStats::default())
+    }
+}
+
+impl PolytopeGenerator4 for ZonotopeGenerator {
+    type Replay = ReplayToken;
+
+    fn generate_next(&mut self) -> Result<Option<PolytopeSample4<ReplayToken>>, GeneratorError> {
+        let token = ReplayToken {
+            seed: self.seed,
+            index: self.next_index,
+        };
+        let polytope = sample_impl(&self.params, token, &mut self.stats)?;
+        self.next_index += 1;
+        Ok(Some(PolytopeSample4 {
+            polytope,
+            replay: token,
+        }))
+    }
+
+    fn regenerate(&self, replay: &ReplayToken) -> Result<Poly4, GeneratorError> {
+        sample_impl(&self.params, *replay, &mut AcceptanceStats::default())
+    }
+}
+
+impl AcceptanceStatsSource for ZonotopeGenerator {
+    fn acceptance_stats(&self) -> &AcceptanceStats {
+        &self.stats
+    }
+}
+
+impl Checkpoint for ZonotopeGenerator {
+    type State = StreamState;
+
+    fn save_state(&self) -> StreamState {
+        StreamState {
+            seed: self.seed,
+            next_index: self.next_index,
+            stats: self.stats.clone(),
+        }
+    }
+
+    fn restore_state(&mut self, state: StreamState) -> Result<(), GeneratorError> {
+        state.check_seed(self.seed)?;
+        self.next_index = state.next_index;
+        self.stats = state.stats;
+        Ok(())
+    }
+}
+
+impl IndexedGenerator for ZonotopeGenerator {
+    fn row_count(&self) -> Option<u64> {
+        None
+    }
+
+    fn replay_at(&self, index: u64) -> Option<ReplayToken> {
+        Some(ReplayToken {
+            seed: self.seed,
+            index,
+        })
+    }
+}
+
+/// Zonotope `Σ_i [−g_i, g_i]` from its generators; `None` if they do not span R⁴.
+pub fn zonotope_from_generators(gens: &[Vector4<f64>]) -> Option<Poly4> {
+    let h = zonotope_halfspaces(gens);
+    if h.len() < 8 {
+        return None;
+    }
+    let mut poly = Poly4::from_h(h);
+    poly.ensure_vertices_from_h();
+    Some(poly)
+}
+
+/// Facets of `Σ_i [−g_i, g_i]`, one pair `±n` per distinct 3-space spanned by generators.
+pub fn zonotope_halfspaces(gens: &[Vector4<f64>]) -> Vec<Hs4> {
+    let scale = gens.iter().map(|g| g.norm()).fold(0.0, f64::max);
+    let mut normals: Vec<Vector4<f64>> = Vec::new();
+    let m = gens.len();
+    for a in 0..m {
+        for b in a + 1..m {
+            for c in b + 1..m {
+                let n = cross3(&gens[a], &gens[b], &gens[c]);
+                let norm = n.norm();
+                if norm <= EPS_RANK * scale.powi(3) {
+                    continue;
+                }
+                let n = n / norm;
+                if normals
+                    .iter()
+                    .any(|p| (p - n).norm() < EPS_NORMAL || (p + n).norm() < EPS_NORMAL)
+                {
+                    continue;
+                }
+                normals.push(n);
+            }
+        }
+    }
+    let mut h = Vec::with_capacity(2 * normals.len());
+    for n in normals {
+        let support: f64 = gens.iter().map(|g| n.dot(g).abs()).sum();
+        h.push(Hs4::new(n, support));
+        h.push(Hs4::new(-n, support));
+    }
+    h
+}
+
+fn sample_impl(
+    params: &ZonotopeParams,
+    token: ReplayToken,
+    stats: &mut AcceptanceStats,
+) -> Result<Poly4, GeneratorError> {
+    let mut rng = token_rng(token);
+    for _ in 0..params.max_attempts {
+        stats.record_attempt();
+        let m = rng.gen_range(params.segments_min..=params.segments_max);
+        let dirs = directions_for_row(params.directions, &mut rng, token, m);
+        let gens: Vec<Vector4<f64>> = dirs
+            .into_iter()
+            .map(|u| u * rng.gen_range(params.length_min..=params.length_max))
+            .collect();
+        let Some(mut poly) = zonotope_from_generators(&gens) else {
+            stats.record_rejection("rank_deficient");
+            continue;
+        };
+        if poly.check_canonical().is_ok() {
+            stats.record_accepted();
+            return Ok(poly);
+        }
+        stats.record_rejection("not_canonical");
+    }
+    stats.record_exhausted();
+    Err(GeneratorError::Degenerate(format!(
+        "zonotope: segments failed to span R^4 after {} attempts (seed={}, index={})",
+        params.max_attempts, token.seed, token.index
+    )))
+}
+
+/// Generalized cross product: a vector orthogonal to `a, b, c` (zero iff they are dependent).
+fn cross3(a: &Vector4<f64>, b: &Vector4<f64>, c: &Vector4<f64>) -> Vector4<f64> {
+    let minor = |i: usize, j: usize, k: usize| {
+        a[i] * (b[j] * c[k] - b[k] * c[j]) - a[j] * (b[i] * c[k] - b[k] * c[i])
+            + a[k] * (b[i] * c[j] - b[j] * c[i])
+    };
+    Vector4::new(
+        minor(1, 2, 3),
+        -minor(0, 2, 3),
+        minor(0, 1, 3),
+        -minor(0, 1, 2),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cross3_is_orthogonal() {
+        let a = Vector4::new(1.0, 2.0, 0.5, -1.0);
+        let b = Vector4::new(0.0, 1.0, 3.0, 2.0);
+        let c = Vector4::new(-2.0, 0.5, 1.0, 1.0);
+        let n = cross3(&a, &b, &c);
+        assert!(n.norm() > 1e-6);
+        for v in [a, b, c] {
+            assert!(n.dot(&v).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn coordinate_segments_give_a_box() {
+        let gens: Vec<Vector4<f64>> = (0..4)
+            .map(|k| {
+                let mut e = Vector4::zeros();
+                e[k] = 1.0 + k as f64;
+                e
+            })
+            .collect();
+        let h = zonotope_halfspaces(&gens);
+        assert_eq!(h.len(), 8);
+        for hs in &h {
+            let axis = hs.n.iamax();
+            assert!((hs.c - (1.0 + axis as f64)).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn general_position_facet_count() {
+        // 2·C(6, 3) = 40 facets for six generic segments.
+        let token = ReplayToken { seed: 5, index: 0 };
+        let gens = directions_for_row(DirectionMode::default(), &mut token_rng(token), token, 6);
+        assert_eq!(zonotope_halfspaces(&gens).len(), 40);
+    }
+
+    #[test]
+    fn zonotope_rows_are_symmetric_and_replay() {
+        let params = ZonotopeParams {
+            segments_min: 5,
+            segments_max: 5,
+            ..ZonotopeParams::default()
+        };
+        let mut gen = ZonotopeGenerator::new(params.clone(), 7).unwrap();
+        let sample = gen.generate_next().unwrap().unwrap();
+        let p = &sample.polytope;
+        assert_eq!(p.h.len(), 20);
+        let stats = gen.acceptance_stats();
+        assert_eq!(stats.accepted, 1);
+        assert_eq!(stats.attempts, 1 + stats.rejected());
+        assert!(p
+            .v
+            .iter()
+            .all(|v| p.v.iter().any(|w| (v + w).norm() < 1e-9)));
+        let again = ZonotopeGenerator::sample_with_token(&params, sample.replay).unwrap();
+        assert_eq!(again.h, p.h);
+    }
+}
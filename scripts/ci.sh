@@ -34,7 +34,9 @@ uv run pytest -q -m "not e2e"
 
 echo ">>> Rust fmt/test/clippy"
 bash scripts/rust-fmt.sh
-bash scripts/rust-test.sh
+# Both sides of the `parallel` feature (rayon batch APIs vs. sequential).
+bash scripts/rust-test.sh --features parallel
+bash scripts/rust-test.sh --no-default-features
 bash scripts/rust-clippy.sh
 
 echo ">>> Build native extension (maturin) — fail fast if missing"
@@ -8,12 +8,27 @@ set -euo pipefail
 # 3. Propagate exit codes: `bash scripts/group-timeout.sh 5 bash -c 'exit 42'` → exits 42.
 # 4. Cleans grandchildren: `bash scripts/group-timeout.sh 2 bash -c 'sleep 5 & sleep 5 & wait'` → all sleeps die once the timeout hits.
 # 5. Env flag: `bash scripts/group-timeout.sh 1 env | grep GROUP_TIMEOUT_ACTIVE` shows the variable is exported for child scripts.
+# 6. No orphans: `bash scripts/group-timeout.sh 60 bash -c 'sleep 30 & wait' & sleep 1; kill -TERM $!` → the wrapper exits 143
+#    and `pgrep -f 'sleep 30'` finds nothing (same for Ctrl-C → 130 and SIGHUP → 129).
+# 7. Grace period: `GROUP_TIMEOUT_GRACE=3 bash scripts/group-timeout.sh 1 bash -c 'trap "sleep 2; echo flushed" TERM; sleep 9 & wait'`
+#    prints "flushed" before the SIGKILL and exits 124.
+# 8. Limits: `GROUP_TIMEOUT_MAX_MEM_MB=200 bash scripts/group-timeout.sh 10 python3 -c 'b = bytearray(500 << 20)'` → MemoryError;
+#    `GROUP_TIMEOUT_MAX_FILES=16 bash scripts/group-timeout.sh 5 bash -c 'ulimit -n'` prints 16.
+# 9. Report: `GROUP_TIMEOUT_REPORT=/tmp/r.jsonl bash scripts/group-timeout.sh 5 bash -c 'sleep 1; exit 3'` exits 3 and
+#    appends {"exit_code": 3, "signal": null, "timed_out": false, "wall_s": 1.0..., ...} to /tmp/r.jsonl.
+#    A timeout reports "signal": 15 (or 9 if the grace period ran out); check 6 reports 15/2/1 with
+#    "exit_code": 143/130/129.
 #
 
 usage() {
   cat >&2 <<'USAGE'
 usage: group-timeout <seconds> [--] <command ...>
 Runs <command> inside its own process group, killing the entire tree when the deadline expires.
+At the deadline the group gets SIGTERM, then SIGKILL after $GROUP_TIMEOUT_GRACE seconds (default 5),
+so stages can flush checkpoints. A timeout exits 124.
+Optional per-process rlimits: GROUP_TIMEOUT_MAX_MEM_MB (address space), GROUP_TIMEOUT_MAX_CPU_S
+(CPU seconds) and GROUP_TIMEOUT_MAX_FILES (open files).
+GROUP_TIMEOUT_REPORT=<path> appends one JSON line per run: exit code, signal, timeout, wall and CPU time.
 USAGE
 }
 
@@ -32,6 +47,27 @@ else
   exit 2
 fi
 
+grace="${GROUP_TIMEOUT_GRACE:-5}"
+if [[ ! "$grace" =~ ^[0-9]+([.][0-9]+)?$ ]]; then
+  echo "group-timeout: GROUP_TIMEOUT_GRACE must be a non-negative number" >&2
+  exit 2
+fi
+
+# rlimits are applied inside the new session right before exec, so they bind the command and
+# everything it forks (each process separately; they are not a budget for the whole group).
+limits=""
+for spec in "MAX_MEM_MB:-v:1024" "MAX_CPU_S:-t:1" "MAX_FILES:-n:1"; do
+  IFS=: read -r name flag scale <<<"$spec"
+  var="GROUP_TIMEOUT_$name"
+  value="${!var:-}"
+  [[ -z "$value" ]] && continue
+  if [[ ! "$value" =~ ^[0-9]+$ ]] || ((value == 0)); then
+    echo "group-timeout: $var must be a positive integer" >&2
+    exit 2
+  fi
+  limits+="ulimit $flag $((value * scale)) || exit 2; "
+done
+
 if [[ "${1:-}" == "--" ]]; then
   shift
 fi
@@ -49,7 +85,8 @@ flag_file="$(mktemp -t group-timeout-flag.XXXXXX)"
 cleanup_flag() { rm -f "$flag_file"; }
 trap cleanup_flag EXIT
 
-setsid bash -c 'exec "$@"' bash "${cmd[@]}" &
+started="$EPOCHREALTIME"
+setsid bash -c "${limits}exec \"\$@\"" bash "${cmd[@]}" &
 child=$!
 if ! kill -0 "$child" 2>/dev/null; then
   echo "group-timeout: failed to start target command" >&2
@@ -63,13 +100,52 @@ if [[ -z "$pgid" ]]; then
   exit 1
 fi
 
+# Appends the GROUP_TIMEOUT_REPORT line: write_report <exit code> <signal number or null>.
+# Whether the deadline fired is read from the flag file; `started`/`finished` give the wall time.
+write_report() {
+  [[ -n "${GROUP_TIMEOUT_REPORT:-}" ]] || return 0
+  local timed_out=false
+  [[ -s "$flag_file" ]] && timed_out=true
+  # `times` must run in this shell: its second line is the CPU time of reaped children, which
+  # covers the command and every descendant it waited for (no peak RSS from bash, hence none here).
+  times >"$flag_file"
+  tail -n 1 "$flag_file" | awk -v rc="$1" -v sig="$2" -v out="$timed_out" \
+    -v wall="$(awk -v a="$started" -v b="$finished" 'BEGIN { printf "%.3f", b - a }')" '
+    function secs(t) { split(t, p, /[ms]/); return p[1] * 60 + p[2] }
+    { printf "{\"exit_code\": %d, \"signal\": %s, \"timed_out\": %s, \"wall_s\": %s, ", rc, sig, out, wall
+      printf "\"user_s\": %.3f, \"sys_s\": %.3f}\n", secs($1), secs($2) }' >>"$GROUP_TIMEOUT_REPORT"
+}
+
+# If the wrapper itself is stopped (Ctrl-C, scheduler SIGTERM, closed terminal), take the whole
+# group down with it instead of leaving solver workers running under init.
+on_signal() {
+  local signum="$1"
+  trap - INT TERM HUP
+  kill "${timer:-}" 2>/dev/null || true
+  kill -TERM "-$pgid" 2>/dev/null || true
+  sleep "$grace"
+  kill -KILL "-$pgid" 2>/dev/null || true
+  wait "$child" 2>/dev/null || true
+  finished="$EPOCHREALTIME"
+  echo "[group-timeout] received signal $signum; terminated the process group." >&2
+  write_report $((128 + signum)) "$signum"
+  exit $((128 + signum))
+}
+trap 'on_signal 2' INT
+trap 'on_signal 15' TERM
+trap 'on_signal 1' HUP
+
 (
   sleep "$deadline"
   if kill -0 "-$pgid" 2>/dev/null; then
-    printf 'timeout' >"$flag_file"
+    # The flag file names the last signal the deadline sent, for the report.
+    printf 'TERM' >"$flag_file"
     kill -TERM "-$pgid" 2>/dev/null || true
-    sleep 5
-    kill -KILL "-$pgid" 2>/dev/null || true
+    sleep "$grace"
+    if kill -0 "-$pgid" 2>/dev/null; then
+      printf 'KILL' >"$flag_file"
+      kill -KILL "-$pgid" 2>/dev/null || true
+    fi
   fi
 ) &
 timer=$!
@@ -77,18 +153,25 @@ timer=$!
 set +e
 wait "$child"
 rc=$?
+finished="$EPOCHREALTIME"
 set -e
 
 kill "$timer" 2>/dev/null || true
 wait "$timer" 2>/dev/null || true
 
+signal=null
+((rc > 128)) && signal=$((rc - 128))
 if [[ -s "$flag_file" ]]; then
-  echo "[group-timeout] command exceeded ${deadline}s; terminated." >&2
+  echo "[group-timeout] command exceeded ${deadline}s; sent SIGTERM (SIGKILL after ${grace}s)." >&2
   rc=124
+  signal=15
+  [[ "$(cat "$flag_file")" == KILL ]] && signal=9
 fi
 
 kill -TERM "-$pgid" 2>/dev/null || true
 sleep 1
 kill -KILL "-$pgid" 2>/dev/null || true
 
+write_report "$rc" "$signal"
+
 exit "$rc"
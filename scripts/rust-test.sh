@@ -4,10 +4,11 @@
 # - Must be invoked under group-timeout (checks GROUP_TIMEOUT_ACTIVE=1).
 # - No internal timeouts; inherits the top-level timeout from group-timeout.
 # Usage:
-#   group-timeout 120 bash scripts/rust-test.sh [-p viterbo] [-- <extra cargo args>]
+#   group-timeout 120 bash scripts/rust-test.sh [-p viterbo] [--features F | --no-default-features] [-- <extra test args>]
 # Examples:
 #   group-timeout 120 bash scripts/rust-test.sh
 #   group-timeout 180 bash scripts/rust-test.sh -p viterbo
+#   group-timeout 180 bash scripts/rust-test.sh --no-default-features
 set -euo pipefail
 
 SCRIPT_NAME="$(basename "${BASH_SOURCE[0]}")"
@@ -31,11 +32,14 @@ if command -v sccache >/dev/null 2>&1; then
 fi
 
 PKG="viterbo"
+FEATURES=()
 EXTRA=()
 
 while [[ $# -gt 0 ]]; do
   case "$1" in
     -p|--package) PKG="$2"; shift 2 ;;
+    --features) FEATURES+=(--features "$2"); shift 2 ;;
+    --no-default-features|--all-features) FEATURES+=("$1"); shift ;;
     --) shift; EXTRA+=("$@"); break ;;
     *) EXTRA+=("$1"); shift ;;
   esac
@@ -43,18 +47,18 @@ done
 
 # Run via nextest if available; fall back to cargo test.
 if command -v cargo-nextest >/dev/null 2>&1; then
-  echo ">>> cargo nextest run (-p $PKG) ${EXTRA[*]:-}"
+  echo ">>> cargo nextest run (-p $PKG ${FEATURES[*]:-}) ${EXTRA[*]:-}"
   if [[ ${#EXTRA[@]} -gt 0 ]]; then
-    cargo nextest run -p "$PKG" -- "${EXTRA[@]}"
+    cargo nextest run -p "$PKG" "${FEATURES[@]}" -- "${EXTRA[@]}"
   else
-    cargo nextest run -p "$PKG"
+    cargo nextest run -p "$PKG" "${FEATURES[@]}"
   fi
 else
-  echo ">>> cargo test (-p $PKG) ${EXTRA[*]:-}"
+  echo ">>> cargo test (-p $PKG ${FEATURES[*]:-}) ${EXTRA[*]:-}"
   if [[ ${#EXTRA[@]} -gt 0 ]]; then
-    cargo test -p "$PKG" -- "${EXTRA[@]}"
+    cargo test -p "$PKG" "${FEATURES[@]}" -- "${EXTRA[@]}"
   else
-    cargo test -p "$PKG"
+    cargo test -p "$PKG" "${FEATURES[@]}"
   fi
 fi
 echo "Rust tests completed."
@@ -1,8 +1,16 @@
 from __future__ import annotations
 
-from dataclasses import dataclass
+import difflib
+from dataclasses import dataclass, field
 from pathlib import Path
-from typing import Any, Mapping, MutableMapping, Sequence
+from typing import Any, Iterable, Mapping, MutableMapping, Sequence
+
+_TOP_KEYS = ("version", "seed", "sources", "out", "solver", "generation", "metadata", "matrix")
+_SOURCE_KEYS = ("name", "family", "rows", "params", "seed")
+_OUT_KEYS = ("dataset", "preview", "preview_limit")
+_SOLVER_KEYS = ("jobs",)
+_GENERATION_KEYS = ("on_degenerate", "max_retries")
+DEGENERATE_POLICIES = ("abort", "skip", "retry")
 
 
 @dataclass(frozen=True)
@@ -21,12 +29,27 @@ class SourceConfig:
     seed: int | None = None
 
 
+@dataclass(frozen=True)
+class GenerationPolicy:
+    """What a source does when the generator reports a degenerate sample.
+
+    ``abort`` re-raises (the default), ``skip`` drops the sample, ``retry``
+    draws again with a derived seed up to ``max_retries`` times, then aborts.
+    """
+
+    on_degenerate: str = "abort"
+    max_retries: int = 3
+
+
 @dataclass(frozen=True)
 class AtlasConfig:
     version: int
     seed: int
     sources: list[SourceConfig]
     out: OutputConfig
+    jobs: int | None = None
+    generation: GenerationPolicy = GenerationPolicy()
+    metadata: dict[str, Any] = field(default_factory=dict)
 
     @classmethod
     def from_mapping(
@@ -35,24 +58,47 @@ class AtlasConfig:
         *,
         base_dir: Path,
     ) -> "AtlasConfig":
+        _check_keys(data, _TOP_KEYS, "config")
+        if "matrix" in data:
+            raise ValueError(
+                "config has a [matrix] section; expand it with viterbo.atlas.stage_matrix"
+            )
         version = int(data.get("version", 1))
         seed = int(data.get("seed", 0))
         out_cfg = cls._parse_out(data.get("out", {}), base_dir)
         sources = cls._parse_sources(data.get("sources", []))
         if not sources:
             raise ValueError("config must provide at least one source")
-        return cls(version=version, seed=seed, sources=sources, out=out_cfg)
+        solver = data.get("solver", {})
+        _check_keys(solver, _SOLVER_KEYS, "solver")
+        jobs = solver.get("jobs")
+        generation = cls._parse_generation(data.get("generation", {}))
+        metadata = data.get("metadata", {})
+        if not isinstance(metadata, Mapping):
+            raise ValueError("'metadata' must be a table/object")
+        return cls(
+            version=version,
+            seed=seed,
+            sources=sources,
+            out=out_cfg,
+            jobs=int(jobs) if jobs is not None else None,
+            generation=generation,
+            metadata=dict(metadata),
+        )
 
     @classmethod
     def from_file(cls, path: Path) -> "AtlasConfig":
-        import json
+        """Load a JSON or (``.toml`` suffix) TOML config; errors name the file."""
 
-        with path.open("r", encoding="utf-8") as handle:
-            payload = json.load(handle)
-        return cls.from_mapping(payload, base_dir=path.parent)
+        payload = read_config_payload(path)
+        try:
+            return cls.from_mapping(payload, base_dir=path.parent)
+        except ValueError as err:
+            raise ValueError(f"{path}: {err}") from err
 
     @staticmethod
     def _parse_out(payload: Mapping[str, Any], base_dir: Path) -> OutputConfig:
+        _check_keys(payload, _OUT_KEYS, "out")
         dataset_raw = payload.get("dataset")
         if not dataset_raw:
             raise ValueError("out.dataset must be set")
@@ -62,14 +108,29 @@ class AtlasConfig:
         preview_limit = int(payload.get("preview_limit", 32))
         return OutputConfig(dataset=dataset, preview=preview, preview_limit=preview_limit)
 
+    @staticmethod
+    def _parse_generation(payload: Mapping[str, Any]) -> GenerationPolicy:
+        _check_keys(payload, _GENERATION_KEYS, "generation")
+        policy = str(payload.get("on_degenerate", "abort"))
+        if policy not in DEGENERATE_POLICIES:
+            raise ValueError(
+                f"generation.on_degenerate must be one of {', '.join(DEGENERATE_POLICIES)}, "
+                f"got '{policy}'"
+            )
+        max_retries = int(payload.get("max_retries", 3))
+        if max_retries < 0:
+            raise ValueError("generation.max_retries must be >= 0")
+        return GenerationPolicy(on_degenerate=policy, max_retries=max_retries)
+
     @staticmethod
     def _parse_sources(payload: Any) -> list[SourceConfig]:
         if not isinstance(payload, Sequence):
             raise ValueError("'sources' must be a list")
         specs: list[SourceConfig] = []
-        for entry in payload:
+        for idx, entry in enumerate(payload):
             if not isinstance(entry, Mapping):
                 raise ValueError("each source entry must be an object")
+            _check_keys(entry, _SOURCE_KEYS, f"sources[{idx}]")
             entry_mut: MutableMapping[str, Any] = dict(entry)
             name = str(entry_mut.get("name") or entry_mut.get("family"))
             family = str(entry_mut.get("family") or entry_mut.get("name"))
@@ -102,6 +163,36 @@ class AtlasConfig:
         raise ValueError(f"source '{entry.get('name')}' missing 'rows' and no inferable count")
 
 
+def read_config_payload(path: Path) -> dict[str, Any]:
+    """The raw mapping of a JSON or (``.toml`` suffix) TOML config file."""
+
+    if path.suffix.lower() == ".toml":
+        import tomllib
+
+        with path.open("rb") as handle:
+            return tomllib.load(handle)
+    import json
+
+    with path.open("r", encoding="utf-8") as handle:
+        return json.load(handle)
+
+
+def _check_keys(payload: Any, allowed: Iterable[str], where: str) -> None:
+    """Reject unknown keys (typos would otherwise be ignored silently)."""
+
+    if not isinstance(payload, Mapping):
+        raise ValueError(f"'{where}' must be a table/object")
+    allowed = tuple(allowed)
+    for key in payload:
+        if key in allowed:
+            continue
+        hint = difflib.get_close_matches(str(key), allowed, n=1)
+        suggestion = f"; did you mean '{hint[0]}'?" if hint else ""
+        raise ValueError(
+            f"unknown key '{key}' in {where} (allowed: {', '.join(allowed)}){suggestion}"
+        )
+
+
 def _resolve_path(candidate: str, base_dir: Path) -> Path:
     path = Path(candidate)
     if path.is_absolute():
@@ -1,48 +1,132 @@
 from __future__ import annotations
 
+import time
+from dataclasses import asdict
 from pathlib import Path
-from typing import Iterable, Iterator
+from typing import Any, Iterable, Iterator, Sequence
 
 import polars as pl
 
+from viterbo.jsonlog import JsonLog
 from viterbo.provenance import write as write_provenance
+from viterbo.seeds import derive, seed_record
 
 from .config import AtlasConfig
+from .formats import format_for, write_table
 from .sources import source_from_spec
-from .types import AtlasRow
+from .limits import SampleLimits
+from .types import AtlasRow, ProgressCallback, solve_capacities
 
 
-def build_dataset(cfg: AtlasConfig) -> pl.DataFrame:
-    rows = list(_iter_records(cfg))
-    if not rows:
-        raise ValueError("atlas dataset produced zero rows")
-    return pl.DataFrame(rows)
+def build_dataset(
+    cfg: AtlasConfig,
+    *,
+    jobs: int | None = None,
+    progress: ProgressCallback | None = None,
+    log: JsonLog | None = None,
+    limits: SampleLimits | None = None,
+) -> pl.DataFrame:
+    """Generate every source, then solve capacities; ``jobs`` solves them in one
+    native batch on that many threads (0 = one per core) instead of row by row.
+    ``progress(done, total)`` is called after each solved row; ``log`` gets one
+    ``sample`` event per row (see ``log_samples``); ``limits`` caps each solve."""
+
+    rows, generate_s = generate_rows(cfg)
+    started = time.perf_counter()
+    solve_s = solve_capacities(rows, jobs, progress=progress, limits=limits)
+    if log is not None:
+        log_samples(log, range(len(rows)), rows, generate_s, solve_s)
+        log.event("solve", rows=len(rows), jobs=jobs, seconds=time.perf_counter() - started)
+    return pl.DataFrame([row.to_record(row_id) for row_id, row in enumerate(rows)])
 
 
-def write_dataset(cfg: AtlasConfig, df: pl.DataFrame) -> Path:
+def write_dataset(cfg: AtlasConfig, df: pl.DataFrame, fmt: str | None = None) -> Path:
     out_path = cfg.out.dataset
-    out_path.parent.mkdir(parents=True, exist_ok=True)
-    df.write_parquet(out_path, compression="zstd")
+    write_table(df, out_path, fmt)
     write_provenance(
         out_path,
         {
             "config_version": cfg.version,
             "seed": cfg.seed,
             "rows": len(df),
+            "format": format_for(out_path, fmt),
+            "generation": asdict(cfg.generation),
+            "metadata": cfg.metadata,
         },
         {
             "command": "python -m viterbo.atlas.stage_build --config <file>",
             "exit_code": 0,
+            "seeds": atlas_seeds(cfg),
         },
     )
     return out_path
 
 
-def _iter_records(cfg: AtlasConfig) -> Iterator[dict[str, object]]:
-    global_row = 0
+def generate_rows(cfg: AtlasConfig) -> tuple[list[AtlasRow], list[float]]:
+    """Every row, unsolved, plus the seconds each took to generate."""
+
+    rows, seconds = [], []
+    iterator = iter_rows(cfg, solve_capacity=False)
+    while True:
+        start = time.perf_counter()
+        row = next(iterator, None)
+        if row is None:
+            break
+        rows.append(row)
+        seconds.append(time.perf_counter() - start)
+    if not rows:
+        raise ValueError("atlas dataset produced zero rows")
+    return rows, seconds
+
+
+def log_samples(
+    log: JsonLog,
+    row_ids: Iterable[int],
+    rows: Sequence[AtlasRow],
+    generate_s: Sequence[float],
+    solve_s: Sequence[float | None],
+) -> None:
+    """One ``sample`` event per row: identity, replay token, timings, outcome.
+
+    ``outcome`` is the row status: ``ok``, ``no_cycle`` (NaN capacity) or
+    ``budget_exceeded``. ``solve_s`` is ``null`` for batch solves.
+    """
+
+    for row_id, row, gen, solve in zip(row_ids, rows, generate_s, solve_s):
+        log.event(
+            "sample",
+            row_id=row_id,
+            family=row.family,
+            family_name=row.family_name,
+            replay_token=row.replay_token,
+            halfspace_count=row.polytope.halfspace_count,
+            generate_s=gen,
+            solve_s=solve,
+            capacity_ehz=row.capacity_ehz,
+            retries=row.retries,
+            outcome=row.status,
+        )
+
+
+def source_seed(cfg: AtlasConfig, index: int) -> int:
+    """Default seed of ``cfg.sources[index]`` (used unless the source sets its own)."""
+
+    return derive(cfg.seed, index)
+
+
+def atlas_seeds(cfg: AtlasConfig) -> dict[str, Any]:
+    """Provenance ``seeds`` entry: root seed, scheme and the seed of every source."""
+
+    derived = {
+        spec.name: spec.seed if spec.seed is not None else source_seed(cfg, idx)
+        for idx, spec in enumerate(cfg.sources)
+    }
+    return seed_record(cfg.seed, "atlas/v1", derived)
+
+
+def iter_rows(cfg: AtlasConfig, *, solve_capacity: bool) -> Iterator[AtlasRow]:
     for idx, spec in enumerate(cfg.sources):
-        seed = cfg.seed + idx * 1_000_003
-        source = source_from_spec(spec, seed)
-        for row in source.generate():
-            yield row.to_record(global_row)
-            global_row += 1
+        source = source_from_spec(
+            spec, source_seed(cfg, idx), solve_capacity=solve_capacity, policy=cfg.generation
+        )
+        yield from source.generate()
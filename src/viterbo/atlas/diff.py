@@ -0,0 +1,121 @@
+"""Compare two atlas datasets: schema, per-family row counts, distribution shifts.
+
+Why this file exists
+- A solver or sampler change should leave the atlas statistically where it
+  was unless it is meant to move it. Row-by-row replay (``verify.py``) only
+  works when both datasets come from the same config. ``diff_datasets``
+  compares any two tables.
+- Distributions are compared with the two-sample Kolmogorov–Smirnov
+  statistic ``D = sup |F_a - F_b|`` and its asymptotic p-value, per metric,
+  for all rows and per shared family. Non-finite values (unsolved
+  capacities) are excluded. Their counts are reported, because a jump in
+  unsolved rows is itself a regression.
+- ``stage_diff`` reads both tables through ``read_table``, so older layouts
+  are migrated first and only real schema differences are reported.
+
+References
+- Docs: docs/src/thesis/atlas-dataset.md
+"""
+
+from __future__ import annotations
+
+import math
+from typing import Any
+
+import numpy as np
+import polars as pl
+
+METRICS = ("capacity_ehz", "volume", "systolic_ratio")
+
+
+def ks_2samp(a: np.ndarray, b: np.ndarray) -> tuple[float, float]:
+    """Two-sample KS statistic and asymptotic p-value; ``(nan, nan)`` if a side is empty."""
+
+    if len(a) == 0 or len(b) == 0:
+        return math.nan, math.nan
+    a, b = np.sort(a), np.sort(b)
+    grid = np.concatenate([a, b])
+    cdf_a = np.searchsorted(a, grid, side="right") / len(a)
+    cdf_b = np.searchsorted(b, grid, side="right") / len(b)
+    d = float(np.max(np.abs(cdf_a - cdf_b)))
+    n = len(a) * len(b) / (len(a) + len(b))
+    lam = (math.sqrt(n) + 0.12 + 0.11 / math.sqrt(n)) * d
+    return d, _kolmogorov_q(lam)
+
+
+def diff_datasets(a: pl.DataFrame, b: pl.DataFrame) -> dict[str, Any]:
+    """Schema, per-family row-count and per-metric KS differences of ``b`` against ``a``."""
+
+    schema_a, schema_b = dict(a.schema), dict(b.schema)
+    schema = {
+        "only_a": sorted(set(schema_a) - set(schema_b)),
+        "only_b": sorted(set(schema_b) - set(schema_a)),
+        "dtype": {
+            name: [str(schema_a[name]), str(schema_b[name])]
+            for name in sorted(set(schema_a) & set(schema_b))
+            if schema_a[name] != schema_b[name]
+        },
+    }
+    counts_a, counts_b = _family_counts(a), _family_counts(b)
+    families = sorted(set(counts_a) | set(counts_b))
+    rows = [
+        {
+            "family": family,
+            "rows_a": counts_a.get(family, 0),
+            "rows_b": counts_b.get(family, 0),
+            "delta": counts_b.get(family, 0) - counts_a.get(family, 0),
+        }
+        for family in families
+    ]
+    shared = [f for f in families if f in counts_a and f in counts_b and f != "all"]
+    shifts = []
+    for metric in METRICS:
+        if metric not in schema_a or metric not in schema_b:
+            continue
+        for family in ["all", *shared]:
+            values_a = _finite(a, metric, family)
+            values_b = _finite(b, metric, family)
+            d, p = ks_2samp(values_a, values_b)
+            shifts.append(
+                {
+                    "metric": metric,
+                    "family": family,
+                    "n_a": len(values_a),
+                    "n_b": len(values_b),
+                    "nonfinite_a": _nonfinite(a, metric, family),
+                    "nonfinite_b": _nonfinite(b, metric, family),
+                    "ks": d,
+                    "p_value": p,
+                }
+            )
+    return {"schema": schema, "rows": rows, "shifts": shifts}
+
+
+def _family_counts(df: pl.DataFrame) -> dict[str, int]:
+    if "family" not in df.columns:
+        return {"all": len(df)}
+    counts = df.group_by("family").len()
+    return dict(zip(counts["family"].to_list(), counts["len"].to_list()))
+
+
+def _select(df: pl.DataFrame, metric: str, family: str) -> pl.Series:
+    if family != "all":
+        df = df.filter(pl.col("family") == family)
+    return df[metric].cast(pl.Float64)
+
+
+def _finite(df: pl.DataFrame, metric: str, family: str) -> np.ndarray:
+    values = _select(df, metric, family).to_numpy()
+    return values[np.isfinite(values)]
+
+
+def _nonfinite(df: pl.DataFrame, metric: str, family: str) -> int:
+    values = _select(df, metric, family)
+    return len(values) - int(np.isfinite(values.to_numpy()).sum())
+
+
+def _kolmogorov_q(lam: float) -> float:
+    if lam < 1e-3:
+        return 1.0
+    total = sum((-1) ** (j - 1) * math.exp(-2.0 * j * j * lam * lam) for j in range(1, 101))
+    return min(1.0, max(0.0, 2.0 * total))
@@ -0,0 +1,155 @@
+"""Static SVG figures from an atlas dataset.
+
+Why this file exists
+- The thesis needs a few recurring plots of every atlas build: the
+  systolic-ratio histogram, capacity against volume, and how many rows have
+  each facet count. They should regenerate from the Parquet file in one
+  command, with a provenance sidecar like every other artifact.
+- Drawing happens in Rust with ``plotters`` (SVG backend only) behind
+  ``_native.bars_svg`` and ``_native.scatter_svg``. The Python side only
+  aggregates, so the plotting stack is one crate inside the extension we
+  already build, not a second toolchain. SVG diffs well in git and mdBook
+  embeds it directly.
+- Reviewers ask for the numbers behind a plot. Each ``Figure`` is therefore
+  split into ``data`` (the aggregated series: histogram bins, plotted points,
+  counts) and ``plot`` (SVG from that series). ``write_figures`` saves the
+  series as a small CSV next to the SVG, so the CSV holds exactly what was
+  drawn and nobody has to re-derive it from the Parquet file.
+
+References
+- Docs: docs/src/thesis/atlas-dataset.md
+"""
+
+from __future__ import annotations
+
+import math
+from dataclasses import dataclass
+from pathlib import Path
+from typing import Any, Callable
+
+import polars as pl
+
+from viterbo import _native as _native_impl
+
+_native: Any = _native_impl
+
+
+@dataclass(frozen=True)
+class Figure:
+    """A plot split into the aggregated series it shows and the drawing of that series."""
+
+    name: str
+    data: Callable[[pl.DataFrame], pl.DataFrame]
+    plot: Callable[[pl.DataFrame], str]
+
+
+def systolic_histogram_data(df: pl.DataFrame, bins: int = 30) -> pl.DataFrame:
+    """One row per bin: ``bin_lo``, ``bin_hi``, ``rows`` (empty bins included)."""
+
+    values = _finite(df, "systolic_ratio")
+    lo, hi = (min(values), max(values)) if values else (0.0, 1.0)
+    if hi <= lo:
+        hi = lo + 1.0
+    width = (hi - lo) / bins
+    counts = [0] * bins
+    for v in values:
+        counts[min(int((v - lo) / width), bins - 1)] += 1
+    return pl.DataFrame(
+        {
+            "bin_lo": [lo + i * width for i in range(bins)],
+            "bin_hi": [lo + (i + 1) * width for i in range(bins)],
+            "rows": counts,
+        },
+        schema={"bin_lo": pl.Float64, "bin_hi": pl.Float64, "rows": pl.Int64},
+    )
+
+
+def _plot_systolic_histogram(data: pl.DataFrame) -> str:
+    return _native.bars_svg(
+        data["bin_lo"].to_list(),
+        data["bin_hi"].to_list(),
+        data["rows"].to_list(),
+        title="Systolic ratio",
+        xlabel="c² / (2 vol)",
+        ylabel="rows",
+    )
+
+
+def systolic_histogram(df: pl.DataFrame, bins: int = 30) -> str:
+    return _plot_systolic_histogram(systolic_histogram_data(df, bins))
+
+
+def capacity_vs_volume_data(df: pl.DataFrame) -> pl.DataFrame:
+    """The plotted points: ``family``, ``volume``, ``capacity_ehz`` (finite rows only)."""
+
+    return df.select("family", "volume", "capacity_ehz").filter(
+        pl.col("volume").is_finite() & pl.col("capacity_ehz").is_finite()
+    )
+
+
+def _plot_capacity_vs_volume(data: pl.DataFrame) -> str:
+    return _native.scatter_svg(
+        data["volume"].to_list(),
+        data["capacity_ehz"].to_list(),
+        data["family"].to_list(),
+        title="Capacity vs volume",
+        xlabel="volume",
+        ylabel="c_EHZ",
+    )
+
+
+def capacity_vs_volume(df: pl.DataFrame) -> str:
+    return _plot_capacity_vs_volume(capacity_vs_volume_data(df))
+
+
+def facet_counts_data(df: pl.DataFrame) -> pl.DataFrame:
+    """One row per facet count: ``halfspace_count``, ``rows``."""
+
+    return df.group_by("halfspace_count").len().rename({"len": "rows"}).sort("halfspace_count")
+
+
+def _plot_facet_counts(data: pl.DataFrame) -> str:
+    keys = data["halfspace_count"].to_list()
+    return _native.bars_svg(
+        [k - 0.4 for k in keys],
+        [k + 0.4 for k in keys],
+        data["rows"].to_list(),
+        title="Facet counts",
+        xlabel="facets",
+        ylabel="rows",
+        colour=2,
+    )
+
+
+def facet_counts(df: pl.DataFrame) -> str:
+    return _plot_facet_counts(facet_counts_data(df))
+
+
+FIGURES = [
+    Figure("systolic_hist", systolic_histogram_data, _plot_systolic_histogram),
+    Figure("capacity_vs_volume", capacity_vs_volume_data, _plot_capacity_vs_volume),
+    Figure("facet_counts", facet_counts_data, _plot_facet_counts),
+]
+
+
+def write_figures(df: pl.DataFrame, out_dir: Path, prefix: str) -> list[tuple[Path, Path]]:
+    """Render every figure to ``<out_dir>/<prefix>_<name>.svg``.
+
+    The series each figure draws goes next to it as ``<prefix>_<name>.csv``.
+    Returns ``(svg, csv)`` pairs.
+    """
+
+    out_dir.mkdir(parents=True, exist_ok=True)
+    paths = []
+    for figure in FIGURES:
+        data = figure.data(df)
+        svg = out_dir / f"{prefix}_{figure.name}.svg"
+        svg.write_text(figure.plot(data), encoding="utf-8")
+        csv = svg.with_suffix(".csv")
+        data.write_csv(csv)
+        paths.append((svg, csv))
+    return paths
+
+
+def _finite(df: pl.DataFrame, column: str) -> list[float]:
+    return [v for v in df[column].to_list() if v is not None and math.isfinite(v)]
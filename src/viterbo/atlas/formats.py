@@ -0,0 +1,179 @@
+"""On-disk formats for atlas tables: Parquet, CSV, JSON lines.
+
+Why this file exists
+- Parquet is the canonical atlas format, but pandas notebooks, spreadsheets
+  and ``jq`` users want CSV or JSON lines. Ad-hoc conversion scripts drifted
+  (column order, NaN handling, nested vertex lists), so every writer and
+  reader goes through ``SCHEMA`` here.
+- CSV has no nested types: ``vertices`` and ``halfspaces`` are stored as JSON
+  strings and decoded again on read. JSON has no NaN: missing capacities are
+  written as ``null`` and read back as NaN, which is what the rest of the
+  pipeline expects.
+- Writes go to a temporary sibling that is renamed into place, so a killed
+  job never leaves a truncated table where readers look.
+- The format follows the file suffix (``.csv``, ``.jsonl``/``.ndjson``,
+  anything else Parquet) unless given explicitly.
+- The column layout is versioned (``SCHEMA_VERSION``). Parquet files carry
+  the version in their key-value metadata. Text formats have no metadata, and
+  Parquet files from before versioning have none either, so their version is
+  inferred from the columns. ``read_table`` upgrades older layouts through
+  ``_MIGRATIONS`` and refuses newer ones instead of misreading them.
+
+References
+- Docs: docs/src/thesis/atlas-dataset.md (row schema)
+"""
+
+from __future__ import annotations
+
+import csv
+import json
+import math
+from pathlib import Path
+from typing import Callable
+
+import polars as pl
+import pyarrow.parquet as pq
+
+_POINTS = pl.List(pl.List(pl.Float64))
+
+SCHEMA: dict[str, pl.DataType] = {
+    "row_id": pl.Int64,
+    "family": pl.Utf8,
+    "family_name": pl.Utf8,
+    "family_parameters": pl.Utf8,
+    "replay_token": pl.Utf8,
+    "vertex_count": pl.Int64,
+    "halfspace_count": pl.Int64,
+    "vertices": _POINTS,
+    "halfspaces": _POINTS,
+    "volume": pl.Float64,
+    "capacity_ehz": pl.Float64,
+    "dominant_orbit": pl.Utf8,
+    "systolic_ratio": pl.Float64,
+    "status": pl.Utf8,
+    "retries": pl.Int64,
+}
+FORMATS = ("parquet", "csv", "jsonl")
+# 1: no ``status`` column. 2: ``status`` (ok / no_cycle / budget_exceeded).
+# 3: ``retries`` (degenerate draws replaced under ``generation.on_degenerate``).
+SCHEMA_VERSION = 3
+VERSION_KEY = b"viterbo.atlas.schema_version"
+
+_NESTED = [name for name, dtype in SCHEMA.items() if dtype == _POINTS]
+_FLOATS = [name for name, dtype in SCHEMA.items() if dtype == pl.Float64]
+_SUFFIXES = {".csv": "csv", ".jsonl": "jsonl", ".ndjson": "jsonl"}
+
+
+def format_for(path: Path, fmt: str | None = None) -> str:
+    """``fmt`` if given (validated), otherwise the format implied by the suffix."""
+
+    if fmt is None:
+        return _SUFFIXES.get(path.suffix.lower(), "parquet")
+    if fmt not in FORMATS:
+        raise ValueError(f"unknown format {fmt!r}; expected one of {', '.join(FORMATS)}")
+    return fmt
+
+
+def write_table(df: pl.DataFrame, path: Path, fmt: str | None = None) -> Path:
+    """Write ``df`` in ``SCHEMA`` column order and types."""
+
+    kind = format_for(path, fmt)
+    table = df.select([pl.col(name).cast(dtype) for name, dtype in SCHEMA.items()])
+    path.parent.mkdir(parents=True, exist_ok=True)
+    tmp = path.with_name(path.name + ".tmp")
+    if kind == "parquet":
+        arrow = table.to_arrow()
+        metadata = {**(arrow.schema.metadata or {}), VERSION_KEY: str(SCHEMA_VERSION).encode()}
+        pq.write_table(arrow.replace_schema_metadata(metadata), tmp, compression="zstd")
+    elif kind == "jsonl":
+        table.with_columns([pl.col(name).fill_nan(None) for name in _FLOATS]).write_ndjson(tmp)
+    else:
+        table.with_columns(
+            [pl.Series(name, [json.dumps(v) for v in table[name].to_list()]) for name in _NESTED]
+        ).write_csv(tmp)
+    tmp.replace(path)
+    return path
+
+
+def read_table(path: Path, fmt: str | None = None) -> pl.DataFrame:
+    """Read a table written by ``write_table``; NaNs restored, nested lists decoded.
+
+    Tables of an older ``SCHEMA_VERSION`` are migrated to the current layout;
+    newer ones raise ``ValueError``.
+    """
+
+    kind = format_for(path, fmt)
+    if kind == "parquet":
+        table = pl.read_parquet(path)
+        version = _parquet_version(path, table.columns)
+    else:
+        columns = _text_columns(path, kind)
+        version = _infer_version(columns)
+        schema = {name: SCHEMA[name] for name in columns if name in SCHEMA}
+        if kind == "jsonl":
+            table = pl.read_ndjson(path, schema=schema)
+        else:
+            text_schema = {name: pl.Utf8 if name in _NESTED else t for name, t in schema.items()}
+            table = pl.read_csv(path, schema=text_schema).with_columns(
+                [pl.col(name).str.json_decode(_POINTS) for name in _NESTED if name in schema]
+            )
+        table = table.with_columns(
+            [pl.col(name).fill_null(math.nan) for name in _FLOATS if name in schema]
+        )
+    return _migrate(table, version, path)
+
+
+def schema_version(path: Path, fmt: str | None = None) -> int:
+    """The layout version of the table at ``path`` (stored or inferred)."""
+
+    kind = format_for(path, fmt)
+    if kind == "parquet":
+        return _parquet_version(path, pq.read_schema(path).names)
+    return _infer_version(_text_columns(path, kind))
+
+
+def _v1_to_v2(table: pl.DataFrame) -> pl.DataFrame:
+    # v1 builds had no per-sample limits, so a NaN capacity meant "no cycle".
+    no_cycle = pl.col("capacity_ehz").is_nan()
+    status = pl.when(no_cycle).then(pl.lit("no_cycle")).otherwise(pl.lit("ok"))
+    return table.with_columns(status.alias("status"))
+
+
+def _v2_to_v3(table: pl.DataFrame) -> pl.DataFrame:
+    return table.with_columns(pl.lit(0, dtype=pl.Int64).alias("retries"))
+
+
+_MIGRATIONS: dict[int, Callable[[pl.DataFrame], pl.DataFrame]] = {1: _v1_to_v2, 2: _v2_to_v3}
+
+
+def _migrate(table: pl.DataFrame, version: int, path: Path) -> pl.DataFrame:
+    if version > SCHEMA_VERSION:
+        raise ValueError(
+            f"{path}: schema version {version} is newer than this reader ({SCHEMA_VERSION}); "
+            "upgrade viterbo"
+        )
+    while version < SCHEMA_VERSION:
+        table = _MIGRATIONS[version](table)
+        version += 1
+    return table
+
+
+def _parquet_version(path: Path, columns: list[str]) -> int:
+    metadata = pq.read_schema(path).metadata or {}
+    if VERSION_KEY in metadata:
+        return int(metadata[VERSION_KEY])
+    return _infer_version(columns)
+
+
+def _infer_version(columns: list[str]) -> int:
+    if "retries" in columns:
+        return 3
+    return 2 if "status" in columns else 1
+
+
+def _text_columns(path: Path, kind: str) -> list[str]:
+    with path.open(encoding="utf-8", newline="") as handle:
+        if kind == "csv":
+            return next(csv.reader(handle), [])
+        first = handle.readline()
+    return list(json.loads(first)) if first.strip() else list(SCHEMA)
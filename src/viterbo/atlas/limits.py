@@ -0,0 +1,98 @@
+"""Per-sample wall-time and memory limits for capacity solves.
+
+Why this file exists
+- One pathological polytope could stall a whole atlas build: the native
+  solver has no cancellation hook, so a runaway solve blocks its thread until
+  it finishes or the machine runs out of memory. With limits set, each solve
+  runs in a forked worker process that the parent watches. The worker is
+  killed when it passes ``timeout_s`` of wall time or ``max_mem_mb`` of
+  resident memory, and the sample is recorded as ``budget_exceeded``.
+- The memory watchdog polls the worker's RSS from ``/proc`` (Linux only;
+  elsewhere only the timeout is enforced). A spike shorter than one poll
+  interval can go unseen, so the limit is a guard against runaway growth,
+  not a hard cap.
+- Forking costs a few milliseconds per sample. Limits are therefore opt-in
+  and only apply to serial solves.
+
+References
+- Docs: docs/src/thesis/atlas-dataset.md
+"""
+
+from __future__ import annotations
+
+import multiprocessing
+import os
+import time
+from dataclasses import dataclass
+from typing import Any, Callable
+
+_POLL_S = 0.05
+_PAGE_BYTES = os.sysconf("SC_PAGE_SIZE") if hasattr(os, "sysconf") else 4096
+
+
+@dataclass(frozen=True)
+class SampleLimits:
+    timeout_s: float | None = None
+    max_mem_mb: float | None = None
+
+    def __post_init__(self) -> None:
+        for name in ("timeout_s", "max_mem_mb"):
+            value = getattr(self, name)
+            if value is not None and value <= 0:
+                raise ValueError(f"{name} must be positive, got {value}")
+
+
+class BudgetExceeded(Exception):
+    """The worker passed a limit and was killed."""
+
+
+def run_limited(fn: Callable[[Any], Any], arg: Any, limits: SampleLimits) -> Any:
+    """Return ``fn(arg)`` computed in a forked worker held to ``limits``.
+
+    Raises ``BudgetExceeded`` if the worker was killed for passing a limit and
+    ``RuntimeError`` if it failed or died on its own.
+    """
+
+    ctx = multiprocessing.get_context("fork")
+    recv, send = ctx.Pipe(duplex=False)
+    proc = ctx.Process(target=_worker, args=(fn, arg, send), daemon=True)
+    deadline = None if limits.timeout_s is None else time.monotonic() + limits.timeout_s
+    proc.start()
+    send.close()
+    try:
+        while not recv.poll(_POLL_S):
+            if deadline is not None and time.monotonic() > deadline:
+                raise BudgetExceeded(f"solve exceeded {limits.timeout_s} s")
+            if limits.max_mem_mb is not None and proc.pid is not None:
+                rss = _rss_mb(proc.pid)
+                if rss is not None and rss > limits.max_mem_mb:
+                    raise BudgetExceeded(f"solve exceeded {limits.max_mem_mb} MB (rss {rss:.0f})")
+            if not proc.is_alive() and not recv.poll():
+                raise RuntimeError(f"solver worker died with exit code {proc.exitcode}")
+        ok, value = recv.recv()
+    finally:
+        if proc.is_alive():
+            proc.kill()
+        proc.join()
+        recv.close()
+    if not ok:
+        raise RuntimeError(f"solver worker failed: {value}")
+    return value
+
+
+def _worker(fn: Callable[[Any], Any], arg: Any, send: Any) -> None:
+    try:
+        send.send((True, fn(arg)))
+    except Exception as exc:  # noqa: BLE001
+        send.send((False, repr(exc)))
+    finally:
+        send.close()
+
+
+def _rss_mb(pid: int) -> float | None:
+    try:
+        with open(f"/proc/{pid}/statm", encoding="ascii") as handle:
+            resident_pages = int(handle.read().split()[1])
+    except (OSError, IndexError, ValueError):
+        return None
+    return resident_pages * _PAGE_BYTES / (1024 * 1024)
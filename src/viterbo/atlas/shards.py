@@ -0,0 +1,132 @@
+"""Sharded atlas builds for cluster array jobs.
+
+Why this file exists
+- On the SLURM cluster one atlas build is split over an array job. Before,
+  every job script hand-rolled an index range. ``--shard K/N`` instead gives
+  worker ``K`` (0-based, e.g. ``$SLURM_ARRAY_TASK_ID``) the rows with
+  ``row_id % N == K``. The slices are disjoint and deterministic, and striding
+  spreads each family evenly, so no shard gets all the slow rows.
+- Generation is cheap and deterministic, so every worker regenerates all rows
+  and solves only its own. Row ids stay global.
+- Each shard writes ``<dataset>.shards/shard-KKKK-of-NNNN.<ext>`` plus a
+  ``.json`` manifest with the config fingerprint. ``merge_shards`` refuses to
+  combine shards of different configs or shard counts, or an incomplete set.
+
+References
+- Docs: docs/src/thesis/atlas-dataset.md
+"""
+
+from __future__ import annotations
+
+import json
+import re
+from dataclasses import dataclass
+from pathlib import Path
+
+import polars as pl
+
+from viterbo.jsonlog import JsonLog
+
+from .checkpoint import config_fingerprint
+from .config import AtlasConfig
+from .dataset import generate_rows, log_samples
+from .formats import SCHEMA, format_for, read_table, write_table
+from .limits import SampleLimits
+from .types import ProgressCallback, solve_capacities
+
+_SHARD_RE = re.compile(r"^(\d+)/(\d+)$")
+
+
+@dataclass(frozen=True)
+class Shard:
+    index: int
+    count: int
+
+    @classmethod
+    def parse(cls, text: str) -> "Shard":
+        match = _SHARD_RE.match(text.strip())
+        if not match:
+            raise ValueError(f"shard must look like K/N, got {text!r}")
+        index, count = int(match.group(1)), int(match.group(2))
+        if count <= 0 or not 0 <= index < count:
+            raise ValueError(f"shard index must satisfy 0 <= K < N, got {text!r}")
+        return cls(index, count)
+
+    def stem(self) -> str:
+        return f"shard-{self.index:04d}-of-{self.count:04d}"
+
+
+def shards_dir(cfg: AtlasConfig) -> Path:
+    dataset = cfg.out.dataset
+    return dataset.with_name(dataset.name + ".shards")
+
+
+def build_shard(
+    cfg: AtlasConfig,
+    shard: Shard,
+    *,
+    jobs: int | None = None,
+    progress: ProgressCallback | None = None,
+    log: JsonLog | None = None,
+    limits: SampleLimits | None = None,
+) -> pl.DataFrame:
+    """Generate every row, solve and return only those of ``shard``."""
+
+    rows, generate_s = generate_rows(cfg)
+    row_ids = range(shard.index, len(rows), shard.count)
+    mine = [rows[row_id] for row_id in row_ids]
+    solve_s = solve_capacities(mine, jobs, progress=progress, limits=limits)
+    if log is not None:
+        log_samples(log, row_ids, mine, [generate_s[i] for i in row_ids], solve_s)
+    records = [row.to_record(row_id) for row_id, row in zip(row_ids, mine)]
+    return pl.DataFrame(records) if records else pl.DataFrame(schema=SCHEMA)
+
+
+def write_shard(cfg: AtlasConfig, shard: Shard, df: pl.DataFrame, fmt: str | None = None) -> Path:
+    """Write the shard table and its manifest; returns the table path."""
+
+    kind = format_for(cfg.out.dataset, fmt)
+    root = shards_dir(cfg)
+    path = write_table(df, root / f"{shard.stem()}.{kind}", kind)
+    manifest = {
+        "index": shard.index,
+        "count": shard.count,
+        "rows": len(df),
+        "format": kind,
+        "fingerprint": config_fingerprint(cfg),
+    }
+    tmp = root / f"{shard.stem()}.json.tmp"
+    tmp.write_text(json.dumps(manifest, indent=2, sort_keys=True) + "\n", encoding="utf-8")
+    tmp.replace(root / f"{shard.stem()}.json")
+    return path
+
+
+def merge_shards(cfg: AtlasConfig) -> tuple[pl.DataFrame, list[Path]]:
+    """Concatenate a complete shard set in ``row_id`` order.
+
+    Returns the merged table and the shard files it was built from.
+    """
+
+    root = shards_dir(cfg)
+    manifests = [
+        json.loads(path.read_text(encoding="utf-8"))
+        for path in sorted(root.glob("shard-*-of-*.json"))
+    ]
+    if not manifests:
+        raise FileNotFoundError(f"no shard manifests under {root}")
+    fingerprint = config_fingerprint(cfg)
+    if any(m["fingerprint"] != fingerprint for m in manifests):
+        raise ValueError(f"{root}: shards were built from a different config")
+    counts = {m["count"] for m in manifests}
+    if len(counts) != 1:
+        raise ValueError(f"{root}: shards disagree on the shard count: {sorted(counts)}")
+    count = counts.pop()
+    missing = sorted(set(range(count)) - {m["index"] for m in manifests})
+    if missing:
+        raise ValueError(f"{root}: missing shards {missing} of {count}")
+    paths = [
+        root / f"{Shard(m['index'], count).stem()}.{m['format']}"
+        for m in sorted(manifests, key=lambda m: m["index"])
+    ]
+    merged = pl.concat([read_table(path) for path in paths]).sort("row_id")
+    return merged, paths
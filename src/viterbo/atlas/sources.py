@@ -1,36 +1,50 @@
 from __future__ import annotations
 
 import itertools
-from typing import Any, Iterator
+from typing import Any, Callable, Iterator, Optional
 
 from viterbo import _native as _native_impl
+from viterbo.seeds import RETRY_STRIDE, derive
 
-from .config import SourceConfig
-from .types import AtlasRow, PolytopeRecord, build_atlas_row
+from .config import GenerationPolicy, SourceConfig
+from .types import AtlasRow, PolytopeRecord, build_atlas_row, poly_dict_to_record
 
 _native: Any = _native_impl
 
 
-def source_from_spec(spec: SourceConfig, default_seed: int) -> "AtlasSource":
-    factory: dict[str, type[AtlasSource]] = {
-        "symmetric_halfspaces": SymmetricHalfspaceSource,
-        "mahler_products": MahlerProductSource,
-        "regular_products": RegularProductSource,
-        "special_catalog": SpecialCatalogSource,
-    }
-    cls = factory.get(spec.family)
+def source_from_spec(
+    spec: SourceConfig,
+    default_seed: int,
+    *,
+    solve_capacity: bool = True,
+    policy: GenerationPolicy = GenerationPolicy(),
+) -> "AtlasSource":
+    cls = SOURCE_FAMILIES.get(spec.family)
     if cls is None:
         raise ValueError(f"unknown atlas source family '{spec.family}'")
-    return cls(spec=spec, default_seed=default_seed)
+    return cls(
+        spec=spec, default_seed=default_seed, solve_capacity=solve_capacity, policy=policy
+    )
 
 
 class AtlasSource:
     spec: SourceConfig
     default_seed: int
+    solve_capacity: bool
+    policy: GenerationPolicy
 
-    def __init__(self, *, spec: SourceConfig, default_seed: int) -> None:
+    def __init__(
+        self,
+        *,
+        spec: SourceConfig,
+        default_seed: int,
+        solve_capacity: bool = True,
+        policy: GenerationPolicy = GenerationPolicy(),
+    ) -> None:
         self.spec = spec
         self.default_seed = default_seed
+        self.solve_capacity = solve_capacity
+        self.policy = policy
 
     def generate(self) -> Iterator[AtlasRow]:
         raise NotImplementedError
@@ -39,12 +53,40 @@ class AtlasSource:
     def seed(self) -> int:
         return self.spec.seed if self.spec.seed is not None else self.default_seed
 
+    def draw(self, sample: Callable[[int], Any], seed: int) -> tuple[Optional[Any], int, int]:
+        """``sample(seed)`` under ``policy``: ``(payload or None if skipped, seed used, retries)``.
+
+        Retry ``r`` uses ``seed + r * RETRY_STRIDE``, so the replay token of the
+        kept sample names the seed that actually produced it.
+        """
+
+        retries, current = 0, seed
+        while True:
+            try:
+                return sample(current), current, retries
+            except _native.DegenerateSampleError as err:
+                if self.policy.on_degenerate == "abort":
+                    raise
+                if self.policy.on_degenerate == "skip":
+                    return None, current, retries
+                if retries >= self.policy.max_retries:
+                    raise ValueError(
+                        f"source '{self.spec.name}': seed {seed} still degenerate after "
+                        f"{retries} retries: {err}"
+                    ) from err
+                retries += 1
+                current = derive(seed, retries, stride=RETRY_STRIDE)
+
 
 class SymmetricHalfspaceSource(AtlasSource):
     def generate(self) -> Iterator[AtlasRow]:
         for idx in range(self.spec.rows):
-            sample_seed = self.seed + idx
-            poly = _native.rand4_symmetric_halfspace_sample(self.spec.params, int(sample_seed))
+            poly, sample_seed, retries = self.draw(
+                lambda seed: _native.rand4_symmetric_halfspace_sample(self.spec.params, seed),
+                self.seed + idx,
+            )
+            if poly is None:
+                continue
             yield build_atlas_row(
                 family="symmetric_halfspaces",
                 family_name=self.spec.name,
@@ -54,14 +96,20 @@ class SymmetricHalfspaceSource(AtlasSource):
                 },
                 replay_token={"seed": sample_seed},
                 poly_payload=poly,
+                solve_capacity=self.solve_capacity,
+                retries=retries,
             )
 
 
 class MahlerProductSource(AtlasSource):
     def generate(self) -> Iterator[AtlasRow]:
-        base_seed = self.seed
         for idx in range(self.spec.rows):
-            poly = _native.rand4_mahler_product_sample(self.spec.params, int(base_seed), int(idx))
+            poly, base_seed, retries = self.draw(
+                lambda seed: _native.rand4_mahler_product_sample(self.spec.params, seed, idx),
+                self.seed,
+            )
+            if poly is None:
+                continue
             yield build_atlas_row(
                 family="mahler_products",
                 family_name=self.spec.name,
@@ -72,6 +120,8 @@ class MahlerProductSource(AtlasSource):
                 },
                 replay_token={"seed": base_seed, "index": idx},
                 poly_payload=poly,
+                solve_capacity=self.solve_capacity,
+                retries=retries,
             )
 
 
@@ -95,6 +145,7 @@ class RegularProductSource(AtlasSource):
                 },
                 replay_token={"pair_index": pair_index - 1},
                 poly_payload=maybe_poly,
+                solve_capacity=self.solve_capacity,
             )
             rows_yielded += 1
         if rows_yielded < self.spec.rows:
@@ -104,6 +155,42 @@ class RegularProductSource(AtlasSource):
             )
 
 
+class RegistrySource(AtlasSource):
+    """Any Rust registry family, streamed through ``_native.PolytopeStream``.
+
+    ``params`` is a registry config minus the seed:
+    ``{"family": ..., "params": {...}, "random_anisotropy": {...}?}``.
+    """
+
+    def registry_config(self) -> dict[str, Any]:
+        family = self.spec.params.get("family")
+        if not family:
+            raise ValueError(f"registry source '{self.spec.name}' requires params.family")
+        config = {k: v for k, v in self.spec.params.items() if k != "seed"}
+        config["seed"] = int(self.seed)
+        return config
+
+    def generate(self) -> Iterator[AtlasRow]:
+        config = self.registry_config()
+        stream = _native.PolytopeStream(config)
+        rows_yielded = 0
+        for sample in itertools.islice(stream, self.spec.rows):
+            yield build_atlas_row(
+                family="registry",
+                family_name=f"{self.spec.name}:{stream.family}",
+                family_parameters=config,
+                replay_token={"replay": sample["replay"]},
+                poly_payload=sample,
+                solve_capacity=self.solve_capacity,
+            )
+            rows_yielded += 1
+        if rows_yielded < self.spec.rows:
+            raise ValueError(
+                f"registry source '{self.spec.name}' produced "
+                f"{rows_yielded} rows, fewer than requested ({self.spec.rows})"
+            )
+
+
 class SpecialCatalogSource(AtlasSource):
     def generate(self) -> Iterator[AtlasRow]:
         members = self.spec.params.get("members") or []
@@ -122,9 +209,49 @@ class SpecialCatalogSource(AtlasSource):
                 family_parameters={"member": ident},
                 replay_token={"member": ident},
                 poly_payload=payload,
+                solve_capacity=self.solve_capacity,
             )
 
 
+SOURCE_FAMILIES: dict[str, type[AtlasSource]] = {
+    "symmetric_halfspaces": SymmetricHalfspaceSource,
+    "mahler_products": MahlerProductSource,
+    "regular_products": RegularProductSource,
+    "special_catalog": SpecialCatalogSource,
+    "registry": RegistrySource,
+}
+
+
+def regenerate_polytope(
+    family: str, family_parameters: dict[str, Any], replay_token: dict[str, Any]
+) -> PolytopeRecord:
+    """Rebuild the polytope of a stored row from its family parameters and replay token."""
+
+    params = family_parameters.get("params") or {}
+    if family == "symmetric_halfspaces":
+        payload = _native.rand4_symmetric_halfspace_sample(params, int(replay_token["seed"]))
+    elif family == "mahler_products":
+        payload = _native.rand4_mahler_product_sample(
+            params, int(replay_token["seed"]), int(replay_token["index"])
+        )
+    elif family == "regular_products":
+        payload = _native.rand4_regular_product_sample(params, int(replay_token["pair_index"]))
+        if payload is None:
+            raise ValueError(f"regular product pair {replay_token['pair_index']} no longer exists")
+    elif family == "special_catalog":
+        return special_polytope(str(replay_token["member"]))
+    elif family == "registry":
+        payload = _native.rand4_regenerate(
+            family_parameters["family"],
+            params,
+            replay_token["replay"],
+            family_parameters.get("random_anisotropy"),
+        )
+    else:
+        raise ValueError(f"unknown atlas source family '{family}'")
+    return poly_dict_to_record(payload)
+
+
 def special_polytope(ident: str) -> PolytopeRecord:
     ident = ident.lower()
     if ident == "hypercube":
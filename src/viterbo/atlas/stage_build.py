@@ -2,16 +2,89 @@ from __future__ import annotations
 
 import argparse
 import sys
+from dataclasses import replace
 from pathlib import Path
 
+from viterbo.estimate import describe
+from viterbo.interrupt import Termination, graceful_termination
+from viterbo.jsonlog import JsonLog
+from viterbo.progress import ProgressReporter
+from viterbo.provenance import sidecar_path
+from viterbo.provenance import write as write_provenance
+from viterbo.seeds import add_seed_argument
+
+from .checkpoint import build_dataset_checkpointed, clear_checkpoint, parts_dir
 from .config import AtlasConfig
-from .dataset import build_dataset, write_dataset
+from .dataset import atlas_seeds, build_dataset, source_seed, write_dataset
+from .formats import FORMATS, format_for
+from .limits import SampleLimits
+from .shards import Shard, build_shard, shards_dir, write_shard
+from .types import ProgressCallback
 from .visualize import write_preview
 
 
 def parse_args(argv: list[str] | None = None) -> argparse.Namespace:
     parser = argparse.ArgumentParser(description="Build the atlas dataset.")
-    parser.add_argument("--config", required=True, help="Path to the JSON config file.")
+    parser.add_argument("--config", required=True, help="Path to the JSON or TOML config file.")
+    parser.add_argument(
+        "--out",
+        help="Override out.dataset from the config.",
+    )
+    add_seed_argument(parser, help="Override the config's root seed (all source seeds follow).")
+    parser.add_argument(
+        "--format",
+        choices=FORMATS,
+        help="Dataset file format (default: from the dataset suffix, .csv/.jsonl or Parquet).",
+    )
+    parser.add_argument(
+        "--jobs",
+        type=int,
+        help="Solve capacities in parallel on N threads (0 = one per core); "
+        "row order is unchanged. Overrides solver.jobs from the config.",
+    )
+    parser.add_argument(
+        "--checkpoint-rows",
+        type=int,
+        help="Persist solved rows in chunks of N next to the dataset so a crashed "
+        "run can be resumed.",
+    )
+    parser.add_argument(
+        "--resume",
+        action="store_true",
+        help="Continue a checkpointed run, skipping chunks already solved.",
+    )
+    parser.add_argument(
+        "--shard",
+        help="Build only rows with row_id %% N == K (K/N, 0-based) into <dataset>.shards/; "
+        "combine with stage_merge_shards.",
+    )
+    parser.add_argument(
+        "--timeout-per-sample",
+        type=float,
+        help="Kill a capacity solve after this many seconds and mark the row "
+        "status=budget_exceeded (serial solves only).",
+    )
+    parser.add_argument(
+        "--max-mem-per-sample",
+        type=float,
+        help="Kill a capacity solve whose resident memory passes this many MB and mark the "
+        "row status=budget_exceeded (serial solves only).",
+    )
+    parser.add_argument(
+        "--progress",
+        action="store_true",
+        help="Print solved rows, rows/s and an ETA to stderr while solving capacities.",
+    )
+    parser.add_argument(
+        "--log-json",
+        help="Append one JSON line per sample (replay token, generate/solve seconds, "
+        "outcome) to this file.",
+    )
+    parser.add_argument(
+        "--dry-run",
+        action="store_true",
+        help="Validate the config, print planned rows, estimated time and outputs, then exit.",
+    )
     parser.add_argument(
         "--preview-only",
         action="store_true",
@@ -24,12 +97,62 @@ def main(argv: list[str] | None = None) -> int:
     args = parse_args(argv)
     config_path = Path(args.config).resolve()
     cfg = AtlasConfig.from_file(config_path)
+    if args.out:
+        cfg = replace(cfg, out=replace(cfg.out, dataset=Path(args.out).resolve()))
+    if args.seed is not None:
+        cfg = replace(cfg, seed=args.seed)
 
     if args.preview_only:
         return _run_preview_only(cfg)
+    jobs = args.jobs if args.jobs is not None else cfg.jobs
+    progress = ProgressReporter("[atlas] capacities") if args.progress else None
+    checkpointed = bool(args.checkpoint_rows or args.resume)
+    if args.shard and checkpointed:
+        raise ValueError("--shard cannot be combined with --checkpoint-rows/--resume")
 
-    df = build_dataset(cfg)
-    dataset_path = write_dataset(cfg, df)
+    limits = None
+    if args.timeout_per_sample is not None or args.max_mem_per_sample is not None:
+        if args.jobs is not None:
+            raise ValueError(
+                "--timeout-per-sample/--max-mem-per-sample cannot be combined with --jobs"
+            )
+        limits = SampleLimits(args.timeout_per_sample, args.max_mem_per_sample)
+        jobs = None
+
+    if args.dry_run:
+        shard = Shard.parse(args.shard) if args.shard else None
+        return _dry_run(cfg, jobs, shard, args)
+
+    log = JsonLog(args.log_json) if args.log_json else None
+    with graceful_termination() as termination:
+        try:
+            if args.shard:
+                shard = Shard.parse(args.shard)
+                return _run_shard(cfg, shard, args.format, jobs, progress, log, limits)
+            if checkpointed:
+                df = build_dataset_checkpointed(
+                    cfg,
+                    chunk_rows=args.checkpoint_rows,
+                    jobs=jobs,
+                    resume=args.resume,
+                    progress=progress,
+                    log=log,
+                    limits=limits,
+                )
+            else:
+                df = build_dataset(cfg, jobs=jobs, progress=progress, log=log, limits=limits)
+        except KeyboardInterrupt:
+            if log is not None:
+                log.event("stopped", signal=termination.name)
+            return _stopped_early(cfg, termination, checkpointed)
+        finally:
+            if log is not None:
+                log.close()
+    dataset_path = write_dataset(cfg, df, args.format)
+    clear_checkpoint(cfg)
+    exceeded = df.filter(df["status"] == "budget_exceeded").height
+    if exceeded:
+        print(f"[atlas] {exceeded} rows exceeded the per-sample budget", file=sys.stderr)
     if cfg.out.preview:
         write_preview(df, cfg.out.preview, limit=cfg.out.preview_limit)
     print(
@@ -39,6 +162,88 @@ def main(argv: list[str] | None = None) -> int:
     return 0
 
 
+def _stopped_early(cfg: AtlasConfig, termination: Termination, checkpointed: bool) -> int:
+    """Nothing half-written remains (tables are written atomically); record what was kept."""
+
+    manifest = parts_dir(cfg) / "manifest.json"
+    if checkpointed and manifest.exists():
+        write_provenance(
+            manifest,
+            {"seed": cfg.seed, "metadata": cfg.metadata},
+            {
+                "command": "python -m viterbo.atlas.stage_build --config <file> (checkpointed)",
+                "exit_code": termination.exit_code,
+                "terminated_early": True,
+                "signal": termination.name,
+                "seeds": atlas_seeds(cfg),
+            },
+        )
+        print(
+            f"[atlas] stopped by {termination.name}; finished chunks are in "
+            f"{manifest.parent}, continue with --resume",
+            file=sys.stderr,
+        )
+    else:
+        print(
+            f"[atlas] stopped by {termination.name} before anything was written; "
+            "use --checkpoint-rows to keep partial progress",
+            file=sys.stderr,
+        )
+    return termination.exit_code
+
+
+def _dry_run(
+    cfg: AtlasConfig, jobs: int | None, shard: Shard | None, args: argparse.Namespace
+) -> int:
+    total = sum(spec.rows for spec in cfg.sources)
+    print(f"[dry-run] seed {cfg.seed}, {len(cfg.sources)} sources, {total} rows")
+    for idx, spec in enumerate(cfg.sources):
+        seed = spec.seed if spec.seed is not None else source_seed(cfg, idx)
+        print(f"[dry-run]   {spec.name} ({spec.family}): {spec.rows} rows, seed {seed}")
+    solves = total
+    if shard is not None:
+        solves = len(range(shard.index, total, shard.count))
+        path = shards_dir(cfg) / f"{shard.stem()}.{format_for(cfg.out.dataset, args.format)}"
+        outputs = [path, path.with_suffix(".json"), sidecar_path(path)]
+    else:
+        outputs = [cfg.out.dataset, sidecar_path(cfg.out.dataset)]
+        if cfg.out.preview:
+            outputs.append(cfg.out.preview)
+        if args.checkpoint_rows or args.resume:
+            outputs.append(parts_dir(cfg))
+    print(f"[dry-run] work: {describe(solves, threads=jobs)}")
+    for path in outputs:
+        print(f"[dry-run] output: {path}")
+    return 0
+
+
+def _run_shard(
+    cfg: AtlasConfig,
+    shard: Shard,
+    fmt: str | None,
+    jobs: int | None,
+    progress: ProgressCallback | None,
+    log: JsonLog | None,
+    limits: SampleLimits | None,
+) -> int:
+    df = build_shard(cfg, shard, jobs=jobs, progress=progress, log=log, limits=limits)
+    path = write_shard(cfg, shard, df, fmt)
+    write_provenance(
+        path,
+        {"seed": cfg.seed, "shard": f"{shard.index}/{shard.count}", "rows": len(df)},
+        {
+            "command": "python -m viterbo.atlas.stage_build --config <file> --shard K/N",
+            "exit_code": 0,
+            "seeds": atlas_seeds(cfg),
+        },
+    )
+    print(
+        f"[atlas] shard {shard.index}/{shard.count}: wrote {len(df)} rows to {path}",
+        file=sys.stderr,
+    )
+    return 0
+
+
 def _run_preview_only(cfg: AtlasConfig) -> int:
     if not cfg.out.preview:
         raise ValueError("config does not specify out.preview, preview-only mode invalid")
@@ -0,0 +1,89 @@
+"""Pipeline stage: compare two atlas datasets.
+
+Usage:
+  uv run python -m viterbo.atlas.stage_diff old.parquet new.parquet [--out diff.json] [--alpha 0.01]
+Prints schema differences, per-family row counts and KS shifts. With ``--alpha`` it exits 1
+when any shift has a p-value below it, or when the schemas differ.
+"""
+
+from __future__ import annotations
+
+import argparse
+import json
+import math
+import sys
+from pathlib import Path
+from typing import Any
+
+from viterbo.provenance import write as write_provenance
+
+from .diff import diff_datasets
+from .formats import read_table
+
+
+def parse_args(argv: list[str] | None = None) -> argparse.Namespace:
+    parser = argparse.ArgumentParser(description="Compare two atlas datasets.")
+    parser.add_argument("a", help="Reference dataset (Parquet, CSV or JSON lines).")
+    parser.add_argument("b", help="Dataset to compare against the reference.")
+    parser.add_argument("--out", help="Also write the report as JSON.")
+    parser.add_argument(
+        "--alpha",
+        type=float,
+        help="Exit 1 if a KS p-value falls below this, or the schemas differ.",
+    )
+    return parser.parse_args(argv)
+
+
+def main(argv: list[str] | None = None) -> int:
+    args = parse_args(argv)
+    path_a, path_b = Path(args.a).resolve(), Path(args.b).resolve()
+    report = diff_datasets(read_table(path_a), read_table(path_b))
+    schema = report["schema"]
+    for name in schema["only_a"]:
+        print(f"[diff] column only in a: {name}")
+    for name in schema["only_b"]:
+        print(f"[diff] column only in b: {name}")
+    for name, (dtype_a, dtype_b) in schema["dtype"].items():
+        print(f"[diff] column {name}: {dtype_a} -> {dtype_b}")
+    for row in report["rows"]:
+        print(f"[diff] rows {row['family']:<24} {row['rows_a']:>8} -> {row['rows_b']:>8}")
+    for shift in report["shifts"]:
+        print(
+            f"[diff] {shift['metric']:<15} {shift['family']:<24} "
+            f"KS={shift['ks']:.4f} p={shift['p_value']:.3g} "
+            f"(n {shift['n_a']} vs {shift['n_b']}, non-finite "
+            f"{shift['nonfinite_a']} vs {shift['nonfinite_b']})"
+        )
+    if args.out:
+        out_path = Path(args.out).resolve()
+        out_path.parent.mkdir(parents=True, exist_ok=True)
+        text = json.dumps(_json_safe(report), indent=2)
+        out_path.write_text(text + "\n", encoding="utf-8")
+        write_provenance(
+            out_path,
+            {"a": str(path_a), "b": str(path_b), "alpha": args.alpha},
+            {"command": "python -m viterbo.atlas.stage_diff <a> <b>", "exit_code": 0},
+            inputs=[path_a, path_b],
+        )
+    if args.alpha is None:
+        return 0
+    schema_changed = any(schema[key] for key in ("only_a", "only_b", "dtype"))
+    shifted = [s for s in report["shifts"] if s["p_value"] < args.alpha]
+    if schema_changed or shifted:
+        print(f"[diff] {len(shifted)} shifts below alpha={args.alpha}", file=sys.stderr)
+        return 1
+    return 0
+
+
+def _json_safe(value: Any) -> Any:
+    if isinstance(value, float) and math.isnan(value):
+        return None
+    if isinstance(value, dict):
+        return {k: _json_safe(v) for k, v in value.items()}
+    if isinstance(value, list):
+        return [_json_safe(v) for v in value]
+    return value
+
+
+if __name__ == "__main__":
+    raise SystemExit(main())
@@ -0,0 +1,82 @@
+from __future__ import annotations
+
+import argparse
+import sys
+from pathlib import Path
+
+import polars as pl
+
+from viterbo.provenance import write as write_provenance
+from viterbo.watch import changes
+
+from .figures import write_figures
+from .formats import read_table
+
+
+def parse_args(argv: list[str] | None = None) -> argparse.Namespace:
+    parser = argparse.ArgumentParser(description="Render SVG figures from an atlas dataset.")
+    parser.add_argument(
+        "--dataset", required=True, help="Path to the dataset (Parquet, CSV or JSON lines)."
+    )
+    parser.add_argument(
+        "--out-dir",
+        default="docs/assets/atlas",
+        help="Directory for the SVG files (default: docs/assets/atlas).",
+    )
+    parser.add_argument(
+        "--prefix",
+        help="File name prefix (default: the dataset stem, e.g. 'test').",
+    )
+    parser.add_argument(
+        "--watch",
+        action="store_true",
+        help=(
+            "Keep running and re-render whenever the dataset changes (Ctrl-C to stop). "
+            "Polls the file's size and mtime every --interval seconds instead of using "
+            "filesystem events, so a change is picked up within one interval."
+        ),
+    )
+    parser.add_argument(
+        "--interval",
+        type=float,
+        default=1.0,
+        help=(
+            "Seconds between dataset checks in --watch mode, i.e. the worst-case delay "
+            "before a re-render (default: 1)."
+        ),
+    )
+    return parser.parse_args(argv)
+
+
+def main(argv: list[str] | None = None) -> int:
+    args = parse_args(argv)
+    dataset_path = Path(args.dataset).resolve()
+    out_dir = Path(args.out_dir).resolve()
+    prefix = args.prefix or dataset_path.stem
+    if not args.watch:
+        _render(dataset_path, out_dir, prefix)
+        return 0
+    print(f"[atlas] watching {dataset_path} (Ctrl-C to stop)", file=sys.stderr)
+    try:
+        for _ in changes(dataset_path, interval=args.interval):
+            try:
+                _render(dataset_path, out_dir, prefix)
+            except (OSError, ValueError, pl.exceptions.PolarsError) as err:
+                print(f"[atlas] could not render {dataset_path}: {err}", file=sys.stderr)
+    except KeyboardInterrupt:
+        pass
+    return 0
+
+
+def _render(dataset_path: Path, out_dir: Path, prefix: str) -> None:
+    df = read_table(dataset_path)
+    config = {"dataset": str(dataset_path), "rows": len(df)}
+    extras = {"command": "python -m viterbo.atlas.stage_figures --dataset <file>", "exit_code": 0}
+    for svg, csv in write_figures(df, out_dir, prefix):
+        write_provenance(svg, config, extras, inputs=[dataset_path])
+        write_provenance(csv, config, {**extras, "figure": str(svg)}, inputs=[dataset_path])
+        print(f"[atlas] wrote {svg} (+ {csv.name})", file=sys.stderr)
+
+
+if __name__ == "__main__":
+    raise SystemExit(main())
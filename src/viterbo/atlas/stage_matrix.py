@@ -0,0 +1,59 @@
+"""Pipeline stage: build every cell of a ``[matrix]`` atlas config.
+
+Usage:
+  uv run python -m viterbo.atlas.stage_matrix --config configs/atlas/sweep.toml [--list]
+Each cell writes ``<dataset stem>/<cell>.<ext>`` with its own provenance sidecar; cells whose
+dataset already exists are skipped unless ``--force``.
+"""
+
+from __future__ import annotations
+
+import argparse
+import fnmatch
+import sys
+from pathlib import Path
+
+from .config import read_config_payload
+from .dataset import build_dataset, write_dataset
+from .matrix import expand_matrix
+from .visualize import write_preview
+
+
+def parse_args(argv: list[str] | None = None) -> argparse.Namespace:
+    parser = argparse.ArgumentParser(description="Build every cell of a parameter grid.")
+    parser.add_argument("--config", required=True, help="Atlas config with a [matrix] section.")
+    parser.add_argument("--jobs", type=int, help="Solve each cell on N threads (0 = all cores).")
+    parser.add_argument("--only", help="Build only cells whose name matches this glob.")
+    parser.add_argument("--force", action="store_true", help="Rebuild cells that already exist.")
+    parser.add_argument("--list", action="store_true", help="Print the cells and exit.")
+    return parser.parse_args(argv)
+
+
+def main(argv: list[str] | None = None) -> int:
+    args = parse_args(argv)
+    config_path = Path(args.config).resolve()
+    try:
+        cells = expand_matrix(read_config_payload(config_path), base_dir=config_path.parent)
+    except ValueError as err:
+        raise ValueError(f"{config_path}: {err}") from err
+    if args.only:
+        cells = [cell for cell in cells if fnmatch.fnmatch(cell.name, args.only)]
+    if args.list:
+        for cell in cells:
+            print(f"{cell.name}  ->  {cell.config.out.dataset}")
+        return 0
+    for index, cell in enumerate(cells, start=1):
+        cfg = cell.config
+        if cfg.out.dataset.exists() and not args.force:
+            print(f"[matrix] {index}/{len(cells)} {cell.name}: exists, skipped", file=sys.stderr)
+            continue
+        df = build_dataset(cfg, jobs=args.jobs if args.jobs is not None else cfg.jobs)
+        write_dataset(cfg, df)
+        if cfg.out.preview:
+            write_preview(df, cfg.out.preview, limit=cfg.out.preview_limit)
+        print(f"[matrix] {index}/{len(cells)} {cell.name}: {len(df)} rows", file=sys.stderr)
+    return 0
+
+
+if __name__ == "__main__":
+    raise SystemExit(main())
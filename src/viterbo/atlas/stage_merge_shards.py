@@ -0,0 +1,73 @@
+"""Pipeline stage: merge the shards of a ``stage_build --shard K/N`` run.
+
+Usage:
+  python -m viterbo.atlas.stage_merge_shards --config configs/atlas/<name>.json
+Writes the full dataset (and preview) exactly as an unsharded build would; the sidecar lists the
+shard files as inputs. ``--keep-shards`` leaves ``<dataset>.shards/`` in place.
+"""
+
+from __future__ import annotations
+
+import argparse
+import shutil
+import sys
+from dataclasses import replace
+from pathlib import Path
+
+from viterbo.provenance import write as write_provenance
+
+from .config import AtlasConfig
+from .dataset import atlas_seeds
+from .formats import FORMATS, format_for, write_table
+from .shards import merge_shards, shards_dir
+from .visualize import write_preview
+
+
+def parse_args(argv: list[str] | None = None) -> argparse.Namespace:
+    parser = argparse.ArgumentParser(description="Merge atlas shards into one dataset.")
+    parser.add_argument("--config", required=True, help="The config the shards were built with.")
+    parser.add_argument("--out", help="Override out.dataset from the config.")
+    parser.add_argument("--format", choices=FORMATS, help="Dataset file format.")
+    parser.add_argument(
+        "--keep-shards", action="store_true", help="Do not delete the shard directory."
+    )
+    return parser.parse_args(argv)
+
+
+def main(argv: list[str] | None = None) -> int:
+    args = parse_args(argv)
+    cfg = AtlasConfig.from_file(Path(args.config).resolve())
+    if args.out:
+        cfg = replace(cfg, out=replace(cfg.out, dataset=Path(args.out).resolve()))
+    df, shard_paths = merge_shards(cfg)
+    dataset_path = write_table(df, cfg.out.dataset, args.format)
+    write_provenance(
+        dataset_path,
+        {
+            "config_version": cfg.version,
+            "seed": cfg.seed,
+            "rows": len(df),
+            "format": format_for(dataset_path, args.format),
+            "shards": len(shard_paths),
+            "metadata": cfg.metadata,
+        },
+        {
+            "command": "python -m viterbo.atlas.stage_merge_shards --config <file>",
+            "exit_code": 0,
+            "seeds": atlas_seeds(cfg),
+        },
+        inputs=shard_paths,
+    )
+    if cfg.out.preview:
+        write_preview(df, cfg.out.preview, limit=cfg.out.preview_limit)
+    if not args.keep_shards:
+        shutil.rmtree(shards_dir(cfg))
+    print(
+        f"[atlas] merged {len(shard_paths)} shards, {len(df)} rows, into {dataset_path}",
+        file=sys.stderr,
+    )
+    return 0
+
+
+if __name__ == "__main__":
+    raise SystemExit(main())
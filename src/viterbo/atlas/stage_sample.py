@@ -0,0 +1,95 @@
+"""Pipeline stage: print a handful of generated polytopes as JSON.
+
+Usage:
+  uv run python -m viterbo.atlas.stage_sample --config configs/atlas/small.json \
+      --source mahler_small --seed 7 --count 5
+  uv run python -m viterbo.atlas.stage_sample --family symmetric_halfspaces \
+      --params '{"directions": 6, "radius_min": 0.7, "radius_max": 1.25}' --count 3 --out s.json
+Each entry has the family, parameters, replay token, vertices, halfspaces and volume.
+Capacities are not solved. Writes to stdout unless ``--out`` names a file.
+"""
+
+from __future__ import annotations
+
+import argparse
+import itertools
+import json
+import math
+import sys
+from dataclasses import replace
+from pathlib import Path
+from typing import Any
+
+from viterbo.seeds import add_seed_argument
+
+from .config import AtlasConfig, SourceConfig
+from .dataset import source_seed
+from .sources import SOURCE_FAMILIES, source_from_spec
+from .types import AtlasRow
+
+
+def parse_args(argv: list[str] | None = None) -> argparse.Namespace:
+    parser = argparse.ArgumentParser(description="Sample a few polytopes from one generator.")
+    group = parser.add_mutually_exclusive_group(required=True)
+    group.add_argument("--family", choices=sorted(SOURCE_FAMILIES), help="Source family.")
+    group.add_argument("--config", help="Atlas config to take a source from (see --source).")
+    parser.add_argument("--source", help="Source name in --config (default: the first one).")
+    parser.add_argument(
+        "--params", default="{}", help="Family params as JSON, or @file.json (with --family)."
+    )
+    add_seed_argument(parser, help="Source seed (default: the config's derived seed, else 0).")
+    parser.add_argument("--count", type=int, default=5, help="Polytopes to print (default: 5).")
+    parser.add_argument("--out", default="-", help="Output file, or - for stdout (default).")
+    return parser.parse_args(argv)
+
+
+def main(argv: list[str] | None = None) -> int:
+    args = parse_args(argv)
+    if args.count <= 0:
+        raise ValueError("--count must be positive")
+    spec, default_seed = _source(args)
+    spec = replace(spec, rows=args.count, seed=args.seed if args.seed is not None else spec.seed)
+    source = source_from_spec(spec, default_seed, solve_capacity=False)
+    samples = [_sample(row) for row in itertools.islice(source.generate(), args.count)]
+    text = json.dumps(samples, indent=2) + "\n"
+    if args.out == "-":
+        sys.stdout.write(text)
+    else:
+        out_path = Path(args.out)
+        out_path.parent.mkdir(parents=True, exist_ok=True)
+        out_path.write_text(text, encoding="utf-8")
+    return 0
+
+
+def _source(args: argparse.Namespace) -> tuple[SourceConfig, int]:
+    if args.config:
+        cfg = AtlasConfig.from_file(Path(args.config).resolve())
+        names = [spec.name for spec in cfg.sources]
+        index = names.index(args.source) if args.source in names else None
+        if args.source is not None and index is None:
+            raise ValueError(f"no source {args.source!r} in {args.config}; have {names}")
+        index = index or 0
+        return cfg.sources[index], source_seed(cfg, index)
+    params_text = args.params
+    if params_text.startswith("@"):
+        params_text = Path(params_text[1:]).read_text(encoding="utf-8")
+    params = json.loads(params_text)
+    if not isinstance(params, dict):
+        raise ValueError("--params must be a JSON object")
+    return SourceConfig(name=args.family, family=args.family, rows=0, params=params), 0
+
+
+def _sample(row: AtlasRow) -> dict[str, Any]:
+    return {
+        "family": row.family,
+        "family_name": row.family_name,
+        "family_parameters": row.family_parameters,
+        "replay_token": row.replay_token,
+        "vertices": row.polytope.vertices,
+        "halfspaces": row.polytope.halfspaces,
+        "volume": None if math.isnan(row.volume) else row.volume,
+    }
+
+
+if __name__ == "__main__":
+    raise SystemExit(main())
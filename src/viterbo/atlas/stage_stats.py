@@ -0,0 +1,55 @@
+"""Pipeline stage: per-family summary statistics of an atlas dataset.
+
+Usage:
+  uv run python -m viterbo.atlas.stage_stats data/atlas/small.parquet [--out stats.csv]
+Prints the table; ``--out`` also writes it as CSV or JSON (by suffix) with a provenance sidecar.
+"""
+
+from __future__ import annotations
+
+import argparse
+import json
+import sys
+from pathlib import Path
+
+import polars as pl
+
+from viterbo.provenance import write as write_provenance
+
+from .formats import read_table
+from .stats import summarize
+
+
+def parse_args(argv: list[str] | None = None) -> argparse.Namespace:
+    parser = argparse.ArgumentParser(description="Summarize an atlas dataset by family.")
+    parser.add_argument("dataset", help="Path to the dataset (Parquet, CSV or JSON lines).")
+    parser.add_argument("--out", help="Write the table to this .csv or .json file as well.")
+    return parser.parse_args(argv)
+
+
+def main(argv: list[str] | None = None) -> int:
+    args = parse_args(argv)
+    dataset_path = Path(args.dataset).resolve()
+    df = read_table(dataset_path)
+    table = summarize(df)
+    with pl.Config(tbl_rows=-1, tbl_cols=-1, tbl_width_chars=160, float_precision=4):
+        print(table)
+    if args.out:
+        out_path = Path(args.out).resolve()
+        out_path.parent.mkdir(parents=True, exist_ok=True)
+        if out_path.suffix.lower() == ".json":
+            out_path.write_text(json.dumps(table.to_dicts(), indent=2) + "\n", encoding="utf-8")
+        else:
+            table.write_csv(out_path)
+        write_provenance(
+            out_path,
+            {"dataset": str(dataset_path), "rows": len(df)},
+            {"command": "python -m viterbo.atlas.stage_stats <dataset>", "exit_code": 0},
+            inputs=[dataset_path],
+        )
+        print(f"[stats] wrote {out_path}", file=sys.stderr)
+    return 0
+
+
+if __name__ == "__main__":
+    raise SystemExit(main())
@@ -0,0 +1,52 @@
+from __future__ import annotations
+
+import argparse
+import json
+import sys
+from dataclasses import asdict
+from pathlib import Path
+
+from viterbo.seeds import add_seed_argument
+
+from .verify import verify_dataset
+
+
+def parse_args(argv: list[str] | None = None) -> argparse.Namespace:
+    parser = argparse.ArgumentParser(
+        description="Regenerate a random subset of atlas rows and compare with the stored values."
+    )
+    parser.add_argument(
+        "--dataset", required=True, help="Path to the dataset (Parquet, CSV or JSON lines)."
+    )
+    parser.add_argument(
+        "--sample",
+        type=float,
+        default=0.01,
+        help="Fraction of rows to replay (default 0.01; at least one row).",
+    )
+    add_seed_argument(parser, default=0, help="Seed for picking the rows (scheme verify/v1).")
+    parser.add_argument("--rtol", type=float, default=1e-6, help="Relative tolerance.")
+    parser.add_argument("--report", help="Optional JSON report path.")
+    return parser.parse_args(argv)
+
+
+def main(argv: list[str] | None = None) -> int:
+    args = parse_args(argv)
+    report = verify_dataset(
+        Path(args.dataset).resolve(), fraction=args.sample, seed=args.seed, rtol=args.rtol
+    )
+    for mismatch in report.mismatches:
+        print(f"[verify] row {mismatch['row_id']}: {'; '.join(mismatch['problems'])}")
+    if args.report:
+        out = Path(args.report)
+        out.parent.mkdir(parents=True, exist_ok=True)
+        out.write_text(json.dumps(asdict(report), indent=2) + "\n", encoding="utf-8")
+    print(
+        f"[verify] checked {report.checked} rows, {len(report.mismatches)} mismatches",
+        file=sys.stderr,
+    )
+    return 0 if report.ok else 1
+
+
+if __name__ == "__main__":
+    raise SystemExit(main())
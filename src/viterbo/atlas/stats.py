@@ -0,0 +1,55 @@
+"""Per-family summary statistics of an atlas dataset.
+
+Why this file exists
+- "What do capacities look like for the Mahler family" used to mean a
+  throwaway notebook cell. ``summarize`` answers it in one Polars pass. For
+  every generator family, plus an ``all`` group, and every metric in
+  ``METRICS`` it gives count, mean, std, min, the ``QUANTILES`` and max.
+- The table is long (one row per family and metric), so it writes to CSV or
+  JSON without nested columns and stays easy to filter.
+- Non-finite values (unsolved capacities) are excluded from the moments and
+  quantiles. ``rows - count`` shows how many were dropped.
+
+References
+- Docs: docs/src/thesis/atlas-dataset.md
+"""
+
+from __future__ import annotations
+
+import polars as pl
+
+METRICS = ("capacity_ehz", "volume", "systolic_ratio", "halfspace_count", "vertex_count")
+QUANTILES = (0.05, 0.25, 0.5, 0.75, 0.95)
+ALL = "all"
+
+
+def summarize(df: pl.DataFrame) -> pl.DataFrame:
+    """One row per (family, metric): rows, count, mean, std, min, p05..p95, max."""
+
+    grouped = pl.concat([df, df.with_columns(pl.lit(ALL).alias("family"))])
+    frames = []
+    for metric in METRICS:
+        value = pl.col(metric).cast(pl.Float64)
+        finite = value.filter(value.is_finite())
+        frames.append(
+            grouped.group_by("family")
+            .agg(
+                pl.len().alias("rows"),
+                finite.count().alias("count"),
+                finite.mean().alias("mean"),
+                finite.std().alias("std"),
+                finite.min().alias("min"),
+                *[finite.quantile(q, "linear").alias(_quantile_name(q)) for q in QUANTILES],
+                finite.max().alias("max"),
+            )
+            .with_columns(pl.lit(metric).alias("metric"))
+        )
+    table = pl.concat(frames)
+    order = pl.when(pl.col("family") == ALL).then(1).otherwise(0)
+    return table.sort(order, "family", "metric").select(
+        "family", "metric", pl.exclude("family", "metric")
+    )
+
+
+def _quantile_name(q: float) -> str:
+    return f"p{round(q * 100):02d}"
@@ -6,6 +6,8 @@ from typing import Sequence
 
 import polars as pl
 
+from .formats import read_table
+
 
 @dataclass
 class AtlasTorchDatasetConfig:
@@ -24,7 +26,7 @@ class AtlasTorchDataset:
         except ModuleNotFoundError as err:
             raise RuntimeError("torch must be installed to use AtlasTorchDataset") from err
 
-        df = pl.read_parquet(cfg.path)
+        df = read_table(Path(cfg.path))
         if cfg.shuffle:
             df = df.sample(fraction=1.0, with_replacement=False, shuffle=True)
         self._torch = torch
@@ -2,13 +2,20 @@ from __future__ import annotations
 
 import json
 import math
+import time
 from dataclasses import dataclass, field
-from typing import Any, Mapping, Sequence
+from typing import Any, Callable, Mapping, Sequence
+
+import numpy as np
 
 from viterbo import _native as _native_impl
 
+from .limits import BudgetExceeded, SampleLimits, run_limited
+
 _NATIVE: Any = _native_impl
 
+ProgressCallback = Callable[[int, int], None]
+
 
 @dataclass
 class PolytopeRecord:
@@ -37,6 +44,8 @@ class AtlasRow:
     capacity_ehz: float
     dominant_orbit: str
     systolic_ratio: float
+    status: str = "ok"
+    retries: int = 0
 
     def to_record(self, row_id: int) -> dict[str, Any]:
         return {
@@ -53,6 +62,8 @@ class AtlasRow:
             "capacity_ehz": self.capacity_ehz,
             "dominant_orbit": self.dominant_orbit,
             "systolic_ratio": self.systolic_ratio,
+            "status": self.status,
+            "retries": self.retries,
         }
 
 
@@ -66,15 +77,10 @@ def poly_dict_to_record(payload: Mapping[str, Any]) -> PolytopeRecord:
     return PolytopeRecord(vertices=vertices, halfspaces=halfspaces)
 
 
-def _halfspaces_for_native(
-    poly: PolytopeRecord,
-) -> list[tuple[tuple[float, float, float, float], float]]:
-    hs_for_native = []
-    for h in poly.halfspaces:
-        if len(h) != 5:
-            raise ValueError("halfspaces must be length-5 lists [n0,n1,n2,n3,c]")
-        normal = (float(h[0]), float(h[1]), float(h[2]), float(h[3]))
-        hs_for_native.append((normal, float(h[4])))
+def _halfspaces_for_native(poly: PolytopeRecord) -> np.ndarray:
+    hs_for_native = np.asarray(poly.halfspaces, dtype=np.float64)
+    if hs_for_native.ndim != 2 or hs_for_native.shape[1] != 5:
+        raise ValueError("halfspaces must be length-5 lists [n0,n1,n2,n3,c]")
     return hs_for_native
 
 
@@ -112,13 +118,17 @@ def build_atlas_row(
     poly_payload: Mapping[str, Any],
     capacity_ehz: float | None = None,
     orbit_label: str | None = None,
+    solve_capacity: bool = True,
+    retries: int = 0,
 ) -> AtlasRow:
     record = poly_dict_to_record(poly_payload)
     volume = compute_volume(record)
     if capacity_ehz is not None:
         capacity = float(capacity_ehz)
-    else:
+    elif solve_capacity:
         capacity = compute_capacity(record)
+    else:
+        capacity = math.nan
     orbit = orbit_label or "unavailable"
     systolic = systolic_ratio(capacity, volume)
     return AtlasRow(
@@ -131,9 +141,61 @@ def build_atlas_row(
         capacity_ehz=capacity,
         dominant_orbit=orbit,
         systolic_ratio=systolic,
+        retries=retries,
     )
 
 
+def solve_capacities(
+    rows: Sequence[AtlasRow],
+    jobs: int | None,
+    *,
+    progress: ProgressCallback | None = None,
+    limits: SampleLimits | None = None,
+) -> list[float | None]:
+    """Fill ``capacity_ehz``/``systolic_ratio``/``status`` of ``rows`` in place.
+
+    ``jobs=None`` solves row by row; otherwise one ``capacity_ehz_batch`` call
+    on ``jobs`` threads, which returns results in input order, so the output
+    does not depend on scheduling. Either way ``progress(done, len(rows))`` is
+    called after every solved row. ``limits`` runs each serial solve in a
+    watched worker (see ``limits.py``); rows that hit a limit get a NaN
+    capacity and ``status="budget_exceeded"``. Returns per-row solve seconds
+    (``None`` in batch mode, where rows overlap in time).
+    """
+
+    if limits is not None and jobs is not None:
+        raise ValueError("per-sample limits apply to serial solves only; drop jobs")
+    seconds: list[float | None]
+    exceeded: set[int] = set()
+    if jobs is None:
+        capacities, seconds = [], []
+        for done, row in enumerate(rows, start=1):
+            start = time.perf_counter()
+            if limits is None:
+                capacities.append(compute_capacity(row.polytope))
+            else:
+                try:
+                    capacities.append(run_limited(compute_capacity, row.polytope, limits))
+                except BudgetExceeded:
+                    exceeded.add(done - 1)
+                    capacities.append(None)
+            seconds.append(time.perf_counter() - start)
+            if progress is not None:
+                progress(done, len(rows))
+    else:
+        batch = [_halfspaces_for_native(row.polytope) for row in rows]
+        capacities = _NATIVE.capacity_ehz_batch(batch, n_threads=jobs, progress=progress)
+        seconds = [None] * len(rows)
+    for index, (row, capacity) in enumerate(zip(rows, capacities)):
+        row.capacity_ehz = math.nan if capacity is None else float(capacity)
+        row.systolic_ratio = systolic_ratio(row.capacity_ehz, row.volume)
+        if index in exceeded:
+            row.status = "budget_exceeded"
+        else:
+            row.status = "no_cycle" if math.isnan(row.capacity_ehz) else "ok"
+    return seconds
+
+
 def systolic_ratio(capacity: float, volume: float) -> float:
     if math.isnan(capacity) or math.isnan(volume) or volume <= 0.0:
         return math.nan
@@ -141,6 +203,8 @@ def systolic_ratio(capacity: float, volume: float) -> float:
 
 
 def _expect_sequence(value: Any, label: str) -> Sequence[Sequence[float]]:
+    if isinstance(value, np.ndarray):
+        return value.tolist()
     if not isinstance(value, Sequence):
         raise ValueError(f"{label} must be a list, got {type(value).__name__}")
     return value  # type: ignore[return-value]
@@ -0,0 +1,94 @@
+"""Replay stored atlas rows and compare them with a fresh computation.
+
+Why this file exists
+- Reproducibility is a project guarantee: every row stores the family
+  parameters and replay token that produced it. This module tests that at
+  dataset scale. It draws a random subset of rows, regenerates the polytopes
+  through the same bindings, recomputes volume and capacity, and reports
+  every disagreement beyond tolerance.
+- Halfspaces are compared too. A generator change that keeps volumes but
+  moves facets is still a replay break.
+- ``NaN`` matches ``NaN``: an unsolved row should stay unsolved.
+"""
+
+from __future__ import annotations
+
+import json
+import math
+import random
+from dataclasses import dataclass, field
+from pathlib import Path
+from typing import Any
+
+import numpy as np
+
+from .formats import read_table
+from .sources import regenerate_polytope
+from .types import compute_capacity, compute_volume
+
+
+@dataclass
+class VerifyReport:
+    dataset: str
+    checked: int = 0
+    mismatches: list[dict[str, Any]] = field(default_factory=list)
+
+    @property
+    def ok(self) -> bool:
+        return not self.mismatches
+
+
+def verify_dataset(
+    dataset: Path,
+    *,
+    fraction: float,
+    seed: int = 0,
+    rtol: float = 1e-6,
+    atol: float = 1e-9,
+) -> VerifyReport:
+    """Replay a ``fraction`` of the rows of ``dataset`` (at least one row)."""
+
+    if not 0.0 < fraction <= 1.0:
+        raise ValueError(f"fraction must be in (0, 1], got {fraction}")
+    df = read_table(dataset)
+    count = max(1, round(fraction * len(df))) if len(df) else 0
+    picked = sorted(random.Random(seed).sample(range(len(df)), count))
+    report = VerifyReport(dataset=str(dataset))
+    for record in df[picked].iter_rows(named=True):
+        report.checked += 1
+        problems = _check_row(record, rtol=rtol, atol=atol)
+        if problems:
+            report.mismatches.append({"row_id": record["row_id"], "problems": problems})
+    return report
+
+
+def _check_row(record: dict[str, Any], *, rtol: float, atol: float) -> list[str]:
+    try:
+        poly = regenerate_polytope(
+            record["family"],
+            json.loads(record["family_parameters"]),
+            json.loads(record["replay_token"]),
+        )
+    except Exception as err:  # replay failure is itself a finding
+        return [f"regeneration failed: {err}"]
+    problems = []
+    stored_hs = np.asarray(record["halfspaces"], dtype=np.float64)
+    fresh_hs = np.asarray(poly.halfspaces, dtype=np.float64)
+    if stored_hs.shape != fresh_hs.shape or not np.allclose(
+        stored_hs, fresh_hs, rtol=rtol, atol=atol
+    ):
+        problems.append(f"halfspaces differ (stored {stored_hs.shape}, fresh {fresh_hs.shape})")
+    for column, fresh in (
+        ("volume", compute_volume(poly)),
+        ("capacity_ehz", compute_capacity(poly)),
+    ):
+        stored = float(record[column]) if record[column] is not None else math.nan
+        if not _close(stored, fresh, rtol=rtol, atol=atol):
+            problems.append(f"{column}: stored {stored!r}, fresh {fresh!r}")
+    return problems
+
+
+def _close(a: float, b: float, *, rtol: float, atol: float) -> bool:
+    if math.isnan(a) or math.isnan(b):
+        return math.isnan(a) and math.isnan(b)
+    return math.isclose(a, b, rel_tol=rtol, abs_tol=atol)
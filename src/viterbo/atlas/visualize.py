@@ -6,6 +6,8 @@ from typing import Iterable, Sequence
 
 import polars as pl
 
+from .formats import read_table
+
 DEFAULT_PREVIEW_COLUMNS = [
     "row_id",
     "family",
@@ -25,7 +27,7 @@ def write_preview(
     limit: int = 32,
     columns: Sequence[str] | None = None,
 ) -> Path:
-    df = dataset if isinstance(dataset, pl.DataFrame) else pl.read_parquet(dataset)
+    df = dataset if isinstance(dataset, pl.DataFrame) else read_table(dataset)
     cols = list(columns) if columns is not None else DEFAULT_PREVIEW_COLUMNS
     missing = [col for col in cols if col not in df.columns]
     if missing:
@@ -0,0 +1,51 @@
+"""Pipeline stage: run a curated capacity bench suite and write its JSON report.
+
+Usage:
+  uv run python -m viterbo.bench.stage_suite --suite oe4 --out data/bench/suite/oe4.json
+Writes the report plus its ``.run.json`` provenance sidecar (git commit, machine facts).
+"""
+
+from __future__ import annotations
+
+import argparse
+import json
+import sys
+from pathlib import Path
+
+from viterbo.provenance import write as write_provenance
+
+from .suite import SUITES, run_suite
+
+
+def parse_args(argv: list[str] | None = None) -> argparse.Namespace:
+    parser = argparse.ArgumentParser(description="Run a capacity bench suite.")
+    parser.add_argument("--suite", choices=sorted(SUITES), default="oe4")
+    parser.add_argument("--out", required=True, help="Path of the JSON report.")
+    parser.add_argument(
+        "--repeats", type=int, default=3, help="Timed solves per case (default: 3)."
+    )
+    return parser.parse_args(argv)
+
+
+def main(argv: list[str] | None = None) -> int:
+    args = parse_args(argv)
+    out_path = Path(args.out).resolve()
+    report = run_suite(args.suite, repeats=args.repeats)
+    out_path.parent.mkdir(parents=True, exist_ok=True)
+    out_path.write_text(json.dumps(report, indent=2) + "\n", encoding="utf-8")
+    write_provenance(
+        out_path,
+        {"suite": args.suite, "repeats": args.repeats},
+        {"command": "python -m viterbo.bench.stage_suite", "exit_code": 0},
+    )
+    for case in report["cases"]:
+        print(
+            f"[bench] {case['name']}: {case['wall_s']['median'] * 1e3:.2f} ms "
+            f"(c_EHZ={case['capacity_ehz']})",
+            file=sys.stderr,
+        )
+    return 0
+
+
+if __name__ == "__main__":
+    raise SystemExit(main())
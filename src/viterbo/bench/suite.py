@@ -0,0 +1,99 @@
+"""Curated end-to-end capacity solves with a normalized JSON report.
+
+Why this file exists
+- Criterion covers kernels, but the number we track across releases is
+  "how long does one c_EHZ solve take on the polytopes we care about". We
+  used to get it by scraping Criterion output in shell. Now a suite is a
+  fixed list of named polytopes (catalog members and atlas families at fixed
+  seeds), timed through the same native entry point the atlas uses.
+- The report is flat, versioned JSON (``schema_version``), one entry per
+  case: wall-time min/median/max over ``repeats`` and the solved capacity.
+  The capacity is there so that speedups which change answers show up in
+  review. The native solver does not expose search-node counts, so they are
+  not reported.
+
+References
+- Docs: docs/src/meta/benchmarks.md
+"""
+
+from __future__ import annotations
+
+import statistics
+import time
+from dataclasses import dataclass
+from typing import Any, Callable
+
+import numpy as np
+
+from viterbo import _native as _native_impl
+from viterbo.atlas.sources import regenerate_polytope, special_polytope
+from viterbo.atlas.types import PolytopeRecord
+
+_native: Any = _native_impl
+
+SCHEMA_VERSION = 1
+_SYMMETRIC = {"directions": 6, "radius_min": 0.7, "radius_max": 1.25}
+
+
+@dataclass(frozen=True)
+class BenchCase:
+    name: str
+    polytope: Callable[[], PolytopeRecord]
+
+
+def _catalog(member: str) -> BenchCase:
+    return BenchCase(member, lambda: special_polytope(member))
+
+
+def _symmetric(seed: int) -> BenchCase:
+    return BenchCase(
+        f"symmetric_halfspaces/seed={seed}",
+        lambda: regenerate_polytope(
+            "symmetric_halfspaces", {"params": _SYMMETRIC, "seed": seed}, {"seed": seed}
+        ),
+    )
+
+
+SUITES: dict[str, list[BenchCase]] = {
+    "oe4": [
+        _catalog("hypercube"),
+        _catalog("cross_polytope"),
+        _catalog("orthogonal_simplex"),
+        *(_symmetric(seed) for seed in (11, 23, 47)),
+    ],
+}
+
+
+def run_suite(suite: str, *, repeats: int = 3) -> dict[str, Any]:
+    """Time every case of ``suite`` ``repeats`` times; return the report payload."""
+
+    if suite not in SUITES:
+        raise ValueError(f"unknown bench suite {suite!r}; expected one of {', '.join(SUITES)}")
+    if repeats <= 0:
+        raise ValueError("repeats must be positive")
+    cases = []
+    for case in SUITES[suite]:
+        halfspaces = np.asarray(case.polytope().halfspaces, dtype=np.float64)
+        timings = []
+        for _ in range(repeats):
+            start = time.perf_counter()
+            capacity = _native.poly4_capacity_ehz_from_halfspaces(halfspaces)
+            timings.append(time.perf_counter() - start)
+        cases.append(
+            {
+                "name": case.name,
+                "halfspace_count": int(halfspaces.shape[0]),
+                "capacity_ehz": None if capacity is None else float(capacity),
+                "wall_s": {
+                    "min": min(timings),
+                    "median": statistics.median(timings),
+                    "max": max(timings),
+                },
+            }
+        )
+    return {
+        "schema_version": SCHEMA_VERSION,
+        "suite": suite,
+        "repeats": repeats,
+        "cases": cases,
+    }
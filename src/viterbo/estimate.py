@@ -0,0 +1,53 @@
+"""Work estimates for ``--dry-run``.
+
+Why this file exists
+- A misconfigured week-long job usually shows up hours in, as a wrong
+  output path or a row count off by a factor of ten. ``--dry-run`` on
+  ``stage_build``, ``stage_run`` and ``stage_search`` validates the config,
+  prints the planned work and output paths, and exits before solving
+  anything.
+- The wall-time estimate is calibrated on this machine's last bench suite
+  report (``viterbo.bench.stage_suite``). It uses the median solve time over
+  the suite's cases, so it gives the order of magnitude, not a schedule.
+  Without a report the estimate is omitted rather than guessed.
+
+References
+- Docs: docs/src/meta/benchmarks.md
+"""
+
+from __future__ import annotations
+
+import json
+import os
+import statistics
+from pathlib import Path
+from typing import Optional
+
+from viterbo.progress import format_clock
+from viterbo.provenance import REPO_ROOT
+
+CALIBRATION = REPO_ROOT / "data" / "bench" / "suite" / "oe4.json"
+
+
+def seconds_per_solve(report: Path = CALIBRATION) -> Optional[float]:
+    """Median per-case solve time of a bench suite report, or ``None`` if absent."""
+
+    if not report.exists():
+        return None
+    cases = json.loads(report.read_text(encoding="utf-8")).get("cases") or []
+    medians = [case["wall_s"]["median"] for case in cases]
+    return statistics.median(medians) if medians else None
+
+
+def describe(solves: int, *, threads: Optional[int] = None, report: Path = CALIBRATION) -> str:
+    """``"N solves, ~h:mm:ss on T threads"`` (or why there is no estimate)."""
+
+    workers = threads or 1
+    if threads == 0:
+        workers = os.cpu_count() or 1
+    per_solve = seconds_per_solve(report)
+    if per_solve is None:
+        return f"{solves} solves, no estimate (run python -m viterbo.bench.stage_suite first)"
+    seconds = solves * per_solve / workers
+    plural = "s" if workers != 1 else ""
+    return f"{solves} solves, ~{format_clock(seconds)} on {workers} thread{plural}"
@@ -0,0 +1,49 @@
+"""Treat SIGTERM like Ctrl-C so long stages stop cleanly.
+
+Why this file exists
+- SLURM and ``group-timeout`` stop jobs with SIGTERM. Python's default is to
+  die on the spot, mid-write. SIGINT already raises ``KeyboardInterrupt``,
+  which the native batch solver honours: it stops dispatching, lets in-flight
+  solves finish, then re-raises. ``graceful_termination`` routes SIGTERM the
+  same way, so stages need only one ``except KeyboardInterrupt`` path, where
+  they flush what is complete and record the early stop.
+- ``Termination.signal`` tells that path which signal arrived; conventional
+  exit codes are ``128 + signum``.
+"""
+
+from __future__ import annotations
+
+import contextlib
+import signal
+from dataclasses import dataclass
+from typing import Iterator, Optional
+
+
+@dataclass
+class Termination:
+    signal: Optional[int] = None
+
+    @property
+    def name(self) -> str:
+        return signal.Signals(self.signal).name if self.signal is not None else "SIGINT"
+
+    @property
+    def exit_code(self) -> int:
+        return 128 + (self.signal if self.signal is not None else signal.SIGINT)
+
+
+@contextlib.contextmanager
+def graceful_termination() -> Iterator[Termination]:
+    """Within the block, SIGTERM raises ``KeyboardInterrupt`` like SIGINT does."""
+
+    termination = Termination()
+
+    def handler(signum: int, _frame: object) -> None:
+        termination.signal = signum
+        raise KeyboardInterrupt
+
+    previous = signal.signal(signal.SIGTERM, handler)
+    try:
+        yield termination
+    finally:
+        signal.signal(signal.SIGTERM, previous)
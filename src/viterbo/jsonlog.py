@@ -0,0 +1,53 @@
+"""Append-only JSON-lines event log for long runs.
+
+Why this file exists
+- After a multi-hour build we want to know where the time went: which
+  families are slow to generate, which rows take the solver long, and which
+  rows fail. stderr lines are not enough for that. A JSON-lines file with one
+  object per event loads straight into polars (``pl.read_ndjson``) for
+  post-hoc analysis.
+- Every record has ``ts`` (UTC ISO time) and ``event``; the remaining fields
+  depend on the event. Lines are flushed as they are written, so a killed
+  run keeps everything logged up to that point.
+"""
+
+from __future__ import annotations
+
+import json
+import math
+import os
+from datetime import datetime, timezone
+from pathlib import Path
+from typing import Any, TextIO
+
+
+class JsonLog:
+    """Writes one JSON object per line to ``path`` (appending)."""
+
+    def __init__(self, path: os.PathLike[str] | str) -> None:
+        self.path = Path(path)
+        self.path.parent.mkdir(parents=True, exist_ok=True)
+        self._file: TextIO = self.path.open("a", encoding="utf-8")
+
+    def event(self, name: str, **fields: Any) -> None:
+        record = {"ts": datetime.now(timezone.utc).isoformat(), "event": name}
+        record.update({k: _plain(v) for k, v in fields.items()})
+        self._file.write(json.dumps(record, sort_keys=True) + "\n")
+        self._file.flush()
+
+    def close(self) -> None:
+        self._file.close()
+
+    def __enter__(self) -> "JsonLog":
+        return self
+
+    def __exit__(self, *exc: object) -> None:
+        self.close()
+
+
+def _plain(value: Any) -> Any:
+    """NaN/inf are not JSON; log them as ``null``."""
+
+    if isinstance(value, float) and not math.isfinite(value):
+        return None
+    return value
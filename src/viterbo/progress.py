@@ -0,0 +1,67 @@
+"""Throughput and ETA lines for long batch runs.
+
+Why this file exists
+- Multi-hour atlas builds used to print nothing between "started" and
+  "wrote N rows". The native batch solver already calls
+  ``progress(done, total)`` after every item; ``ProgressReporter`` turns those
+  calls into a throttled stderr line with items/s and an ETA.
+- We stay on plain stderr instead of a progress-bar dependency: on a terminal
+  the line is redrawn in place, in logs (``group-timeout``, CI) it becomes one
+  line per interval, which greps well.
+"""
+
+from __future__ import annotations
+
+import sys
+import time
+from typing import Callable, Optional, TextIO
+
+
+class ProgressReporter:
+    """``progress(done, total)`` callback printing ``label done/total rate ETA``.
+
+    Prints at most once per ``interval`` seconds, plus always on completion.
+    """
+
+    def __init__(
+        self,
+        label: str,
+        *,
+        stream: Optional[TextIO] = None,
+        interval: float = 1.0,
+        clock: Callable[[], float] = time.monotonic,
+    ) -> None:
+        self.label = label
+        self.stream = stream if stream is not None else sys.stderr
+        self.interval = interval
+        self.clock = clock
+        self.started = clock()
+        self.last_print: Optional[float] = None
+        self.in_place = bool(getattr(self.stream, "isatty", lambda: False)())
+
+    def __call__(self, done: int, total: int) -> None:
+        now = self.clock()
+        finished = done >= total
+        if not finished and self.last_print is not None and now - self.last_print < self.interval:
+            return
+        self.last_print = now
+        elapsed = now - self.started
+        rate = done / elapsed if elapsed > 0 else 0.0
+        eta = (total - done) / rate if rate > 0 else None
+        line = (
+            f"{self.label} {done}/{total}  {rate:.1f}/s  "
+            f"{'done in ' + format_clock(elapsed) if finished else 'ETA ' + format_clock(eta)}"
+        )
+        if self.in_place:
+            self.stream.write("\r" + line + ("\n" if finished else ""))
+        else:
+            self.stream.write(line + "\n")
+        self.stream.flush()
+
+
+def format_clock(seconds: Optional[float]) -> str:
+    if seconds is None:
+        return "?"
+    minutes, secs = divmod(int(round(seconds)), 60)
+    hours, minutes = divmod(minutes, 60)
+    return f"{hours}:{minutes:02d}:{secs:02d}"
@@ -1,13 +1,49 @@
+"""JSON provenance sidecars next to artifacts.
+
+Why this file exists
+- Every artifact under ``data/`` or ``docs/assets/`` gets a
+  ``<artifact>.<ext>.run.json`` recording how it was made: the (possibly
+  mutated) config, the git commit, and a timestamp.
+- Reproducing a numerical discrepancy on another machine also needs to
+  identify the bytes and the environment. So sidecars carry the SHA-256 of
+  the artifact and of its declared ``inputs``, plus ``environment()``:
+  hostname, CPU model, OS, Python and rustc versions, and the workspace
+  crate versions.
+- Capacities can still differ in the last bits between two machines with the
+  same versions: FMA and vector width depend on the target features the
+  extension was compiled with, not only on the CPU it runs on. So
+  ``environment()["numerics"]`` adds the runtime CPU flags that matter, the
+  native build's target features and ``RUSTFLAGS`` (``_native.build_info()``),
+  and the linear-algebra backend. nalgebra is pure Rust and does its products
+  through ``matrixmultiply``; no BLAS is linked, so ``blas`` is ``None``.
+- Lineage: an input that has its own sidecar is also listed under
+  ``upstream`` with the sidecar path and hash. A figure therefore points at
+  the dataset sidecar, which points at its own inputs, and ``lineage()``
+  walks that chain. ``python -m viterbo.provenance lineage <artifact>``
+  prints it.
+- ``report()`` flattens every sidecar under a directory (``data/`` by
+  default) into one row per artifact, so "which runs are stale, and with what
+  settings" is one command instead of opening JSON files by hand.
+"""
+
 from __future__ import annotations
 
+import csv
+import functools
+import hashlib
 import json
 import os
+import platform
 import shlex
 import subprocess
+import sys
+import tomllib
 from dataclasses import asdict, dataclass, field
 from datetime import datetime, timezone
 from pathlib import Path
-from typing import Any, Dict, Mapping, MutableMapping, Optional
+from typing import Any, Dict, Iterable, Mapping, MutableMapping, Optional
+
+REPO_ROOT = Path(__file__).resolve().parents[2]
 
 
 def _git_rev() -> Optional[str]:
@@ -22,27 +58,158 @@ def _now_iso() -> str:
     return datetime.now(timezone.utc).isoformat()
 
 
-def _sidecar_path(output_path: Path) -> Path:
-    return output_path.with_suffix(output_path.suffix + ".run.json")
+def sha256_file(path: os.PathLike[str] | str) -> Optional[str]:
+    """Hex SHA-256 of a file's bytes; ``None`` if it does not exist."""
+
+    digest = hashlib.sha256()
+    try:
+        with Path(path).open("rb") as f:
+            for chunk in iter(lambda: f.read(1 << 20), b""):
+                digest.update(chunk)
+    except FileNotFoundError:
+        return None
+    return digest.hexdigest()
+
+
+def _command_output(cmd: list[str]) -> Optional[str]:
+    try:
+        return subprocess.check_output(cmd, text=True, stderr=subprocess.DEVNULL).strip() or None
+    except Exception:
+        return None
+
+
+def _cpu_model() -> str:
+    try:
+        for line in Path("/proc/cpuinfo").read_text(encoding="utf-8").splitlines():
+            if line.startswith("model name"):
+                return line.split(":", 1)[1].strip()
+    except OSError:
+        pass
+    return platform.processor() or platform.machine()
+
+
+def _crate_versions() -> Dict[str, str]:
+    versions: Dict[str, str] = {}
+    for manifest in sorted(REPO_ROOT.glob("crates/*/Cargo.toml")):
+        try:
+            package = tomllib.loads(manifest.read_text(encoding="utf-8")).get("package", {})
+        except (OSError, tomllib.TOMLDecodeError):
+            continue
+        if "name" in package and isinstance(package.get("version"), str):
+            versions[package["name"]] = package["version"]
+    return versions
+
+
+_NUMERIC_CPU_FLAGS = (
+    "sse2",
+    "sse4_1",
+    "sse4_2",
+    "avx",
+    "avx2",
+    "fma",
+    "avx512f",
+    "neon",
+    "asimd",
+)
+
+
+def _cpu_flags() -> list[str]:
+    """CPU flags that change floating-point code paths (x86 ``flags``, ARM ``Features``)."""
+
+    try:
+        for line in Path("/proc/cpuinfo").read_text(encoding="utf-8").splitlines():
+            key, _, value = line.partition(":")
+            if key.strip() in ("flags", "Features"):
+                present = set(value.split())
+                return [flag for flag in _NUMERIC_CPU_FLAGS if flag in present]
+    except OSError:
+        pass
+    return []
+
+
+def _locked_versions(*names: str) -> Dict[str, str]:
+    versions: Dict[str, str] = {}
+    try:
+        lock = tomllib.loads((REPO_ROOT / "Cargo.lock").read_text(encoding="utf-8"))
+    except (OSError, tomllib.TOMLDecodeError):
+        return versions
+    for package in lock.get("package", []):
+        if package.get("name") in names:
+            versions[package["name"]] = package.get("version")
+    return versions
+
+
+def _native_build_info() -> Optional[Dict[str, Any]]:
+    try:
+        from viterbo import _native
+
+        return dict(_native.build_info())
+    except Exception:
+        return None
+
+
+def numerics() -> Dict[str, Any]:
+    """Facts that decide the last bits of a float: CPU flags, build flags, linear algebra."""
+
+    locked = _locked_versions("nalgebra", "matrixmultiply")
+    matmul = locked.get("matrixmultiply")
+    return {
+        "cpu_flags": _cpu_flags(),
+        "native_build": _native_build_info(),
+        "nalgebra": locked.get("nalgebra"),
+        "matmul": f"matrixmultiply {matmul}" if matmul else None,
+        "blas": None,
+    }
+
+
+@functools.lru_cache(maxsize=1)
+def environment() -> Dict[str, Any]:
+    """Machine and toolchain facts needed to reproduce a number elsewhere."""
+
+    from viterbo import __version__
+
+    return {
+        "hostname": platform.node(),
+        "cpu_model": _cpu_model(),
+        "os": f"{platform.system()} {platform.release()}".strip(),
+        "python": sys.version.split()[0],
+        "rustc": _command_output(["rustc", "--version"]),
+        "viterbo_python": __version__,
+        "crates": _crate_versions(),
+        "numerics": numerics(),
+    }
+
+
+def sidecar_path(output_path: os.PathLike[str] | str) -> Path:
+    """Where ``write()`` puts the sidecar of an artifact: ``<artifact>.<ext>.run.json``."""
+
+    out = Path(output_path)
+    return out.with_suffix(out.suffix + ".run.json")
 
 
 def write(
     output_path: os.PathLike[str] | str,
     config: Mapping[str, Any],
     extras: Optional[Mapping[str, Any]] = None,
+    inputs: Optional[Iterable[os.PathLike[str] | str]] = None,
 ) -> Path:
     """
     Write a small JSON sidecar next to an artifact.
-    Always writes `<artifact>.<ext>.run.json` and embeds the (possibly mutated) config.
+    Always writes `<artifact>.<ext>.run.json` and embeds the (possibly mutated) config,
+    the SHA-256 of the artifact and of any `inputs`, and the `environment()` facts.
     """
     out = Path(output_path)
-    sidecar = _sidecar_path(out)
+    sidecar = sidecar_path(out)
     sidecar.parent.mkdir(parents=True, exist_ok=True)
 
     payload: Dict[str, Any] = {
         "config": dict(config),
         "git_commit": _git_rev(),
         "timestamp": _now_iso(),
+        "output_sha256": sha256_file(out),
+        "inputs": {str(Path(p)): sha256_file(p) for p in inputs or ()},
+        "upstream": _upstream(inputs or ()),
+        "environment": environment(),
     }
     if extras:
         payload.update(extras)
@@ -53,3 +220,135 @@ def write(
         f.write("\n")
     tmp.replace(sidecar)
     return sidecar
+
+
+def _upstream(inputs: Iterable[os.PathLike[str] | str]) -> list[Dict[str, Any]]:
+    refs = []
+    for path in inputs:
+        sidecar = sidecar_path(path)
+        if sidecar.exists():
+            refs.append(
+                {
+                    "artifact": str(Path(path)),
+                    "sidecar": str(sidecar),
+                    "sha256": sha256_file(sidecar),
+                }
+            )
+    return refs
+
+
+def lineage(artifact: os.PathLike[str] | str) -> list[Dict[str, Any]]:
+    """Sidecar chain of ``artifact``, depth-first from the artifact upstream.
+
+    Each entry has ``artifact``, ``depth``, ``sidecar`` (``None`` if missing),
+    the sidecar's ``git_commit``/``timestamp``, and ``stale``: the recorded
+    sidecar hash no longer matches the file on disk.
+    """
+
+    chain: list[Dict[str, Any]] = []
+    seen: set[Path] = set()
+
+    def visit(path: Path, depth: int, expected_sha: Optional[str]) -> None:
+        sidecar = sidecar_path(path)
+        entry: Dict[str, Any] = {"artifact": str(path), "depth": depth, "sidecar": None}
+        chain.append(entry)
+        if not sidecar.exists() or sidecar.resolve() in seen:
+            return
+        seen.add(sidecar.resolve())
+        payload = json.loads(sidecar.read_text(encoding="utf-8"))
+        entry.update(
+            sidecar=str(sidecar),
+            git_commit=payload.get("git_commit"),
+            timestamp=payload.get("timestamp"),
+            stale=expected_sha is not None and sha256_file(sidecar) != expected_sha,
+        )
+        for ref in payload.get("upstream", []):
+            visit(Path(ref["artifact"]), depth + 1, ref.get("sha256"))
+
+    visit(Path(artifact), 0, None)
+    return chain
+
+
+REPORT_COLUMNS = ("artifact", "algo", "config_digest", "git_commit", "timestamp")
+
+
+def report(root: os.PathLike[str] | str = REPO_ROOT / "data") -> list[Dict[str, Any]]:
+    """One row per sidecar under ``root``, sorted by artifact path.
+
+    ``algo`` is the config's ``algo`` entry, falling back to the recorded
+    ``command``. ``config_digest`` is the first 12 hex digits of the SHA-256 of
+    the canonical config JSON, so runs with identical settings share it.
+    Sidecars that are not JSON objects are skipped.
+    """
+
+    base = Path(root)
+    rows = []
+    for sidecar in sorted(base.rglob("*.run.json")):
+        try:
+            payload = json.loads(sidecar.read_text(encoding="utf-8"))
+        except (OSError, UnicodeDecodeError, json.JSONDecodeError):
+            continue
+        if not isinstance(payload, dict):
+            continue
+        config = payload.get("config") if isinstance(payload.get("config"), dict) else {}
+        canonical = json.dumps(config, sort_keys=True, default=str).encode("utf-8")
+        rows.append(
+            {
+                "artifact": str(sidecar.relative_to(base))[: -len(".run.json")],
+                "algo": config.get("algo") or payload.get("command"),
+                "config_digest": hashlib.sha256(canonical).hexdigest()[:12],
+                "git_commit": payload.get("git_commit"),
+                "timestamp": payload.get("timestamp"),
+            }
+        )
+    return rows
+
+
+def _print_table(rows: list[Dict[str, Any]]) -> None:
+    cells = [REPORT_COLUMNS] + [
+        tuple("" if row[c] is None else str(row[c]) for c in REPORT_COLUMNS) for row in rows
+    ]
+    widths = [max(len(line[i]) for line in cells) for i in range(len(REPORT_COLUMNS))]
+    for line in cells:
+        print("  ".join(cell.ljust(w) for cell, w in zip(line, widths)).rstrip())
+
+
+def main(argv: Optional[list[str]] = None) -> int:
+    import argparse
+
+    parser = argparse.ArgumentParser(description="Inspect provenance sidecars.")
+    commands = parser.add_subparsers(dest="command", required=True)
+    lineage_cmd = commands.add_parser("lineage", help="Print the upstream chain of an artifact.")
+    lineage_cmd.add_argument("artifact")
+    report_cmd = commands.add_parser("report", help="Summarize every sidecar under a directory.")
+    report_cmd.add_argument("--root", default=str(REPO_ROOT / "data"))
+    report_cmd.add_argument("--json", dest="json_out", help="Also write the rows as JSON here.")
+    report_cmd.add_argument("--csv", dest="csv_out", help="Also write the rows as CSV here.")
+    args = parser.parse_args(argv)
+
+    if args.command == "lineage":
+        for entry in lineage(args.artifact):
+            indent = "  " * entry["depth"]
+            if entry["sidecar"] is None:
+                print(f"{indent}{entry['artifact']}  (no sidecar)")
+                continue
+            flag = "  [sidecar changed since recorded]" if entry["stale"] else ""
+            print(
+                f"{indent}{entry['artifact']}  git={entry['git_commit']} "
+                f"at={entry['timestamp']}{flag}"
+            )
+    elif args.command == "report":
+        rows = report(args.root)
+        _print_table(rows)
+        if args.json_out:
+            Path(args.json_out).write_text(json.dumps(rows, indent=2) + "\n", encoding="utf-8")
+        if args.csv_out:
+            with Path(args.csv_out).open("w", encoding="utf-8", newline="") as f:
+                writer = csv.DictWriter(f, fieldnames=REPORT_COLUMNS)
+                writer.writeheader()
+                writer.writerows(rows)
+    return 0
+
+
+if __name__ == "__main__":
+    raise SystemExit(main())
@@ -1,7 +1,8 @@
 """Thin Python helpers around the native 4D volume binding.
 
 Why this file exists
-- Keep the PyO3 signatures ergonomic for callers that work with Python sequences.
+- Keep the PyO3 signatures ergonomic for callers that work with Python sequences
+  (the binding itself takes an ``(m, 5)`` float64 NumPy array).
 - Provide light validation so that downstream experiments fail fast before
   touching the native module.
 """
@@ -10,10 +11,11 @@ from __future__ import annotations
 
 from typing import Callable, Iterable, List, Sequence, Tuple, cast
 
+import numpy as np
+
 from viterbo import _native
 
 Halfspace4 = Tuple[Sequence[float], float]
-NativeHalfspace = Tuple[Tuple[float, float, float, float], float]
 
 
 def volume_from_halfspaces(halfspaces: Iterable[Halfspace4]) -> float:
@@ -26,19 +28,20 @@ def volume_from_halfspaces(halfspaces: Iterable[Halfspace4]) -> float:
         The hypervolume as a float.
     """
 
-    hs_norm: List[NativeHalfspace] = []
+    rows: List[List[float]] = []
     for n, c in halfspaces:
         if len(n) != 4:
             msg = f"half-space normal must have 4 components, got {len(n)}"
             raise ValueError(msg)
-        hs_norm.append(((float(n[0]), float(n[1]), float(n[2]), float(n[3])), float(c)))
+        rows.append([float(n[0]), float(n[1]), float(n[2]), float(n[3]), float(c)])
+    hs_array = np.asarray(rows, dtype=np.float64).reshape(-1, 5)
     volume_fn = cast(
-        Callable[[List[NativeHalfspace]], float],
+        Callable[[np.ndarray], float],
         getattr(_native, "poly4_volume_from_halfspaces", None),
     )
     if volume_fn is None:
         raise AttributeError("Rust extension missing poly4_volume_from_halfspaces")
-    return float(volume_fn(hs_norm))
+    return float(volume_fn(hs_array))
 
 
 __all__ = ["volume_from_halfspaces", "Halfspace4"]
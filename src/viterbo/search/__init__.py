@@ -0,0 +1,5 @@
+"""Counterexample hunting: long-running systolic-ratio searches."""
+
+from .hunt import HuntConfig, run_hunt
+
+__all__ = ["HuntConfig", "run_hunt"]
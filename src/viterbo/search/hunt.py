@@ -0,0 +1,177 @@
+"""Resumable systolic-ratio search loop around the native optimizers.
+
+Why this file exists
+- The project exists to find a 4D convex polytope with systolic ratio above 1,
+  or to gather evidence that none exists. ``viterbo::optimize`` has the
+  optimizers (CMA-ES, Nelder–Mead over facet offsets). A hunt runs them for
+  hours, so the loop lives here: a *round* is one native
+  ``optimize_systolic`` pass of ``budget`` evaluations, restarted from the
+  best body so far with seed ``seed + round``.
+- After every round ``state.json`` is replaced atomically with the round
+  counter, the best body and a config fingerprint. ``resume`` continues from
+  there, so Ctrl-C or a killed job costs at most one round. Whenever the best
+  value improves, ``best.json`` is rewritten with the halfspaces, capacity,
+  volume and systolic ratio, plus a provenance sidecar.
+
+References
+- Rust: crates/viterbo/src/optimize/dfo.rs
+- Docs: docs/src/thesis/random-polytopes.md ("Optimization")
+"""
+
+from __future__ import annotations
+
+import hashlib
+import json
+import math
+import sys
+from dataclasses import asdict, dataclass, field
+from pathlib import Path
+from typing import Any, Mapping
+
+import numpy as np
+
+from viterbo import _native as _native_impl
+from viterbo.atlas.sources import special_polytope
+from viterbo.provenance import write as write_provenance
+from viterbo.seeds import seed_record
+from viterbo.solve.run import read_halfspaces
+
+_native: Any = _native_impl
+
+_OPTIMIZER_KEYS = ("name", "budget", "sigma0", "lambda", "step")
+
+
+@dataclass(frozen=True)
+class HuntConfig:
+    out_dir: Path
+    start: str
+    rounds: int = 10
+    seed: int = 0
+    optimizer: dict[str, Any] = field(default_factory=lambda: {"name": "cma_es"})
+
+    @classmethod
+    def from_mapping(cls, data: Mapping[str, Any], *, base_dir: Path) -> "HuntConfig":
+        unknown = set(data) - {"out_dir", "start", "rounds", "seed", "optimizer"}
+        if unknown:
+            raise ValueError(f"unknown hunt config keys: {sorted(unknown)}")
+        optimizer = dict(data.get("optimizer", {"name": "cma_es"}))
+        if set(optimizer) - set(_OPTIMIZER_KEYS):
+            raise ValueError(f"optimizer keys must be among {_OPTIMIZER_KEYS}")
+        if "out_dir" not in data or "start" not in data:
+            raise ValueError("hunt config needs 'out_dir' and 'start'")
+        start = str(data["start"])
+        if Path(start).suffix.lower() in (".json", ".csv"):
+            start = str(_resolve(start, base_dir))
+        return cls(
+            out_dir=_resolve(data["out_dir"], base_dir),
+            start=start,
+            rounds=int(data.get("rounds", 10)),
+            seed=int(data.get("seed", 0)),
+            optimizer=optimizer,
+        )
+
+    @classmethod
+    def from_file(cls, path: Path) -> "HuntConfig":
+        """Load a TOML (``.toml``) or JSON config; relative paths resolve next to it."""
+
+        if path.suffix.lower() == ".toml":
+            import tomllib
+
+            data = tomllib.loads(path.read_text(encoding="utf-8"))
+        else:
+            data = json.loads(path.read_text(encoding="utf-8"))
+        return cls.from_mapping(data, base_dir=path.parent)
+
+    def start_halfspaces(self) -> np.ndarray:
+        """``start`` is a catalog member (``hypercube``, ...) or an H-rep file."""
+
+        path = Path(self.start)
+        if path.suffix.lower() in (".json", ".csv"):
+            return read_halfspaces(path)
+        return np.asarray(special_polytope(self.start).halfspaces, dtype=np.float64)
+
+    def fingerprint(self) -> str:
+        payload = {"start": self.start, "seed": self.seed, "optimizer": self.optimizer}
+        return hashlib.sha256(json.dumps(payload, sort_keys=True).encode("utf-8")).hexdigest()
+
+
+def _resolve(raw: str, base_dir: Path) -> Path:
+    path = Path(raw)
+    return path if path.is_absolute() else (base_dir / path).resolve()
+
+
+def run_hunt(cfg: HuntConfig, *, resume: bool = False) -> dict[str, Any]:
+    """Run rounds until ``cfg.rounds`` are done; returns the final state."""
+
+    cfg.out_dir.mkdir(parents=True, exist_ok=True)
+    state_path = cfg.out_dir / "state.json"
+    if resume:
+        state = json.loads(state_path.read_text(encoding="utf-8"))
+        if state["fingerprint"] != cfg.fingerprint():
+            raise ValueError(f"{state_path}: state was written for a different hunt config")
+    else:
+        state = {
+            "fingerprint": cfg.fingerprint(),
+            "round": 0,
+            "evaluations": 0,
+            "best_value": -math.inf,
+            "best_halfspaces": cfg.start_halfspaces().tolist(),
+            "best_per_round": [],
+        }
+    options = {k: v for k, v in cfg.optimizer.items() if k != "name"}
+    if "lambda" in options:
+        options["lambda_"] = options.pop("lambda")
+    while state["round"] < cfg.rounds:
+        result = _native.optimize_systolic(
+            np.asarray(state["best_halfspaces"], dtype=np.float64),
+            optimizer=cfg.optimizer.get("name", "cma_es"),
+            seed=cfg.seed + state["round"],
+            **options,
+        )
+        state["round"] += 1
+        state["evaluations"] += result["evaluations"]
+        if result["best_value"] > state["best_value"]:
+            state["best_value"] = result["best_value"]
+            state["best_halfspaces"] = result["best_halfspaces"].tolist()
+            _dump_best(cfg, state)
+        state["best_per_round"].append(state["best_value"])
+        _write_state(state_path, state)
+        print(
+            f"[search] round {state['round']}/{cfg.rounds}: best {state['best_value']:.6f}",
+            file=sys.stderr,
+        )
+    return state
+
+
+def _dump_best(cfg: HuntConfig, state: Mapping[str, Any]) -> Path:
+    halfspaces = np.asarray(state["best_halfspaces"], dtype=np.float64)
+    measures = _native.systolic_ratio(halfspaces)
+    path = cfg.out_dir / "best.json"
+    payload = {
+        "round": state["round"],
+        "evaluations": state["evaluations"],
+        "halfspaces": state["best_halfspaces"],
+        "systolic_ratio": measures["systolic_ratio"],
+        "capacity_ehz": measures["capacity"],
+        "volume": measures["volume"],
+    }
+    path.write_text(json.dumps(payload, indent=2) + "\n", encoding="utf-8")
+    config = {k: str(v) if isinstance(v, Path) else v for k, v in asdict(cfg).items()}
+    write_provenance(
+        path,
+        config,
+        {
+            "command": "python -m viterbo.search.stage_search --config <file>",
+            "exit_code": 0,
+            "seeds": seed_record(
+                cfg.seed, "search/v1", {"best_round": cfg.seed + state["round"] - 1}
+            ),
+        },
+    )
+    return path
+
+
+def _write_state(path: Path, state: Mapping[str, Any]) -> None:
+    tmp = path.with_suffix(".json.tmp")
+    tmp.write_text(json.dumps(state, indent=2) + "\n", encoding="utf-8")
+    tmp.replace(path)
@@ -0,0 +1,73 @@
+"""Pipeline stage: run (or resume) a systolic-ratio hunt.
+
+Usage:
+  python -m viterbo.search.stage_search --config configs/search/hunt.toml [--resume]
+Writes ``<out_dir>/state.json`` after every round and ``<out_dir>/best.json`` (+ sidecar) on
+every improvement.
+"""
+
+from __future__ import annotations
+
+import argparse
+import sys
+from dataclasses import replace
+from pathlib import Path
+
+from viterbo.estimate import describe
+from viterbo.interrupt import graceful_termination
+from viterbo.seeds import add_seed_argument
+
+from .hunt import HuntConfig, run_hunt
+
+
+def parse_args(argv: list[str] | None = None) -> argparse.Namespace:
+    parser = argparse.ArgumentParser(description="Hunt for polytopes with large systolic ratio.")
+    parser.add_argument("--config", required=True, help="Path to the TOML or JSON hunt config.")
+    parser.add_argument(
+        "--resume",
+        action="store_true",
+        help="Continue from <out_dir>/state.json instead of starting over.",
+    )
+    add_seed_argument(parser, help="Override the config seed; round r uses seed + r.")
+    parser.add_argument(
+        "--dry-run",
+        action="store_true",
+        help="Validate the config, print planned evaluations, estimated time and outputs.",
+    )
+    return parser.parse_args(argv)
+
+
+def main(argv: list[str] | None = None) -> int:
+    args = parse_args(argv)
+    cfg = HuntConfig.from_file(Path(args.config).resolve())
+    if args.seed is not None:
+        cfg = replace(cfg, seed=args.seed)
+    if args.dry_run:
+        start = cfg.start_halfspaces()
+        # Budget default as in the optimize_systolic binding.
+        evaluations = cfg.rounds * int(cfg.optimizer.get("budget", 200))
+        print(f"[dry-run] start {cfg.start} ({start.shape[0]} facets), {cfg.rounds} rounds")
+        print(f"[dry-run] work: {describe(evaluations)}")
+        for name in ("state.json", "best.json", "best.json.run.json"):
+            print(f"[dry-run] output: {cfg.out_dir / name}")
+        return 0
+    with graceful_termination() as termination:
+        try:
+            state = run_hunt(cfg, resume=args.resume)
+        except KeyboardInterrupt:
+            print(
+                f"[search] stopped by {termination.name}; state.json holds the last finished "
+                "round, continue with --resume",
+                file=sys.stderr,
+            )
+            return termination.exit_code
+    print(
+        f"[search] {state['round']} rounds, {state['evaluations']} evaluations, "
+        f"best systolic ratio {state['best_value']:.6f}",
+        file=sys.stderr,
+    )
+    return 0
+
+
+if __name__ == "__main__":
+    raise SystemExit(main())
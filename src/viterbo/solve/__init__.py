@@ -0,0 +1,6 @@
+"""Single-polytope solve experiment (capacity / volume / systolic ratio)."""
+
+from .diagnostics import inspect_polytope
+from .run import ALGOS, read_halfspaces, run_algo
+
+__all__ = ["ALGOS", "inspect_polytope", "read_halfspaces", "run_algo"]
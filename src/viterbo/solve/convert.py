@@ -0,0 +1,112 @@
+"""Polytope file formats and conversion between them.
+
+Why this file exists
+- Collaborators send polytopes as H-rep JSON, vertex lists, CSV halfspace
+  tables, or rows cut from an atlas table, and we kept writing one-off parsers.
+  Every format now loads into a native ``Poly4`` and is written back from it.
+  A conversion that crosses representations (H→V or V→H) therefore runs the
+  native vertex enumeration or hull on the fly.
+- Formats: ``h-json`` (``{"halfspaces": [[n0, n1, n2, n3, c], ...]}`` or a
+  bare list), ``v-json`` (``{"vertices": [[x1, x2, y1, y2], ...]}``), ``csv``
+  (halfspace rows, optional header), and ``atlas`` (one row of an atlas table
+  in any ``atlas.formats`` format, picked by ``row_id``). Written atlas rows
+  get ``family = "imported"`` and NaN capacity, so they concatenate with
+  real datasets.
+
+References
+- Code: src/viterbo/solve/run.py::read_halfspaces, src/viterbo/atlas/formats.py
+"""
+
+from __future__ import annotations
+
+import csv
+import json
+from pathlib import Path
+from typing import Any
+
+import numpy as np
+import polars as pl
+
+from viterbo import _native as _native_impl
+from viterbo.atlas.formats import read_table, write_table
+from viterbo.atlas.types import build_atlas_row
+
+from .run import read_halfspaces
+
+_native: Any = _native_impl
+
+FORMATS = ("h-json", "v-json", "csv", "atlas")
+_ATLAS_SUFFIXES = (".parquet", ".jsonl", ".ndjson")
+
+
+def detect_format(path: Path) -> str:
+    """Guess the format from the suffix and, for existing files, the content."""
+
+    suffix = path.suffix.lower()
+    if suffix in _ATLAS_SUFFIXES:
+        return "atlas"
+    if suffix == ".csv":
+        if path.exists():
+            with path.open("r", encoding="utf-8") as handle:
+                if handle.readline().startswith("row_id"):
+                    return "atlas"
+        return "csv"
+    if suffix == ".json" and path.exists():
+        payload = json.loads(path.read_text(encoding="utf-8"))
+        if isinstance(payload, dict) and "vertices" in payload and "halfspaces" not in payload:
+            return "v-json"
+    return "h-json"
+
+
+def load_polytope(path: Path, fmt: str | None = None, *, row_id: int | None = None) -> Any:
+    """Read ``path`` into a native ``Poly4``; ``row_id`` selects an atlas row."""
+
+    kind = fmt or detect_format(path)
+    if kind in ("h-json", "csv"):
+        return _native.Poly4.from_halfspaces(read_halfspaces(path))
+    if kind == "v-json":
+        vertices = json.loads(path.read_text(encoding="utf-8"))["vertices"]
+        return _native.Poly4.from_vertices(np.asarray(vertices, dtype=np.float64))
+    if kind == "atlas":
+        table = read_table(path)
+        if row_id is None:
+            if len(table) != 1:
+                raise ValueError(f"{path}: {len(table)} rows, pass a row_id")
+            row_id = int(table["row_id"][0])
+        rows = table.filter(table["row_id"] == row_id)
+        if len(rows) == 0:
+            raise ValueError(f"{path}: no row with row_id {row_id}")
+        halfspaces = np.asarray(rows["halfspaces"][0].to_list(), dtype=np.float64)
+        return _native.Poly4.from_halfspaces(halfspaces)
+    raise ValueError(f"unknown format {kind!r}; expected one of {', '.join(FORMATS)}")
+
+
+def save_polytope(poly: Any, path: Path, fmt: str | None = None) -> Path:
+    """Write the native ``Poly4`` ``poly`` to ``path``."""
+
+    kind = fmt or detect_format(path)
+    path.parent.mkdir(parents=True, exist_ok=True)
+    if kind == "h-json":
+        payload = {"halfspaces": np.asarray(poly.halfspaces).tolist()}
+        path.write_text(json.dumps(payload, indent=2) + "\n", encoding="utf-8")
+    elif kind == "v-json":
+        payload = {"vertices": np.asarray(poly.vertices).tolist()}
+        path.write_text(json.dumps(payload, indent=2) + "\n", encoding="utf-8")
+    elif kind == "csv":
+        with path.open("w", encoding="utf-8", newline="") as handle:
+            writer = csv.writer(handle)
+            writer.writerow(["n0", "n1", "n2", "n3", "c"])
+            writer.writerows(np.asarray(poly.halfspaces).tolist())
+    elif kind == "atlas":
+        row = build_atlas_row(
+            family="imported",
+            family_name=path.stem,
+            family_parameters={},
+            replay_token={},
+            poly_payload={"vertices": poly.vertices, "halfspaces": poly.halfspaces},
+            solve_capacity=False,
+        )
+        write_table(pl.DataFrame([row.to_record(0)]), path)
+    else:
+        raise ValueError(f"unknown format {kind!r}; expected one of {', '.join(FORMATS)}")
+    return path
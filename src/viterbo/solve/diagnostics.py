@@ -0,0 +1,78 @@
+"""Quick structural diagnostics for one polytope.
+
+Why this file exists
+- Triaging a misbehaving sample used to mean writing a one-off Rust test to
+  print its combinatorics. ``inspect_polytope`` collects what we usually look
+  at first into one flat dict, computed from the H-rep with existing native
+  bindings: f-vector, redundant rows, volume, origin in/outradius,
+  canonicality, a few linear symmetries, and Lagrangian-product structure.
+- Symmetries are checked against a fixed candidate list (``SYMMETRIES``)
+  rather than searched for: we only need the ones the solvers exploit or that
+  explain degenerate behaviour. An orthogonal map ``A`` sends the facet
+  ``⟨n, x⟩ ≤ c`` to ``⟨A n, y⟩ ≤ c``, so ``A`` is a symmetry iff it permutes
+  the normalized facets.
+- "Lagrangian product" means every facet normal lies in the ``q = (x1, x2)``
+  plane or the ``p = (y1, y2)`` plane, with both kinds present. That is
+  exactly the shape ``lagrangian_product`` builds.
+
+References
+- Code: crates/viterbo-py/src/geom.rs::enumerate_faces,
+  crates/viterbo/src/geom4/constructions.rs
+"""
+
+from __future__ import annotations
+
+from typing import Any
+
+import numpy as np
+
+from viterbo import _native as _native_impl
+
+_native: Any = _native_impl
+
+_TOL = 1e-8
+
+_J = np.array([[0, 0, -1, 0], [0, 0, 0, -1], [1, 0, 0, 0], [0, 1, 0, 0]], dtype=np.float64)
+SYMMETRIES: dict[str, np.ndarray] = {
+    "central (x -> -x)": -np.eye(4),
+    "complex structure J": _J,
+    "swap q <-> p": np.block([[np.zeros((2, 2)), np.eye(2)], [np.eye(2), np.zeros((2, 2))]]),
+    "conjugation (q, p) -> (q, -p)": np.diag([1.0, 1.0, -1.0, -1.0]),
+}
+
+
+def inspect_polytope(hs: np.ndarray) -> dict[str, Any]:
+    """Diagnostics for the ``(m, 5)`` H-rep ``hs``; see the module docstring."""
+
+    report: dict[str, Any] = {"halfspace_rows": int(hs.shape[0])}
+    try:
+        faces = _native.enumerate_faces(hs)
+    except Exception as err:  # non-canonical input: say why and stop
+        report.update(canonical=False, problem=str(err))
+        return report
+    facets = [i for i, verts in enumerate(faces["facets"]) if len(verts)]
+    vertices = np.asarray(faces["vertices"], dtype=np.float64)
+    live = hs[facets]
+    unit = live / np.linalg.norm(live[:, :4], axis=1, keepdims=True)
+    report.update(
+        canonical=True,
+        f_vector=[len(vertices), len(faces["edges"]), len(faces["ridges"]), len(facets)],
+        redundant_rows=sorted(set(range(hs.shape[0])) - set(facets)),
+        volume=float(_native.poly4_volume_from_halfspaces(hs)),
+        origin_inradius=float(unit[:, 4].min()),
+        origin_outradius=float(np.linalg.norm(vertices, axis=1).max()),
+        symmetries=[name for name, a in SYMMETRIES.items() if _is_symmetry(unit, a)],
+        lagrangian_product=_is_lagrangian_product(unit),
+    )
+    return report
+
+
+def _is_symmetry(unit: np.ndarray, a: np.ndarray) -> bool:
+    mapped = np.hstack([unit[:, :4] @ a.T, unit[:, 4:]])
+    return all(np.abs(unit - row).max(axis=1).min() <= _TOL for row in mapped)
+
+
+def _is_lagrangian_product(unit: np.ndarray) -> bool:
+    in_q = np.abs(unit[:, 2:4]).max(axis=1) <= _TOL
+    in_p = np.abs(unit[:, 0:2]).max(axis=1) <= _TOL
+    return bool(np.all(in_q | in_p) and in_q.any() and in_p.any())
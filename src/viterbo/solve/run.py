@@ -0,0 +1,186 @@
+"""Run one algorithm on one polytope file.
+
+Why this file exists
+- Triage of a single polytope (a collaborator's file, a misbehaving atlas row)
+  should not need a notebook: read an H-rep, call the native solver, write a
+  structured JSON result with a provenance sidecar.
+- Inputs are either JSON (``{"halfspaces": [[n0, n1, n2, n3, c], ...]}`` or a
+  bare list of rows) or CSV with five numeric columns per row (an optional
+  header line is skipped). Everything funnels into an ``(m, 5)`` float64 array,
+  the layout every native binding takes.
+- ``ALGOS`` is a registry of ``Algo`` entries, each with a summary and
+  declared ``Param``s. ``Algo.validate`` rejects unknown or ill-typed
+  parameters and suggests the nearest name before anything runs, so a typo
+  fails loudly instead of producing an empty result.
+
+References
+- Docs: docs/src/thesis/capacity-algorithm-oriented-edge-graph.md
+- Code: crates/viterbo-py/src/capacity.rs::systolic_ratio
+"""
+
+from __future__ import annotations
+
+import csv
+import difflib
+import json
+import math
+from dataclasses import dataclass
+from pathlib import Path
+from typing import Any, Callable, Mapping
+
+import numpy as np
+
+from viterbo import _native as _native_impl
+
+from .diagnostics import inspect_polytope
+
+_native: Any = _native_impl
+
+
+def read_halfspaces(path: Path) -> np.ndarray:
+    """Load an ``(m, 5)`` H-rep from a JSON or CSV file."""
+
+    if path.suffix.lower() == ".csv":
+        with path.open("r", encoding="utf-8", newline="") as handle:
+            rows = [row for row in csv.reader(handle) if row]
+        if rows and not _is_numeric_row(rows[0]):
+            rows = rows[1:]
+    else:
+        payload = json.loads(path.read_text(encoding="utf-8"))
+        rows = payload.get("halfspaces") if isinstance(payload, dict) else payload
+        if rows is None:
+            raise ValueError(f"{path}: JSON input needs a 'halfspaces' list")
+    hs = np.asarray(rows, dtype=np.float64)
+    if hs.ndim != 2 or hs.shape[1] != 5:
+        raise ValueError(f"{path}: halfspaces must have shape (m, 5), got {hs.shape}")
+    return hs
+
+
+@dataclass(frozen=True)
+class Param:
+    name: str
+    kind: type
+    default: Any
+    help: str
+    choices: tuple[Any, ...] | None = None
+    minimum: float | None = None
+
+    def coerce(self, raw: Any) -> Any:
+        """``raw`` (a CLI string or a JSON value) as ``kind``, checked."""
+
+        try:
+            if self.kind is bool and isinstance(raw, str):
+                if raw.lower() not in ("true", "false", "1", "0"):
+                    raise ValueError(raw)
+                value: Any = raw.lower() in ("true", "1")
+            else:
+                value = self.kind(raw)
+        except (TypeError, ValueError):
+            raise ValueError(f"{self.name} must be {self.kind.__name__}, got {raw!r}") from None
+        if self.choices is not None and value not in self.choices:
+            raise ValueError(f"{self.name} must be one of {list(self.choices)}, got {value!r}")
+        if self.minimum is not None and value < self.minimum:
+            raise ValueError(f"{self.name} must be >= {self.minimum}, got {value!r}")
+        return value
+
+
+@dataclass(frozen=True)
+class Algo:
+    name: str
+    summary: str
+    handler: Callable[[np.ndarray, Mapping[str, Any]], dict[str, Any]]
+    params: tuple[Param, ...] = ()
+
+    def validate(self, raw: Mapping[str, Any]) -> dict[str, Any]:
+        """Defaults overlaid with ``raw``; unknown names and bad values raise ``ValueError``."""
+
+        declared = {param.name: param for param in self.params}
+        for key in raw:
+            if key not in declared:
+                hint = difflib.get_close_matches(key, list(declared), n=1)
+                suggestion = f"; did you mean '{hint[0]}'?" if hint else ""
+                known = ", ".join(declared) or "none"
+                raise ValueError(
+                    f"algo '{self.name}' has no parameter '{key}' (known: {known}){suggestion}"
+                )
+        return {
+            name: param.coerce(raw[name]) if name in raw else param.default
+            for name, param in declared.items()
+        }
+
+
+def _capacity(hs: np.ndarray, _params: Mapping[str, Any]) -> dict[str, Any]:
+    return {"capacity": _native.poly4_capacity_ehz_from_halfspaces(hs)}
+
+
+def _volume(hs: np.ndarray, _params: Mapping[str, Any]) -> dict[str, Any]:
+    return {"volume": float(_native.poly4_volume_from_halfspaces(hs))}
+
+
+def _systolic(hs: np.ndarray, _params: Mapping[str, Any]) -> dict[str, Any]:
+    return dict(_native.systolic_ratio(hs))
+
+
+def _inspect(hs: np.ndarray, _params: Mapping[str, Any]) -> dict[str, Any]:
+    return inspect_polytope(hs)
+
+
+def _optimize(hs: np.ndarray, params: Mapping[str, Any]) -> dict[str, Any]:
+    report = dict(_native.optimize_systolic(hs, **params))
+    report["best_halfspaces"] = np.asarray(report["best_halfspaces"]).tolist()
+    return report
+
+
+_ALGO_LIST = [
+    Algo("capacity", "EHZ capacity (oriented-edge solver).", _capacity),
+    Algo("volume", "Euclidean volume.", _volume),
+    Algo("systolic", "Capacity, volume and systolic ratio.", _systolic),
+    Algo("inspect", "Structural diagnostics (see stage_inspect).", _inspect),
+    Algo(
+        "optimize",
+        "One systolic-ratio optimizer pass over the facet offsets.",
+        _optimize,
+        (
+            Param("optimizer", str, "cma_es", "Optimizer.", choices=("cma_es", "nelder_mead")),
+            Param("budget", int, 200, "Objective evaluations.", minimum=1),
+            Param("seed", int, 0, "Optimizer seed."),
+            Param("sigma0", float, 0.1, "CMA-ES initial step.", minimum=0.0),
+            Param("step", float, 0.1, "Nelder-Mead simplex edge.", minimum=0.0),
+        ),
+    ),
+]
+ALGOS: dict[str, Algo] = {algo.name: algo for algo in _ALGO_LIST}
+
+
+def run_algo(
+    algo: str, hs: np.ndarray, params: Mapping[str, Any] | None = None
+) -> dict[str, Any]:
+    """Run ``algo`` on ``hs``; values the solver cannot produce come back as ``None``."""
+
+    entry = ALGOS.get(algo)
+    if entry is None:
+        hint = difflib.get_close_matches(algo, list(ALGOS), n=1)
+        suggestion = f"; did you mean '{hint[0]}'?" if hint else ""
+        raise ValueError(f"unknown algo '{algo}' (expected one of {sorted(ALGOS)}){suggestion}")
+    result = entry.handler(hs, entry.validate(params or {}))
+    return {k: None if isinstance(v, float) and math.isnan(v) else v for k, v in result.items()}
+
+
+def parse_params(items: list[str]) -> dict[str, str]:
+    """``["key=value", ...]`` from the command line as a dict (values stay strings)."""
+
+    params = {}
+    for item in items:
+        key, sep, value = item.partition("=")
+        if not sep or not key:
+            raise ValueError(f"parameter must look like key=value, got {item!r}")
+        params[key.strip()] = value.strip()
+    return params
+
+
+def _is_numeric_row(row: list[str]) -> bool:
+    try:
+        [float(cell) for cell in row]
+    except ValueError:
+        return False
+    return True
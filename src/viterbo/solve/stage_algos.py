@@ -0,0 +1,52 @@
+"""Pipeline stage: list the algorithms ``stage_run --algo`` accepts, with their parameters.
+
+Usage:
+  uv run python -m viterbo.solve.stage_algos [--json]
+"""
+
+from __future__ import annotations
+
+import argparse
+import json
+
+from .run import ALGOS
+
+
+def parse_args(argv: list[str] | None = None) -> argparse.Namespace:
+    parser = argparse.ArgumentParser(description="List the registered solve algorithms.")
+    parser.add_argument("--json", action="store_true", help="Print the registry as JSON.")
+    return parser.parse_args(argv)
+
+
+def main(argv: list[str] | None = None) -> int:
+    args = parse_args(argv)
+    if args.json:
+        listing = {
+            algo.name: {
+                "summary": algo.summary,
+                "params": [
+                    {
+                        "name": p.name,
+                        "type": p.kind.__name__,
+                        "default": p.default,
+                        "help": p.help,
+                        "choices": list(p.choices) if p.choices is not None else None,
+                        "minimum": p.minimum,
+                    }
+                    for p in algo.params
+                ],
+            }
+            for algo in ALGOS.values()
+        }
+        print(json.dumps(listing, indent=2))
+        return 0
+    for algo in ALGOS.values():
+        print(f"{algo.name:<10}  {algo.summary}")
+        for p in algo.params:
+            extra = f" one of {', '.join(map(str, p.choices))}" if p.choices else ""
+            print(f"  {p.name}={p.default!r} ({p.kind.__name__}{extra})  {p.help}")
+    return 0
+
+
+if __name__ == "__main__":
+    raise SystemExit(main())
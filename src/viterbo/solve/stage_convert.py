@@ -0,0 +1,46 @@
+"""Pipeline stage: convert one polytope between file formats.
+
+Usage:
+  uv run python -m viterbo.solve.stage_convert poly.csv poly_vertices.json --to v-json
+Formats are guessed from the file names unless ``--from``/``--to`` are given; the output gets a
+``.run.json`` provenance sidecar.
+"""
+
+from __future__ import annotations
+
+import argparse
+import sys
+from pathlib import Path
+
+from viterbo.provenance import write as write_provenance
+
+from .convert import FORMATS, load_polytope, save_polytope
+
+
+def parse_args(argv: list[str] | None = None) -> argparse.Namespace:
+    parser = argparse.ArgumentParser(description="Convert a polytope between file formats.")
+    parser.add_argument("input")
+    parser.add_argument("output")
+    parser.add_argument("--from", dest="source_format", choices=FORMATS)
+    parser.add_argument("--to", dest="target_format", choices=FORMATS)
+    parser.add_argument("--row-id", type=int, help="Atlas input: which row to convert.")
+    return parser.parse_args(argv)
+
+
+def main(argv: list[str] | None = None) -> int:
+    args = parse_args(argv)
+    source, target = Path(args.input).resolve(), Path(args.output).resolve()
+    poly = load_polytope(source, args.source_format, row_id=args.row_id)
+    save_polytope(poly, target, args.target_format)
+    write_provenance(
+        target,
+        {"input": str(source), "from": args.source_format, "to": args.target_format},
+        {"command": "python -m viterbo.solve.stage_convert", "exit_code": 0},
+        inputs=[source],
+    )
+    print(f"[convert] {source.name} -> {target}", file=sys.stderr)
+    return 0
+
+
+if __name__ == "__main__":
+    raise SystemExit(main())
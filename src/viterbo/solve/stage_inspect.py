@@ -0,0 +1,40 @@
+"""Pipeline stage: print structural diagnostics for one polytope file.
+
+Usage:
+  uv run python -m viterbo.solve.stage_inspect poly.json [--json]
+Prints a short summary (or JSON with ``--json``); exits 1 if the polytope is not canonical.
+"""
+
+from __future__ import annotations
+
+import argparse
+import json
+from pathlib import Path
+
+from .diagnostics import inspect_polytope
+from .run import read_halfspaces
+
+
+def parse_args(argv: list[str] | None = None) -> argparse.Namespace:
+    parser = argparse.ArgumentParser(description="Inspect one polytope.")
+    parser.add_argument("input", help="H-rep as JSON or CSV (rows n0..n3, c).")
+    parser.add_argument("--json", action="store_true", help="Print the report as JSON.")
+    return parser.parse_args(argv)
+
+
+def main(argv: list[str] | None = None) -> int:
+    args = parse_args(argv)
+    report = inspect_polytope(read_halfspaces(Path(args.input).resolve()))
+    if args.json:
+        print(json.dumps(report, indent=2))
+    else:
+        width = max(len(key) for key in report)
+        for key, value in report.items():
+            if isinstance(value, list) and value and isinstance(value[0], str):
+                value = ", ".join(value)
+            print(f"{key.ljust(width)}  {value}")
+    return 0 if report["canonical"] else 1
+
+
+if __name__ == "__main__":
+    raise SystemExit(main())
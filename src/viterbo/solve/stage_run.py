@@ -0,0 +1,75 @@
+"""Pipeline stage: run one algorithm on one polytope file.
+
+Usage:
+  uv run python -m viterbo.solve.stage_run --algo systolic --input poly.json --out result.json
+  uv run python -m viterbo.solve.stage_run --algo optimize --param budget=500 --input ... --out ...
+Writes ``result.json`` plus its ``.run.json`` provenance sidecar.
+"""
+
+from __future__ import annotations
+
+import argparse
+import json
+import sys
+from pathlib import Path
+
+from viterbo.estimate import describe
+from viterbo.provenance import write as write_provenance
+
+from .run import ALGOS, parse_params, read_halfspaces, run_algo
+
+
+def parse_args(argv: list[str] | None = None) -> argparse.Namespace:
+    parser = argparse.ArgumentParser(description="Run one algorithm on one polytope.")
+    parser.add_argument("--algo", required=True, choices=sorted(ALGOS))
+    parser.add_argument("--input", required=True, help="H-rep as JSON or CSV (rows n0..n3, c).")
+    parser.add_argument("--out", required=True, help="Result JSON path.")
+    parser.add_argument(
+        "--param",
+        action="append",
+        default=[],
+        metavar="KEY=VALUE",
+        help="Algorithm parameter (repeatable); see python -m viterbo.solve.stage_algos.",
+    )
+    parser.add_argument(
+        "--dry-run",
+        action="store_true",
+        help="Validate the algo, parameters and input, print the planned work, then exit.",
+    )
+    return parser.parse_args(argv)
+
+
+def main(argv: list[str] | None = None) -> int:
+    args = parse_args(argv)
+    params = ALGOS[args.algo].validate(parse_params(args.param))
+    input_path = Path(args.input).resolve()
+    out_path = Path(args.out).resolve()
+    hs = read_halfspaces(input_path)
+    if args.dry_run:
+        # ``optimize`` evaluates the objective ``budget`` times; the rest solve once.
+        solves = int(params.get("budget", 1))
+        print(f"[dry-run] {args.algo} on {hs.shape[0]} halfspaces, params {params}")
+        print(f"[dry-run] work: {describe(solves)}")
+        print(f"[dry-run] output: {out_path} (+ .run.json)")
+        return 0
+    payload = {
+        "algo": args.algo,
+        "input": str(input_path),
+        "halfspace_count": int(hs.shape[0]),
+        "params": params,
+        "result": run_algo(args.algo, hs, params),
+    }
+    out_path.parent.mkdir(parents=True, exist_ok=True)
+    out_path.write_text(json.dumps(payload, indent=2, sort_keys=True) + "\n", encoding="utf-8")
+    write_provenance(
+        out_path,
+        {"algo": args.algo, "params": params, "input": str(input_path)},
+        {"command": "python -m viterbo.solve.stage_run", "exit_code": 0},
+        inputs=[input_path],
+    )
+    print(f"[solve] {args.algo}: {payload['result']}", file=sys.stderr)
+    return 0
+
+
+if __name__ == "__main__":
+    raise SystemExit(main())
@@ -0,0 +1,57 @@
+"""Poll a file and yield whenever it changes.
+
+Why this file exists
+- Iterating on a figure meant re-running the stage by hand after every
+  pipeline tweak. ``changes`` drives ``--watch`` loops: it yields once
+  straight away, then again each time the file's size or modification time
+  changes.
+- It polls ``stat`` instead of using inotify (or the notify crate). That
+  needs no dependency and works on network filesystems, where cluster
+  outputs often live. The price is latency: a change is seen up to one
+  ``interval`` (default 1 s) after it lands. Writers
+  replace files atomically (``.tmp`` then rename), so a changed stat always
+  means a complete file.
+
+References
+- Docs: docs/src/thesis/atlas-dataset.md
+"""
+
+from __future__ import annotations
+
+import os
+import time
+from pathlib import Path
+from typing import Callable, Iterator, Optional
+
+
+def changes(
+    path: Path,
+    *,
+    interval: float = 1.0,
+    sleep: Callable[[float], None] = time.sleep,
+) -> Iterator[None]:
+    """Yield now, then after every change of ``path`` (forever; stop with Ctrl-C).
+
+    A missing file is waited for; its reappearance counts as a change.
+    """
+
+    if interval <= 0:
+        raise ValueError("interval must be positive")
+    last = _signature(path)
+    yield
+    while True:
+        sleep(interval)
+        current = _signature(path)
+        if current != last and current is not None:
+            last = current
+            yield
+        elif current is None:
+            last = None
+
+
+def _signature(path: Path) -> Optional[tuple[int, int]]:
+    try:
+        stat = os.stat(path)
+    except FileNotFoundError:
+        return None
+    return stat.st_mtime_ns, stat.st_size
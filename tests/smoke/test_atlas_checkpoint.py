@@ -0,0 +1,45 @@
+import json
+from pathlib import Path
+
+import pytest
+
+from viterbo.atlas.config import AtlasConfig
+
+
+def _config(tmp_path: Path, seed: int) -> AtlasConfig:
+    return AtlasConfig.from_mapping(
+        {
+            "seed": seed,
+            "sources": [
+                {
+                    "name": "cat",
+                    "family": "special_catalog",
+                    "rows": 5,
+                    "params": {"members": ["hypercube", "cross_polytope"]},
+                },
+            ],
+            "out": {"dataset": str(tmp_path / "atlas.parquet")},
+        },
+        base_dir=tmp_path,
+    )
+
+
+def test_resume_solves_only_missing_chunks(tmp_path: Path):
+    from viterbo.atlas.checkpoint import build_dataset_checkpointed, parts_dir
+
+    cfg = _config(tmp_path, seed=1)
+    full = build_dataset_checkpointed(cfg, chunk_rows=2)
+    # Simulate a crash before the last chunk (rows 4..) was recorded.
+    manifest_path = parts_dir(cfg) / "manifest.json"
+    manifest = json.loads(manifest_path.read_text())
+    assert manifest["done"] == [0, 2, 4]
+    manifest["done"].remove(4)
+    manifest_path.write_text(json.dumps(manifest))
+    (parts_dir(cfg) / "part-000000000004.parquet").unlink()
+
+    resumed = build_dataset_checkpointed(cfg, resume=True)
+    assert resumed["row_id"].to_list() == list(range(5))
+    assert resumed["volume"].to_list() == full["volume"].to_list()
+
+    with pytest.raises(ValueError, match="different config"):
+        build_dataset_checkpointed(_config(tmp_path, seed=2), resume=True)
@@ -0,0 +1,38 @@
+from pathlib import Path
+
+import pytest
+
+from viterbo.atlas.config import AtlasConfig
+
+TOML = """
+seed = 7
+
+[solver]
+jobs = 2
+
+[metadata]
+purpose = "smoke"
+
+[out]
+dataset = "data/atlas/smoke.parquet"
+
+[[sources]]
+name = "cat"
+family = "special_catalog"
+params = { members = ["hypercube"] }
+"""
+
+
+def test_toml_config_loads_solver_and_metadata(tmp_path: Path):
+    path = tmp_path / "atlas.toml"
+    path.write_text(TOML, encoding="utf-8")
+    cfg = AtlasConfig.from_file(path)
+    assert (cfg.seed, cfg.jobs, cfg.metadata) == (7, 2, {"purpose": "smoke"})
+    assert cfg.sources[0].rows == 1
+
+
+def test_unknown_key_error_names_file_and_suggests(tmp_path: Path):
+    path = tmp_path / "typo.toml"
+    path.write_text(TOML.replace("family =", "famly ="), encoding="utf-8")
+    with pytest.raises(ValueError, match=r"typo\.toml.*famly.*did you mean 'family'"):
+        AtlasConfig.from_file(path)
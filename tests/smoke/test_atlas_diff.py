@@ -0,0 +1,43 @@
+import math
+
+import numpy as np
+import polars as pl
+import pytest
+
+from viterbo.atlas.diff import diff_datasets, ks_2samp
+
+
+def _table(family: list[str], capacity: list[float]) -> pl.DataFrame:
+    return pl.DataFrame(
+        {
+            "family": family,
+            "capacity_ehz": capacity,
+            "volume": [1.0] * len(family),
+            "systolic_ratio": [c * c / 2.0 for c in capacity],
+        }
+    )
+
+
+def test_ks_detects_a_shift_and_accepts_identical_samples():
+    rng = np.random.default_rng(0)
+    a = rng.normal(size=400)
+    d_same, p_same = ks_2samp(a, a.copy())
+    assert d_same == 0.0 and p_same == 1.0
+    d_shift, p_shift = ks_2samp(a, a + 1.0)
+    assert d_shift > 0.3 and p_shift < 1e-6
+    assert all(math.isnan(x) for x in ks_2samp(a, np.array([])))
+
+
+def test_diff_reports_schema_counts_and_nonfinite_rows():
+    a = _table(["x", "x", "y"], [1.0, 2.0, 3.0])
+    b = _table(["x", "y", "y", "z"], [1.0, math.nan, 3.0, 4.0]).with_columns(
+        pl.lit("ok").alias("status")
+    )
+    report = diff_datasets(a, b)
+    assert report["schema"]["only_b"] == ["status"] and not report["schema"]["only_a"]
+    assert {r["family"]: r["delta"] for r in report["rows"]} == {"x": -1, "y": 1, "z": 1}
+    shift = next(
+        s for s in report["shifts"] if s["metric"] == "capacity_ehz" and s["family"] == "y"
+    )
+    assert (shift["n_b"], shift["nonfinite_b"]) == (1, 1)
+    assert shift["ks"] == pytest.approx(0.0)
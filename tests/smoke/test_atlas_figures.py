@@ -0,0 +1,48 @@
+import math
+
+import polars as pl
+
+
+def test_figures_render_svg_with_one_mark_per_point():
+    from viterbo.atlas.figures import capacity_vs_volume, facet_counts, systolic_histogram
+
+    df = pl.DataFrame(
+        {
+            "family": ["a", "a", "b"],
+            "volume": [16.0, 2.0, math.nan],
+            "capacity_ehz": [4.0, 1.0, 3.0],
+            "systolic_ratio": [0.5, 0.25, math.nan],
+            "halfspace_count": [8, 8, 16],
+        }
+    )
+    scatter = capacity_vs_volume(df)
+    # plotters writes colours as uppercase hex; legend markers use r="4".
+    assert scatter.startswith("<svg") and scatter.count('r="3"') == 2
+    assert systolic_histogram(df).count('fill="#1F77B4"') == 2
+    assert facet_counts(df).count('fill="#2CA02C"') == 2
+
+
+def test_write_figures_saves_the_plotted_series_next_to_each_svg(tmp_path):
+    from viterbo.atlas.figures import write_figures
+
+    df = pl.DataFrame(
+        {
+            "family": ["a", "b"],
+            "volume": [16.0, math.nan],
+            "capacity_ehz": [4.0, 3.0],
+            "systolic_ratio": [0.5, math.nan],
+            "halfspace_count": [8, 16],
+        }
+    )
+    paths = write_figures(df, tmp_path, "t")
+    assert [svg.name for svg, _ in paths] == [
+        "t_systolic_hist.svg",
+        "t_capacity_vs_volume.svg",
+        "t_facet_counts.svg",
+    ]
+    series = {svg.stem: pl.read_csv(csv) for svg, csv in paths}
+    assert series["t_capacity_vs_volume"].to_dicts() == [
+        {"family": "a", "volume": 16.0, "capacity_ehz": 4.0}
+    ]
+    assert series["t_facet_counts"]["rows"].to_list() == [1, 1]
+    assert series["t_systolic_hist"]["rows"].sum() == 1
@@ -0,0 +1,88 @@
+import json
+import math
+from pathlib import Path
+
+import polars as pl
+import pyarrow.parquet as pq
+import pytest
+
+from viterbo.atlas.formats import (
+    SCHEMA,
+    SCHEMA_VERSION,
+    VERSION_KEY,
+    read_table,
+    schema_version,
+    write_table,
+)
+
+
+def _table() -> pl.DataFrame:
+    return pl.DataFrame(
+        {
+            "row_id": [0, 1],
+            "family": ["a", "b"],
+            "family_name": ["a:x", "b:y"],
+            "family_parameters": ['{"k": 1}', "{}"],
+            "replay_token": ["{}", '{"seed": 2}'],
+            "vertex_count": [2, 1],
+            "halfspace_count": [1, 0],
+            "vertices": [[[0.0, 1.0, 2.0, 3.0], [1.5, 0.0, 0.0, 0.0]], [[0.25, 0.0, 0.0, 0.0]]],
+            "halfspaces": [[[1.0, 0.0, 0.0, 0.0, 1.0]], []],
+            "volume": [1.0, 2.0],
+            "capacity_ehz": [0.5, math.nan],
+            "dominant_orbit": ["unavailable", "unavailable"],
+            "systolic_ratio": [0.125, math.nan],
+            "status": ["ok", "no_cycle"],
+            "retries": [0, 2],
+        }
+    )
+
+
+@pytest.mark.parametrize("name", ["atlas.parquet", "atlas.csv", "atlas.jsonl"])
+def test_every_format_round_trips_the_shared_schema(tmp_path: Path, name: str):
+    df = _table()
+    back = read_table(write_table(df, tmp_path / name))
+    assert back.columns == list(SCHEMA)
+    assert back["vertices"].to_list() == df["vertices"].to_list()
+    assert back["halfspaces"].to_list() == df["halfspaces"].to_list()
+    assert math.isnan(back["capacity_ehz"][1]) and back["capacity_ehz"][0] == 0.5
+
+
+def test_unknown_format_is_rejected(tmp_path: Path):
+    with pytest.raises(ValueError, match="unknown format"):
+        write_table(_table(), tmp_path / "atlas.xlsx", "xlsx")
+
+
+def _write_v1(path: Path) -> None:
+    """The layout before the ``status`` column, as the old writers produced it."""
+
+    v1 = _table().drop("status", "retries")
+    if path.suffix == ".parquet":
+        v1.write_parquet(path)
+    elif path.suffix == ".jsonl":
+        v1.with_columns(pl.col("capacity_ehz", "systolic_ratio").fill_nan(None)).write_ndjson(path)
+    else:
+        nested = ("vertices", "halfspaces")
+        encoded = [pl.Series(n, [json.dumps(v) for v in v1[n].to_list()]) for n in nested]
+        v1.with_columns(encoded).write_csv(path)
+
+
+@pytest.mark.parametrize("name", ["atlas.parquet", "atlas.csv", "atlas.jsonl"])
+def test_version_1_tables_are_migrated(tmp_path: Path, name: str):
+    path = tmp_path / name
+    _write_v1(path)
+    assert schema_version(path) == 1
+    back = read_table(path)
+    assert back.columns == list(SCHEMA)
+    assert back["status"].to_list() == ["ok", "no_cycle"]
+    assert back["retries"].to_list() == [0, 0]
+
+
+def test_newer_schema_version_is_refused(tmp_path: Path):
+    path = write_table(_table(), tmp_path / "atlas.parquet")
+    assert schema_version(path) == SCHEMA_VERSION
+    table = pq.read_table(path)
+    future = {VERSION_KEY: str(SCHEMA_VERSION + 1).encode()}
+    pq.write_table(table.replace_schema_metadata(future), path)
+    with pytest.raises(ValueError, match="newer than this reader"):
+        read_table(path)
@@ -0,0 +1,78 @@
+from pathlib import Path
+
+from viterbo.atlas.config import AtlasConfig
+
+
+def test_parallel_build_matches_serial_row_for_row(tmp_path: Path):
+    from viterbo.atlas.dataset import build_dataset
+
+    cfg = AtlasConfig.from_mapping(
+        {
+            "seed": 3,
+            "sources": [
+                {
+                    "name": "sym",
+                    "family": "symmetric_halfspaces",
+                    "rows": 3,
+                    "params": {"directions": 6, "radius_min": 0.7, "radius_max": 1.25},
+                },
+                {"name": "cat", "family": "special_catalog", "params": {"members": ["hypercube"]}},
+            ],
+            "out": {"dataset": str(tmp_path / "atlas.parquet")},
+        },
+        base_dir=tmp_path,
+    )
+    serial = build_dataset(cfg)
+    parallel = build_dataset(cfg, jobs=2)
+    assert parallel["row_id"].to_list() == serial["row_id"].to_list()
+    for col in ("capacity_ehz", "systolic_ratio"):
+        assert parallel[col].fill_nan(None).to_list() == serial[col].fill_nan(None).to_list()
+
+
+def test_build_reports_progress_for_every_row(tmp_path: Path):
+    from viterbo.atlas.dataset import build_dataset
+
+    cfg = AtlasConfig.from_mapping(
+        {
+            "seed": 5,
+            "sources": [
+                {"name": "cat", "family": "special_catalog", "params": {"members": ["hypercube"]}}
+            ],
+            "out": {"dataset": str(tmp_path / "atlas.parquet")},
+        },
+        base_dir=tmp_path,
+    )
+    for jobs in (None, 1):
+        calls: list[tuple[int, int]] = []
+        df = build_dataset(cfg, jobs=jobs, progress=lambda done, total: calls.append((done, total)))
+        assert calls[-1] == (len(df), len(df))
+
+
+def test_json_log_has_one_sample_event_per_row(tmp_path: Path):
+    import json
+
+    from viterbo.atlas.dataset import build_dataset
+    from viterbo.jsonlog import JsonLog
+
+    cfg = AtlasConfig.from_mapping(
+        {
+            "seed": 5,
+            "sources": [
+                {
+                    "name": "cat",
+                    "family": "special_catalog",
+                    "rows": 2,
+                    "params": {"members": ["hypercube"]},
+                }
+            ],
+            "out": {"dataset": str(tmp_path / "atlas.parquet")},
+        },
+        base_dir=tmp_path,
+    )
+    with JsonLog(tmp_path / "run.jsonl") as log:
+        build_dataset(cfg, log=log)
+    events = [json.loads(line) for line in (tmp_path / "run.jsonl").read_text().splitlines()]
+    samples = [e for e in events if e["event"] == "sample"]
+    assert [e["row_id"] for e in samples] == [0, 1]
+    assert all(e["outcome"] == "ok" and e["solve_s"] >= 0.0 for e in samples)
+    assert samples[0]["replay_token"] == {"member": "hypercube"}
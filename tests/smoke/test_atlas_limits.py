@@ -0,0 +1,22 @@
+import time
+
+import pytest
+
+from viterbo.atlas.limits import BudgetExceeded, SampleLimits, run_limited
+
+
+def _hog(megabytes: int) -> None:
+    block = bytearray(megabytes << 20)
+    time.sleep(10.0)
+    del block
+
+
+def test_run_limited_returns_the_worker_result():
+    assert run_limited(lambda x: x * 2, 21, SampleLimits(timeout_s=5.0)) == 42
+
+
+def test_run_limited_kills_slow_and_hungry_workers():
+    with pytest.raises(BudgetExceeded):
+        run_limited(time.sleep, 10.0, SampleLimits(timeout_s=0.2))
+    with pytest.raises(BudgetExceeded):
+        run_limited(_hog, 512, SampleLimits(timeout_s=8.0, max_mem_mb=256))
@@ -0,0 +1,45 @@
+from pathlib import Path
+
+import pytest
+
+from viterbo.atlas.config import AtlasConfig
+from viterbo.atlas.matrix import expand_matrix
+
+
+def _payload(tmp_path: Path) -> dict:
+    return {
+        "seed": 1,
+        "sources": [
+            {
+                "name": "sym",
+                "family": "symmetric_halfspaces",
+                "rows": 2,
+                "params": {"directions": 6, "radius_min": 0.7, "radius_max": 1.25},
+            }
+        ],
+        "out": {"dataset": str(tmp_path / "sweep.parquet")},
+        "matrix": {"sym.directions": [6, 10], "seed": [1, 2]},
+    }
+
+
+def test_matrix_expands_to_one_config_per_cell(tmp_path: Path):
+    cells = expand_matrix(_payload(tmp_path), base_dir=tmp_path)
+    assert [cell.name for cell in cells] == [
+        "sym.directions=6__seed=1",
+        "sym.directions=6__seed=2",
+        "sym.directions=10__seed=1",
+        "sym.directions=10__seed=2",
+    ]
+    last = cells[-1].config
+    assert last.seed == 2 and last.sources[0].params["directions"] == 10
+    assert last.out.dataset == tmp_path / "sweep" / "sym.directions=10__seed=2.parquet"
+    assert last.metadata["matrix"] == {"sym.directions": 10, "seed": 2}
+
+
+def test_matrix_errors_name_the_problem(tmp_path: Path):
+    payload = _payload(tmp_path)
+    with pytest.raises(ValueError, match="stage_matrix"):
+        AtlasConfig.from_mapping(payload, base_dir=tmp_path)
+    payload["matrix"] = {"symm.directions": [6]}
+    with pytest.raises(ValueError, match="did you mean 'sym'"):
+        expand_matrix(payload, base_dir=tmp_path)
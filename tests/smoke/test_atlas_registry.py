@@ -0,0 +1,21 @@
+import math
+
+
+def test_registry_source_streams_rows_with_replay():
+    from viterbo.atlas.config import SourceConfig
+    from viterbo.atlas.sources import source_from_spec
+
+    spec = SourceConfig(
+        name="sym_registry",
+        family="registry",
+        rows=2,
+        params={
+            "family": "symmetric_halfspaces",
+            "params": {"directions": 6, "radius_min": 0.7, "radius_max": 1.25},
+        },
+    )
+    rows = list(source_from_spec(spec, default_seed=42).generate())
+    assert [row.family_name for row in rows] == ["sym_registry:symmetric_halfspaces"] * 2
+    assert all(row.family_parameters["seed"] == 42 for row in rows)
+    assert all("replay" in row.replay_token for row in rows)
+    assert all(row.polytope.halfspace_count >= 5 and not math.isnan(row.volume) for row in rows)
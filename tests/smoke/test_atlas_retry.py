@@ -0,0 +1,48 @@
+from pathlib import Path
+
+import pytest
+
+from viterbo import _native
+from viterbo.atlas.config import AtlasConfig, GenerationPolicy, SourceConfig
+from viterbo.seeds import RETRY_STRIDE
+
+
+def _source(policy: GenerationPolicy):
+    from viterbo.atlas.sources import source_from_spec
+
+    spec = SourceConfig(name="sym", family="symmetric_halfspaces", rows=1, params={}, seed=5)
+    return source_from_spec(spec, 0, solve_capacity=False, policy=policy)
+
+
+def _degenerate_until(good_seed: int):
+    def sample(seed: int) -> dict:
+        if seed != good_seed:
+            raise _native.DegenerateSampleError(f"seed {seed}")
+        return {"seed": seed}
+
+    return sample
+
+
+def test_degenerate_policies_retry_skip_and_abort():
+    retry = _source(GenerationPolicy("retry", max_retries=3))
+    good = 5 + 2 * RETRY_STRIDE
+    assert retry.draw(_degenerate_until(good), 5) == ({"seed": good}, good, 2)
+    with pytest.raises(ValueError, match="still degenerate after 3 retries"):
+        retry.draw(_degenerate_until(-1), 5)
+    assert _source(GenerationPolicy("skip")).draw(_degenerate_until(-1), 5) == (None, 5, 0)
+    with pytest.raises(_native.DegenerateSampleError):
+        _source(GenerationPolicy()).draw(_degenerate_until(-1), 5)
+
+
+def test_generation_policy_is_validated(tmp_path: Path):
+    base = {
+        "sources": [{"family": "special_catalog", "params": {"members": ["hypercube"]}}],
+        "out": {"dataset": str(tmp_path / "a.parquet")},
+    }
+    cfg = AtlasConfig.from_mapping(
+        {**base, "generation": {"on_degenerate": "retry", "max_retries": 5}}, base_dir=tmp_path
+    )
+    assert cfg.generation == GenerationPolicy("retry", 5)
+    bad = {**base, "generation": {"on_degenerate": "ignore"}}
+    with pytest.raises(ValueError, match="on_degenerate must be one of"):
+        AtlasConfig.from_mapping(bad, base_dir=tmp_path)
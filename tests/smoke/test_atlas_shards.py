@@ -0,0 +1,53 @@
+import json
+from pathlib import Path
+
+import pytest
+
+from viterbo.atlas.config import AtlasConfig
+
+
+def _write_config(tmp_path: Path) -> Path:
+    path = tmp_path / "atlas.json"
+    config = {
+        "seed": 4,
+        "sources": [
+            {
+                "name": "cat",
+                "family": "special_catalog",
+                "rows": 5,
+                "params": {"members": ["hypercube", "cross_polytope"]},
+            },
+        ],
+        "out": {"dataset": str(tmp_path / "atlas.parquet")},
+    }
+    path.write_text(json.dumps(config), encoding="utf-8")
+    return path
+
+
+def test_shards_merge_back_to_the_unsharded_dataset(tmp_path: Path):
+    from viterbo.atlas import stage_build, stage_merge_shards
+    from viterbo.atlas.dataset import build_dataset
+    from viterbo.atlas.formats import read_table
+
+    config = _write_config(tmp_path)
+    for index in range(3):
+        assert stage_build.main(["--config", str(config), "--shard", f"{index}/3"]) == 0
+    assert stage_merge_shards.main(["--config", str(config)]) == 0
+
+    merged = read_table(tmp_path / "atlas.parquet")
+    direct = build_dataset(AtlasConfig.from_file(config))
+    assert merged["row_id"].to_list() == list(range(5))
+    assert merged["volume"].to_list() == direct["volume"].to_list()
+    assert not (tmp_path / "atlas.parquet.shards").exists()
+
+
+def test_merge_refuses_an_incomplete_shard_set(tmp_path: Path):
+    from viterbo.atlas import stage_build
+    from viterbo.atlas.shards import Shard, merge_shards
+
+    config = _write_config(tmp_path)
+    stage_build.main(["--config", str(config), "--shard", "0/2"])
+    with pytest.raises(ValueError, match=r"missing shards \[1\]"):
+        merge_shards(AtlasConfig.from_file(config))
+    with pytest.raises(ValueError, match="0 <= K < N"):
+        Shard.parse("2/2")
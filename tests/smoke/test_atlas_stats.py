@@ -0,0 +1,36 @@
+import math
+
+import polars as pl
+
+from viterbo.atlas.stats import METRICS, summarize
+
+
+def _table() -> pl.DataFrame:
+    return pl.DataFrame(
+        {
+            "family": ["a", "a", "a", "b"],
+            "capacity_ehz": [1.0, 2.0, math.nan, 4.0],
+            "volume": [1.0, 1.0, 1.0, 2.0],
+            "systolic_ratio": [0.5, 2.0, math.nan, 4.0],
+            "halfspace_count": [8, 10, 12, 8],
+            "vertex_count": [16, 16, 16, 24],
+        }
+    )
+
+
+def test_summary_has_one_row_per_family_and_metric():
+    table = summarize(_table())
+    assert table["family"].unique().sort().to_list() == ["a", "all", "b"]
+    assert table.height == 3 * len(METRICS)
+    assert table.columns[:2] == ["family", "metric"]
+
+
+def test_non_finite_values_are_dropped_from_the_statistics():
+    table = summarize(_table())
+    row = table.filter((pl.col("family") == "a") & (pl.col("metric") == "capacity_ehz")).row(
+        0, named=True
+    )
+    assert (row["rows"], row["count"]) == (3, 2)
+    assert row["mean"] == 1.5 and row["min"] == 1.0 and row["max"] == 2.0
+    facets = table.filter((pl.col("family") == "all") & (pl.col("metric") == "halfspace_count"))
+    assert facets["p50"].item() == 9.0
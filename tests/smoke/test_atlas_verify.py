@@ -0,0 +1,36 @@
+from pathlib import Path
+
+import polars as pl
+
+from viterbo.atlas.config import AtlasConfig
+
+
+def test_verify_replays_rows_and_flags_tampering(tmp_path: Path):
+    from viterbo.atlas.dataset import build_dataset
+    from viterbo.atlas.verify import verify_dataset
+
+    cfg = AtlasConfig.from_mapping(
+        {
+            "seed": 5,
+            "sources": [
+                {
+                    "name": "sym",
+                    "family": "symmetric_halfspaces",
+                    "rows": 2,
+                    "params": {"directions": 6, "radius_min": 0.7, "radius_max": 1.25},
+                },
+                {"name": "cat", "family": "special_catalog", "params": {"members": ["hypercube"]}},
+            ],
+            "out": {"dataset": str(tmp_path / "atlas.parquet")},
+        },
+        base_dir=tmp_path,
+    )
+    df = build_dataset(cfg)
+    clean, tampered = tmp_path / "clean.parquet", tmp_path / "tampered.parquet"
+    df.write_parquet(clean)
+    df.with_columns(pl.col("volume") * 1.01).write_parquet(tampered)
+
+    assert verify_dataset(clean, fraction=1.0).ok
+    report = verify_dataset(tampered, fraction=1.0)
+    assert report.checked == 3
+    assert {m["row_id"] for m in report.mismatches} == {0, 1, 2}
@@ -0,0 +1,16 @@
+import json
+from pathlib import Path
+
+
+def test_oe4_suite_report_is_normalized(tmp_path: Path):
+    from viterbo.bench import stage_suite
+
+    out = tmp_path / "oe4.json"
+    assert stage_suite.main(["--suite", "oe4", "--out", str(out), "--repeats", "1"]) == 0
+    report = json.loads(out.read_text())
+    assert report["schema_version"] == 1 and report["suite"] == "oe4"
+    cases = {case["name"]: case for case in report["cases"]}
+    assert abs(cases["hypercube"]["capacity_ehz"] - 4.0) < 1e-6
+    for case in cases.values():
+        assert case["wall_s"]["min"] <= case["wall_s"]["median"] <= case["wall_s"]["max"]
+    assert (tmp_path / "oe4.json.run.json").exists()
@@ -0,0 +1,26 @@
+import json
+from pathlib import Path
+
+from viterbo.estimate import describe, seconds_per_solve
+
+
+def test_estimate_uses_the_median_case_of_the_bench_report(tmp_path: Path):
+    report = tmp_path / "oe4.json"
+    cases = [{"wall_s": {"median": m}} for m in (0.5, 2.0, 1.0)]
+    report.write_text(json.dumps({"schema_version": 1, "cases": cases}))
+    assert seconds_per_solve(report) == 1.0
+    assert describe(7200, threads=2, report=report) == "7200 solves, ~1:00:00 on 2 threads"
+    assert "no estimate" in describe(10, report=tmp_path / "missing.json")
+
+
+def test_stage_run_dry_run_writes_nothing(tmp_path: Path, capsys):
+    from viterbo.solve import stage_run
+
+    poly = tmp_path / "cube.json"
+    rows = [[float(k == i) * s for k in range(4)] + [1.0] for i in range(4) for s in (1, -1)]
+    poly.write_text(json.dumps({"halfspaces": rows}))
+    out = tmp_path / "result.json"
+    argv = ["--algo", "optimize", "--param", "budget=40", "--input", str(poly), "--out", str(out)]
+    assert stage_run.main([*argv, "--dry-run"]) == 0
+    assert "40 solves" in capsys.readouterr().out
+    assert not out.exists()
@@ -0,0 +1,15 @@
+import os
+import signal
+
+import pytest
+
+from viterbo.interrupt import graceful_termination
+
+
+def test_sigterm_raises_keyboard_interrupt_and_is_restored():
+    previous = signal.getsignal(signal.SIGTERM)
+    with graceful_termination() as termination:
+        with pytest.raises(KeyboardInterrupt):
+            os.kill(os.getpid(), signal.SIGTERM)
+    assert termination.name == "SIGTERM" and termination.exit_code == 143
+    assert signal.getsignal(signal.SIGTERM) is previous
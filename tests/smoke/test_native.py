@@ -3,6 +3,9 @@ import subprocess
 from pathlib import Path
 from typing import Any
 
+import numpy as np
+import pytest
+
 
 def test_native_import_and_function():
     # Basic presence + trivial function sanity
@@ -19,20 +22,309 @@ def test_native_import_and_function():
     assert "src/viterbo/" in ext.__file__
 
 
+def _cube_halfspaces(scale: float) -> np.ndarray:
+    eye = np.eye(4)
+    normals = np.concatenate([eye, -eye])
+    return np.column_stack([normals, np.full(8, scale)])
+
+
 def test_volume4_binding_matches_hypercube():
     from viterbo import _native
 
-    hs = []
-    for axis in range(4):
-        normal = [0.0, 0.0, 0.0, 0.0]
-        normal[axis] = 1.0
-        hs.append((tuple(normal), 1.0))
-        normal[axis] = -1.0
-        hs.append((tuple(normal), 1.0))
-    vol = getattr(_native, "poly4_volume_from_halfspaces")(hs)
+    vol = getattr(_native, "poly4_volume_from_halfspaces")(_cube_halfspaces(1.0))
+    assert abs(vol - 16.0) < 1e-9
+
+
+def test_volume4_from_vertices_matches_hypercube():
+    from viterbo import _native
+
+    corners = np.array([[(b >> k) & 1 for k in range(4)] for b in range(16)], dtype=float)
+    vol = getattr(_native, "poly4_volume_from_vertices")(2.0 * corners - 1.0)
     assert abs(vol - 16.0) < 1e-9
 
 
+def test_generator_outputs_are_arrays():
+    from viterbo import _native
+
+    params = {"directions": 6, "radius_min": 0.8, "radius_max": 1.2}
+    poly = getattr(_native, "rand4_symmetric_halfspace_sample")(params, 3)
+    assert isinstance(poly["vertices"], np.ndarray)
+    assert poly["vertices"].shape[1] == 4
+    assert poly["halfspaces"].shape[1] == 5
+
+
+def test_capacity_batch_matches_single_solves():
+    from viterbo import _native
+
+    cubes = [_cube_halfspaces(scale) for scale in (1.0, 2.0, 0.5)]
+    single = getattr(_native, "poly4_capacity_ehz_from_halfspaces")
+    batch = getattr(_native, "capacity_ehz_batch")(cubes, 2)
+    assert batch == [single(hs) for hs in cubes]
+
+
+def test_poly4_class_round_trip():
+    from viterbo import _native
+
+    cube = getattr(_native, "Poly4").from_halfspaces(_cube_halfspaces(1.0))
+    assert cube.contains_origin()
+    assert cube.vertices.shape == (16, 4)
+    assert abs(cube.volume() - 16.0) < 1e-9
+    moved = cube.push_forward(2.0 * np.eye(4), np.full(4, 3.0))
+    assert not moved.contains_origin()
+    assert abs(moved.volume() - 256.0) < 1e-6
+    hull = getattr(_native, "Poly4").from_vertices(cube.vertices)
+    assert hull.halfspaces.shape == (8, 5)
+
+
+def test_poly2_from_radial_sampler():
+    from viterbo import _native
+
+    poly2 = getattr(_native, "Poly2")
+    k = poly2.from_radial(5, 2, {"vertex_count": 7})
+    assert k.vertices().shape == (7, 2)
+    centred, r_in, r_out = k.recenter_rescale({"r_in_min": 0.2, "r_out_max": 2.0})
+    assert 0.0 < r_in <= r_out
+    assert centred.polar().area() > 0.0
+    assert poly2.from_radial(5, 2, {"vertex_count": 7}).area() == k.area()
+
+
+def test_enumerate_faces_of_cube():
+    from viterbo import _native
+
+    faces = getattr(_native, "enumerate_faces")(_cube_halfspaces(1.0))
+    assert faces["vertices"].shape == (16, 4)
+    assert [len(f) for f in faces["facets"]] == [8] * 8
+    assert faces["ridges"].shape == (24, 2)
+    assert all(len(v) == 4 for v in faces["ridge_vertices"])
+    assert faces["edges"].shape == (32, 2)
+
+
+def test_oriented_edge_graph_inspection():
+    from viterbo import _native
+
+    graph = getattr(_native, "build_oriented_edge_graph")(_cube_halfspaces(1.0))
+    assert graph.num_ridges > 0
+    edges = graph.edges()
+    assert len(edges) == graph.num_edges
+    for src, dst, facet, _lb, _rho in edges:
+        assert src < graph.num_ridges and dst < graph.num_ridges
+        assert facet in graph.ridge_facets(src)
+
+
+def test_systolic_ratio_matches_components():
+    from viterbo import _native
+
+    out = getattr(_native, "systolic_ratio")(_cube_halfspaces(1.0))
+    assert abs(out["volume"] - 16.0) < 1e-9
+    if out["capacity"] is not None:
+        expected = out["capacity"] ** 2 / (2.0 * out["volume"])
+        assert abs(out["systolic_ratio"] - expected) < 1e-12
+
+
+def test_polytope_stream_iterates_with_replay():
+    from viterbo import _native
+
+    stream = getattr(_native, "PolytopeStream")(
+        {"family": "zonotopes", "seed": 11, "params": {"segments_min": 5, "segments_max": 5}}
+    )
+    assert stream.family == "zonotopes"
+    rows = [next(stream) for _ in range(2)]
+    assert rows[1]["replay"] == {"seed": 11, "index": 1}
+    assert rows[1]["params"]["segments_min"] == 5
+    assert rows[1]["halfspaces"].shape[1] == 5
+    assert rows[1]["vertices"].shape[1] == 4
+
+
+def test_rand4_regenerate_matches_stream_row():
+    from viterbo import _native
+
+    params = {"segments_min": 5, "segments_max": 5}
+    config = {"family": "zonotopes", "seed": 3, "params": params}
+    stream = getattr(_native, "PolytopeStream")(config)
+    row = next(stream)
+    again = getattr(_native, "rand4_regenerate")("zonotopes", params, row["replay"])
+    assert np.allclose(again["halfspaces"], row["halfspaces"])
+
+
+def test_symplectic_push_forward_preserves_volume():
+    from viterbo import _native
+
+    m = getattr(_native, "random_symplectic_4")(7)
+    j = np.block([[np.zeros((2, 2)), -np.eye(2)], [np.eye(2), np.zeros((2, 2))]])
+    assert np.allclose(m.T @ j @ m, j)
+    image = getattr(_native, "poly4_push_forward")(_cube_halfspaces(1.0), m, np.ones(4))
+    volume = getattr(_native, "poly4_volume_from_halfspaces")(image["halfspaces"])
+    assert abs(volume - 16.0) < 1e-6
+
+
+def test_errors_use_viterbo_exception_hierarchy():
+    from viterbo import _native
+
+    base = getattr(_native, "ViterboError")
+    invalid = getattr(_native, "InvalidParams")
+    assert issubclass(invalid, base) and issubclass(base, ValueError)
+    for name in ("DegenerateSampleError", "UnboundedPolytopeError", "SolverBudgetExceeded"):
+        assert issubclass(getattr(_native, name), base)
+    try:
+        getattr(_native, "poly4_volume_from_halfspaces")(np.zeros((5, 3)))
+    except invalid:
+        pass
+    else:
+        raise AssertionError("expected InvalidParams for a (5, 3) array")
+
+
+def test_node_budget_raises_solver_budget_exceeded():
+    from viterbo import _native
+
+    single = getattr(_native, "poly4_capacity_ehz_from_halfspaces")
+    with pytest.raises(getattr(_native, "SolverBudgetExceeded")):
+        single(_cube_halfspaces(1.0), max_nodes=1)
+    assert abs(single(_cube_halfspaces(1.0), max_nodes=100_000) - 4.0) < 1e-9
+    with pytest.raises(getattr(_native, "SolverBudgetExceeded"), match="batch\\[0\\]"):
+        getattr(_native, "capacity_ehz_batch")([_cube_halfspaces(1.0)], 1, max_nodes=1)
+
+
+def test_unbounded_halfspaces_raise_unbounded_polytope_error():
+    from viterbo import _native
+
+    open_slab = _cube_halfspaces(1.0)[1:]  # drop x0 <= 1: the ray +x0 stays inside
+    with pytest.raises(getattr(_native, "UnboundedPolytopeError")):
+        getattr(_native, "poly4_volume_from_halfspaces")(open_slab)
+    no_interior = _cube_halfspaces(1.0)
+    no_interior[:, 4] = -1.0  # empty body: not unbounded, just degenerate
+    with pytest.raises(getattr(_native, "DegenerateSampleError")):
+        getattr(_native, "poly4_volume_from_halfspaces")(no_interior)
+
+
+def test_oriented_edge_graph_accepts_geom_cfg():
+    from viterbo import _native
+
+    build = getattr(_native, "build_oriented_edge_graph")
+    loose = build(_cube_halfspaces(1.0), geom_cfg={"eps_feas": 1e-6})
+    assert loose.num_ridges == build(_cube_halfspaces(1.0)).num_ridges
+    try:
+        build(_cube_halfspaces(1.0), geom_cfg={"eps_typo": 1.0})
+    except getattr(_native, "InvalidParams"):
+        pass
+    else:
+        raise AssertionError("unknown geom_cfg keys must be rejected")
+
+
+def test_draw_polygon_radial_matches_poly2_class():
+    from viterbo import _native
+
+    out = getattr(_native, "draw_polygon_radial")(None, 5, 2)
+    poly = getattr(_native, "Poly2").from_radial(5, 2)
+    assert out["halfspaces"].shape[1] == 3
+    assert np.allclose(out["vertices"], poly.vertices())
+
+
+def test_polygon_polar_of_recentred_square_is_diamond():
+    from viterbo import _native
+
+    # Square [-0.5, 1.5] x [-1, 1]: centroid (0.5, 0), polar after recentring is |x|+|y|<=1.
+    square = np.array([[1.0, 0.0, 1.5], [-1.0, 0.0, 0.5], [0.0, 1.0, 1.0], [0.0, -1.0, 1.0]])
+    dual = getattr(_native, "polygon_polar")(square, recenter=True)
+    assert len(dual["vertices"]) == 4
+    assert np.allclose(np.abs(dual["vertices"]).sum(axis=1), 1.0)
+
+
+def test_composite_constructors():
+    from viterbo import _native
+
+    square = np.array([[1.0, 0.0, 1.0], [-1.0, 0.0, 1.0], [0.0, 1.0, 1.0], [0.0, -1.0, 1.0]])
+    poly2 = getattr(_native, "Poly2").from_halfspaces(square)
+    cube = getattr(_native, "lagrangian_product")(poly2, poly2)
+    assert abs(cube.volume() - 16.0) < 1e-9
+    doubled = getattr(_native, "minkowski_sum")(cube, cube)
+    assert abs(doubled.volume() - 256.0) < 1e-6
+    cross = getattr(_native, "poly4_polar")(_cube_halfspaces(1.0))
+    assert cross["vertices"].shape == (8, 4)
+
+
+def test_capacity_batch_reports_progress():
+    from viterbo import _native
+
+    seen = []
+    batch = [_cube_halfspaces(1.0), _cube_halfspaces(2.0)]
+    getattr(_native, "capacity_ehz_batch")(
+        batch, progress=lambda done, total: seen.append((done, total))
+    )
+    assert sorted(seen) == [(1, 2), (2, 2)]
+
+
+def test_bound_classes_pickle_round_trip():
+    import pickle
+
+    from viterbo import _native
+
+    poly4 = getattr(_native, "Poly4").from_halfspaces(_cube_halfspaces(1.0))
+    _ = poly4.vertices
+    again = pickle.loads(pickle.dumps(poly4))
+    assert np.allclose(again.halfspaces, poly4.halfspaces)
+    assert np.allclose(again.vertices, poly4.vertices)
+    poly2 = getattr(_native, "Poly2").from_radial(1, 0)
+    assert np.allclose(pickle.loads(pickle.dumps(poly2)).vertices(), poly2.vertices())
+
+
+def test_sample_capacity_batch_as_arrow():
+    from viterbo.rust.arrow import sample_capacity_batch
+
+    config = {"family": "zonotopes", "seed": 2, "params": {"segments_min": 5, "segments_max": 5}}
+    batch = sample_capacity_batch(config, rows=3)
+    assert batch.num_rows == 3
+    assert batch.schema.names == ["halfspaces", "replay", "capacity", "volume", "systolic_ratio"]
+    assert json.loads(batch.column("replay")[1].as_py()) == {"seed": 2, "index": 1}
+
+
+def test_face2_polygon_of_cube_ridge_is_a_square():
+    from viterbo import _native
+
+    # Rows 0 (x1 <= 1) and 2 (y1 <= 1) meet in a square, symplectic 2-face.
+    face = getattr(_native, "face2_polygon")(_cube_halfspaces(1.0), 0, 2)
+    assert face["chart_ut"].shape == (4, 2)
+    assert face["vertices"].shape == (4, 2)
+
+
+def test_poly4_contains_and_hausdorff():
+    from viterbo import _native
+
+    small, big = _cube_halfspaces(0.5), _cube_halfspaces(1.0)
+    assert getattr(_native, "poly4_contains")(big, small)
+    assert not getattr(_native, "poly4_contains")(small, big)
+    assert abs(getattr(_native, "poly4_hausdorff")(big, small) - 1.0) < 1e-9
+
+
+def test_poly4_mahler_volume_of_cube():
+    from viterbo import _native
+
+    mahler = getattr(_native, "poly4_mahler_volume")
+    assert abs(mahler(_cube_halfspaces(1.0)) - 32.0 / 3.0) < 1e-9
+    assert abs(mahler(_cube_halfspaces(2.0), center="santalo") - 32.0 / 3.0) < 1e-6
+
+
+def test_capacity_batch_stops_when_progress_raises():
+    from viterbo import _native
+
+    calls = []
+
+    def stop(done, total):
+        calls.append(done)
+        raise KeyboardInterrupt
+
+    batch = [_cube_halfspaces(1.0)] * 4
+    with pytest.raises(KeyboardInterrupt):
+        getattr(_native, "capacity_ehz_batch")(batch, n_threads=1, progress=stop)
+    assert calls == [1]
+
+
+def test_poly4_volume_mc_of_cube_is_exact():
+    from viterbo import _native
+
+    estimate, stderr = getattr(_native, "poly4_volume_mc")(_cube_halfspaces(1.0), 1000, 0)
+    assert (estimate, stderr) == (16.0, 0.0)
+
+
 # Intentionally no staleness check:
 # We do NOT assert the native .so stamp matches HEAD. Staleness is reliably
 # surfaced when a newly added Rust function is called but not present in the
@@ -0,0 +1,19 @@
+import io
+
+from viterbo.progress import ProgressReporter
+
+
+def test_reporter_throttles_and_always_prints_completion():
+    ticks = iter([0.0, 0.5, 0.8, 2.0, 2.1])
+    out = io.StringIO()
+    report = ProgressReporter("[test]", stream=out, interval=1.0, clock=lambda: next(ticks))
+    report(1, 4)  # t=0.5, first call prints
+    report(2, 4)  # t=0.8, throttled
+    report(3, 4)  # t=2.0
+    report(4, 4)  # t=2.1, completion prints regardless
+    lines = out.getvalue().splitlines()
+    assert lines == [
+        "[test] 1/4  2.0/s  ETA 0:00:02",
+        "[test] 3/4  1.5/s  ETA 0:00:01",
+        "[test] 4/4  1.9/s  done in 0:00:02",
+    ]
@@ -0,0 +1,64 @@
+import hashlib
+import json
+from pathlib import Path
+
+
+def test_sidecar_records_hashes_and_environment(tmp_path: Path):
+    from viterbo import provenance
+
+    source = tmp_path / "in.csv"
+    source.write_text("1,2\n", encoding="utf-8")
+    artifact = tmp_path / "out.json"
+    artifact.write_text("{}", encoding="utf-8")
+
+    sidecar = provenance.write(artifact, {"k": 1}, inputs=[source])
+    assert sidecar == provenance.sidecar_path(artifact) == tmp_path / "out.json.run.json"
+    payload = json.loads(sidecar.read_text())
+    assert payload["output_sha256"] == hashlib.sha256(b"{}").hexdigest()
+    assert payload["inputs"] == {str(source): hashlib.sha256(b"1,2\n").hexdigest()}
+    assert payload["environment"]["crates"]["viterbo"]
+    assert {"hostname", "cpu_model", "rustc", "python"} <= payload["environment"].keys()
+    numerics = payload["environment"]["numerics"]
+    assert numerics["nalgebra"] and numerics["blas"] is None
+    assert "target_features" in numerics["native_build"]
+
+
+def test_lineage_follows_upstream_sidecars_and_flags_edits(tmp_path: Path):
+    from viterbo import provenance
+
+    dataset = tmp_path / "atlas.parquet"
+    dataset.write_bytes(b"rows")
+    provenance.write(dataset, {"rows": 1})
+    figure = tmp_path / "fig.svg"
+    figure.write_text("<svg/>", encoding="utf-8")
+    provenance.write(figure, {}, inputs=[dataset])
+
+    chain = provenance.lineage(figure)
+    assert [(e["artifact"], e["depth"], e["stale"]) for e in chain] == [
+        (str(figure), 0, False),
+        (str(dataset), 1, False),
+    ]
+    provenance.write(dataset, {"rows": 2})  # re-run upstream without refreshing the figure
+    assert provenance.lineage(figure)[1]["stale"]
+
+
+def test_report_lists_every_sidecar_with_a_config_digest(tmp_path: Path):
+    from viterbo import provenance
+
+    for name, algo in [("a/result.json", "capacity"), ("b/result.json", "volume")]:
+        artifact = tmp_path / name
+        artifact.parent.mkdir()
+        artifact.write_text("{}", encoding="utf-8")
+        provenance.write(artifact, {"algo": algo})
+    (tmp_path / "junk.run.json").write_text("not json", encoding="utf-8")
+
+    rows = provenance.report(tmp_path)
+    assert [(r["artifact"], r["algo"]) for r in rows] == [
+        ("a/result.json", "capacity"),
+        ("b/result.json", "volume"),
+    ]
+    assert rows[0]["config_digest"] != rows[1]["config_digest"]
+
+    csv_out = tmp_path / "report.csv"
+    assert provenance.main(["report", "--root", str(tmp_path), "--csv", str(csv_out)]) == 0
+    assert csv_out.read_text().splitlines()[0] == ",".join(provenance.REPORT_COLUMNS)
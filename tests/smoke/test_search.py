@@ -0,0 +1,30 @@
+import json
+from pathlib import Path
+
+from viterbo.search import HuntConfig, run_hunt
+
+
+def _config(tmp_path: Path, rounds: int) -> HuntConfig:
+    return HuntConfig.from_mapping(
+        {
+            "out_dir": "hunt",
+            "start": "hypercube",
+            "rounds": rounds,
+            "seed": 1,
+            "optimizer": {"name": "nelder_mead", "budget": 12, "step": 0.05},
+        },
+        base_dir=tmp_path,
+    )
+
+
+def test_hunt_dumps_best_and_resumes(tmp_path: Path):
+    state = run_hunt(_config(tmp_path, rounds=2))
+    assert state["round"] == 2 and state["evaluations"] <= 24
+    best = json.loads((tmp_path / "hunt" / "best.json").read_text())
+    # The cube (ratio 4² / (2·16) = 0.5) is a valid start, so the best cannot be worse.
+    assert best["systolic_ratio"] >= 0.5 - 1e-9
+    assert (tmp_path / "hunt" / "best.json.run.json").exists()
+
+    resumed = run_hunt(_config(tmp_path, rounds=3), resume=True)
+    assert resumed["round"] == 3
+    assert resumed["best_per_round"][:2] == state["best_per_round"]
@@ -0,0 +1,29 @@
+from pathlib import Path
+
+from viterbo.atlas.config import AtlasConfig
+from viterbo.seeds import SOURCE_STRIDE, derive, seed_record
+
+
+def test_atlas_seeds_derive_from_the_root_and_keep_explicit_ones(tmp_path: Path):
+    from viterbo.atlas.dataset import atlas_seeds
+
+    cfg = AtlasConfig.from_mapping(
+        {
+            "seed": 100,
+            "sources": [
+                {"name": "a", "family": "special_catalog", "params": {"members": ["hypercube"]}},
+                {"name": "b", "family": "special_catalog", "seed": 7, "params": {}},
+                {"name": "c", "family": "special_catalog", "params": {}},
+            ],
+            "out": {"dataset": str(tmp_path / "atlas.parquet")},
+        },
+        base_dir=tmp_path,
+    )
+    record = atlas_seeds(cfg)
+    assert record["root"] == 100 and record["scheme"] == "atlas/v1"
+    assert record["derived"] == {"a": 100, "b": 7, "c": 100 + 2 * SOURCE_STRIDE}
+
+
+def test_seed_record_is_plain_json():
+    assert derive(3, 2, stride=1) == 5
+    assert seed_record(3, "search/v1") == {"root": 3, "scheme": "search/v1", "derived": {}}
@@ -0,0 +1,28 @@
+import json
+from pathlib import Path
+
+import numpy as np
+import pytest
+
+from viterbo.atlas.sources import build_hypercube
+
+
+def test_round_trip_through_every_format(tmp_path: Path):
+    from viterbo.solve import stage_convert
+    from viterbo.solve.convert import load_polytope
+
+    start = tmp_path / "cube.json"
+    start.write_text(json.dumps({"halfspaces": build_hypercube().halfspaces}), encoding="utf-8")
+    chain = ["cube_v.json", "cube.csv", "cube.parquet", "back.json"]
+    formats = ["v-json", "csv", "atlas", "h-json"]
+    previous = start
+    for name, fmt in zip(chain, formats):
+        target = tmp_path / name
+        assert stage_convert.main([str(previous), str(target), "--to", fmt]) == 0
+        previous = target
+
+    assert len(json.loads((tmp_path / "cube_v.json").read_text())["vertices"]) == 16
+    back = load_polytope(tmp_path / "back.json")
+    assert back.volume() == pytest.approx(16.0)
+    assert np.asarray(back.halfspaces).shape == (8, 5)
+    assert (tmp_path / "back.json.run.json").exists()
@@ -0,0 +1,23 @@
+import numpy as np
+import pytest
+
+from viterbo.atlas.sources import build_cross_polytope, build_hypercube
+from viterbo.solve import inspect_polytope
+
+
+def test_cube_report():
+    report = inspect_polytope(np.asarray(build_hypercube().halfspaces, dtype=np.float64))
+    assert report["canonical"] and report["f_vector"] == [16, 32, 24, 8]
+    assert report["redundant_rows"] == []
+    assert report["volume"] == pytest.approx(16.0)
+    assert report["origin_inradius"] == pytest.approx(1.0)
+    assert report["origin_outradius"] == pytest.approx(2.0)
+    assert "complex structure J" in report["symmetries"]
+    assert report["lagrangian_product"]
+
+
+def test_cross_polytope_is_not_a_lagrangian_product():
+    report = inspect_polytope(np.asarray(build_cross_polytope().halfspaces, dtype=np.float64))
+    assert report["f_vector"] == [8, 24, 32, 16]
+    assert not report["lagrangian_product"]
+    assert "central (x -> -x)" in report["symmetries"]
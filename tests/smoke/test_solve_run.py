@@ -0,0 +1,49 @@
+import json
+from pathlib import Path
+
+import numpy as np
+import pytest
+
+
+def test_stage_run_systolic_on_cube(tmp_path: Path):
+    from viterbo.solve import stage_run
+
+    poly = tmp_path / "cube.csv"
+    rows = ["n0,n1,n2,n3,c"]
+    for k in range(4):
+        for sign in (1.0, -1.0):
+            normal = [0.0] * 4
+            normal[k] = sign
+            rows.append(",".join(str(x) for x in [*normal, 1.0]))
+    poly.write_text("\n".join(rows) + "\n", encoding="utf-8")
+    out = tmp_path / "result.json"
+
+    assert stage_run.main(["--algo", "systolic", "--input", str(poly), "--out", str(out)]) == 0
+    result = json.loads(out.read_text())["result"]
+    assert result["volume"] == pytest.approx(16.0)
+    assert result["systolic_ratio"] == pytest.approx(0.5)
+    assert out.with_suffix(".json.run.json").exists()
+
+
+def test_algo_params_are_validated_before_running():
+    from viterbo.solve.run import ALGOS, run_algo
+
+    optimize = ALGOS["optimize"]
+    params = optimize.validate({"budget": "50", "optimizer": "nelder_mead"})
+    assert params["budget"] == 50 and params["seed"] == 0
+    with pytest.raises(ValueError, match="did you mean 'budget'"):
+        optimize.validate({"budgte": "50"})
+    with pytest.raises(ValueError, match="must be one of"):
+        optimize.validate({"optimizer": "adam"})
+    with pytest.raises(ValueError, match="did you mean 'systolic'"):
+        run_algo("systolc", np.zeros((0, 5)))
+
+
+def test_stage_algos_lists_every_registered_algo(capsys):
+    from viterbo.solve import stage_algos
+    from viterbo.solve.run import ALGOS
+
+    assert stage_algos.main(["--json"]) == 0
+    listing = json.loads(capsys.readouterr().out)
+    assert list(listing) == list(ALGOS)
+    assert [p["name"] for p in listing["optimize"]["params"]][:2] == ["optimizer", "budget"]
@@ -0,0 +1,29 @@
+from pathlib import Path
+
+import pytest
+
+from viterbo.watch import changes
+
+
+def test_changes_yields_once_then_on_every_edit(tmp_path: Path):
+    path = tmp_path / "data.txt"
+    path.write_text("a")
+    edits = iter(["bb", None, "ccc"])
+
+    def sleep(_seconds: float) -> None:
+        text = next(edits, StopIteration)
+        if text is StopIteration:
+            raise KeyboardInterrupt
+        if text is not None:
+            path.write_text(text)
+
+    seen = []
+    with pytest.raises(KeyboardInterrupt):
+        for _ in changes(path, interval=0.01, sleep=sleep):
+            seen.append(path.read_text())
+    assert seen == ["a", "bb", "ccc"]
+
+
+def test_interval_must_be_positive(tmp_path: Path):
+    with pytest.raises(ValueError):
+        next(changes(tmp_path / "x", interval=0))